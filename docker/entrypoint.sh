@@ -16,6 +16,14 @@ log_progress() {
 
 log_progress "=== Claude Code Environment Initializing ==="
 
+# Repository location inside the container (per-repository override).
+# Docker creates a custom WorkingDir owned by root, so hand it to node.
+WORKSPACE="${WORKSPACE_DIR:-/workspace}"
+if [ ! -w "$WORKSPACE" ]; then
+    sudo -n -u orkroot mkdir -p "$WORKSPACE" 2>/dev/null || true
+    sudo -n -u orkroot chown node:node "$WORKSPACE" 2>/dev/null || log_progress "Warning: $WORKSPACE is not writable"
+fi
+
 # Initialize firewall if running with NET_ADMIN capability
 # Use sudo -E to preserve environment variables (NETWORK_MODE, ALLOWED_DOMAINS)
 if [ -x /usr/local/bin/init-firewall.sh ]; then
@@ -130,7 +138,7 @@ if [ -f /claude-config.json ]; then
     # 1. Remove host-specific attributes (githubRepoPaths, projects)
     # 2. Add bypassPermissionsModeAccepted for --dangerously-skip-permissions
     # 3. Add hasCompletedOnboarding to skip first-run theme selection
-    # 4. Add /workspace project settings with trust accepted
+    # 4. Add workspace project settings with trust accepted
     #
     # We retry up to 3 times with validation to handle race conditions
     TEMP_CLAUDE_JSON="$HOME/.claude.json.tmp"
@@ -138,11 +146,11 @@ if [ -f /claude-config.json ]; then
 
     for attempt in 1 2 3; do
         # Important: Don't redirect stderr to stdout (2>&1) as it corrupts the JSON output
-        if jq 'del(.githubRepoPaths, .projects) |
+        if jq --arg workspace "$WORKSPACE" 'del(.githubRepoPaths, .projects) |
               .bypassPermissionsModeAccepted = true |
               .hasCompletedOnboarding = true |
               .theme = "dark" |
-              .projects = {"/workspace": {"hasTrustDialogAccepted": true, "hasCompletedProjectOnboarding": true}}' \
+              .projects = {($workspace): {"hasTrustDialogAccepted": true, "hasCompletedProjectOnboarding": true}}' \
               /claude-config.json > "$TEMP_CLAUDE_JSON" 2>/dev/null; then
 
             # Validate the output is valid JSON before using it
@@ -173,13 +181,13 @@ if [ -f /claude-config.json ]; then
     # Fallback: create minimal config if all attempts failed
     if [ "$CLAUDE_JSON_SUCCESS" != "true" ]; then
         echo "Warning: Failed to process host .claude.json after 3 attempts, creating minimal config"
-        cat > "$HOME/.claude.json" << 'FALLBACK_EOF'
+        cat > "$HOME/.claude.json" << FALLBACK_EOF
 {
   "bypassPermissionsModeAccepted": true,
   "hasCompletedOnboarding": true,
   "theme": "dark",
   "projects": {
-    "/workspace": {
+    "$WORKSPACE": {
       "hasTrustDialogAccepted": true,
       "hasCompletedProjectOnboarding": true
     }
@@ -191,13 +199,13 @@ FALLBACK_EOF
 else
     # No host config - create minimal config with bypass permissions
     echo "Creating minimal .claude.json with bypass permissions..."
-    cat > "$HOME/.claude.json" << 'EOF'
+    cat > "$HOME/.claude.json" << EOF
 {
   "bypassPermissionsModeAccepted": true,
   "hasCompletedOnboarding": true,
   "theme": "dark",
   "projects": {
-    "/workspace": {
+    "$WORKSPACE": {
       "hasTrustDialogAccepted": true,
       "hasCompletedProjectOnboarding": true
     }
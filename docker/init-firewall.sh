@@ -134,11 +134,32 @@ else
     )
 fi
 
+# Applied entries are recorded for update-firewall.sh --list-domains
+DOMAINS_FILE="/etc/allowed-domains.list"
+: > "$DOMAINS_FILE"
+record_entry() {
+    if [ -n "$1" ] && ! grep -qxF -- "$1" "$DOMAINS_FILE"; then
+        echo "$1" >> "$DOMAINS_FILE"
+    fi
+}
+
 # Resolve and add allowed domains from configuration
 for domain in "${DOMAIN_ARRAY[@]}"; do
     # Skip github.com domains (handled separately via GitHub API)
     if [[ "$domain" == *"github.com"* ]]; then
         echo "Skipping $domain (GitHub IPs are fetched via API)"
+        record_entry "$domain"
+        continue
+    fi
+
+    # Raw IPv4 addresses and CIDR ranges are added directly without DNS resolution
+    if [[ "$domain" =~ ^[0-9]{1,3}\.[0-9]{1,3}\.[0-9]{1,3}\.[0-9]{1,3}(/[0-9]{1,2})?$ ]]; then
+        echo "Adding $domain"
+        if ipset add allowed-domains "$domain" 2>/dev/null; then
+            record_entry "$domain"
+        else
+            echo "WARNING: Failed to add $domain, skipping"
+        fi
         continue
     fi
 
@@ -149,6 +170,7 @@ for domain in "${DOMAIN_ARRAY[@]}"; do
         continue
     fi
 
+    applied=false
     while read -r ip; do
         if [[ ! "$ip" =~ ^[0-9]{1,3}\.[0-9]{1,3}\.[0-9]{1,3}\.[0-9]{1,3}$ ]]; then
             echo "WARNING: Invalid IP from DNS for $domain: $ip, skipping"
@@ -156,7 +178,13 @@ for domain in "${DOMAIN_ARRAY[@]}"; do
         fi
         echo "Adding $ip for $domain"
         ipset add allowed-domains "$ip" 2>/dev/null || true
+        if ipset test allowed-domains "$ip" &>/dev/null; then
+            applied=true
+        fi
     done < <(echo "$ips")
+    if [ "$applied" = true ]; then
+        record_entry "$domain"
+    fi
 done
 
 # Get host IP from default route
@@ -173,6 +201,18 @@ echo "Host network detected as: $HOST_NETWORK"
 iptables -A INPUT -s "$HOST_NETWORK" -j ACCEPT
 iptables -A OUTPUT -d "$HOST_NETWORK" -j ACCEPT
 
+# Allow the subnets of every attached Docker network. On a custom project
+# network this keeps shared services (e.g. a database container) reachable,
+# even when the subnet is wider than the /24 guessed above.
+while read -r subnet; do
+    if [[ ! "$subnet" =~ ^[0-9]{1,3}\.[0-9]{1,3}\.[0-9]{1,3}\.[0-9]{1,3}/[0-9]{1,2}$ ]]; then
+        continue
+    fi
+    echo "Allowing attached network $subnet"
+    iptables -A INPUT -s "$subnet" -j ACCEPT
+    iptables -A OUTPUT -d "$subnet" -j ACCEPT
+done < <(ip -o -4 route show scope link | awk '{print $1}')
+
 # Set default policies to DROP first
 iptables -P INPUT DROP
 iptables -P FORWARD DROP
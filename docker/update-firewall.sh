@@ -6,6 +6,21 @@
 #   update-firewall.sh --add domain1,domain2,...
 #   update-firewall.sh --remove domain1,domain2,...
 #   update-firewall.sh --list
+#   update-firewall.sh --list-domains
+#   update-firewall.sh --bypass
+#   update-firewall.sh --restore
+#
+# --bypass temporarily allows all traffic by inserting ACCEPT rules ahead of
+# the whitelist; --restore removes them again. The ipset is left untouched and
+# the bypass does not survive a container restart (init-firewall.sh re-runs).
+#
+# Entries may be domain names, raw IPv4 addresses, or IPv4 CIDR ranges.
+# IPs and CIDR ranges are added to the ipset directly without DNS resolution.
+#
+# Entries that were applied (not the IPs they resolve to) are kept in
+# $DOMAINS_FILE, seeded by init-firewall.sh; --list-domains prints them.
+# An entry is only recorded once it is in the ipset, so entries that failed to
+# resolve or be added are left out until a later --add applies them.
 #
 # Must be run with sudo (sudoers rule allows node user to run this)
 
@@ -18,32 +33,82 @@ GREEN='\033[0;32m'
 YELLOW='\033[1;33m'
 NC='\033[0m' # No Color
 
+# Configured allowed-domains entries, one per line
+DOMAINS_FILE="/etc/allowed-domains.list"
+
 usage() {
-    echo "Usage: $0 [--add|--remove|--list] [domain1,domain2,...]"
+    echo "Usage: $0 [--add|--remove|--list|--list-domains|--bypass|--restore] [domain1,domain2,...]"
     echo ""
     echo "Options:"
     echo "  --add domain1,domain2,...    Add domains to the firewall whitelist"
     echo "  --remove domain1,domain2,... Remove domains from the firewall whitelist"
     echo "  --list                       List current ipset entries"
+    echo "  --list-domains               List the applied domains, IPs and CIDR ranges"
+    echo "  --bypass                     Temporarily allow all traffic"
+    echo "  --restore                    Remove the bypass and re-apply the whitelist"
     echo ""
     echo "Examples:"
     echo "  $0 --add api.example.com,cdn.example.com"
+    echo "  $0 --add 10.0.0.5,192.168.10.0/24"
     echo "  $0 --remove api.example.com"
     echo "  $0 --list"
     exit 1
 }
 
-# Validate domain format
+# Check whether an entry is a raw IPv4 address or IPv4 CIDR range
+is_ip_or_cidr() {
+    local entry="$1"
+    local addr="${entry%%/*}"
+    local octet
+
+    if [[ ! "$entry" =~ ^[0-9]{1,3}\.[0-9]{1,3}\.[0-9]{1,3}\.[0-9]{1,3}(/[0-9]{1,2})?$ ]]; then
+        return 1
+    fi
+
+    IFS='.' read -ra OCTETS <<< "$addr"
+    for octet in "${OCTETS[@]}"; do
+        if (( 10#$octet > 255 )); then
+            return 1
+        fi
+    done
+
+    if [[ "$entry" == */* ]] && (( 10#${entry##*/} > 32 )); then
+        return 1
+    fi
+
+    return 0
+}
+
+# Validate entry format (domain name, IPv4 address, or IPv4 CIDR range)
 validate_domain() {
     local domain="$1"
+    if is_ip_or_cidr "$domain"; then
+        return 0
+    fi
     # Basic domain validation regex
     if [[ ! "$domain" =~ ^([a-zA-Z0-9]([a-zA-Z0-9-]*[a-zA-Z0-9])?\.)+[a-zA-Z]{2,}$ ]]; then
-        echo -e "${RED}ERROR: Invalid domain format: $domain${NC}" >&2
+        echo -e "${RED}ERROR: Invalid domain, IP, or CIDR format: $domain${NC}" >&2
         return 1
     fi
     return 0
 }
 
+# Record a configured entry, ignoring duplicates
+record_entry() {
+    touch "$DOMAINS_FILE"
+    if ! grep -qxF -- "$1" "$DOMAINS_FILE"; then
+        echo "$1" >> "$DOMAINS_FILE"
+    fi
+}
+
+# Forget a configured entry
+forget_entry() {
+    if [ -f "$DOMAINS_FILE" ]; then
+        grep -vxF -- "$1" "$DOMAINS_FILE" > "$DOMAINS_FILE.tmp" || true
+        mv "$DOMAINS_FILE.tmp" "$DOMAINS_FILE"
+    fi
+}
+
 # Resolve domain to IP addresses
 resolve_domain() {
     local domain="$1"
@@ -82,15 +147,32 @@ add_domains() {
             continue
         fi
 
+        # Validate domain format
+        if ! validate_domain "$domain"; then
+            ((failed++))
+            continue
+        fi
+
         # Skip github.com domains (handled by init-firewall.sh via API)
         if [[ "$domain" == *"github.com"* ]]; then
             echo -e "${YELLOW}Skipping $domain (GitHub IPs are managed via API)${NC}"
+            record_entry "$domain"
             continue
         fi
 
-        # Validate domain format
-        if ! validate_domain "$domain"; then
-            ((failed++))
+        # Raw IPs and CIDR ranges skip DNS resolution
+        if is_ip_or_cidr "$domain"; then
+            if ipset add allowed-domains "$domain" 2>/dev/null; then
+                echo -e "${GREEN}Added $domain${NC}"
+                ((added++))
+                record_entry "$domain"
+            elif ipset test allowed-domains "$domain" &>/dev/null; then
+                echo -e "${YELLOW}$domain already in whitelist${NC}"
+                record_entry "$domain"
+            else
+                echo -e "${RED}ERROR: Failed to add $domain${NC}" >&2
+                ((failed++))
+            fi
             continue
         fi
 
@@ -101,6 +183,8 @@ add_domains() {
             continue
         fi
 
+        # Recorded once at least one of its IPs is in the ipset
+        local applied=false
         while read -r ip; do
             if [[ ! "$ip" =~ ^[0-9]{1,3}\.[0-9]{1,3}\.[0-9]{1,3}\.[0-9]{1,3}$ ]]; then
                 echo -e "${YELLOW}WARNING: Invalid IP from DNS for $domain: $ip${NC}" >&2
@@ -111,10 +195,20 @@ add_domains() {
             if ipset add allowed-domains "$ip" 2>/dev/null; then
                 echo -e "${GREEN}Added $ip for $domain${NC}"
                 ((added++))
+                applied=true
+            elif ipset test allowed-domains "$ip" &>/dev/null; then
+                echo -e "${YELLOW}$ip already in whitelist${NC}"
+                applied=true
             else
-                echo -e "${YELLOW}$ip already in whitelist (or error)${NC}"
+                echo -e "${YELLOW}WARNING: Failed to add $ip for $domain${NC}" >&2
             fi
         done <<< "$ips"
+
+        if [ "$applied" = true ]; then
+            record_entry "$domain"
+        else
+            ((failed++))
+        fi
     done
 
     echo ""
@@ -150,6 +244,19 @@ remove_domains() {
             continue
         fi
 
+        forget_entry "$domain"
+
+        # Raw IPs and CIDR ranges skip DNS resolution
+        if is_ip_or_cidr "$domain"; then
+            if ipset del allowed-domains "$domain" 2>/dev/null; then
+                echo -e "${GREEN}Removed $domain${NC}"
+                ((removed++))
+            else
+                echo -e "${YELLOW}$domain not in whitelist (or error)${NC}"
+            fi
+            continue
+        fi
+
         echo "Resolving $domain..."
         local ips
         if ! ips=$(resolve_domain "$domain"); then
@@ -189,6 +296,40 @@ list_entries() {
     ipset list allowed-domains
 }
 
+# List the applied entries
+list_domains() {
+    if [ ! -f "$DOMAINS_FILE" ]; then
+        echo -e "${RED}ERROR: $DOMAINS_FILE does not exist. Was the firewall initialized by an older image?${NC}" >&2
+        exit 1
+    fi
+    cat "$DOMAINS_FILE"
+}
+
+# Comment used to tag the bypass rules so they can be found and removed
+BYPASS_COMMENT="orkestrator-firewall-bypass"
+
+# Allow all traffic without discarding the whitelist rules
+enable_bypass() {
+    local chain
+    for chain in INPUT OUTPUT; do
+        if ! iptables -C "$chain" -m comment --comment "$BYPASS_COMMENT" -j ACCEPT 2>/dev/null; then
+            iptables -I "$chain" 1 -m comment --comment "$BYPASS_COMMENT" -j ACCEPT
+        fi
+    done
+    echo -e "${YELLOW}Firewall bypassed: all traffic is allowed${NC}"
+}
+
+# Remove the bypass rules so the whitelist applies again
+disable_bypass() {
+    local chain
+    for chain in INPUT OUTPUT; do
+        while iptables -C "$chain" -m comment --comment "$BYPASS_COMMENT" -j ACCEPT 2>/dev/null; do
+            iptables -D "$chain" -m comment --comment "$BYPASS_COMMENT" -j ACCEPT
+        done
+    done
+    echo -e "${GREEN}Firewall restored: whitelist is enforced${NC}"
+}
+
 # Main
 if [ $# -lt 1 ]; then
     usage
@@ -212,6 +353,15 @@ case "$1" in
     --list)
         list_entries
         ;;
+    --list-domains)
+        list_domains
+        ;;
+    --bypass)
+        enable_bypass
+        ;;
+    --restore)
+        disable_bypass
+        ;;
     *)
         echo -e "${RED}ERROR: Unknown option: $1${NC}" >&2
         usage
@@ -11,6 +11,9 @@ YELLOW=$'\033[1;33m'
 RED=$'\033[0;31m'
 NC=$'\033[0m' # No Color
 
+# Repository location inside the container (per-repository override, see WORKSPACE_DIR)
+WORKSPACE="${WORKSPACE_DIR:-/workspace}"
+
 # Load shared git branch helpers when available.
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 if [ -f "/usr/local/bin/git-branch-helpers.sh" ]; then
@@ -179,7 +182,7 @@ append_git_exclude_pattern() {
 
 # Function to add Orkestrator workspace artifacts to .git/info/exclude
 add_workspace_artifacts_to_git_exclude() {
-    local workspace="${WORKSPACE_DIR:-/workspace}"
+    local workspace="$WORKSPACE"
     if [ -d "$workspace/.git" ]; then
         local exclude_file="$workspace/.git/info/exclude"
         mkdir -p "$(dirname "$exclude_file")"
@@ -195,7 +198,7 @@ add_workspace_artifacts_to_git_exclude() {
 # Initial prompt attachments may be uploaded before this setup script runs.
 # Preserve Orkestrator's private workspace state while clearing /workspace for clone.
 ORKESTRATOR_WORKSPACE_STATE_BACKUP=""
-ORKESTRATOR_WORKSPACE_STATE_WORKSPACE="/workspace"
+ORKESTRATOR_WORKSPACE_STATE_WORKSPACE="$WORKSPACE"
 
 cleanup_orkestrator_workspace_state_backup() {
     if [ -n "$ORKESTRATOR_WORKSPACE_STATE_BACKUP" ] && [ -d "$ORKESTRATOR_WORKSPACE_STATE_BACKUP" ]; then
@@ -206,7 +209,7 @@ cleanup_orkestrator_workspace_state_backup() {
 trap cleanup_orkestrator_workspace_state_backup EXIT
 
 preserve_orkestrator_workspace_state() {
-    local workspace="${1:-/workspace}"
+    local workspace="${1:-$WORKSPACE}"
     local state_path="$workspace/.orkestrator"
     ORKESTRATOR_WORKSPACE_STATE_WORKSPACE="$workspace"
 
@@ -227,7 +230,7 @@ preserve_orkestrator_workspace_state() {
 }
 
 restore_orkestrator_workspace_state() {
-    local workspace="${1:-/workspace}"
+    local workspace="${1:-$WORKSPACE}"
     local state_path="$workspace/.orkestrator"
     if [ -n "$ORKESTRATOR_WORKSPACE_STATE_BACKUP" ] && [ -d "$ORKESTRATOR_WORKSPACE_STATE_BACKUP" ]; then
         if [ -e "$state_path" ] || [ -L "$state_path" ]; then
@@ -304,21 +307,30 @@ fi
 
 print_workspace_disk_status() {
     echo "Disk availability:"
-    df -h /workspace /tmp 2>/dev/null | awk 'NR==1 || $6=="/workspace" || $6=="/tmp" {print "  " $0}'
+    df -h "$WORKSPACE" /tmp 2>/dev/null | awk -v ws="$WORKSPACE" 'NR==1 || $6==ws || $6=="/tmp" {print "  " $0}'
 }
 
 clone_repository() {
     local clone_url="$1"
     local clone_dest="$2"
 
+    # Shallow clone when the repository configures a clone depth. Keep every
+    # branch tip so the requested branch and base branch can be checked out;
+    # history beyond the depth is fetched later with `git fetch --unshallow`.
+    local depth_args=()
+    if [ -n "${GIT_CLONE_DEPTH:-}" ]; then
+        echo "Shallow clone: last ${GIT_CLONE_DEPTH} commits"
+        depth_args=(--depth "$GIT_CLONE_DEPTH" --no-single-branch)
+    fi
+
     # Try partial clone first to reduce disk usage for large repositories.
-    if git clone --filter=blob:none --no-tags "$clone_url" "$clone_dest"; then
+    if git clone --filter=blob:none --no-tags "${depth_args[@]}" "$clone_url" "$clone_dest"; then
         return 0
     fi
 
     echo -e "${YELLOW}Partial clone failed, retrying full clone...${NC}"
     rm -rf "$clone_dest"/* "$clone_dest"/.[!.]* 2>/dev/null || true
-    git clone "$clone_url" "$clone_dest"
+    git clone "${depth_args[@]}" "$clone_url" "$clone_dest"
 }
 
 # Check if setup already completed
@@ -328,7 +340,7 @@ if [ -f /tmp/.workspace-setup-complete ]; then
 fi
 
 # Clone repository if GIT_URL is set and /workspace/.git doesn't exist
-if [ -n "$GIT_URL" ] && [ ! -d "/workspace/.git" ]; then
+if [ -n "$GIT_URL" ] && [ ! -d "$WORKSPACE/.git" ]; then
     echo ""
     echo -e "${BLUE}>>> Cloning Repository <<<${NC}"
     echo -e "URL: ${GREEN}$GIT_URL${NC}"
@@ -340,13 +352,14 @@ if [ -n "$GIT_URL" ] && [ ! -d "/workspace/.git" ]; then
 
     BRANCH="${GIT_BRANCH:-main}"
     BASE_BRANCH="${GIT_BASE_BRANCH:-}"
+    BASE_REMOTE="${GIT_BASE_REMOTE_NAME:-}"
 
     # Clean /workspace
     echo "Preparing workspace..."
     preserve_orkestrator_workspace_state
-    rm -rf /workspace/* 2>/dev/null || true
-    rm -rf /workspace/.* 2>/dev/null || true
-    find /workspace -mindepth 1 -delete 2>/dev/null || true
+    rm -rf "$WORKSPACE"/* 2>/dev/null || true
+    rm -rf "$WORKSPACE"/.* 2>/dev/null || true
+    find "$WORKSPACE" -mindepth 1 -delete 2>/dev/null || true
     print_workspace_disk_status
 
     # Prepare clone URL - inject token directly for more reliable auth
@@ -362,9 +375,21 @@ if [ -n "$GIT_URL" ] && [ ! -d "/workspace/.git" ]; then
 
     # Clone directly into /workspace
     echo "Cloning..."
-    if clone_repository "$CLONE_URL" /workspace; then
+    if clone_repository "$CLONE_URL" "$WORKSPACE"; then
         echo -e "${GREEN}Clone successful!${NC}"
-        cd /workspace
+        cd "$WORKSPACE"
+
+        # Base branch of a forked pull request lives on the fork, not origin
+        if [ -n "$BASE_REMOTE" ] && [ -n "${GIT_BASE_REMOTE_URL:-}" ] && [ -n "$BASE_BRANCH" ]; then
+            # Token auth comes from the url.insteadOf rules configured above
+            echo "Fetching $BASE_BRANCH from remote $BASE_REMOTE..."
+            if git remote add "$BASE_REMOTE" "$GIT_BASE_REMOTE_URL" 2>/dev/null && git fetch "$BASE_REMOTE" "$BASE_BRANCH" 2>/dev/null; then
+                echo -e "${GREEN}Fetched $BASE_REMOTE/$BASE_BRANCH${NC}"
+            else
+                echo -e "${YELLOW}Failed to fetch $BASE_BRANCH from $BASE_REMOTE${NC}"
+                BASE_REMOTE=""
+            fi
+        fi
 
         # Checkout requested branch if different from current
         CURRENT=$(git branch --show-current)
@@ -374,6 +399,10 @@ if [ -n "$GIT_URL" ] && [ ! -d "/workspace/.git" ]; then
                 echo -e "${GREEN}Checked out: $BRANCH${NC}"
             elif git checkout -b "$BRANCH" "origin/$BRANCH" 2>/dev/null; then
                 echo -e "${GREEN}Checked out remote: origin/$BRANCH${NC}"
+            elif [ -n "$BASE_REMOTE" ] && git checkout -b "$BRANCH" --track "$BASE_REMOTE/$BASE_BRANCH" 2>/dev/null; then
+                # Push back to the fork branch the pull request was opened from
+                git config push.default upstream
+                echo -e "${GREEN}Checked out remote: $BASE_REMOTE/$BASE_BRANCH${NC}"
             else
                 # Branch doesn't exist remotely - create a new branch from configured/default base
                 echo -e "${BLUE}Creating new branch: $BRANCH${NC}"
@@ -417,13 +446,13 @@ if [ -n "$GIT_URL" ] && [ ! -d "/workspace/.git" ]; then
 
         if clone_repository "$CLONE_URL" "$TEMP_CLONE"; then
             echo "Moving files to workspace..."
-            mv "$TEMP_CLONE"/* /workspace/ 2>/dev/null || true
-            mv "$TEMP_CLONE"/.[!.]* /workspace/ 2>/dev/null || true
+            mv "$TEMP_CLONE"/* "$WORKSPACE"/ 2>/dev/null || true
+            mv "$TEMP_CLONE"/.[!.]* "$WORKSPACE"/ 2>/dev/null || true
             rm -rf "$TEMP_CLONE"
 
-            if [ -d "/workspace/.git" ]; then
+            if [ -d "$WORKSPACE/.git" ]; then
                 echo -e "${GREEN}Fallback succeeded!${NC}"
-                cd /workspace
+                cd "$WORKSPACE"
                 # Add Orkestrator workspace artifacts to .git/info/exclude so they're ignored locally
                 add_workspace_artifacts_to_git_exclude
             else
@@ -436,9 +465,9 @@ if [ -n "$GIT_URL" ] && [ ! -d "/workspace/.git" ]; then
 else
     if [ -z "$GIT_URL" ]; then
         echo -e "${YELLOW}No GIT_URL provided - skipping clone${NC}"
-    elif [ -d "/workspace/.git" ]; then
-        echo "Repository already exists in /workspace"
-        cd /workspace
+    elif [ -d "$WORKSPACE/.git" ]; then
+        echo "Repository already exists in $WORKSPACE"
+        cd "$WORKSPACE"
         echo "  Branch: $(git branch --show-current 2>/dev/null || echo 'unknown')"
     fi
 fi
@@ -451,18 +480,18 @@ echo -e "${BLUE}>>> Setting up environment files <<<${NC}"
 
 if [ -d /project-env ]; then
     if [ -f /project-env/.env ]; then
-        cp /project-env/.env /workspace/.env
+        cp /project-env/.env "$WORKSPACE/.env"
         echo -e "  ${GREEN}Copied .env from project folder${NC}"
     fi
     if [ -f /project-env/.env.local ]; then
-        cp /project-env/.env.local /workspace/.env.local
+        cp /project-env/.env.local "$WORKSPACE/.env.local"
         echo -e "  ${GREEN}Copied .env.local from project folder${NC}"
     fi
 elif [ -f /env/.env ]; then
-    cp /env/.env /workspace/.env
+    cp /env/.env "$WORKSPACE/.env"
     echo -e "  ${GREEN}Copied .env file${NC}"
 elif [ -f /env/.env.local ]; then
-    cp /env/.env.local /workspace/.env
+    cp /env/.env.local "$WORKSPACE/.env"
     echo -e "  ${GREEN}Copied .env.local file${NC}"
 else
     echo "  No .env files to copy"
@@ -480,7 +509,7 @@ if [ -d /project-files ]; then
     while read -r file; do
         # Remove leading ./ from path
         rel_path="${file#./}"
-        dest="/workspace/$rel_path"
+        dest="$WORKSPACE/$rel_path"
         dest_dir=$(dirname "$dest")
 
         # Create parent directories if needed
@@ -505,16 +534,16 @@ if [ -f /opencode-project-json ]; then
     echo -e "${BLUE}>>> Setting up OpenCode configuration <<<${NC}"
 
     # Copy to workspace root
-    cp /opencode-project-json /workspace/opencode.json
+    cp /opencode-project-json "$WORKSPACE/opencode.json"
 
     # Add default model attribute if missing (use OPENCODE_MODEL env var or fallback to default)
-    if ! jq -e '.model' /workspace/opencode.json > /dev/null 2>&1; then
+    if ! jq -e '.model' "$WORKSPACE/opencode.json" > /dev/null 2>&1; then
         DEFAULT_MODEL="${OPENCODE_MODEL:-opencode/grok-code}"
         echo -e "  ${YELLOW}No model specified, adding default: $DEFAULT_MODEL${NC}"
-        jq --arg model "$DEFAULT_MODEL" '. + {"model": $model}' /workspace/opencode.json > /tmp/opencode.json.tmp
-        mv /tmp/opencode.json.tmp /workspace/opencode.json
+        jq --arg model "$DEFAULT_MODEL" '. + {"model": $model}' "$WORKSPACE/opencode.json" > /tmp/opencode.json.tmp
+        mv /tmp/opencode.json.tmp "$WORKSPACE/opencode.json"
     else
-        MODEL=$(jq -r '.model' /workspace/opencode.json)
+        MODEL=$(jq -r '.model' "$WORKSPACE/opencode.json")
         echo -e "  ${GREEN}Using configured model: $MODEL${NC}"
     fi
 
@@ -525,22 +554,22 @@ fi
 echo ""
 echo -e "${BLUE}>>> Checking for project setup script <<<${NC}"
 
-if [ -f /workspace/orkestrator-ai.json ]; then
+if [ -f "$WORKSPACE/orkestrator-ai.json" ]; then
     echo -e "${GREEN}Found orkestrator-ai.json${NC}"
-    cat /workspace/orkestrator-ai.json
+    cat "$WORKSPACE/orkestrator-ai.json"
     echo ""
 
     # Parse the root field (string or array) - runs as root user before regular scripts
-    ROOT_SCRIPT=$(jq -r '.root // empty' /workspace/orkestrator-ai.json 2>/dev/null)
-    ROOT_SCRIPT_TYPE=$(jq -r 'if .root==null then "empty" elif (.root|type)=="array" then "array" elif (.root|type)=="string" then "string" else "other" end' /workspace/orkestrator-ai.json 2>/dev/null)
-    ROOT_ARRAY_LENGTH=$(jq -r '.root | if type=="array" then length else 0 end' /workspace/orkestrator-ai.json 2>/dev/null)
+    ROOT_SCRIPT=$(jq -r '.root // empty' "$WORKSPACE/orkestrator-ai.json" 2>/dev/null)
+    ROOT_SCRIPT_TYPE=$(jq -r 'if .root==null then "empty" elif (.root|type)=="array" then "array" elif (.root|type)=="string" then "string" else "other" end' "$WORKSPACE/orkestrator-ai.json" 2>/dev/null)
+    ROOT_ARRAY_LENGTH=$(jq -r '.root | if type=="array" then length else 0 end' "$WORKSPACE/orkestrator-ai.json" 2>/dev/null)
 
     if [ -n "$ROOT_SCRIPT" ] || { [ "$ROOT_SCRIPT_TYPE" = "array" ] && [ "$ROOT_ARRAY_LENGTH" -gt 0 ]; }; then
         echo ""
         echo -e "${BLUE}=== Running Root Setup ===${NC}"
         echo ""
 
-        cd /workspace
+        cd "$WORKSPACE"
 
         run_root_step() {
             local step="$1"
@@ -562,7 +591,7 @@ if [ -f /workspace/orkestrator-ai.json ]; then
                 if [ $ROOT_EXIT -ne 0 ]; then
                     break
                 fi
-            done < <(jq -r '.root[]' /workspace/orkestrator-ai.json 2>/dev/null)
+            done < <(jq -r '.root[]' "$WORKSPACE/orkestrator-ai.json" 2>/dev/null)
         else
             # Single command string
             run_root_step "$ROOT_SCRIPT"
@@ -581,15 +610,15 @@ if [ -f /workspace/orkestrator-ai.json ]; then
     fi
 
     # Parse the setupContainer field (string or array) - runs for container environments
-    SETUP_SCRIPT=$(jq -r '.setupContainer // empty' /workspace/orkestrator-ai.json 2>/dev/null)
-    SETUP_SCRIPT_TYPE=$(jq -r 'if .setupContainer==null then "empty" elif (.setupContainer|type)=="array" then "array" elif (.setupContainer|type)=="string" then "string" else "other" end' /workspace/orkestrator-ai.json 2>/dev/null)
+    SETUP_SCRIPT=$(jq -r '.setupContainer // empty' "$WORKSPACE/orkestrator-ai.json" 2>/dev/null)
+    SETUP_SCRIPT_TYPE=$(jq -r 'if .setupContainer==null then "empty" elif (.setupContainer|type)=="array" then "array" elif (.setupContainer|type)=="string" then "string" else "other" end' "$WORKSPACE/orkestrator-ai.json" 2>/dev/null)
 
     if [ -n "$SETUP_SCRIPT" ] || [ "$SETUP_SCRIPT_TYPE" = "array" ]; then
         echo ""
         echo -e "${BLUE}=== Running Container Setup ===${NC}"
         echo ""
 
-        cd /workspace
+        cd "$WORKSPACE"
 
         run_setup_step() {
             local step="$1"
@@ -613,7 +642,7 @@ if [ -f /workspace/orkestrator-ai.json ]; then
                 if [ $SCRIPT_EXIT -ne 0 ]; then
                     break
                 fi
-            done < <(jq -r '.setupContainer[]' /workspace/orkestrator-ai.json 2>/dev/null)
+            done < <(jq -r '.setupContainer[]' "$WORKSPACE/orkestrator-ai.json" 2>/dev/null)
         else
             # Single command string
             run_setup_step "$SETUP_SCRIPT"
@@ -14,8 +14,11 @@ use tracing::debug;
 pub enum Backend {
     /// Run commands directly on the host. `cwd` is the worktree path.
     Local { cwd: String },
-    /// Run commands via `docker exec` as the `node` user in `/workspace`.
-    Container { container_id: String },
+    /// Run commands via `docker exec` as the `node` user in `workspace`.
+    Container {
+        container_id: String,
+        workspace: String,
+    },
 }
 
 #[derive(Debug)]
@@ -33,7 +36,7 @@ impl ExecOutput {
 
 impl Backend {
     /// Run a shell command. `args[0]` is the executable; the rest are its args.
-    /// For [`Backend::Container`], the command runs as `node` inside `/workspace`.
+    /// For [`Backend::Container`], the command runs as `node` inside the workspace.
     pub async fn exec(&self, args: &[&str]) -> Result<ExecOutput, String> {
         self.exec_with_stdin(args, None).await
     }
@@ -51,13 +54,16 @@ impl Backend {
                 c.current_dir(cwd);
                 c
             }
-            Backend::Container { container_id } => {
-                let mut c = Command::new("docker");
+            Backend::Container {
+                container_id,
+                workspace,
+            } => {
+                let mut c = Command::new(crate::docker::engine_cli());
                 c.arg("exec");
                 c.arg("-u");
                 c.arg("node");
                 c.arg("-w");
-                c.arg("/workspace");
+                c.arg(workspace);
                 if stdin.is_some() {
                     c.arg("-i");
                 }
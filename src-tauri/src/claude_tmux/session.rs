@@ -162,7 +162,7 @@ impl TmuxSession {
 
         let workspace = match &backend {
             Backend::Local { cwd } => cwd.clone(),
-            Backend::Container { .. } => "/workspace".to_string(),
+            Backend::Container { workspace, .. } => workspace.clone(),
         };
         // NOTE: Container paths assume Orkestrator's base image — see the
         // module docs in `mod.rs` for the layout this code depends on.
@@ -223,7 +223,7 @@ impl TmuxSession {
 
         let cwd = match &self.backend {
             Backend::Local { cwd } => cwd.clone(),
-            Backend::Container { .. } => "/workspace".to_string(),
+            Backend::Container { workspace, .. } => workspace.clone(),
         };
         // Fresh tmux tabs are launched with a stable `--session-id`, so the
         // transcript must match that exact id. Falling back to the newest JSONL
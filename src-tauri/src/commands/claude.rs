@@ -16,7 +16,7 @@ const SERVER_STARTUP_POLL_INTERVAL_MS: u64 = 200;
 
 fn build_claude_bridge_start_command() -> &'static str {
     r#"
-        cd /workspace
+        cd "${WORKSPACE_DIR:-/workspace}"
         rm -f /tmp/claude-bridge.log
         source /etc/profile 2>/dev/null || true
         source ~/.profile 2>/dev/null || true
@@ -198,10 +198,15 @@ pub async fn stop_claude_server(container_id: String) -> Result<(), String> {
     Ok(())
 }
 
-/// Get the Claude bridge server log from a container (for debugging)
+/// Get the Claude bridge server log from a container (for debugging).
+/// `since` skips the first that many bytes, `tail` keeps the last that many lines.
 #[tauri::command]
-pub async fn get_claude_server_log(container_id: String) -> Result<String, String> {
-    debug!(container_id = %container_id, "Getting Claude bridge server log");
+pub async fn get_claude_server_log(
+    container_id: String,
+    tail: Option<usize>,
+    since: Option<u64>,
+) -> Result<String, String> {
+    debug!(container_id = %container_id, tail = ?tail, since = ?since, "Getting Claude bridge server log");
 
     let client = docker::client::get_docker_client().map_err(|e| e.to_string())?;
 
@@ -216,8 +221,9 @@ pub async fn get_claude_server_log(container_id: String) -> Result<String, Strin
     }
 
     // Read the log file
+    let command = super::server_log_command("/tmp/claude-bridge.log", tail, since);
     let log_content = client
-        .exec_in_container(&container_id, vec!["cat", "/tmp/claude-bridge.log"], None)
+        .exec_in_container(&container_id, vec!["sh", "-c", &command], None)
         .await
         .map_err(|e| format!("Failed to read log: {}", e))?;
 
@@ -6,11 +6,46 @@
 // - GitHub CLI (gh command for PR operations)
 
 use crate::claude_cli;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Result of a CLI availability check
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliStatus {
+    pub available: bool,
+    /// Resolved path of the binary that will be used
+    pub path: Option<String>,
+    /// First line of `--version` output
+    pub version: Option<String>,
+}
+
+/// Probe a resolved CLI path for its version off the async runtime
+async fn cli_status(find: fn() -> Option<PathBuf>) -> CliStatus {
+    tokio::task::spawn_blocking(move || match find() {
+        Some(path) => CliStatus {
+            available: true,
+            version: claude_cli::get_cli_version(&path),
+            path: Some(path.to_string_lossy().into_owned()),
+        },
+        None => CliStatus {
+            available: false,
+            path: None,
+            version: None,
+        },
+    })
+    .await
+    .unwrap_or(CliStatus {
+        available: false,
+        path: None,
+        version: None,
+    })
+}
 
-/// Check if the Claude CLI binary is installed and available
+/// Check if the Claude CLI binary is installed, and which one is used
 #[tauri::command]
-pub fn check_claude_cli() -> bool {
-    claude_cli::is_claude_cli_available()
+pub async fn check_claude_cli() -> CliStatus {
+    cli_status(claude_cli::find_claude_cli).await
 }
 
 /// Check if the Claude configuration file (~/.claude.json) exists
@@ -20,22 +55,22 @@ pub fn check_claude_config() -> bool {
     claude_cli::has_claude_config_file()
 }
 
-/// Check if the OpenCode CLI binary is installed and available
+/// Check if the OpenCode CLI binary is installed, and which one is used
 #[tauri::command]
-pub fn check_opencode_cli() -> bool {
-    claude_cli::is_opencode_cli_available()
+pub async fn check_opencode_cli() -> CliStatus {
+    cli_status(claude_cli::find_opencode_cli).await
 }
 
-/// Check if the Codex CLI binary is installed and available
+/// Check if the Codex CLI binary is installed, and which one is used
 #[tauri::command]
-pub fn check_codex_cli() -> bool {
-    claude_cli::is_codex_cli_available()
+pub async fn check_codex_cli() -> CliStatus {
+    cli_status(claude_cli::find_codex_cli).await
 }
 
-/// Check if the GitHub CLI (gh) binary is installed and available
+/// Check if the GitHub CLI (gh) binary is installed, and which one is used
 #[tauri::command]
-pub fn check_github_cli() -> bool {
-    claude_cli::is_github_cli_available()
+pub async fn check_github_cli() -> CliStatus {
+    cli_status(claude_cli::find_github_cli).await
 }
 
 /// Check if any AI CLI (Claude, OpenCode, or Codex) is available for name generation
@@ -55,12 +90,10 @@ pub fn get_available_ai_cli() -> Option<String> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_check_claude_cli_returns_bool() {
-        // Smoke test: verify the function doesn't panic and returns a boolean
-        // The actual result depends on whether Claude CLI is installed
-        let result = check_claude_cli();
-        assert!(result == true || result == false);
+    #[tokio::test]
+    async fn test_check_claude_cli_reports_path_when_available() {
+        let status = check_claude_cli().await;
+        assert_eq!(status.available, status.path.is_some());
     }
 
     #[test]
@@ -71,22 +104,22 @@ mod tests {
         assert!(result == true || result == false);
     }
 
-    #[test]
-    fn test_check_opencode_cli_returns_bool() {
-        let result = check_opencode_cli();
-        assert!(result == true || result == false);
+    #[tokio::test]
+    async fn test_check_opencode_cli_reports_path_when_available() {
+        let status = check_opencode_cli().await;
+        assert_eq!(status.available, status.path.is_some());
     }
 
-    #[test]
-    fn test_check_codex_cli_returns_bool() {
-        let result = check_codex_cli();
-        assert!(result == true || result == false);
+    #[tokio::test]
+    async fn test_check_codex_cli_reports_path_when_available() {
+        let status = check_codex_cli().await;
+        assert_eq!(status.available, status.path.is_some());
     }
 
-    #[test]
-    fn test_check_github_cli_returns_bool() {
-        let result = check_github_cli();
-        assert!(result == true || result == false);
+    #[tokio::test]
+    async fn test_check_github_cli_reports_path_when_available() {
+        let status = check_github_cli().await;
+        assert_eq!(status.available, status.path.is_some());
     }
 
     #[test]
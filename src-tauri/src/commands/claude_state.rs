@@ -3,7 +3,6 @@
 
 use bollard::container::LogOutput;
 use bollard::exec::{CreateExecOptions, StartExecResults};
-use bollard::Docker;
 use futures::StreamExt;
 use std::collections::HashMap;
 use std::sync::OnceLock;
@@ -57,7 +56,7 @@ impl ClaudeStateManager {
             let mut last_state = String::new();
 
             // Connect to Docker
-            let docker = match Docker::connect_with_local_defaults() {
+            let docker = match crate::docker::connect_engine() {
                 Ok(d) => d,
                 Err(e) => {
                     warn!(error = ?e, "Failed to connect to Docker for state polling");
@@ -40,7 +40,11 @@ fn resolve_backend(environment_id: &str) -> Result<Backend, String> {
                 .container_id
                 .clone()
                 .ok_or_else(|| "container environment has no container id".to_string())?;
-            Ok(Backend::Container { container_id })
+            let config = storage.load_config().map_err(|e| e.to_string())?;
+            Ok(Backend::Container {
+                container_id,
+                workspace: config.workspace_path(&env.project_id).to_string(),
+            })
         }
     }
 }
@@ -50,7 +54,7 @@ fn resolve_backend(environment_id: &str) -> Result<Backend, String> {
 fn workspace_and_claude_home(backend: &Backend) -> (String, String) {
     let workspace = match backend {
         Backend::Local { cwd } => cwd.clone(),
-        Backend::Container { .. } => "/workspace".to_string(),
+        Backend::Container { workspace, .. } => workspace.clone(),
     };
     let claude_home = match backend {
         Backend::Local { .. } => {
@@ -486,7 +490,7 @@ pub async fn claude_tmux_create_interactive_terminal(
                 .await
                 .map_err(|e| e.to_string())
         }
-        Backend::Container { container_id } => {
+        Backend::Container { container_id, .. } => {
             let manager = get_terminal_manager()
                 .ok_or_else(|| "Terminal manager not initialized".to_string())?;
             manager
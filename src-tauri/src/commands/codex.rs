@@ -141,7 +141,7 @@ fn resolve_container_dist_path(dist_path: &Path, dist_file_path: &Path) -> Resul
 
 fn build_codex_bridge_start_command(raw_event_logging: bool) -> String {
     let command = r#"
-        cd /workspace
+        cd "${WORKSPACE_DIR:-/workspace}"
         rm -f /tmp/codex-bridge.log
         mkdir -p /tmp/orkestrator-ai
         source /etc/profile 2>/dev/null || true
@@ -152,7 +152,7 @@ fn build_codex_bridge_start_command(raw_event_logging: bool) -> String {
         orkestrator_source_runtime_env 2>/dev/null || true
         export PORT=4098
         export HOSTNAME=0.0.0.0
-        export CWD=/workspace
+        export CWD="${WORKSPACE_DIR:-/workspace}"
         if [ -n "${CODEX_CLI_PATH:-}" ] && [ -x "$CODEX_CLI_PATH" ]; then
             export CODEX_PATH="$CODEX_CLI_PATH"
         elif [ -x /usr/local/share/npm-global/bin/codex ]; then
@@ -1,6 +1,21 @@
 // Configuration management Tauri commands
 
-use crate::models::{AppConfig, GlobalConfig, RepositoryConfig};
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+use tracing::{error, info, instrument, warn};
+
+use super::environments::{
+    resolve_clone_depth, resolve_environment_base_branch, resolve_restart_policy,
+    validate_default_sessions,
+};
+use crate::docker::firewall::effective_allowed_domains;
+use crate::models::{
+    is_valid_accelerator, is_valid_docker_network, is_valid_menu_action, is_valid_workspace_path,
+    parse_native_server_host, AgentStyle, AppConfig, ClaudeMode, ClaudeNativeBackend, CodexMode,
+    ContainerResources, DefaultAgent, Environment, EnvironmentType, GlobalConfig,
+    NetworkAccessMode, OpenCodeMode, PortMapping, RepositoryConfig, RestartPolicy,
+    TerminalAppearance,
+};
 use crate::storage::{get_storage, StorageError};
 
 /// Convert storage errors to string for Tauri
@@ -8,6 +23,245 @@ fn storage_error_to_string(err: StorageError) -> String {
     err.to_string()
 }
 
+/// Allowed range for the terminal scrollback buffer (lines)
+const SCROLLBACK_RANGE: std::ops::RangeInclusive<u32> = 100..=100_000;
+/// Allowed range for the terminal font size (pixels)
+const FONT_SIZE_RANGE: std::ops::RangeInclusive<u32> = 6..=72;
+/// Allowed range for the terminal input/output channel capacity (chunks)
+const CHANNEL_CAPACITY_RANGE: std::ops::RangeInclusive<u32> = 16..=65_536;
+/// Allowed range for the per-session terminal output buffer (bytes). The
+/// upper bound matches what storage keeps of a saved buffer.
+const BUFFER_MAX_BYTES_RANGE: std::ops::RangeInclusive<u32> = 16 * 1024..=500 * 1024;
+/// Allowed range for the in-memory log buffer (lines)
+const LOG_BUFFER_LINES_RANGE: std::ops::RangeInclusive<u32> = 100..=50_000;
+/// Allowed range for the word cap on AI-generated environment names
+const NAME_MAX_WORDS_RANGE: std::ops::RangeInclusive<u32> = 1..=10;
+/// Longest quiet period (ms) before settings changes are written
+const CONFIG_SAVE_DEBOUNCE_MS_MAX: u64 = 10_000;
+
+/// Event payload emitted when terminal appearance or scrollback settings change,
+/// so already-open terminals can apply them without reconnecting
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalAppearanceChangedPayload {
+    pub terminal_appearance: TerminalAppearance,
+    pub terminal_scrollback: u32,
+}
+
+/// A single invalid config value, addressed by its camelCase JSON path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigFieldError {
+    /// Path to the field, e.g. `global.containerResources.cpuCores`
+    pub field_path: String,
+    /// Human-readable description of the problem
+    pub message: String,
+}
+
+/// Whether a string is a `#RGB` or `#RRGGBB` hex color
+fn is_valid_hex_color(value: &str) -> bool {
+    value
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Check a config for values that deserialize fine but would break the app
+/// (e.g. zero CPU cores). Returns an empty list when the config is valid.
+pub fn validate_config(config: &AppConfig) -> Vec<ConfigFieldError> {
+    let mut errors = Vec::new();
+    let mut push = |field_path: &str, message: String| {
+        errors.push(ConfigFieldError {
+            field_path: field_path.to_string(),
+            message,
+        });
+    };
+    let global = &config.global;
+
+    if global.container_resources.cpu_cores < 1 {
+        push(
+            "global.containerResources.cpuCores",
+            "must be at least 1".to_string(),
+        );
+    }
+    if global.container_resources.memory_gb < 1 {
+        push(
+            "global.containerResources.memoryGb",
+            "must be at least 1".to_string(),
+        );
+    }
+    if !SCROLLBACK_RANGE.contains(&global.terminal_scrollback) {
+        push(
+            "global.terminalScrollback",
+            format!(
+                "must be between {} and {} lines",
+                SCROLLBACK_RANGE.start(),
+                SCROLLBACK_RANGE.end()
+            ),
+        );
+    }
+    if !CHANNEL_CAPACITY_RANGE.contains(&global.terminal_channel_capacity) {
+        push(
+            "global.terminalChannelCapacity",
+            format!(
+                "must be between {} and {} chunks",
+                CHANNEL_CAPACITY_RANGE.start(),
+                CHANNEL_CAPACITY_RANGE.end()
+            ),
+        );
+    }
+    if !BUFFER_MAX_BYTES_RANGE.contains(&global.terminal_buffer_max_bytes) {
+        push(
+            "global.terminalBufferMaxBytes",
+            format!(
+                "must be between {} and {} bytes",
+                BUFFER_MAX_BYTES_RANGE.start(),
+                BUFFER_MAX_BYTES_RANGE.end()
+            ),
+        );
+    }
+    if !LOG_BUFFER_LINES_RANGE.contains(&global.log_buffer_lines) {
+        push(
+            "global.logBufferLines",
+            format!(
+                "must be between {} and {} lines",
+                LOG_BUFFER_LINES_RANGE.start(),
+                LOG_BUFFER_LINES_RANGE.end()
+            ),
+        );
+    }
+    if global.config_save_debounce_ms > CONFIG_SAVE_DEBOUNCE_MS_MAX {
+        push(
+            "global.configSaveDebounceMs",
+            format!("must be at most {} ms", CONFIG_SAVE_DEBOUNCE_MS_MAX),
+        );
+    }
+    if !FONT_SIZE_RANGE.contains(&global.terminal_appearance.font_size) {
+        push(
+            "global.terminalAppearance.fontSize",
+            format!(
+                "must be between {} and {} pixels",
+                FONT_SIZE_RANGE.start(),
+                FONT_SIZE_RANGE.end()
+            ),
+        );
+    }
+    if global.terminal_appearance.font_family.trim().is_empty() {
+        push(
+            "global.terminalAppearance.fontFamily",
+            "cannot be empty".to_string(),
+        );
+    }
+    if !is_valid_hex_color(&global.terminal_appearance.background_color) {
+        push(
+            "global.terminalAppearance.backgroundColor",
+            "must be a hex color like #RGB or #RRGGBB".to_string(),
+        );
+    }
+    if !NAME_MAX_WORDS_RANGE.contains(&global.environment_name_max_words) {
+        push(
+            "global.environmentNameMaxWords",
+            format!(
+                "must be between {} and {} words",
+                NAME_MAX_WORDS_RANGE.start(),
+                NAME_MAX_WORDS_RANGE.end()
+            ),
+        );
+    }
+    if parse_native_server_host(&global.native_server_host).is_none() {
+        push(
+            "global.nativeServerHost",
+            "must be 127.0.0.1 (this machine only) or 0.0.0.0 / :: (all interfaces)".to_string(),
+        );
+    }
+
+    let mut seen_actions = std::collections::HashSet::new();
+    let mut seen_accelerators = std::collections::HashSet::new();
+    for (index, binding) in global.keybindings.iter().enumerate() {
+        if !is_valid_menu_action(&binding.action) {
+            push(
+                &format!("global.keybindings[{}].action", index),
+                "must use lowercase letters, digits and underscores".to_string(),
+            );
+        } else if !seen_actions.insert(binding.action.as_str()) {
+            push(
+                &format!("global.keybindings[{}].action", index),
+                format!("duplicate action \"{}\"", binding.action),
+            );
+        }
+        if binding.label.trim().is_empty() {
+            push(
+                &format!("global.keybindings[{}].label", index),
+                "cannot be empty".to_string(),
+            );
+        }
+        if let Some(accelerator) = &binding.accelerator {
+            if !is_valid_accelerator(accelerator) {
+                push(
+                    &format!("global.keybindings[{}].accelerator", index),
+                    "must be modifiers followed by one key, like CmdOrCtrl+Shift+N".to_string(),
+                );
+            } else if !seen_accelerators.insert(accelerator.replace(' ', "").to_uppercase()) {
+                push(
+                    &format!("global.keybindings[{}].accelerator", index),
+                    format!("\"{}\" is already used by another action", accelerator),
+                );
+            }
+        }
+    }
+
+    let mut project_ids: Vec<&String> = config.repositories.keys().collect();
+    project_ids.sort();
+    for project_id in project_ids {
+        let repo = &config.repositories[project_id];
+        if let Some(path) = &repo.workspace_path {
+            if !is_valid_workspace_path(path) {
+                push(
+                    &format!("repositories.{}.workspacePath", project_id),
+                    "must be an absolute path like /workspace".to_string(),
+                );
+            }
+        }
+        if let Some(network) = &repo.docker_network {
+            if !network.trim().is_empty() && !is_valid_docker_network(network.trim()) {
+                push(
+                    &format!("repositories.{}.dockerNetwork", project_id),
+                    "must be a Docker network name (letters, digits, _ . -), not host or none"
+                        .to_string(),
+                );
+            }
+        }
+        if repo.clone_depth == Some(0) {
+            push(
+                &format!("repositories.{}.cloneDepth", project_id),
+                "must be at least 1 (omit it for a full clone)".to_string(),
+            );
+        }
+        if let Some(sessions) = &repo.default_sessions {
+            if let Err(message) = validate_default_sessions(sessions) {
+                push(
+                    &format!("repositories.{}.defaultSessions", project_id),
+                    message,
+                );
+            }
+        }
+    }
+
+    errors
+}
+
+/// Reject configs that fail [`validate_config`], listing every invalid field
+pub(crate) fn ensure_valid_config(config: &AppConfig) -> Result<(), String> {
+    let errors = validate_config(config);
+    if errors.is_empty() {
+        return Ok(());
+    }
+    let details: Vec<String> = errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field_path, e.message))
+        .collect();
+    Err(format!("Invalid configuration: {}", details.join("; ")))
+}
+
 /// Get the application configuration
 #[tauri::command]
 pub async fn get_config() -> Result<AppConfig, String> {
@@ -18,6 +272,7 @@ pub async fn get_config() -> Result<AppConfig, String> {
 /// Save the application configuration
 #[tauri::command]
 pub async fn save_config(config: AppConfig) -> Result<(), String> {
+    ensure_valid_config(&config)?;
     let storage = get_storage().map_err(storage_error_to_string)?;
     storage
         .save_config(&config)
@@ -34,35 +289,117 @@ pub async fn get_global_config() -> Result<GlobalConfig, String> {
 
 /// Update the global configuration
 #[tauri::command]
-pub async fn update_global_config(global: GlobalConfig) -> Result<AppConfig, String> {
-    println!("[config] update_global_config called");
-    println!("[config] Received global config: {:?}", global);
+#[instrument(skip_all)]
+pub async fn update_global_config(
+    app_handle: tauri::AppHandle,
+    global: GlobalConfig,
+) -> Result<AppConfig, String> {
+    update_global_config_with(&app_handle, |_| Ok(global))
+}
+
+/// Merge `patch` into the global configuration and save it. Only the keys
+/// present in `patch` change (nested objects merge key by key, `null` clears
+/// optional fields), so settings a frontend doesn't know about are kept.
+/// Unknown keys are ignored. The merged config is validated before saving.
+#[tauri::command]
+#[instrument(skip_all)]
+pub async fn update_global_config_partial(
+    app_handle: tauri::AppHandle,
+    patch: serde_json::Value,
+) -> Result<AppConfig, String> {
+    update_global_config_with(&app_handle, |global| {
+        apply_global_config_patch(global, patch)
+    })
+}
 
+/// Apply a JSON merge patch to a copy of `global`
+fn apply_global_config_patch(
+    global: &GlobalConfig,
+    patch: serde_json::Value,
+) -> Result<GlobalConfig, String> {
+    if !patch.is_object() {
+        return Err("Config patch must be a JSON object".to_string());
+    }
+
+    let mut merged = serde_json::to_value(global).map_err(|e| e.to_string())?;
+    merge_json(&mut merged, patch);
+    serde_json::from_value(merged).map_err(|e| format!("Invalid config patch: {}", e))
+}
+
+/// Merge `patch` into `target`: objects merge key by key, any other value
+/// replaces what was there
+fn merge_json(target: &mut serde_json::Value, patch: serde_json::Value) {
+    match (target, patch) {
+        (serde_json::Value::Object(target), serde_json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                match target.get_mut(&key) {
+                    Some(existing) if existing.is_object() && value.is_object() => {
+                        merge_json(existing, value)
+                    }
+                    _ => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
+
+/// Replace the global config with what `update` makes of the stored one,
+/// then validate and save it in one locked storage operation, so concurrent
+/// updates can't overwrite each other. Applies the side effects of the
+/// settings that changed.
+fn update_global_config_with(
+    app_handle: &tauri::AppHandle,
+    update: impl FnOnce(&GlobalConfig) -> Result<GlobalConfig, String>,
+) -> Result<AppConfig, String> {
     let storage = get_storage().map_err(|e| {
         let err = storage_error_to_string(e);
-        println!("[config] Failed to get storage: {}", err);
+        error!(error = %err, "Failed to get storage");
         err
     })?;
 
-    let mut config = storage.load_config().map_err(|e| {
-        let err = storage_error_to_string(e);
-        println!("[config] Failed to load config: {}", err);
-        err
-    })?;
+    // Settings UIs send rapid updates (slider drags), so coalesce the writes
+    let (previous, config) = storage
+        .update_config_scheduled(|config| {
+            config.global = update(&config.global).map_err(StorageError::InvalidConfig)?;
+            ensure_valid_config(config).map_err(StorageError::InvalidConfig)
+        })
+        .map_err(|e| {
+            if !matches!(e, StorageError::InvalidConfig(_)) {
+                error!(error = %e, "Failed to load or save config");
+            }
+            storage_error_to_string(e)
+        })?;
 
-    println!(
-        "[config] Loaded existing config version: {}",
-        config.version
-    );
-    config.global = global;
+    info!("Global config saved");
 
-    storage.save_config(&config).map_err(|e| {
-        let err = storage_error_to_string(e);
-        println!("[config] Failed to save config: {}", err);
-        err
-    })?;
+    let terminal_changed = previous.global.terminal_appearance != config.global.terminal_appearance
+        || previous.global.terminal_scrollback != config.global.terminal_scrollback;
+    let keybindings_changed = previous.global.keybindings != config.global.keybindings;
+    let engine_changed = previous.global.container_engine != config.global.container_engine;
+
+    crate::logging::set_buffer_lines(config.global.log_buffer_lines);
+
+    if keybindings_changed {
+        crate::menu::refresh_app_menu(app_handle);
+    }
+
+    if engine_changed {
+        crate::docker::reset_docker_client();
+    }
+
+    if terminal_changed {
+        let payload = TerminalAppearanceChangedPayload {
+            terminal_appearance: config.global.terminal_appearance.clone(),
+            terminal_scrollback: config.global.terminal_scrollback,
+        };
+        if let Err(e) = app_handle.emit("terminal-appearance-changed", payload) {
+            warn!(error = %e, "Failed to emit terminal-appearance-changed");
+        }
+    }
 
-    println!("[config] Config saved successfully");
     Ok(config)
 }
 
@@ -88,20 +425,365 @@ pub async fn update_repository_config(
     let storage = get_storage().map_err(storage_error_to_string)?;
     let mut config = storage.load_config().map_err(storage_error_to_string)?;
     config.repositories.insert(project_id, repo_config);
+    ensure_valid_config(&config)?;
     storage
         .save_config(&config)
         .map_err(storage_error_to_string)?;
     Ok(config)
 }
 
+/// Copy the repository config of one project onto another. Fails if the
+/// source has none, or if the target already has one and `force` is false.
+fn copy_repository_entry(
+    config: &mut AppConfig,
+    from_project_id: &str,
+    to_project_id: &str,
+    force: bool,
+) -> Result<(), String> {
+    if from_project_id == to_project_id {
+        return Err("Cannot copy a repository config onto the same project".to_string());
+    }
+    let repo_config = config
+        .repositories
+        .get(from_project_id)
+        .cloned()
+        .ok_or_else(|| format!("Project {} has no repository config", from_project_id))?;
+    if !force && config.repositories.contains_key(to_project_id) {
+        return Err(format!(
+            "Project {} already has a repository config; pass force to overwrite it",
+            to_project_id
+        ));
+    }
+    config
+        .repositories
+        .insert(to_project_id.to_string(), repo_config);
+    Ok(())
+}
+
+/// Copy a project's repository config (branches, ports, files to copy,
+/// agent defaults, ...) to another project, e.g. a fork.
+/// An existing config of the target is only overwritten with `force`.
+#[tauri::command]
+pub async fn copy_repository_config(
+    from_project_id: String,
+    to_project_id: String,
+    force: Option<bool>,
+) -> Result<AppConfig, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    for project_id in [&from_project_id, &to_project_id] {
+        storage
+            .get_project(project_id)
+            .map_err(storage_error_to_string)?
+            .ok_or_else(|| format!("Project not found: {}", project_id))?;
+    }
+
+    let (config, ()) = storage
+        .update_config(|config| {
+            copy_repository_entry(
+                config,
+                &from_project_id,
+                &to_project_id,
+                force.unwrap_or(false),
+            )
+            .map_err(StorageError::InvalidConfig)?;
+            ensure_valid_config(config).map_err(StorageError::InvalidConfig)
+        })
+        .map_err(storage_error_to_string)?;
+    Ok(config)
+}
+
+/// Fully resolved settings for an environment after applying the
+/// environment → repository → global override chain. Secrets are reported
+/// only as presence flags.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveEnvironmentConfig {
+    pub environment_id: String,
+    pub project_id: String,
+    pub environment_type: EnvironmentType,
+
+    // === Container ===
+    /// CPU and memory limits applied to the container
+    pub container_resources: ContainerResources,
+    /// Docker restart policy applied when the container is created
+    pub restart_policy: RestartPolicy,
+    /// Branch new environment branches are created from (None = repo default)
+    pub base_branch: Option<String>,
+    /// Branch pull requests target
+    pub pr_base_branch: String,
+    /// Repository checkout path inside the container
+    pub workspace_path: String,
+    /// Commits of history the container clones (None = full history)
+    pub clone_depth: Option<u32>,
+
+    // === Network ===
+    /// Docker network the container joins (None = default bridge)
+    pub docker_network: Option<String>,
+    pub network_access_mode: NetworkAccessMode,
+    /// Domains whitelisted in restricted mode (global plus environment additions)
+    pub allowed_domains: Vec<String>,
+    /// Whether the restricted-mode firewall is currently bypassed
+    pub firewall_bypassed: bool,
+
+    // === Ports ===
+    pub port_mappings: Vec<PortMapping>,
+    /// Container entry port exposed with a dynamic host port
+    pub entry_port: Option<u16>,
+
+    // === Environment variables and files ===
+    /// Patterns of env files copied into the environment
+    pub env_file_patterns: Vec<String>,
+    /// Additional files copied from the project's local path
+    pub files_to_copy: Vec<String>,
+    /// Whether an Anthropic API key will be injected
+    pub has_anthropic_api_key: bool,
+    /// Whether a GitHub token will be injected
+    pub has_github_token: bool,
+
+    // === Session defaults ===
+    pub default_agent: DefaultAgent,
+    pub claude_mode: ClaudeMode,
+    pub claude_native_backend: ClaudeNativeBackend,
+    pub opencode_mode: OpenCodeMode,
+    pub codex_mode: CodexMode,
+    /// Model new sessions of the default agent start with
+    pub default_model: String,
+    /// Effort/thinking level for the default agent (None = agent default)
+    pub default_effort: Option<String>,
+}
+
+fn is_set(value: Option<&str>) -> bool {
+    value.is_some_and(|v| !v.trim().is_empty())
+}
+
+/// Merge global, repository, and environment settings into the values an
+/// environment actually uses.
+pub fn compute_effective_environment_config(
+    environment: &Environment,
+    config: &AppConfig,
+) -> EffectiveEnvironmentConfig {
+    let global = &config.global;
+    let repo = config
+        .repositories
+        .get(&environment.project_id)
+        .cloned()
+        .unwrap_or_default();
+    let agent_style = repo.agent_style;
+
+    let default_agent = environment
+        .default_agent
+        .or(repo.default_agent)
+        .unwrap_or(global.default_agent);
+
+    let claude_mode = environment
+        .claude_mode
+        .or(agent_style.map(|style| match style {
+            AgentStyle::Terminal => ClaudeMode::Terminal,
+            AgentStyle::Native => ClaudeMode::Native,
+        }))
+        .unwrap_or(global.claude_mode);
+    let opencode_mode = environment
+        .opencode_mode
+        .or(agent_style.map(|style| match style {
+            AgentStyle::Terminal => OpenCodeMode::Terminal,
+            AgentStyle::Native => OpenCodeMode::Native,
+        }))
+        .unwrap_or(global.opencode_mode);
+    let codex_mode = environment
+        .codex_mode
+        .or(agent_style.map(|style| match style {
+            AgentStyle::Terminal => CodexMode::Terminal,
+            AgentStyle::Native => CodexMode::Native,
+        }))
+        .unwrap_or(global.codex_mode);
+
+    let environment_model = match default_agent {
+        DefaultAgent::Opencode => environment.opencode_model_override.clone(),
+        _ => None,
+    };
+    let default_model = environment_model
+        .or_else(|| repo.default_model.clone())
+        .filter(|m| !m.trim().is_empty())
+        .unwrap_or_else(|| match default_agent {
+            DefaultAgent::Claude => global.claude_model.clone(),
+            DefaultAgent::Opencode => global.opencode_model.clone(),
+            DefaultAgent::Codex => global.codex_model.clone(),
+        });
+    let default_effort = repo
+        .default_effort
+        .clone()
+        .filter(|e| !e.trim().is_empty())
+        .or_else(|| {
+            (default_agent == DefaultAgent::Codex).then(|| global.codex_reasoning_effort.clone())
+        });
+
+    EffectiveEnvironmentConfig {
+        environment_id: environment.id.clone(),
+        project_id: environment.project_id.clone(),
+        environment_type: environment.environment_type.clone(),
+        container_resources: global.container_resources.clone(),
+        restart_policy: resolve_restart_policy(config, environment),
+        base_branch: resolve_environment_base_branch(Some(config), environment),
+        pr_base_branch: repo.pr_base_branch.clone(),
+        workspace_path: repo.workspace_path().to_string(),
+        clone_depth: resolve_clone_depth(config, environment),
+        docker_network: repo.docker_network().map(str::to_string),
+        network_access_mode: environment.network_access_mode.clone(),
+        allowed_domains: effective_allowed_domains(environment, &global.allowed_domains),
+        firewall_bypassed: environment.firewall_bypassed,
+        port_mappings: environment.port_mappings.clone().unwrap_or_default(),
+        entry_port: repo.entry_port,
+        env_file_patterns: global.env_file_patterns.clone(),
+        files_to_copy: repo.files_to_copy.clone().unwrap_or_default(),
+        has_anthropic_api_key: is_set(global.anthropic_api_key.as_deref()),
+        has_github_token: is_set(global.github_token.as_deref()),
+        default_agent,
+        claude_mode,
+        claude_native_backend: environment
+            .claude_native_backend
+            .or(repo.claude_native_backend)
+            .unwrap_or(global.claude_native_backend),
+        opencode_mode,
+        codex_mode,
+        default_model,
+        default_effort,
+    }
+}
+
+/// Get the fully merged configuration an environment will use
+#[tauri::command]
+pub async fn get_effective_environment_config(
+    environment_id: String,
+) -> Result<EffectiveEnvironmentConfig, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+    let config = storage.load_config().map_err(storage_error_to_string)?;
+    Ok(compute_effective_environment_config(&environment, &config))
+}
+
+/// Validate a config without saving it, so the settings UI can show
+/// field-level errors before submitting
+#[tauri::command]
+pub async fn check_config(config: AppConfig) -> Result<Vec<ConfigFieldError>, String> {
+    Ok(validate_config(&config))
+}
+
 /// Get the path where debug logs are written
 #[tauri::command]
 pub async fn get_log_directory() -> Result<String, String> {
-    Ok(crate::log_dir_path().to_string_lossy().to_string())
+    Ok(crate::logging::log_dir_path().to_string_lossy().to_string())
+}
+
+/// Get the log file currently being written, or `None` when saving logs for
+/// debugging is disabled
+#[tauri::command]
+pub async fn get_log_file_path() -> Result<Option<String>, String> {
+    Ok(crate::logging::current_log_file().map(|path| path.to_string_lossy().to_string()))
+}
+
+/// Get recently captured log lines, oldest first. `min_level` defaults to
+/// "trace" (everything) and `limit` to the whole buffer.
+#[tauri::command]
+pub async fn get_recent_logs(
+    limit: Option<usize>,
+    min_level: Option<String>,
+) -> Result<Vec<crate::logging::LogRecord>, String> {
+    crate::logging::recent_records(
+        limit.unwrap_or(usize::MAX),
+        min_level.as_deref().unwrap_or("trace"),
+    )
+}
+
+/// Change the log level until the app restarts. Returns the applied filter.
+#[tauri::command]
+pub async fn set_log_level(level: String) -> Result<String, String> {
+    let directive = crate::logging::set_level(&level)?;
+    info!(filter = %directive, "Log level changed");
+    Ok(directive)
+}
+
+/// Open the app data directory in the system file manager (Finder / Explorer)
+#[tauri::command]
+pub async fn open_data_dir(app: tauri::AppHandle) -> Result<String, String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    // Write any debounced settings change so the files on disk are current
+    storage
+        .flush_pending_config()
+        .map_err(storage_error_to_string)?;
+    let data_dir = storage.data_dir().to_string_lossy().to_string();
+    app.opener()
+        .open_path(data_dir.as_str(), None::<&str>)
+        .map_err(|e| format!("Failed to open data directory: {}", e))?;
+    Ok(data_dir)
+}
+
+/// Reveal config.json in the system file manager, selecting it where the
+/// platform supports that and opening the containing directory otherwise
+#[tauri::command]
+pub async fn open_config_file(app: tauri::AppHandle) -> Result<String, String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    // Write any debounced settings change so the revealed file is current
+    storage
+        .flush_pending_config()
+        .map_err(storage_error_to_string)?;
+    let config_path = storage.config_path();
+    if !config_path.exists() {
+        // Write the defaults so there is a file to reveal
+        let config = storage.load_config().map_err(storage_error_to_string)?;
+        storage
+            .save_config(&config)
+            .map_err(storage_error_to_string)?;
+    }
+
+    app.opener()
+        .reveal_item_in_dir(&config_path)
+        .map_err(|e| format!("Failed to reveal config file: {}", e))?;
+    Ok(config_path.to_string_lossy().to_string())
 }
 
 #[cfg(test)]
 mod tests {
+    #[test]
+    fn test_apply_global_config_patch_keeps_unpatched_fields() {
+        use super::apply_global_config_patch;
+        use crate::models::GlobalConfig;
+
+        let mut global = GlobalConfig::default();
+        global.github_token = Some("token".to_string());
+        global.container_resources.memory_gb = 16;
+
+        let patched = apply_global_config_patch(
+            &global,
+            serde_json::json!({
+                "containerResources": { "cpuCores": 6 },
+                "minFreeDiskGb": 10,
+                "fieldFromANewerVersion": true,
+            }),
+        )
+        .unwrap();
+        assert_eq!(patched.container_resources.cpu_cores, 6);
+        assert_eq!(patched.container_resources.memory_gb, 16);
+        assert_eq!(patched.min_free_disk_gb, 10);
+        assert_eq!(patched.github_token.as_deref(), Some("token"));
+
+        let cleared =
+            apply_global_config_patch(&global, serde_json::json!({ "githubToken": null })).unwrap();
+        assert_eq!(cleared.github_token, None);
+
+        assert!(apply_global_config_patch(&global, serde_json::json!([1, 2])).is_err());
+        assert!(
+            apply_global_config_patch(&global, serde_json::json!({ "minFreeDiskGb": "ten" }))
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_config_defaults() {
         use crate::models::{AppConfig, GlobalConfig, RepositoryConfig};
@@ -116,4 +798,153 @@ mod tests {
         let repo = RepositoryConfig::default();
         assert_eq!(repo.default_branch, "main");
     }
+
+    #[test]
+    fn test_validate_config_accepts_defaults() {
+        use super::validate_config;
+        use crate::models::AppConfig;
+
+        assert!(validate_config(&AppConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_validate_config_reports_field_errors() {
+        use super::validate_config;
+        use crate::models::AppConfig;
+
+        let mut config = AppConfig::default();
+        config.global.container_resources.cpu_cores = 0;
+        config.global.container_resources.memory_gb = 0;
+        config.global.terminal_scrollback = u32::MAX;
+        config.global.terminal_appearance.font_size = 2;
+        config.global.terminal_appearance.background_color = "#12345g".to_string();
+        config.global.environment_name_max_words = 0;
+        config.global.native_server_host = "localhost".to_string();
+        config.global.keybindings[1].accelerator = Some("CmdOrCtrl+".to_string());
+        config.global.keybindings[2].accelerator = Some("CmdOrCtrl+=".to_string());
+        config.repositories.insert(
+            "project-1".to_string(),
+            crate::models::RepositoryConfig {
+                workspace_path: Some("workspace".to_string()),
+                docker_network: Some("host".to_string()),
+                clone_depth: Some(0),
+                default_sessions: Some(vec![
+                    crate::models::SessionType::Plain;
+                    crate::storage::Storage::MAX_SESSIONS_PER_ENVIRONMENT
+                        + 1
+                ]),
+                ..Default::default()
+            },
+        );
+
+        let paths: Vec<String> = validate_config(&config)
+            .into_iter()
+            .map(|e| e.field_path)
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "global.containerResources.cpuCores",
+                "global.containerResources.memoryGb",
+                "global.terminalScrollback",
+                "global.terminalAppearance.fontSize",
+                "global.terminalAppearance.backgroundColor",
+                "global.environmentNameMaxWords",
+                "global.nativeServerHost",
+                "global.keybindings[1].accelerator",
+                "global.keybindings[2].accelerator",
+                "repositories.project-1.workspacePath",
+                "repositories.project-1.dockerNetwork",
+                "repositories.project-1.cloneDepth",
+                "repositories.project-1.defaultSessions",
+            ]
+        );
+
+        config.global.terminal_appearance.background_color = "#abc".to_string();
+        assert!(validate_config(&config)
+            .iter()
+            .all(|e| e.field_path != "global.terminalAppearance.backgroundColor"));
+    }
+
+    #[test]
+    fn test_copy_repository_entry_requires_force_to_overwrite() {
+        use super::copy_repository_entry;
+        use crate::models::{AppConfig, RepositoryConfig};
+
+        let mut config = AppConfig::default();
+        config.repositories.insert(
+            "upstream".to_string(),
+            RepositoryConfig {
+                default_branch: "develop".to_string(),
+                entry_port: Some(3000),
+                ..RepositoryConfig::default()
+            },
+        );
+
+        assert!(copy_repository_entry(&mut config, "missing", "fork", false).is_err());
+        assert!(copy_repository_entry(&mut config, "upstream", "upstream", true).is_err());
+
+        copy_repository_entry(&mut config, "upstream", "fork", false).unwrap();
+        assert_eq!(config.repositories["fork"].default_branch, "develop");
+        assert_eq!(config.repositories["fork"].entry_port, Some(3000));
+
+        config
+            .repositories
+            .get_mut("upstream")
+            .unwrap()
+            .default_branch = "main".to_string();
+        assert!(copy_repository_entry(&mut config, "upstream", "fork", false).is_err());
+        assert_eq!(config.repositories["fork"].default_branch, "develop");
+
+        copy_repository_entry(&mut config, "upstream", "fork", true).unwrap();
+        assert_eq!(config.repositories["fork"].default_branch, "main");
+    }
+
+    #[test]
+    fn test_effective_environment_config_applies_overrides_in_order() {
+        use super::compute_effective_environment_config;
+        use crate::models::{
+            AgentStyle, AppConfig, ClaudeMode, CodexMode, DefaultAgent, Environment, OpenCodeMode,
+            RepositoryConfig, RestartPolicy,
+        };
+
+        let mut config = AppConfig::default();
+        config.global.github_token = Some("  ".to_string());
+        config.global.allowed_domains = vec!["github.com".to_string()];
+        config.repositories.insert(
+            "project-123".to_string(),
+            RepositoryConfig {
+                default_agent: Some(DefaultAgent::Codex),
+                agent_style: Some(AgentStyle::Native),
+                restart_policy: Some(RestartPolicy::OnFailure),
+                default_model: Some("gpt-5".to_string()),
+                ..RepositoryConfig::default()
+            },
+        );
+
+        let mut env = Environment::new("project-123".to_string());
+        env.opencode_mode = Some(OpenCodeMode::Terminal);
+        env.restart_policy = Some(RestartPolicy::UnlessStopped);
+        env.allowed_domains = Some(vec!["api.example.com".to_string()]);
+
+        let effective = compute_effective_environment_config(&env, &config);
+        assert_eq!(effective.default_agent, DefaultAgent::Codex);
+        assert_eq!(effective.claude_mode, ClaudeMode::Native);
+        assert_eq!(effective.codex_mode, CodexMode::Native);
+        assert_eq!(effective.opencode_mode, OpenCodeMode::Terminal);
+        assert_eq!(effective.restart_policy, RestartPolicy::UnlessStopped);
+        assert_eq!(effective.default_model, "gpt-5");
+        assert_eq!(
+            effective.allowed_domains,
+            vec!["github.com", "api.example.com"]
+        );
+        assert!(!effective.has_github_token);
+
+        // Environments in projects without repo config fall back to globals
+        let other = Environment::new("other-project".to_string());
+        let effective = compute_effective_environment_config(&other, &config);
+        assert_eq!(effective.default_agent, config.global.default_agent);
+        assert_eq!(effective.restart_policy, RestartPolicy::No);
+        assert_eq!(effective.default_model, config.global.claude_model);
+    }
 }
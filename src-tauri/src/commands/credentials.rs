@@ -1,14 +1,36 @@
 // Credentials management Tauri commands
 
 use crate::credentials;
+use crate::models::EnvironmentStatus;
 use serde::Serialize;
+use tracing::{info, warn};
 
-/// Response for credential status check
+/// Response for credential status check.
+/// Never includes the tokens themselves.
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CredentialStatus {
     pub available: bool,
     pub expires_at: Option<i64>,
+    /// Whether the access token has already expired
+    pub expired: bool,
+    /// Human-friendly time until (or since) expiry, e.g. "3 days"
+    pub expires_in: Option<String>,
+    /// Subscription plan reported by the keychain, e.g. "pro" or "max"
+    pub subscription_type: Option<String>,
+    pub rate_limit_tier: Option<String>,
+}
+
+/// Outcome of re-injecting credentials into a running container
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerCredentialsRefresh {
+    /// Whether unexpired credentials were found in the keychain
+    pub valid: bool,
+    /// Whether the credentials were written into the container
+    pub injected: bool,
+    pub expires_at: Option<i64>,
+    pub error: Option<String>,
 }
 
 /// Check if Claude credentials are available
@@ -17,17 +39,135 @@ pub fn has_claude_credentials() -> bool {
     credentials::has_claude_credentials()
 }
 
-/// Get credential status (available + expiry)
+/// Get credential status (availability, expiry and subscription info)
 #[tauri::command]
 pub fn get_credential_status() -> CredentialStatus {
     match credentials::get_claude_credentials() {
-        Ok(creds) => CredentialStatus {
-            available: true,
-            expires_at: Some(creds.claude_ai_oauth.expires_at),
-        },
+        Ok(creds) => {
+            let oauth = creds.claude_ai_oauth;
+            let remaining_ms = oauth.expires_at - chrono::Utc::now().timestamp_millis();
+            CredentialStatus {
+                available: true,
+                expires_at: Some(oauth.expires_at),
+                expired: remaining_ms <= 0,
+                expires_in: Some(format_duration_ms(remaining_ms)),
+                subscription_type: oauth.subscription_type,
+                rate_limit_tier: oauth.rate_limit_tier,
+            }
+        }
         Err(_) => CredentialStatus {
             available: false,
             expires_at: None,
+            expired: false,
+            expires_in: None,
+            subscription_type: None,
+            rate_limit_tier: None,
         },
     }
 }
+
+/// Drop the cached keychain credentials and read them again
+#[tauri::command]
+pub fn refresh_credentials() -> CredentialStatus {
+    credentials::invalidate_cached_credentials();
+    get_credential_status()
+}
+
+/// Re-read the keychain credentials (refreshing them if needed) and write them
+/// to `.credentials.json` in an environment's running container, so an
+/// expired Claude session can be fixed without recreating the container
+#[tauri::command]
+pub async fn refresh_container_credentials(
+    environment_id: String,
+) -> Result<ContainerCredentialsRefresh, String> {
+    let storage = crate::storage::get_storage().map_err(|e| e.to_string())?;
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+
+    if environment.status != EnvironmentStatus::Running {
+        return Err("Environment must be running to refresh its credentials".to_string());
+    }
+    let container_id = environment
+        .container_id
+        .ok_or("Environment has no container")?;
+
+    credentials::invalidate_cached_credentials();
+    let creds = match credentials::get_or_refresh_claude_credentials().await {
+        Ok(creds) => creds,
+        Err(e) => {
+            warn!(environment_id = %environment_id, error = %e, "No usable credentials to inject");
+            return Ok(ContainerCredentialsRefresh {
+                valid: false,
+                injected: false,
+                expires_at: None,
+                error: Some(e.to_string()),
+            });
+        }
+    };
+
+    let expires_at = creds.claude_ai_oauth.expires_at;
+    if expires_at <= chrono::Utc::now().timestamp_millis() {
+        return Ok(ContainerCredentialsRefresh {
+            valid: false,
+            injected: false,
+            expires_at: Some(expires_at),
+            error: Some("Keychain credentials have expired".to_string()),
+        });
+    }
+
+    let creds_json = serde_json::to_vec(&creds)
+        .map_err(|e| format!("Failed to serialize credentials: {}", e))?;
+    let error = credentials::sync::push_to_container(&container_id, &creds_json)
+        .await
+        .err();
+    match &error {
+        None => {
+            info!(environment_id = %environment_id, "Injected refreshed credentials into container")
+        }
+        Some(e) => {
+            warn!(environment_id = %environment_id, error = %e, "Failed to inject credentials into container")
+        }
+    }
+
+    Ok(ContainerCredentialsRefresh {
+        valid: true,
+        injected: error.is_none(),
+        expires_at: Some(expires_at),
+        error,
+    })
+}
+
+/// Format a duration in milliseconds using its largest whole unit.
+/// Negative durations are formatted by magnitude.
+fn format_duration_ms(ms: i64) -> String {
+    let minutes = ms.unsigned_abs() / 60_000;
+    let (count, unit) = if minutes >= 24 * 60 {
+        (minutes / (24 * 60), "day")
+    } else if minutes >= 60 {
+        (minutes / 60, "hour")
+    } else {
+        (minutes.max(1), "minute")
+    };
+
+    if count == 1 {
+        format!("1 {}", unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_duration_ms_uses_largest_unit() {
+        assert_eq!(format_duration_ms(3 * 24 * 60 * 60_000 + 5_000), "3 days");
+        assert_eq!(format_duration_ms(90 * 60_000), "1 hour");
+        assert_eq!(format_duration_ms(45 * 60_000), "45 minutes");
+        assert_eq!(format_duration_ms(10_000), "1 minute");
+        assert_eq!(format_duration_ms(-2 * 60 * 60_000), "2 hours");
+    }
+}
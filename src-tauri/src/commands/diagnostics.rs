@@ -0,0 +1,373 @@
+// Diagnostics Tauri command
+// Bundles the onboarding checks into a single report for troubleshooting
+
+use crate::claude_cli;
+use crate::credentials::{self, CredentialsError};
+use crate::docker::{self, EngineBackend};
+use crate::models::ContainerEngineKind;
+use crate::storage::{get_config, get_storage};
+use serde::Serialize;
+
+use super::network::resolve_domain;
+
+/// Hosts that containers and the host CLIs need to reach
+const REQUIRED_HOSTS: &[&str] = &["api.anthropic.com", "github.com"];
+
+const PODMAN_REMEDIATION: &str = "Start the Podman API socket (`podman machine start` on macOS, `systemctl --user start podman.socket` on Linux) and run diagnostics again";
+
+/// Outcome of a single diagnostic check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// One item of the diagnostics report
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticCheck {
+    /// Stable identifier, e.g. "docker_daemon"
+    pub id: &'static str,
+    pub label: &'static str,
+    pub status: DiagnosticStatus,
+    /// What was found
+    pub detail: String,
+    /// How to fix it (omitted for passing checks)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+}
+
+impl DiagnosticCheck {
+    fn pass(id: &'static str, label: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            id,
+            label,
+            status: DiagnosticStatus::Pass,
+            detail: detail.into(),
+            remediation: None,
+        }
+    }
+
+    fn problem(
+        id: &'static str,
+        label: &'static str,
+        status: DiagnosticStatus,
+        detail: impl Into<String>,
+        remediation: impl Into<String>,
+    ) -> Self {
+        Self {
+            id,
+            label,
+            status,
+            detail: detail.into(),
+            remediation: Some(remediation.into()),
+        }
+    }
+}
+
+/// Structured result of `run_diagnostics`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsReport {
+    /// Worst status across all checks
+    pub overall: DiagnosticStatus,
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+impl DiagnosticsReport {
+    fn new(checks: Vec<DiagnosticCheck>) -> Self {
+        let overall = checks
+            .iter()
+            .map(|check| check.status)
+            .max()
+            .unwrap_or(DiagnosticStatus::Pass);
+        Self { overall, checks }
+    }
+}
+
+/// Run every onboarding check and return a single report with a
+/// pass/warn/fail status and remediation hint per item
+#[tauri::command]
+pub async fn run_diagnostics() -> Result<DiagnosticsReport, String> {
+    let mut checks = Vec::new();
+
+    let docker_available = docker::is_docker_available().await;
+    checks.push(check_docker_daemon(docker_available).await);
+    checks.push(check_base_image(docker_available).await);
+    checks.extend(check_clis());
+    checks.push(check_credentials());
+    checks.push(check_network().await);
+
+    Ok(DiagnosticsReport::new(checks))
+}
+
+/// About-style summary of the app and its data, for support and bug reports
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppInfo {
+    pub version: String,
+    /// Git commit the app was built from ("unknown" outside a checkout)
+    pub build_commit: String,
+    /// Operating system and CPU architecture, e.g. "macos aarch64"
+    pub os: String,
+    /// Container engine in use (Docker or Podman)
+    pub container_engine: String,
+    pub data_dir: String,
+    pub project_count: usize,
+    pub environment_count: usize,
+    pub session_count: usize,
+}
+
+/// Get the app version, build, platform, engine, data dir and entity counts
+#[tauri::command]
+pub fn app_info() -> Result<AppInfo, String> {
+    let storage = get_storage().map_err(|e| e.to_string())?;
+    let project_count = storage.load_projects().map_err(|e| e.to_string())?.len();
+    let environment_count = storage
+        .load_environments()
+        .map_err(|e| e.to_string())?
+        .len();
+    let session_count = storage.load_sessions().map_err(|e| e.to_string())?.len();
+
+    Ok(AppInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        build_commit: option_env!("ORKESTRATOR_BUILD_COMMIT")
+            .unwrap_or("unknown")
+            .to_string(),
+        os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        container_engine: docker::engine_backend().name().to_string(),
+        data_dir: storage.data_dir().to_string_lossy().to_string(),
+        project_count,
+        environment_count,
+        session_count,
+    })
+}
+
+async fn check_docker_daemon(docker_available: bool) -> DiagnosticCheck {
+    const ID: &str = "docker_daemon";
+    const LABEL: &str = "Container engine";
+
+    let preference = get_config()
+        .map(|config| config.global.container_engine)
+        .unwrap_or_default();
+    let backend = docker::get_docker_client().map(|client| client.backend());
+
+    if !docker_available {
+        let detail = match &backend {
+            Ok(backend) => format!("{} is not reachable", backend.name()),
+            Err(e) => e.to_string(),
+        };
+        let remediation = match backend {
+            Ok(EngineBackend::Podman) => PODMAN_REMEDIATION,
+            _ if preference == ContainerEngineKind::Podman => PODMAN_REMEDIATION,
+            _ => "Start Docker Desktop (or the Docker daemon) and run diagnostics again",
+        };
+        return DiagnosticCheck::problem(ID, LABEL, DiagnosticStatus::Fail, detail, remediation);
+    }
+
+    let name = backend.map(EngineBackend::name).unwrap_or("Docker");
+    let source = if preference == ContainerEngineKind::Auto {
+        " (auto-detected)"
+    } else {
+        ""
+    };
+    match docker::get_docker_version().await {
+        Ok(version) => DiagnosticCheck::pass(ID, LABEL, format!("{} {}{}", name, version, source)),
+        Err(_) => DiagnosticCheck::pass(ID, LABEL, format!("{} is reachable{}", name, source)),
+    }
+}
+
+async fn check_base_image(docker_available: bool) -> DiagnosticCheck {
+    const ID: &str = "base_image";
+    const LABEL: &str = "Base image";
+    let build_hint = format!(
+        "Build it with `{} build -t {} -f docker/Dockerfile .` from the Orkestrator repository",
+        docker::engine_cli(),
+        docker::BASE_IMAGE
+    );
+
+    if !docker_available {
+        return DiagnosticCheck::problem(
+            ID,
+            LABEL,
+            DiagnosticStatus::Warn,
+            "Skipped because the container engine is not reachable",
+            "Fix the container engine check first",
+        );
+    }
+
+    let exists = match docker::client::get_docker_client() {
+        Ok(client) => client.image_exists(docker::BASE_IMAGE).await,
+        Err(e) => Err(e),
+    };
+    match exists {
+        Ok(true) => DiagnosticCheck::pass(ID, LABEL, format!("{} is present", docker::BASE_IMAGE)),
+        Ok(false) => DiagnosticCheck::problem(
+            ID,
+            LABEL,
+            DiagnosticStatus::Fail,
+            format!("{} is missing", docker::BASE_IMAGE),
+            build_hint,
+        ),
+        Err(e) => DiagnosticCheck::problem(
+            ID,
+            LABEL,
+            DiagnosticStatus::Fail,
+            format!("Failed to inspect {}: {}", docker::BASE_IMAGE, e),
+            build_hint,
+        ),
+    }
+}
+
+fn check_clis() -> Vec<DiagnosticCheck> {
+    let ai_cli = match claude_cli::get_available_ai_cli() {
+        Some(cli) => DiagnosticCheck::pass("ai_cli", "AI CLI", format!("Using {}", cli)),
+        None => DiagnosticCheck::problem(
+            "ai_cli",
+            "AI CLI",
+            DiagnosticStatus::Fail,
+            "No Claude, OpenCode or Codex CLI found",
+            "Install Claude Code, OpenCode or Codex so environments can be named and local agents can run",
+        ),
+    };
+
+    let claude_cli = if let Some(path) = claude_cli::find_claude_cli() {
+        DiagnosticCheck::pass(
+            "claude_cli",
+            "Claude CLI",
+            format!("Using {}", path.display()),
+        )
+    } else {
+        DiagnosticCheck::problem(
+            "claude_cli",
+            "Claude CLI",
+            DiagnosticStatus::Warn,
+            "claude was not found",
+            "Install Claude Code: npm install -g @anthropic-ai/claude-code",
+        )
+    };
+
+    let claude_config = if claude_cli::has_claude_config_file() {
+        DiagnosticCheck::pass("claude_config", "Claude login", "~/.claude.json exists")
+    } else {
+        DiagnosticCheck::problem(
+            "claude_config",
+            "Claude login",
+            DiagnosticStatus::Warn,
+            "~/.claude.json was not found",
+            "Run `claude` once and log in",
+        )
+    };
+
+    let github_cli = if let Some(path) = claude_cli::find_github_cli() {
+        DiagnosticCheck::pass(
+            "github_cli",
+            "GitHub CLI",
+            format!("Using {}", path.display()),
+        )
+    } else {
+        DiagnosticCheck::problem(
+            "github_cli",
+            "GitHub CLI",
+            DiagnosticStatus::Warn,
+            "gh was not found; pull request features are unavailable",
+            "Install the GitHub CLI and run `gh auth login`",
+        )
+    };
+
+    vec![ai_cli, claude_cli, claude_config, github_cli]
+}
+
+fn check_credentials() -> DiagnosticCheck {
+    const ID: &str = "keychain_credentials";
+    const LABEL: &str = "Claude credentials";
+
+    let has_api_key = get_config()
+        .ok()
+        .and_then(|config| config.global.anthropic_api_key)
+        .is_some_and(|key| !key.trim().is_empty());
+
+    match credentials::get_claude_credentials() {
+        Ok(_) => DiagnosticCheck::pass(ID, LABEL, "Keychain credentials found"),
+        Err(_) if has_api_key => DiagnosticCheck::pass(
+            ID,
+            LABEL,
+            "No keychain credentials; containers use the Anthropic API key",
+        ),
+        Err(CredentialsError::NotFound) => DiagnosticCheck::problem(
+            ID,
+            LABEL,
+            DiagnosticStatus::Warn,
+            "No keychain credentials and no Anthropic API key",
+            "Log in with `claude` on this machine, or add an Anthropic API key in Settings",
+        ),
+        Err(e @ CredentialsError::KeychainLocked(_)) => DiagnosticCheck::problem(
+            ID,
+            LABEL,
+            DiagnosticStatus::Fail,
+            e.to_string(),
+            "Unlock the login keychain (Keychain Access or `security unlock-keychain`) and retry",
+        ),
+        Err(e) => DiagnosticCheck::problem(
+            ID,
+            LABEL,
+            DiagnosticStatus::Fail,
+            e.to_string(),
+            "Allow Orkestrator to access the \"Claude Code-credentials\" keychain item, or add an Anthropic API key in Settings",
+        ),
+    }
+}
+
+async fn check_network() -> DiagnosticCheck {
+    const ID: &str = "network";
+    const LABEL: &str = "Network";
+
+    let unresolved = tokio::task::spawn_blocking(|| {
+        REQUIRED_HOSTS
+            .iter()
+            .filter(|host| resolve_domain(host).is_err())
+            .map(|host| host.to_string())
+            .collect::<Vec<_>>()
+    })
+    .await
+    .unwrap_or_else(|_| REQUIRED_HOSTS.iter().map(|h| h.to_string()).collect());
+
+    if unresolved.is_empty() {
+        DiagnosticCheck::pass(ID, LABEL, format!("Resolved {}", REQUIRED_HOSTS.join(", ")))
+    } else {
+        DiagnosticCheck::problem(
+            ID,
+            LABEL,
+            DiagnosticStatus::Warn,
+            format!("Could not resolve {}", unresolved.join(", ")),
+            "Check your internet connection, VPN or DNS settings",
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_overall_is_worst_status() {
+        let report = DiagnosticsReport::new(vec![
+            DiagnosticCheck::pass("a", "A", "ok"),
+            DiagnosticCheck::problem("b", "B", DiagnosticStatus::Warn, "meh", "fix"),
+        ]);
+        assert_eq!(report.overall, DiagnosticStatus::Warn);
+
+        let report = DiagnosticsReport::new(vec![
+            DiagnosticCheck::problem("a", "A", DiagnosticStatus::Fail, "bad", "fix"),
+            DiagnosticCheck::problem("b", "B", DiagnosticStatus::Warn, "meh", "fix"),
+        ]);
+        assert_eq!(report.overall, DiagnosticStatus::Fail);
+
+        assert_eq!(
+            DiagnosticsReport::new(vec![]).overall,
+            DiagnosticStatus::Pass
+        );
+    }
+}
@@ -1,10 +1,12 @@
 // Docker-related Tauri commands
 // Exposes Docker operations to the frontend
 
+use crate::docker::compose::{self, ComposeOutputStream};
 use crate::docker::{self, ContainerConfig};
-use crate::models::EnvironmentStatus;
+use crate::models::{Environment, EnvironmentStatus};
 use crate::storage::get_storage;
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use tracing::{debug, info, trace, warn};
 
 /// Check if Docker is available
@@ -41,6 +43,8 @@ pub async fn provision_environment(environment_id: String) -> Result<String, Str
     // Create container config
     let config = ContainerConfig::new(&environment, &project.git_url);
 
+    super::environments::ensure_container_resource_limits(&environment_id).await?;
+
     // Create the container
     let container_id = docker::create_environment_container(&config, None)
         .await
@@ -193,6 +197,61 @@ pub struct ContainerInfo {
     pub cpu_percent: Option<f64>,
 }
 
+/// Disk space used by Docker, per category, from `docker system df`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DockerDiskBreakdown {
+    /// Image layers, deduplicated across images (bytes)
+    pub images: u64,
+    /// Writable layers of containers (bytes)
+    pub containers: u64,
+    /// Volumes (bytes)
+    pub volumes: u64,
+    /// Build cache (bytes)
+    pub build_cache: u64,
+}
+
+impl DockerDiskBreakdown {
+    pub fn total(&self) -> u64 {
+        self.images + self.containers + self.volumes + self.build_cache
+    }
+}
+
+/// Split a `docker system df` response into per-category sizes.
+/// Container sizes use `size_rw` only, since `size_root_fs` includes the
+/// image layers already counted under images.
+fn disk_breakdown(df: &bollard::models::SystemDataUsageResponse) -> DockerDiskBreakdown {
+    let containers = df
+        .containers
+        .iter()
+        .flatten()
+        .filter_map(|container| container.size_rw)
+        .map(|size| size.max(0) as u64)
+        .sum();
+    let volumes = df
+        .volumes
+        .iter()
+        .flatten()
+        .filter_map(|volume| volume.usage_data.as_ref())
+        .map(|usage| usage.size.max(0) as u64)
+        .sum();
+
+    DockerDiskBreakdown {
+        images: df.layers_size.unwrap_or(0).max(0) as u64,
+        containers,
+        volumes,
+        build_cache: docker::client::build_cache_size(df),
+    }
+}
+
+/// Get Docker disk usage split into images, containers, volumes and build cache
+#[tauri::command]
+pub async fn get_docker_disk_breakdown() -> Result<DockerDiskBreakdown, String> {
+    let client = docker::client::get_docker_client().map_err(|e| e.to_string())?;
+    let df = client.disk_usage().await.map_err(|e| e.to_string())?;
+    Ok(disk_breakdown(&df))
+}
+
 /// Get Docker system statistics
 #[tauri::command]
 pub async fn get_docker_system_stats() -> Result<DockerSystemStats, String> {
@@ -210,47 +269,7 @@ pub async fn get_docker_system_stats() -> Result<DockerSystemStats, String> {
     // Get disk usage from `docker system df`
     let df = client.disk_usage().await.map_err(|e| e.to_string())?;
 
-    // Calculate total disk usage correctly:
-    // - layers_size is the total unique size of all image layers (deduplicated)
-    // - container size_rw is the writable layer size (don't use size_root_fs as it includes image layers)
-    // - volume sizes
-    // - build cache sizes
-
-    // Start with image layers size (this is the deduplicated total)
-    let mut disk_used: u64 = df.layers_size.unwrap_or(0) as u64;
-
-    // Add container writable layer sizes (size_rw only, not size_root_fs which includes image)
-    if let Some(containers) = &df.containers {
-        for container in containers {
-            if let Some(size) = container.size_rw {
-                // size_rw can be negative in some cases, only add positive values
-                if size > 0 {
-                    disk_used += size as u64;
-                }
-            }
-        }
-    }
-
-    // Add volume disk usage
-    if let Some(volumes) = &df.volumes {
-        for volume in volumes {
-            if let Some(usage) = &volume.usage_data {
-                // usage.size is i64, only add positive values
-                if usage.size > 0 {
-                    disk_used += usage.size as u64;
-                }
-            }
-        }
-    }
-
-    // Add build cache disk usage
-    if let Some(caches) = &df.build_cache {
-        for cache in caches {
-            if let Some(size) = cache.size {
-                disk_used += size as u64;
-            }
-        }
-    }
+    let disk_used = disk_breakdown(&df).total();
 
     // Try to get disk_total from Docker.raw file on macOS (Docker Desktop)
     // This file represents the virtual disk allocated to Docker
@@ -306,7 +325,7 @@ fn get_visible_container_ids(
 /// Get all containers using the orkestrator-ai image with assignment status
 #[tauri::command]
 pub async fn get_orkestrator_containers() -> Result<Vec<ContainerInfo>, String> {
-    let client = docker::client::get_docker_client().map_err(|e| e.to_string())?;
+    let client = docker::get_container_engine().map_err(|e| e.to_string())?;
     let storage = get_storage().map_err(|e| e.to_string())?;
 
     // Get container IDs that are visible in the sidebar
@@ -370,11 +389,27 @@ pub async fn get_orkestrator_containers() -> Result<Vec<ContainerInfo>, String>
     Ok(result)
 }
 
-/// Remove orphaned containers (those not visible in the sidebar as environments)
+/// Result of removing orphaned containers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanCleanupResult {
+    /// Number of containers removed
+    pub removed: u32,
+    /// Orphaned containers left in place because they are pinned
+    pub skipped_pinned: Vec<String>,
+}
+
+/// Remove orphaned containers (those not visible in the sidebar as environments).
+/// Pinned containers are skipped.
 #[tauri::command]
-pub async fn cleanup_orphaned_containers() -> Result<u32, String> {
+pub async fn cleanup_orphaned_containers() -> Result<OrphanCleanupResult, String> {
     let client = docker::client::get_docker_client().map_err(|e| e.to_string())?;
     let storage = get_storage().map_err(|e| e.to_string())?;
+    let pinned = storage
+        .load_config()
+        .map_err(|e| e.to_string())?
+        .global
+        .pinned_containers;
 
     // Get container IDs that are visible in the sidebar
     let visible_container_ids = get_visible_container_ids(&storage)?;
@@ -390,24 +425,41 @@ pub async fn cleanup_orphaned_containers() -> Result<u32, String> {
         .await
         .map_err(|e| e.to_string())?;
 
-    let mut removed_count = 0;
+    let mut removed = 0;
+    let mut skipped_pinned = Vec::new();
 
     for container in containers {
         if let Some(id) = container.id {
             // Container is orphaned if it's not associated with any visible environment
             if !visible_container_ids.contains(&id) {
+                let names: Vec<&str> = container
+                    .names
+                    .iter()
+                    .flatten()
+                    .map(|name| name.trim_start_matches('/'))
+                    .collect();
+                if docker::client::is_pinned(&pinned, &id, &names) {
+                    info!(container_id = %id, "Skipping pinned orphaned container");
+                    skipped_pinned
+                        .push(names.first().map_or_else(|| id.clone(), |n| n.to_string()));
+                    continue;
+                }
+
                 // Force remove the container (it might be running)
                 if let Err(e) = client.remove_container(&id, true).await {
                     warn!(container_id = %id, error = %e, "Failed to remove orphaned container");
                 } else {
-                    removed_count += 1;
+                    removed += 1;
                     info!(container_id = %id, "Removed orphaned container");
                 }
             }
         }
     }
 
-    Ok(removed_count)
+    Ok(OrphanCleanupResult {
+        removed,
+        skipped_pinned,
+    })
 }
 
 /// Result of a Docker system prune operation for the UI
@@ -424,9 +476,12 @@ pub struct SystemPruneResult {
     pub volumes_deleted: u32,
     /// Total space reclaimed in bytes
     pub space_reclaimed: u64,
+    /// Pinned containers and images that were left in place
+    pub skipped_pinned: Vec<String>,
 }
 
-/// Perform Docker system prune - removes unused containers, images, networks, and optionally volumes
+/// Perform Docker system prune - removes unused containers, images, networks, and optionally volumes.
+/// Pinned containers and images are skipped.
 #[tauri::command]
 pub async fn docker_system_prune(prune_volumes: bool) -> Result<SystemPruneResult, String> {
     info!(
@@ -434,9 +489,16 @@ pub async fn docker_system_prune(prune_volumes: bool) -> Result<SystemPruneResul
         "Starting Docker system prune"
     );
 
+    let global = crate::storage::get_config()
+        .map_err(|e| e.to_string())?
+        .global;
     let client = docker::client::get_docker_client().map_err(|e| e.to_string())?;
     let result = client
-        .system_prune(prune_volumes)
+        .system_prune(
+            prune_volumes,
+            &global.pinned_containers,
+            &global.pinned_images,
+        )
         .await
         .map_err(|e| e.to_string())?;
 
@@ -455,9 +517,160 @@ pub async fn docker_system_prune(prune_volumes: bool) -> Result<SystemPruneResul
         networks_deleted: result.networks_deleted,
         volumes_deleted: result.volumes_deleted,
         space_reclaimed: result.space_reclaimed,
+        skipped_pinned: result.skipped_pinned,
     })
 }
 
+/// Category of unused Docker data that `prune_docker` can remove
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PruneTarget {
+    /// Stopped containers
+    Containers,
+    /// Dangling images
+    Images,
+    /// Volumes not used by any container
+    Volumes,
+    /// Build cache
+    BuildCache,
+}
+
+/// Space reclaimed by `prune_docker` per category, in bytes.
+/// Categories that were not selected are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DockerPruneResult {
+    pub containers: Option<u64>,
+    pub images: Option<u64>,
+    pub volumes: Option<u64>,
+    pub build_cache: Option<u64>,
+    /// Pinned containers and images that were left in place
+    pub skipped_pinned: Vec<String>,
+}
+
+/// Prune only the selected categories of unused Docker data.
+/// Pinned containers and images are skipped.
+#[tauri::command]
+pub async fn prune_docker(targets: Vec<PruneTarget>) -> Result<DockerPruneResult, String> {
+    info!(targets = ?targets, "Starting Docker prune");
+
+    let global = crate::storage::get_config()
+        .map_err(|e| e.to_string())?
+        .global;
+    let client = docker::client::get_docker_client().map_err(|e| e.to_string())?;
+    let mut result = DockerPruneResult::default();
+
+    // Containers go first so the images and volumes they held can be pruned
+    for target in [
+        PruneTarget::Containers,
+        PruneTarget::Images,
+        PruneTarget::Volumes,
+        PruneTarget::BuildCache,
+    ] {
+        if !targets.contains(&target) {
+            continue;
+        }
+        let outcome = match target {
+            PruneTarget::Containers => {
+                client
+                    .prune_stopped_containers(&global.pinned_containers)
+                    .await
+            }
+            PruneTarget::Images => client.prune_dangling_images(&global.pinned_images).await,
+            PruneTarget::Volumes => client.prune_unused_volumes().await,
+            PruneTarget::BuildCache => client.prune_build_cache().await,
+        }
+        .map_err(|e| e.to_string())?;
+
+        let slot = match target {
+            PruneTarget::Containers => &mut result.containers,
+            PruneTarget::Images => &mut result.images,
+            PruneTarget::Volumes => &mut result.volumes,
+            PruneTarget::BuildCache => &mut result.build_cache,
+        };
+        *slot = Some(outcome.space_reclaimed);
+        result.skipped_pinned.extend(outcome.skipped);
+    }
+
+    info!(
+        containers = ?result.containers,
+        images = ?result.images,
+        volumes = ?result.volumes,
+        build_cache = ?result.build_cache,
+        skipped_pinned = ?result.skipped_pinned,
+        "Docker prune completed"
+    );
+
+    Ok(result)
+}
+
+/// Pin a container (by ID or name) so prune and orphan cleanup skip it.
+/// Returns the pinned containers.
+#[tauri::command]
+pub async fn pin_container(container_id: String) -> Result<Vec<String>, String> {
+    set_container_pinned(&container_id, true)
+}
+
+/// Unpin a container. Returns the pinned containers.
+#[tauri::command]
+pub async fn unpin_container(container_id: String) -> Result<Vec<String>, String> {
+    set_container_pinned(&container_id, false)
+}
+
+fn set_container_pinned(container: &str, pinned: bool) -> Result<Vec<String>, String> {
+    let container = container.trim();
+    if container.is_empty() {
+        return Err("Container ID cannot be empty".to_string());
+    }
+    get_storage()
+        .map_err(|e| e.to_string())?
+        .set_container_pinned(container, pinned)
+        .map_err(|e| e.to_string())
+}
+
+/// Pin an image (by tag or ID) so prune skips it. A tag is resolved to the
+/// image's ID, which is what gets pinned, so the pin still holds once the tag
+/// moves to a newer image and the pinned one is left dangling.
+/// Returns the pinned images.
+#[tauri::command]
+pub async fn pin_image(tag: String) -> Result<Vec<String>, String> {
+    let image = tag.trim();
+    if image.is_empty() {
+        return Err("Image tag cannot be empty".to_string());
+    }
+    let image_id = resolve_image_id(image)
+        .await?
+        .ok_or_else(|| format!("Image not found: {}", image))?;
+    set_image_pinned(&image_id, true)
+}
+
+/// Unpin an image (by tag or ID), removing both the entry as given and the
+/// ID it currently resolves to. Returns the pinned images.
+#[tauri::command]
+pub async fn unpin_image(tag: String) -> Result<Vec<String>, String> {
+    let image = tag.trim();
+    if image.is_empty() {
+        return Err("Image tag cannot be empty".to_string());
+    }
+    let pinned = set_image_pinned(image, false)?;
+    match resolve_image_id(image).await? {
+        Some(image_id) if image_id != image => set_image_pinned(&image_id, false),
+        _ => Ok(pinned),
+    }
+}
+
+async fn resolve_image_id(image: &str) -> Result<Option<String>, String> {
+    let client = docker::client::get_docker_client().map_err(|e| e.to_string())?;
+    client.image_id(image).await.map_err(|e| e.to_string())
+}
+
+fn set_image_pinned(image_id: &str, pinned: bool) -> Result<Vec<String>, String> {
+    get_storage()
+        .map_err(|e| e.to_string())?
+        .set_image_pinned(image_id, pinned)
+        .map_err(|e| e.to_string())
+}
+
 /// Get the host port mapped to a specific container port
 /// Returns None if the port is not mapped or the container is not running
 #[tauri::command]
@@ -633,7 +846,7 @@ pub async fn get_container_logs(
     tail: Option<String>,
 ) -> Result<String, String> {
     debug!(container_id = %container_id, tail = ?tail, "Getting container logs");
-    let client = docker::client::get_docker_client().map_err(|e| e.to_string())?;
+    let client = docker::get_container_engine().map_err(|e| e.to_string())?;
     client
         .get_container_logs(&container_id, tail.as_deref())
         .await
@@ -675,3 +888,538 @@ pub async fn stream_container_logs(
 
     Ok(())
 }
+
+/// A mount as reported by `docker inspect`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerMountDetails {
+    /// Mount type (bind, volume, tmpfs, ...)
+    pub mount_type: Option<String>,
+    /// Volume name (for named volumes)
+    pub name: Option<String>,
+    /// Host path or volume location
+    pub source: Option<String>,
+    /// Path inside the container
+    pub destination: Option<String>,
+    /// Whether the mount is writable
+    pub read_write: bool,
+}
+
+/// A published port as reported by `docker inspect`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerPortBindingDetails {
+    /// Container port with protocol (e.g. "3000/tcp")
+    pub container_port: String,
+    /// Host interface the port is bound to
+    pub host_ip: Option<String>,
+    /// Host port the container port is published on
+    pub host_port: Option<String>,
+}
+
+/// Curated subset of `docker inspect` output for diagnostics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerInspectDetails {
+    /// Container ID
+    pub id: String,
+    /// Container name (without leading slash)
+    pub name: String,
+    /// Image the container was created from
+    pub image: Option<String>,
+    /// Container state (running, exited, created, etc.)
+    pub status: String,
+    /// Whether the container is running
+    pub running: bool,
+    /// Whether the container is restarting
+    pub restarting: bool,
+    /// Whether the container was killed for running out of memory
+    pub oom_killed: bool,
+    /// Exit code of the last run
+    pub exit_code: Option<i64>,
+    /// Error message reported by Docker, if any
+    pub error: Option<String>,
+    /// Number of times Docker has restarted the container
+    pub restart_count: i64,
+    /// Creation timestamp (RFC 3339)
+    pub created_at: Option<String>,
+    /// Last start timestamp (RFC 3339)
+    pub started_at: Option<String>,
+    /// Last finish timestamp (RFC 3339)
+    pub finished_at: Option<String>,
+    /// Mounts attached to the container
+    pub mounts: Vec<ContainerMountDetails>,
+    /// Names of the environment variables set in the container. Values are
+    /// never returned since they routinely carry tokens and API keys.
+    pub env_var_names: Vec<String>,
+    /// Published port bindings
+    pub port_bindings: Vec<ContainerPortBindingDetails>,
+}
+
+/// A container environment variable name, without its value
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerEnvKey {
+    pub name: String,
+    /// Whether the name suggests the value is a credential
+    pub secret: bool,
+}
+
+/// Name segments (split on `_`) that mark a variable as a credential
+const SECRET_ENV_SEGMENTS: &[&str] = &[
+    "TOKEN",
+    "SECRET",
+    "KEY",
+    "APIKEY",
+    "PASSWORD",
+    "PASSWD",
+    "PASS",
+    "CREDENTIAL",
+    "CREDENTIALS",
+    "AUTH",
+    "OAUTH",
+    "PRIVATE",
+    "COOKIE",
+    "SESSION",
+    "DSN",
+];
+
+/// Whether an env var name looks like it holds a credential, e.g.
+/// `GITHUB_TOKEN` or `ANTHROPIC_API_KEY`
+fn is_secret_env_name(name: &str) -> bool {
+    name.to_ascii_uppercase()
+        .split('_')
+        .any(|segment| SECRET_ENV_SEGMENTS.contains(&segment))
+}
+
+/// Default and maximum timeouts for `wait_for_workspace_ready` (seconds)
+const DEFAULT_WORKSPACE_READY_TIMEOUT_SECS: u64 = 60;
+const MAX_WORKSPACE_READY_TIMEOUT_SECS: u64 = 600;
+/// Number of setup log lines returned when waiting times out
+const WORKSPACE_READY_LOG_TAIL_LINES: usize = 50;
+
+/// Result of waiting for a container's workspace setup to complete
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceReadyResult {
+    /// Whether the workspace setup marker appeared before the timeout
+    pub ready: bool,
+    /// Tail of the setup progress log, included on timeout for diagnostics
+    pub log_tail: Option<String>,
+}
+
+/// Wait until workspace setup inside a container has completed, so callers can
+/// run setup/launch commands without racing it. Times out after `timeout_secs`
+/// (default 60, capped at 600).
+#[tauri::command]
+pub async fn wait_for_workspace_ready(
+    container_id: String,
+    timeout_secs: Option<u64>,
+) -> Result<WorkspaceReadyResult, String> {
+    let timeout_secs = timeout_secs
+        .unwrap_or(DEFAULT_WORKSPACE_READY_TIMEOUT_SECS)
+        .min(MAX_WORKSPACE_READY_TIMEOUT_SECS);
+    debug!(container_id = %container_id, timeout_secs, "Waiting for workspace setup");
+
+    let ready = docker::wait_for_workspace_setup(&container_id, timeout_secs)
+        .await
+        .map_err(|e| e.to_string())?;
+    if ready {
+        return Ok(WorkspaceReadyResult {
+            ready: true,
+            log_tail: None,
+        });
+    }
+
+    warn!(container_id = %container_id, timeout_secs, "Timed out waiting for workspace setup");
+    let log_tail =
+        match docker::workspace_setup_log_tail(&container_id, WORKSPACE_READY_LOG_TAIL_LINES).await
+        {
+            Ok(tail) => Some(tail),
+            Err(e) => {
+                debug!(container_id = %container_id, error = %e, "Failed to read setup log");
+                None
+            }
+        };
+
+    Ok(WorkspaceReadyResult {
+        ready: false,
+        log_tail,
+    })
+}
+
+/// Inspect a container and return details useful for debugging.
+/// Unlike `docker_container_status` this does not touch stored environment state.
+#[tauri::command]
+pub async fn inspect_container(container_id: String) -> Result<ContainerInspectDetails, String> {
+    debug!(container_id = %container_id, "Inspecting container");
+    let client = docker::client::get_docker_client().map_err(|e| e.to_string())?;
+    let info = client
+        .inspect_container(&container_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let state = info.state.unwrap_or_default();
+
+    let mounts = info
+        .mounts
+        .unwrap_or_default()
+        .into_iter()
+        .map(|m| ContainerMountDetails {
+            mount_type: m.typ.map(|t| t.to_string()),
+            name: m.name,
+            source: m.source,
+            destination: m.destination,
+            read_write: m.rw.unwrap_or(false),
+        })
+        .collect();
+
+    let env = info.config.and_then(|c| c.env).unwrap_or_default();
+
+    let mut port_bindings: Vec<ContainerPortBindingDetails> = info
+        .network_settings
+        .and_then(|ns| ns.ports)
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|(container_port, bindings)| {
+            bindings
+                .unwrap_or_default()
+                .into_iter()
+                .map(move |b| ContainerPortBindingDetails {
+                    container_port: container_port.clone(),
+                    host_ip: b.host_ip,
+                    host_port: b.host_port,
+                })
+        })
+        .collect();
+    port_bindings.sort_by(|a, b| a.container_port.cmp(&b.container_port));
+
+    Ok(ContainerInspectDetails {
+        id: info.id.unwrap_or(container_id),
+        name: info
+            .name
+            .map(|n| n.trim_start_matches('/').to_string())
+            .unwrap_or_default(),
+        image: info.image,
+        status: state
+            .status
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "unknown".to_string()),
+        running: state.running.unwrap_or(false),
+        restarting: state.restarting.unwrap_or(false),
+        oom_killed: state.oom_killed.unwrap_or(false),
+        exit_code: state.exit_code,
+        error: state.error.filter(|e| !e.is_empty()),
+        restart_count: info.restart_count.unwrap_or(0),
+        created_at: info.created,
+        started_at: state.started_at,
+        finished_at: state.finished_at,
+        mounts,
+        env_var_names: docker::env_var_names(&env),
+        port_bindings,
+    })
+}
+
+/// List the environment variable names set in a container, flagging the ones
+/// that look secret. Values are never returned, so the result is safe to log
+/// or show when checking that expected variables (e.g. API keys) are present.
+#[tauri::command]
+pub async fn list_container_env_keys(container_id: String) -> Result<Vec<ContainerEnvKey>, String> {
+    debug!(container_id = %container_id, "Listing container env keys");
+    let engine = docker::get_container_engine().map_err(|e| e.to_string())?;
+    let info = engine
+        .inspect_container(&container_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let env = info.config.and_then(|c| c.env).unwrap_or_default();
+    Ok(docker::env_var_names(&env)
+        .into_iter()
+        .map(|name| ContainerEnvKey {
+            secret: is_secret_env_name(&name),
+            name,
+        })
+        .collect())
+}
+
+/// Payload for "compose-output" events
+#[derive(Clone, Serialize)]
+pub struct ComposeOutputPayload {
+    pub environment_id: String,
+    pub stream: ComposeOutputStream,
+    pub line: String,
+}
+
+/// Payload for "compose-status" events
+#[derive(Clone, Serialize)]
+pub struct ComposeStatusPayload {
+    pub environment_id: String,
+    pub status: EnvironmentStatus,
+}
+
+/// Resolve the directory `docker compose` runs in for an environment: the
+/// worktree for local environments, otherwise the project's host checkout.
+/// Fails unless compose support is enabled for the repository and a compose
+/// file is present.
+async fn resolve_compose_context(environment: &Environment) -> Result<PathBuf, String> {
+    let storage = get_storage().map_err(|e| e.to_string())?;
+    let config = storage.load_config().map_err(|e| e.to_string())?;
+    let compose_enabled = config
+        .repositories
+        .get(&environment.project_id)
+        .is_some_and(|repo| repo.compose_enabled);
+    if !compose_enabled {
+        return Err("Docker Compose is not enabled for this repository".to_string());
+    }
+
+    let context_dir = if environment.is_local() {
+        environment.worktree_path.clone()
+    } else {
+        storage
+            .get_project(&environment.project_id)
+            .map_err(|e| e.to_string())?
+            .and_then(|project| project.local_path)
+    }
+    .map(PathBuf::from)
+    .ok_or("Environment has no local checkout to run docker compose in")?;
+
+    if compose::find_compose_file(&context_dir)
+        .await
+        .map_err(|e| format!("Failed to read compose file: {}", e))?
+        .is_none()
+    {
+        return Err(format!(
+            "No compose file found in {}",
+            context_dir.display()
+        ));
+    }
+
+    Ok(context_dir)
+}
+
+/// Query the compose project's containers and emit the aggregate status
+async fn emit_compose_status(
+    app_handle: &tauri::AppHandle,
+    environment_id: &str,
+    context_dir: &Path,
+    project_name: &str,
+) -> Result<EnvironmentStatus, String> {
+    use tauri::Emitter;
+
+    let containers = compose::compose_ps(context_dir, project_name).await?;
+    let status = compose::aggregate_compose_status(&containers);
+    let payload = ComposeStatusPayload {
+        environment_id: environment_id.to_string(),
+        status: status.clone(),
+    };
+    if let Err(e) = app_handle.emit("compose-status", payload) {
+        warn!(error = %e, "Failed to emit compose status event");
+    }
+    Ok(status)
+}
+
+/// Run a compose command for an environment, streaming output as "compose-output" events
+async fn run_environment_compose(
+    app_handle: &tauri::AppHandle,
+    environment_id: &str,
+    context_dir: &Path,
+    project_name: &str,
+    args: &[&str],
+) -> Result<(), String> {
+    use tauri::Emitter;
+
+    compose::run_compose(context_dir, project_name, args, |stream, line| {
+        let payload = ComposeOutputPayload {
+            environment_id: environment_id.to_string(),
+            stream,
+            line,
+        };
+        if let Err(e) = app_handle.emit("compose-output", payload) {
+            trace!(error = %e, "Failed to emit compose output event");
+        }
+    })
+    .await
+}
+
+/// Start the repository's compose services for an environment
+/// (`docker compose -p <project> up -d`).
+///
+/// Output is streamed as "compose-output" events and the resulting aggregate
+/// state is emitted as a "compose-status" event and returned. The compose
+/// project name is stored on the environment.
+#[tauri::command]
+pub async fn compose_up(
+    app_handle: tauri::AppHandle,
+    environment_id: String,
+) -> Result<EnvironmentStatus, String> {
+    let storage = get_storage().map_err(|e| e.to_string())?;
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+    let context_dir = resolve_compose_context(&environment).await?;
+
+    let project_name = environment
+        .compose_project_name
+        .clone()
+        .unwrap_or_else(|| compose::compose_project_name(&environment_id));
+    storage
+        .update_environment(
+            &environment_id,
+            serde_json::json!({ "composeProjectName": project_name }),
+        )
+        .map_err(|e| e.to_string())?;
+
+    info!(
+        environment_id = %environment_id,
+        project_name = %project_name,
+        context_dir = %context_dir.display(),
+        "Starting compose services"
+    );
+    let result = run_environment_compose(
+        &app_handle,
+        &environment_id,
+        &context_dir,
+        &project_name,
+        &["up", "-d"],
+    )
+    .await;
+
+    let status = emit_compose_status(&app_handle, &environment_id, &context_dir, &project_name)
+        .await
+        .unwrap_or(EnvironmentStatus::Error);
+    result.map(|_| status)
+}
+
+/// Stop and remove an environment's compose services (`docker compose -p <project> down`)
+/// and clear the stored compose project name.
+#[tauri::command]
+pub async fn compose_down(
+    app_handle: tauri::AppHandle,
+    environment_id: String,
+) -> Result<EnvironmentStatus, String> {
+    let storage = get_storage().map_err(|e| e.to_string())?;
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+    let Some(project_name) = environment.compose_project_name.clone() else {
+        return Ok(EnvironmentStatus::Stopped);
+    };
+    let context_dir = resolve_compose_context(&environment).await?;
+
+    info!(
+        environment_id = %environment_id,
+        project_name = %project_name,
+        "Stopping compose services"
+    );
+    run_environment_compose(
+        &app_handle,
+        &environment_id,
+        &context_dir,
+        &project_name,
+        &["down"],
+    )
+    .await?;
+
+    storage
+        .update_environment(
+            &environment_id,
+            serde_json::json!({ "composeProjectName": null }),
+        )
+        .map_err(|e| e.to_string())?;
+
+    emit_compose_status(&app_handle, &environment_id, &context_dir, &project_name).await
+}
+
+/// Aggregate state of an environment's compose services
+/// (`Stopped` when compose is not up for the environment)
+#[tauri::command]
+pub async fn get_compose_status(environment_id: String) -> Result<EnvironmentStatus, String> {
+    let storage = get_storage().map_err(|e| e.to_string())?;
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+    let Some(project_name) = environment.compose_project_name.as_deref() else {
+        return Ok(EnvironmentStatus::Stopped);
+    };
+    let context_dir = resolve_compose_context(&environment).await?;
+
+    let containers = compose::compose_ps(&context_dir, project_name).await?;
+    Ok(compose::aggregate_compose_status(&containers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bollard::models::{
+        BuildCache, ContainerSummary, SystemDataUsageResponse, Volume, VolumeUsageData,
+    };
+
+    #[test]
+    fn test_disk_breakdown_sums_each_category() {
+        let df = SystemDataUsageResponse {
+            layers_size: Some(1_000),
+            containers: Some(vec![
+                ContainerSummary {
+                    size_rw: Some(200),
+                    size_root_fs: Some(5_000),
+                    ..Default::default()
+                },
+                ContainerSummary {
+                    size_rw: Some(-1),
+                    ..Default::default()
+                },
+            ]),
+            volumes: Some(vec![Volume {
+                usage_data: Some(VolumeUsageData {
+                    size: 300,
+                    ref_count: 1,
+                }),
+                ..Default::default()
+            }]),
+            build_cache: Some(vec![BuildCache {
+                size: Some(40),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+
+        let breakdown = disk_breakdown(&df);
+        assert_eq!(
+            breakdown,
+            DockerDiskBreakdown {
+                images: 1_000,
+                containers: 200,
+                volumes: 300,
+                build_cache: 40,
+            }
+        );
+        assert_eq!(breakdown.total(), 1_540);
+    }
+
+    #[test]
+    fn test_is_secret_env_name() {
+        for name in [
+            "GITHUB_TOKEN",
+            "ANTHROPIC_API_KEY",
+            "OPENAI_APIKEY",
+            "DB_PASSWORD",
+            "OAUTH_CREDENTIALS_JSON",
+            "aws_secret_access_key",
+            "SENTRY_DSN",
+        ] {
+            assert!(is_secret_env_name(name), "{name} should be secret");
+        }
+        for name in [
+            "PATH",
+            "GIT_BRANCH",
+            "NETWORK_MODE",
+            "KEYBOARD_LAYOUT",
+            "TOKENIZERS_PARALLELISM",
+        ] {
+            assert!(!is_secret_env_name(name), "{name} should not be secret");
+        }
+    }
+}
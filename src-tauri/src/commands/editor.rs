@@ -1,6 +1,7 @@
 // Editor integration commands
 // Opens VS Code or Cursor attached to a running container or for a local path
 
+use crate::docker::client::get_docker_client;
 use crate::models::PreferredEditor;
 use std::path::Path;
 use std::process::Command;
@@ -14,10 +15,16 @@ pub async fn open_in_editor(container_id: String, editor: PreferredEditor) -> Re
     // So we encode the UTF-8 bytes of the container ID string
     let hex_id = hex::encode(container_id.as_bytes());
 
+    // Open the container's working directory (the repository's workspace path)
+    let workspace = get_docker_client()
+        .map_err(|e| e.to_string())?
+        .get_workspace_path(&container_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
     // Build the VS Code remote URI for attached container
-    // Format: vscode-remote://attached-container+{hex_encoded_container_id}/workspace
-    // The /workspace path matches the WORKDIR in our Dockerfile
-    let uri = format!("vscode-remote://attached-container+{}/workspace", hex_id);
+    // Format: vscode-remote://attached-container+{hex_encoded_container_id}{workspace}
+    let uri = format!("vscode-remote://attached-container+{}{}", hex_id, workspace);
 
     // Determine the command to run based on editor preference
     let cmd = editor.cli_command();
@@ -0,0 +1,211 @@
+// Environment terminal commands
+// One command set for terminals of any environment type. A session's
+// environment is looked up when it is created, and its commands route to the
+// container (Docker exec) or local (host PTY) terminal manager, so the
+// frontend never has to pick the API.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use tauri::AppHandle;
+use tracing::instrument;
+
+use super::local_terminal::{
+    close_local_terminal_session, create_local_terminal_session, local_terminal_resize,
+    local_terminal_write, start_local_terminal_session,
+};
+use super::terminal::{
+    create_terminal_session, detach_terminal, start_terminal_session, terminal_resize,
+    terminal_write,
+};
+use crate::models::Environment;
+use crate::storage::get_storage;
+
+/// Terminal manager serving an environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TerminalBackend {
+    /// Docker exec sessions in the environment's container
+    Container,
+    /// Host shells in the environment's worktree
+    Local,
+}
+
+impl TerminalBackend {
+    fn for_environment(environment: &Environment) -> Self {
+        if environment.is_local() {
+            TerminalBackend::Local
+        } else {
+            TerminalBackend::Container
+        }
+    }
+}
+
+fn load_environment(environment_id: &str) -> Result<Environment, String> {
+    get_storage()
+        .map_err(|e| e.to_string())?
+        .get_environment(environment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))
+}
+
+fn resolve_backend(environment_id: &str) -> Result<TerminalBackend, String> {
+    load_environment(environment_id)
+        .map(|environment| TerminalBackend::for_environment(&environment))
+}
+
+/// Backend of each open session, recorded at creation so writes and resizes
+/// don't read environments.json on every keystroke
+static SESSION_BACKENDS: LazyLock<Mutex<HashMap<String, TerminalBackend>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn session_backends() -> std::sync::MutexGuard<'static, HashMap<String, TerminalBackend>> {
+    SESSION_BACKENDS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Backend of a session, falling back to the environment for sessions not
+/// created through `create_environment_terminal`
+fn session_backend(environment_id: &str, session_id: &str) -> Result<TerminalBackend, String> {
+    if let Some(backend) = session_backends().get(session_id) {
+        return Ok(*backend);
+    }
+    let backend = resolve_backend(environment_id)?;
+    session_backends().insert(session_id.to_string(), backend);
+    Ok(backend)
+}
+
+/// Create a terminal session for an environment without starting it, so the
+/// frontend can listen for "terminal-output-{session_id}" first. `user` only
+/// applies to container environments.
+#[tauri::command]
+#[instrument(skip(app), fields(environment_id = %environment_id, cols, rows, user))]
+pub async fn create_environment_terminal(
+    app: AppHandle,
+    environment_id: String,
+    cols: u16,
+    rows: u16,
+    user: Option<String>,
+) -> Result<String, String> {
+    let environment = load_environment(&environment_id)?;
+    let backend = TerminalBackend::for_environment(&environment);
+    let session_id = match backend {
+        TerminalBackend::Local => {
+            create_local_terminal_session(app, environment_id, cols, rows).await?
+        }
+        TerminalBackend::Container => {
+            let container_id = environment
+                .container_id
+                .ok_or_else(|| "Environment has no container".to_string())?;
+            create_terminal_session(container_id, cols, rows, user).await?
+        }
+    };
+    session_backends().insert(session_id.clone(), backend);
+    Ok(session_id)
+}
+
+/// Start an environment terminal session and begin forwarding output
+#[tauri::command]
+#[instrument(skip(app), fields(environment_id = %environment_id, session_id = %session_id))]
+pub async fn start_environment_terminal(
+    app: AppHandle,
+    environment_id: String,
+    session_id: String,
+) -> Result<(), String> {
+    match session_backend(&environment_id, &session_id)? {
+        TerminalBackend::Local => start_local_terminal_session(app, session_id).await,
+        TerminalBackend::Container => start_terminal_session(app, session_id).await,
+    }
+}
+
+/// Write data to an environment terminal session
+#[tauri::command]
+#[instrument(fields(environment_id = %environment_id, session_id = %session_id, data_len = data.len()))]
+pub async fn environment_terminal_write(
+    environment_id: String,
+    session_id: String,
+    data: String,
+) -> Result<(), String> {
+    match session_backend(&environment_id, &session_id)? {
+        TerminalBackend::Local => local_terminal_write(session_id, data).await,
+        TerminalBackend::Container => terminal_write(session_id, data).await,
+    }
+}
+
+/// Resize an environment terminal session
+#[tauri::command]
+#[instrument(fields(environment_id = %environment_id, session_id = %session_id, cols, rows))]
+pub async fn environment_terminal_resize(
+    environment_id: String,
+    session_id: String,
+    cols: u16,
+    rows: u16,
+) -> Result<(), String> {
+    match session_backend(&environment_id, &session_id)? {
+        TerminalBackend::Local => local_terminal_resize(session_id, cols, rows),
+        TerminalBackend::Container => terminal_resize(session_id, cols, rows).await,
+    }
+}
+
+/// Close an environment terminal session (detaches container sessions, ends
+/// local shells)
+#[tauri::command]
+#[instrument(fields(environment_id = %environment_id, session_id = %session_id))]
+pub async fn close_environment_terminal(
+    environment_id: String,
+    session_id: String,
+) -> Result<(), String> {
+    let backend = session_backend(&environment_id, &session_id)?;
+    session_backends().remove(&session_id);
+    match backend {
+        TerminalBackend::Local => close_local_terminal_session(session_id),
+        TerminalBackend::Container => detach_terminal(session_id).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EnvironmentType;
+    use crate::storage::{set_storage_for_test, Storage};
+
+    #[test]
+    fn routes_by_environment_type() {
+        let guard =
+            set_storage_for_test(Storage::new_for_tests(tempfile::tempdir().unwrap().keep()));
+        let mut local = Environment::new("project-1".to_string());
+        local.environment_type = EnvironmentType::Local;
+        let local = guard.storage.add_environment(local).unwrap();
+        let container = guard
+            .storage
+            .add_environment(Environment::new("project-1".to_string()))
+            .unwrap();
+
+        assert_eq!(resolve_backend(&local.id), Ok(TerminalBackend::Local));
+        assert_eq!(
+            resolve_backend(&container.id),
+            Ok(TerminalBackend::Container)
+        );
+        assert!(resolve_backend("missing").is_err());
+    }
+
+    #[test]
+    fn session_backend_is_resolved_once() {
+        let guard =
+            set_storage_for_test(Storage::new_for_tests(tempfile::tempdir().unwrap().keep()));
+        let mut local = Environment::new("project-1".to_string());
+        local.environment_type = EnvironmentType::Local;
+        let local = guard.storage.add_environment(local).unwrap();
+
+        assert_eq!(
+            session_backend(&local.id, "session-cached"),
+            Ok(TerminalBackend::Local)
+        );
+        // Later lookups use the recorded backend, not environments.json
+        guard.storage.remove_environment(&local.id).unwrap();
+        assert_eq!(
+            session_backend(&local.id, "session-cached"),
+            Ok(TerminalBackend::Local)
+        );
+        assert!(session_backend(&local.id, "session-other").is_err());
+        session_backends().remove("session-cached");
+    }
+}
@@ -4,27 +4,47 @@ use tracing::{debug, error, info, warn};
 
 use crate::claude_cli;
 use crate::credentials;
+use crate::disk::{self, DiskLocation, DiskSpaceWarning};
+use crate::docker::firewall::AllowedDomainsDiff;
 use crate::docker::{
-    create_environment_container, get_container_environment_status, get_docker_client,
+    create_environment_container, describe_environment_container, docker_restart_policy,
+    find_environment_containers, get_container_engine, get_container_environment_status,
+    get_container_started_at, get_host_capacity, kill_environment_container,
     remove_environment_container, start_environment_container, stop_environment_container,
-    ContainerConfig, DockerError,
+    wait_for_workspace_setup, ContainerConfig, ContainerConfigPreview, DockerError,
 };
+use crate::host_resources::{self, ResourceLimitWarning};
 use crate::local::{
     allocate_ports, close_local_terminal_sessions_for_environment, configure_local_git_artifacts,
-    copy_env_files, copy_project_files, create_worktree, delete_worktree, get_setup_local_commands,
-    isolated_opencode_data_home, stop_all_local_servers,
+    copy_env_files, copy_project_files, create_worktree, delete_branch, delete_worktree,
+    get_setup_container_commands, get_setup_local_commands, isolated_opencode_data_home,
+    move_worktree, remove_clean_worktree, rename_branch, stop_all_local_servers, switch_branch,
 };
 use crate::models::{
-    sanitize_branch_name, sanitize_environment_name, ClaudeMode, ClaudeNativeBackend, CodexMode,
-    DefaultAgent, Environment, EnvironmentStatus, EnvironmentType, NetworkAccessMode, OpenCodeMode,
-    PortMapping, PrState,
+    is_valid_branch_name, sanitize_branch_name, sanitize_environment_name, AuthPreference,
+    ClaudeMode, ClaudeNativeBackend, CodexMode, DefaultAgent, Environment, EnvironmentStatus,
+    EnvironmentType, GlobalConfig, NetworkAccessMode, OpenCodeMode, PortMapping, PrState,
+    RepositoryConfig, RestartPolicy, Session, SessionType, WorktreeConflict,
+    WorktreeConflictReason, DEFAULT_ENVIRONMENT_NAME_MAX_WORDS, DEFAULT_WORKSPACE_PATH,
 };
 use crate::storage::{get_config, get_storage, Storage, StorageError};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
 use tauri::Emitter;
+use tokio::sync::watch;
 
 use super::claude_tmux::stop_tmux_sessions_for_environment;
+use super::config::ensure_valid_config;
+use super::local_servers::stop_local_server_log_streams_for_environment;
+use super::network::{
+    is_ip_or_cidr, is_valid_firewall_entry, parse_domain_list, resolve_domain,
+    validate_firewall_entries,
+};
 
 /// Event payload emitted when an environment is renamed in the background
 #[derive(Clone, Serialize, Deserialize)]
@@ -34,12 +54,226 @@ pub struct EnvironmentRenamedPayload {
     pub new_branch: String,
 }
 
+/// Event payload emitted when background naming starts for an environment
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EnvironmentNamingStartedPayload {
+    pub environment_id: String,
+}
+
+/// How a background naming task ended
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NamingOutcome {
+    /// The environment was renamed to the generated name
+    Renamed,
+    /// The task was cancelled before it applied a name
+    Cancelled,
+    /// The user renamed the environment while the name was being generated
+    Skipped,
+    /// Name generation or the rename itself failed
+    Failed,
+}
+
+/// Event payload emitted when background naming finishes for an environment
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EnvironmentNamingFinishedPayload {
+    pub environment_id: String,
+    pub outcome: NamingOutcome,
+}
+
+/// Event payload emitted when a project's environments were saved, so the UI
+/// can reload just that project's list
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EnvironmentsInvalidatedPayload {
+    pub project_id: String,
+}
+
+/// Emit `environments-invalidated` after every storage write to a project's
+/// environments, so commands don't have to emit it themselves
+pub fn emit_environments_invalidated_events(app_handle: &tauri::AppHandle) {
+    let storage = match get_storage() {
+        Ok(storage) => storage,
+        Err(e) => {
+            warn!(error = %e, "Storage unavailable, environments-invalidated events disabled");
+            return;
+        }
+    };
+    let app_handle = app_handle.clone();
+    storage.set_environments_listener(move |project_id| {
+        let payload = EnvironmentsInvalidatedPayload {
+            project_id: project_id.to_string(),
+        };
+        if let Err(e) = app_handle.emit("environments-invalidated", payload) {
+            warn!(project_id = %project_id, error = %e, "Failed to emit environments-invalidated event");
+        }
+    });
+}
+
+/// Event payload emitted when an in-flight environment start is cancelled
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EnvironmentStartCancelledPayload {
+    pub environment_id: String,
+    pub message: String,
+}
+
+/// Step reached by an environment start, reported via `environment-start-progress`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StartProgressStage {
+    /// Creating the Docker container
+    CreatingContainer,
+    /// Starting the Docker container
+    StartingContainer,
+    /// Container is up and its workspace setup (clone, entrypoint) is running
+    RunningSetup,
+}
+
+/// Event payload emitted as `start_environment` / `recreate_environment` progress
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EnvironmentStartProgressPayload {
+    pub environment_id: String,
+    pub stage: StartProgressStage,
+}
+
+fn emit_start_progress(
+    app_handle: &tauri::AppHandle,
+    environment_id: &str,
+    stage: StartProgressStage,
+) {
+    debug!(environment_id = %environment_id, stage = ?stage, "Environment start progress");
+    let payload = EnvironmentStartProgressPayload {
+        environment_id: environment_id.to_string(),
+        stage,
+    };
+    if let Err(e) = app_handle.emit("environment-start-progress", payload) {
+        warn!(environment_id = %environment_id, error = %e, "Failed to emit start progress event");
+    }
+}
+
+/// How long stopping a container may take before the environment is marked as
+/// errored instead of staying `stopping` forever
+const STOP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Event payload emitted on each `stop_environment` status transition
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EnvironmentStatusChangedPayload {
+    pub environment_id: String,
+    pub status: EnvironmentStatus,
+}
+
+fn emit_status_changed(
+    app_handle: &tauri::AppHandle,
+    environment_id: &str,
+    status: EnvironmentStatus,
+) {
+    debug!(environment_id = %environment_id, status = %status, "Environment status changed");
+    let payload = EnvironmentStatusChangedPayload {
+        environment_id: environment_id.to_string(),
+        status,
+    };
+    if let Err(e) = app_handle.emit("environment-status-changed", payload) {
+        warn!(environment_id = %environment_id, error = %e, "Failed to emit status changed event");
+    }
+}
+
+/// Registered tasks keyed by environment ID. Each entry holds a task ID (so a
+/// finished task can't unregister a newer one) and a channel that is set to
+/// `true` to cancel the task.
+type CancellableTasks = HashMap<String, (u64, watch::Sender<bool>)>;
+
+/// Cancellable per-environment tasks
+struct CancellationRegistry {
+    tasks: OnceLock<Mutex<CancellableTasks>>,
+    next_task_id: AtomicU64,
+}
+
+impl CancellationRegistry {
+    const fn new() -> Self {
+        Self {
+            tasks: OnceLock::new(),
+            next_task_id: AtomicU64::new(0),
+        }
+    }
+
+    fn tasks(&self) -> std::sync::MutexGuard<'_, CancellableTasks> {
+        self.tasks
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Register a task for an environment, cancelling any previous one.
+    /// Returns the task ID and a receiver that flips to `true` on cancellation.
+    fn begin(&self, environment_id: &str) -> (u64, watch::Receiver<bool>) {
+        let task_id = self.next_task_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = watch::channel(false);
+        if let Some((_, previous)) = self
+            .tasks()
+            .insert(environment_id.to_string(), (task_id, tx))
+        {
+            let _ = previous.send(true);
+        }
+        (task_id, rx)
+    }
+
+    /// Unregister a task once it has finished
+    fn end(&self, environment_id: &str, task_id: u64) {
+        let mut tasks = self.tasks();
+        if tasks
+            .get(environment_id)
+            .is_some_and(|(id, _)| *id == task_id)
+        {
+            tasks.remove(environment_id);
+        }
+    }
+
+    /// Cancel the task for an environment. Returns whether one was running.
+    fn cancel(&self, environment_id: &str) -> bool {
+        match self.tasks().remove(environment_id) {
+            Some((_, tx)) => {
+                let _ = tx.send(true);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn is_active(&self, environment_id: &str) -> bool {
+        self.tasks().contains_key(environment_id)
+    }
+}
+
+/// Background naming tasks in progress
+static NAMING_TASKS: CancellationRegistry = CancellationRegistry::new();
+/// Environment starts in progress
+static START_TASKS: CancellationRegistry = CancellationRegistry::new();
+
+/// Event payload emitted when an environment's firewall bypass is toggled
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FirewallBypassPayload {
+    pub environment_id: String,
+    pub bypassed: bool,
+}
+
 /// Result from starting an environment
 #[derive(Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct StartEnvironmentResult {
-    /// Setup commands to run in a terminal (for local environments with orkestrator-ai.json)
+    /// Setup commands declared in orkestrator-ai.json: `setupLocal` for local
+    /// environments, `setupContainer` for containerized ones
+    /// (see `SetupCommandsKind` for the schema)
     pub setup_commands: Option<Vec<String>>,
+    /// Locations that were low on disk space when the environment started
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disk_space_warnings: Vec<DiskSpaceWarning>,
+    /// Container resource limits above the host's capacity when the
+    /// environment's container was created
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resource_limit_warnings: Vec<ResourceLimitWarning>,
+    /// Branch conflict hit while creating a local environment's worktree;
+    /// settle it with `resolve_worktree_conflict`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree_conflict: Option<WorktreeConflict>,
 }
 
 /// Convert storage errors to string for Tauri
@@ -47,7 +281,7 @@ fn storage_error_to_string(err: StorageError) -> String {
     err.to_string()
 }
 
-fn resolve_base_branch_override(
+pub(crate) fn resolve_base_branch_override(
     config: &crate::models::AppConfig,
     project_id: &str,
 ) -> Option<String> {
@@ -58,6 +292,101 @@ fn resolve_base_branch_override(
         .filter(|branch| !branch.is_empty())
 }
 
+/// Resolve the branch an environment's branch is created from: the
+/// environment's own base ref first, then the repository default branch.
+pub(crate) fn resolve_environment_base_branch(
+    config: Option<&crate::models::AppConfig>,
+    environment: &Environment,
+) -> Option<String> {
+    environment
+        .base_ref
+        .as_deref()
+        .map(str::trim)
+        .filter(|base_ref| !base_ref.is_empty())
+        .map(str::to_string)
+        .or_else(|| {
+            config.and_then(|config| resolve_base_branch_override(config, &environment.project_id))
+        })
+}
+
+/// Resolve the restart policy for an environment's container: environment
+/// override first, then the repository default, then `no`.
+pub(crate) fn resolve_restart_policy(
+    config: &crate::models::AppConfig,
+    environment: &Environment,
+) -> RestartPolicy {
+    environment
+        .restart_policy
+        .or_else(|| {
+            config
+                .repositories
+                .get(&environment.project_id)
+                .and_then(|repo| repo.restart_policy)
+        })
+        .unwrap_or_default()
+}
+
+/// Clone depth for an environment's container: the repository's
+/// `clone_depth` unless the environment asks for a full clone
+pub(crate) fn resolve_clone_depth(
+    config: &crate::models::AppConfig,
+    environment: &Environment,
+) -> Option<u32> {
+    if environment.full_clone {
+        return None;
+    }
+    config
+        .repositories
+        .get(&environment.project_id)
+        .and_then(|repo| repo.clone_depth)
+        .filter(|depth| *depth > 0)
+}
+
+/// Sessions to auto-create for an environment: environment override first,
+/// then the repository default, then none.
+pub(crate) fn resolve_default_sessions(
+    config: &crate::models::AppConfig,
+    environment: &Environment,
+) -> Vec<SessionType> {
+    environment
+        .default_sessions
+        .clone()
+        .or_else(|| {
+            config
+                .repositories
+                .get(&environment.project_id)
+                .and_then(|repo| repo.default_sessions.clone())
+        })
+        .unwrap_or_default()
+}
+
+/// Reject default session lists that would evict their own sessions when
+/// created, since an environment keeps a limited number of sessions
+pub(crate) fn validate_default_sessions(sessions: &[SessionType]) -> Result<(), String> {
+    let max = crate::storage::Storage::MAX_SESSIONS_PER_ENVIRONMENT;
+    if sessions.len() > max {
+        return Err(format!(
+            "At most {} default sessions are allowed, got {}",
+            max,
+            sessions.len()
+        ));
+    }
+    Ok(())
+}
+
+/// The OpenCode model an environment uses: its override if set, otherwise the
+/// global `opencode_model`.
+pub(crate) fn resolve_opencode_model(
+    config: &crate::models::AppConfig,
+    environment: &Environment,
+) -> String {
+    environment
+        .opencode_model_override
+        .clone()
+        .filter(|model| !model.trim().is_empty())
+        .unwrap_or_else(|| config.global.opencode_model.clone())
+}
+
 fn resolve_container_github_token(
     configured_token: Option<&str>,
     environment_id: &str,
@@ -94,17 +423,23 @@ fn resolve_container_github_token(
 /// Fetch setup commands from orkestrator-ai.json and log if any are found
 ///
 /// Returns `None` if no setup commands are configured, otherwise `Some(commands)`.
+/// An invalid `orkestrator-ai.json` is logged and treated as having no commands
+/// so environment start isn't blocked by a config mistake.
 async fn fetch_setup_commands(worktree_path: &str, environment_id: &str) -> Option<Vec<String>> {
-    let commands = get_setup_local_commands(worktree_path).await;
-    if commands.is_empty() {
-        None
-    } else {
-        info!(
-            environment_id = %environment_id,
-            command_count = commands.len(),
-            "Found setupLocal commands to run in terminal"
-        );
-        Some(commands)
+    match get_setup_local_commands(worktree_path).await {
+        Ok(commands) if commands.is_empty() => None,
+        Ok(commands) => {
+            info!(
+                environment_id = %environment_id,
+                command_count = commands.len(),
+                "Found setupLocal commands to run in terminal"
+            );
+            Some(commands)
+        }
+        Err(e) => {
+            warn!(environment_id = %environment_id, error = %e, "Ignoring setupLocal commands");
+            None
+        }
     }
 }
 
@@ -124,6 +459,41 @@ async fn fetch_setup_commands_for_start(
     }
 }
 
+/// Read `setupContainer` commands from the project's local checkout so they can
+/// be surfaced to the frontend after a container starts. workspace-setup.sh
+/// runs these inside the container itself; returning them lets the UI show or
+/// re-run them in a terminal. Skipped once setup has completed.
+async fn fetch_container_setup_commands_for_start(
+    project: &crate::models::Project,
+    environment_id: &str,
+    setup_scripts_complete: bool,
+) -> Option<Vec<String>> {
+    if setup_scripts_complete {
+        debug!(
+            environment_id = %environment_id,
+            "Skipping setupContainer commands for completed container environment"
+        );
+        return None;
+    }
+
+    let local_path = project.local_path.as_deref()?;
+    match get_setup_container_commands(local_path).await {
+        Ok(commands) if commands.is_empty() => None,
+        Ok(commands) => {
+            info!(
+                environment_id = %environment_id,
+                command_count = commands.len(),
+                "Found setupContainer commands"
+            );
+            Some(commands)
+        }
+        Err(e) => {
+            warn!(environment_id = %environment_id, error = %e, "Ignoring setupContainer commands");
+            None
+        }
+    }
+}
+
 /// Resolve and persist entry port mapping for a container environment.
 ///
 /// When `entry_port` is `Some`, queries Docker for the host port mapped to the
@@ -136,42 +506,36 @@ async fn resolve_and_store_entry_port(
     entry_port: Option<u16>,
 ) {
     if let Some(ep) = entry_port {
-        match get_docker_client() {
-            Ok(docker) => match docker.get_host_port(container_id, ep, "tcp").await {
-                Ok(Some(host_port)) => {
-                    debug!(
-                        environment_id = %environment_id,
-                        container_port = ep,
-                        host_port = host_port,
-                        "Resolved dynamic entry port mapping"
-                    );
-                    let _ = storage.update_environment(
-                        environment_id,
-                        json!({ "entryPort": ep, "hostEntryPort": host_port }),
-                    );
-                }
-                Ok(None) => {
-                    warn!(
-                        environment_id = %environment_id,
-                        container_port = ep,
-                        "Entry port not found in container port bindings"
-                    );
-                    let _ = storage.update_environment(environment_id, json!({ "entryPort": ep }));
-                }
-                Err(e) => {
-                    warn!(
-                        environment_id = %environment_id,
-                        error = %e,
-                        "Failed to query entry port mapping"
-                    );
-                    let _ = storage.update_environment(environment_id, json!({ "entryPort": ep }));
-                }
-            },
+        let host_port = match get_container_engine() {
+            Ok(engine) => engine.get_host_port(container_id, ep, "tcp").await,
+            Err(e) => Err(e),
+        };
+        match host_port {
+            Ok(Some(host_port)) => {
+                debug!(
+                    environment_id = %environment_id,
+                    container_port = ep,
+                    host_port = host_port,
+                    "Resolved dynamic entry port mapping"
+                );
+                let _ = storage.update_environment(
+                    environment_id,
+                    json!({ "entryPort": ep, "hostEntryPort": host_port }),
+                );
+            }
+            Ok(None) => {
+                warn!(
+                    environment_id = %environment_id,
+                    container_port = ep,
+                    "Entry port not found in container port bindings"
+                );
+                let _ = storage.update_environment(environment_id, json!({ "entryPort": ep }));
+            }
             Err(e) => {
                 warn!(
                     environment_id = %environment_id,
                     error = %e,
-                    "Failed to get docker client for port query"
+                    "Failed to query entry port mapping"
                 );
                 let _ = storage.update_environment(environment_id, json!({ "entryPort": ep }));
             }
@@ -347,6 +711,35 @@ fn normalize_initial_prompt(initial_prompt: Option<&str>) -> Option<String> {
         .map(str::to_string)
 }
 
+/// Maximum number of characters of the initial prompt kept on the environment
+const MAX_STORED_INITIAL_PROMPT_CHARS: usize = 2000;
+
+/// Truncate a prompt for storage on the environment, at a UTF-8 character boundary.
+fn truncate_initial_prompt(prompt: &str) -> String {
+    match prompt.char_indices().nth(MAX_STORED_INITIAL_PROMPT_CHARS) {
+        Some((end_idx, _)) => format!("{}...", &prompt[..end_idx]),
+        None => prompt.to_string(),
+    }
+}
+
+/// Maximum size of an environment's notes, in bytes
+const MAX_ENVIRONMENT_NOTES_BYTES: usize = 10 * 1024;
+
+/// Blank notes clear the field; notes over the size cap are rejected
+fn normalize_environment_notes(notes: Option<String>) -> Result<Option<String>, String> {
+    let notes = notes.filter(|n| !n.trim().is_empty());
+    if let Some(n) = &notes {
+        if n.len() > MAX_ENVIRONMENT_NOTES_BYTES {
+            return Err(format!(
+                "Notes are too long ({} bytes, maximum {})",
+                n.len(),
+                MAX_ENVIRONMENT_NOTES_BYTES
+            ));
+        }
+    }
+    Ok(notes)
+}
+
 fn persist_last_environment_type(
     storage: &Storage,
     project_id: &str,
@@ -361,10 +754,37 @@ fn persist_last_environment_type(
     storage.save_config(&config)
 }
 
+/// App ports from the repository's default port mappings, used to seed the
+/// reserved localhost ports of new local environments.
+fn default_local_app_ports(storage: &Storage, project_id: &str) -> Vec<u16> {
+    storage
+        .load_config()
+        .ok()
+        .and_then(|mut config| config.repositories.remove(project_id))
+        .and_then(|repo| repo.default_port_mappings)
+        .map(|mappings| {
+            mappings
+                .iter()
+                .map(|mapping| mapping.container_port)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Configured word cap for AI-generated environment names, falling back to the
+/// default when the config cannot be loaded.
+fn environment_name_max_words() -> usize {
+    get_storage()
+        .and_then(|storage| storage.load_config())
+        .map(|config| config.global.environment_name_max_words)
+        .unwrap_or(DEFAULT_ENVIRONMENT_NAME_MAX_WORDS) as usize
+}
+
 async fn generate_initial_environment_name(prompt: &str) -> Option<String> {
     let prompt = prompt.to_string();
+    let max_words = environment_name_max_words();
     match tokio::task::spawn_blocking(move || {
-        claude_cli::generate_environment_name_with_fallback(&prompt)
+        claude_cli::generate_environment_name_with_fallback(&prompt, max_words)
     })
     .await
     {
@@ -380,7 +800,28 @@ async fn generate_initial_environment_name(prompt: &str) -> Option<String> {
     }
 }
 
+/// Suggest an environment name for a prompt without creating anything.
+/// Lets the UI show the AI-generated name up front so the user can accept or
+/// edit it, instead of having the name change after creation.
+#[tauri::command]
+pub async fn suggest_environment_name(prompt: String) -> Result<String, String> {
+    let prompt = prompt.trim().to_string();
+    if prompt.is_empty() {
+        return Err("Prompt cannot be empty".to_string());
+    }
+
+    let max_words = environment_name_max_words();
+    let name = tokio::task::spawn_blocking(move || {
+        claude_cli::generate_environment_name_with_fallback(&prompt, max_words)
+    })
+    .await
+    .map_err(|e| format!("Name generation task failed: {}", e))??;
+
+    Ok(sanitize_environment_name(&name))
+}
+
 /// Create a new environment for a project
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
 pub async fn create_environment(
     _app_handle: tauri::AppHandle,
@@ -390,7 +831,17 @@ pub async fn create_environment(
     initial_prompt: Option<String>,
     port_mappings: Option<Vec<PortMapping>>,
     environment_type: Option<String>,
+    base_ref: Option<String>,
 ) -> Result<Environment, String> {
+    let base_ref = base_ref
+        .map(|base_ref| base_ref.trim().to_string())
+        .filter(|base_ref| !base_ref.is_empty());
+    if let Some(base_ref) = &base_ref {
+        if !is_valid_branch_name(base_ref) {
+            return Err(format!("Invalid base branch: {}", base_ref));
+        }
+    }
+
     let storage = get_storage().map_err(storage_error_to_string)?;
 
     // Verify project exists
@@ -490,11 +941,16 @@ pub async fn create_environment(
 
     // Set the network access mode
     environment.network_access_mode = network_mode;
-    environment.initial_prompt = trimmed_initial_prompt.clone();
+    environment.base_ref = base_ref;
+    environment.initial_prompt = trimmed_initial_prompt
+        .as_deref()
+        .map(truncate_initial_prompt);
 
-    // For local environments, allocate ports now
+    // For local environments, allocate server ports and reserve the repository's
+    // default app ports now
     if env_type == EnvironmentType::Local {
-        let port_allocation = allocate_ports(&existing_environments)
+        let app_ports = default_local_app_ports(storage, &project_id);
+        let port_allocation = allocate_ports(&existing_environments, &app_ports)
             .map_err(|e| format!("Failed to allocate ports: {}", e))?;
         environment.local_opencode_port = Some(port_allocation.opencode_port);
         environment.local_claude_port = Some(port_allocation.claude_port);
@@ -503,8 +959,12 @@ pub async fn create_environment(
             opencode_port = port_allocation.opencode_port,
             claude_port = port_allocation.claude_port,
             codex_port = port_allocation.codex_port,
+            app_ports = ?port_allocation.app_ports,
             "Allocated ports for local environment"
         );
+        if !port_allocation.app_ports.is_empty() {
+            environment.local_app_ports = Some(port_allocation.app_ports);
+        }
     }
 
     // Set port mappings if provided (only for containerized environments)
@@ -698,52 +1158,131 @@ async fn rename_local_worktree_branch(
     }
 }
 
-/// Background task to generate a name via Claude CLI and rename the environment
+/// Background task to generate a name via Claude CLI and rename the environment.
+/// Registers the task so it can be cancelled and emits
+/// `environment-naming-started` / `environment-naming-finished` around it.
 async fn background_rename_environment(
     app_handle: tauri::AppHandle,
     environment_id: String,
     old_branch: String,
     prompt: String,
-) {
-    debug!(environment_id = %environment_id, "Starting background naming");
+) -> NamingOutcome {
+    let (task_id, cancel_rx) = NAMING_TASKS.begin(&environment_id);
+    let started = EnvironmentNamingStartedPayload {
+        environment_id: environment_id.clone(),
+    };
+    if let Err(e) = app_handle.emit("environment-naming-started", started) {
+        warn!(environment_id = %environment_id, error = %e, "Failed to emit naming started event");
+    }
 
-    // Generate name using available AI CLI (Claude preferred, OpenCode fallback)
-    // This is a blocking call, but we're in a background task
-    let generated_name = match tokio::task::spawn_blocking(move || {
-        claude_cli::generate_environment_name_with_fallback(&prompt)
-    })
-    .await
-    {
-        Ok(Ok(name)) => name,
-        Ok(Err(e)) => {
-            warn!(environment_id = %environment_id, error = %e, "Failed to generate name");
-            return;
-        }
-        Err(e) => {
-            warn!(environment_id = %environment_id, error = %e, "Task panicked");
-            return;
-        }
+    let outcome = generate_and_apply_environment_name(
+        &app_handle,
+        &environment_id,
+        &old_branch,
+        prompt,
+        cancel_rx,
+    )
+    .await;
+
+    NAMING_TASKS.end(&environment_id, task_id);
+    debug!(environment_id = %environment_id, outcome = ?outcome, "Background naming finished");
+    let finished = EnvironmentNamingFinishedPayload {
+        environment_id: environment_id.clone(),
+        outcome,
     };
+    if let Err(e) = app_handle.emit("environment-naming-finished", finished) {
+        warn!(environment_id = %environment_id, error = %e, "Failed to emit naming finished event");
+    }
 
-    debug!(environment_id = %environment_id, generated_name = %generated_name, "Name generated");
+    outcome
+}
 
-    // Get storage and make name unique
+/// Generate a name for the environment and rename it (storage, branch, and
+/// container). Gives up without renaming if cancelled or if the user renamed
+/// the environment while the name was being generated.
+async fn generate_and_apply_environment_name(
+    app_handle: &tauri::AppHandle,
+    environment_id: &str,
+    old_branch: &str,
+    prompt: String,
+    mut cancel_rx: watch::Receiver<bool>,
+) -> NamingOutcome {
+    let environment_id = environment_id.to_string();
+    let old_branch = old_branch.to_string();
+    debug!(environment_id = %environment_id, "Starting background naming");
+
+    // Get storage and remember the current name so a manual rename made while
+    // the name is being generated isn't overwritten
     let storage = match get_storage() {
         Ok(s) => s,
         Err(e) => {
             warn!(environment_id = %environment_id, error = %e, "Failed to get storage");
-            return;
+            return NamingOutcome::Failed;
+        }
+    };
+    let original_name = match storage.get_environment(&environment_id) {
+        Ok(Some(env)) => env.name,
+        Ok(None) => return NamingOutcome::Failed,
+        Err(e) => {
+            warn!(environment_id = %environment_id, error = %e, "Failed to load environment");
+            return NamingOutcome::Failed;
+        }
+    };
+
+    // Generate name using available AI CLI (Claude preferred, OpenCode fallback).
+    // The CLI call is blocking; if cancelled we stop waiting for it and drop its result.
+    let max_words = environment_name_max_words();
+    let generation = tokio::task::spawn_blocking(move || {
+        claude_cli::generate_environment_name_with_fallback(&prompt, max_words)
+    });
+    let generated_name = tokio::select! {
+        result = generation => match result {
+            Ok(Ok(name)) => name,
+            Ok(Err(e)) => {
+                warn!(environment_id = %environment_id, error = %e, "Failed to generate name");
+                return NamingOutcome::Failed;
+            }
+            Err(e) => {
+                warn!(environment_id = %environment_id, error = %e, "Task panicked");
+                return NamingOutcome::Failed;
+            }
+        },
+        _ = cancel_rx.wait_for(|cancelled| *cancelled) => {
+            debug!(environment_id = %environment_id, "Background naming cancelled");
+            return NamingOutcome::Cancelled;
         }
     };
 
+    debug!(environment_id = %environment_id, generated_name = %generated_name, "Name generated");
+
+    if *cancel_rx.borrow() {
+        return NamingOutcome::Cancelled;
+    }
+
     let existing_environments = match storage.load_environments() {
         Ok(envs) => envs,
         Err(e) => {
             warn!(environment_id = %environment_id, error = %e, "Failed to load environments");
-            return;
+            return NamingOutcome::Failed;
         }
     };
 
+    match existing_environments
+        .iter()
+        .find(|e| e.id == environment_id)
+    {
+        Some(env) if env.name != original_name => {
+            info!(
+                environment_id = %environment_id,
+                current_name = %env.name,
+                "Environment was renamed manually, skipping generated name"
+            );
+            return NamingOutcome::Skipped;
+        }
+        Some(_) => {}
+        None => return NamingOutcome::Failed,
+    }
+
     // Sanitize the generated name to kebab-case lowercase (matching branch/container convention)
     let sanitized_name = sanitize_environment_name(&generated_name);
 
@@ -762,7 +1301,7 @@ async fn background_rename_environment(
     let update = build_rename_update(&unique_name, &unique_branch, &old_branch);
     if let Err(e) = storage.update_environment(&environment_id, update) {
         warn!(environment_id = %environment_id, error = %e, "Failed to update environment");
-        return;
+        return NamingOutcome::Failed;
     }
 
     debug!(environment_id = %environment_id, "Environment updated in storage");
@@ -786,41 +1325,35 @@ async fn background_rename_environment(
             // Containerized environment: rename branch inside the container
             if let Some(container_id) = &env.container_id {
                 debug!(environment_id = %environment_id, container_id = %container_id, "Renaming git branch in container");
-                if let Ok(docker) = get_docker_client() {
+                if let Ok(docker) = get_container_engine() {
                     // Wait for workspace setup to complete (max 60 seconds)
-                    // The workspace-setup.sh creates /tmp/.workspace-setup-complete when done
-                    let wait_cmd = r#"
-                        count=0
-                        while [ ! -f /tmp/.workspace-setup-complete ] && [ $count -lt 120 ]; do
-                            sleep 0.5
-                            count=$((count + 1))
-                        done
-                        [ -f /tmp/.workspace-setup-complete ]
-                    "#;
-
-                    match docker
-                        .exec_command(container_id, vec!["sh", "-c", wait_cmd])
-                        .await
-                    {
-                        Ok(_) => {
+                    match wait_for_workspace_setup(container_id, 60).await {
+                        Ok(true) => {
                             debug!(environment_id = %environment_id, "Workspace setup complete, proceeding with branch rename");
                         }
-                        Err(e) => {
-                            warn!(environment_id = %environment_id, error = %e, "Timeout waiting for workspace setup");
+                        Ok(false) => {
+                            warn!(environment_id = %environment_id, "Timeout waiting for workspace setup");
                             // Continue anyway - the branch rename might still work
                         }
+                        Err(e) => {
+                            warn!(environment_id = %environment_id, error = %e, "Failed to wait for workspace setup");
+                        }
                     }
 
                     // Rename the git branch: git branch -m <old_branch> <new_branch>
                     // Pass arguments directly to git to avoid shell injection vulnerabilities
                     // Using git -C to set the working directory instead of sh -c with cd
+                    let workspace_path = docker
+                        .get_workspace_path(container_id)
+                        .await
+                        .unwrap_or_else(|_| DEFAULT_WORKSPACE_PATH.to_string());
                     match docker
                         .exec_command(
                             container_id,
                             vec![
                                 "git",
                                 "-C",
-                                "/workspace",
+                                &workspace_path,
                                 "branch",
                                 "-m",
                                 "--",
@@ -872,12 +1405,32 @@ async fn background_rename_environment(
     } else {
         debug!(environment_id = %environment_id, "Emitted environment-renamed event");
     }
+
+    NamingOutcome::Renamed
 }
 
-/// Delete an environment
+/// Cancel an in-progress background naming task for an environment.
+/// Returns whether a task was running.
+#[tauri::command]
+pub async fn cancel_environment_naming(environment_id: String) -> Result<bool, String> {
+    let cancelled = NAMING_TASKS.cancel(&environment_id);
+    if cancelled {
+        info!(environment_id = %environment_id, "Cancelled background naming");
+    }
+    Ok(cancelled)
+}
+
+/// Whether a background naming task is running for an environment
+#[tauri::command]
+pub async fn is_environment_naming(environment_id: String) -> Result<bool, String> {
+    Ok(NAMING_TASKS.is_active(&environment_id))
+}
+
+/// Delete an environment
 #[tauri::command]
 pub async fn delete_environment(environment_id: String) -> Result<(), String> {
     let storage = get_storage().map_err(storage_error_to_string)?;
+    NAMING_TASKS.cancel(&environment_id);
 
     // Get the environment first to check if we need to stop a container or delete a worktree
     // If this fails, we still try to remove the environment from storage
@@ -905,6 +1458,8 @@ pub async fn delete_environment(environment_id: String) -> Result<(), String> {
             if let Err(e) = stop_all_local_servers(&environment_id).await {
                 warn!(environment_id = %environment_id, error = %e, "Failed to stop local servers during deletion");
             }
+            stop_local_server_log_streams_for_environment(&environment_id);
+            crate::local::logs::remove_server_logs(&environment_id);
 
             // Delete the worktree if it exists
             if let (Some(worktree_path), Some(local_path)) = (
@@ -966,10 +1521,55 @@ pub async fn delete_environment(environment_id: String) -> Result<(), String> {
 }
 
 /// Sync all environments with Docker state
-/// Clears container references for environments whose Docker containers no longer exist
-/// Returns a list of environment IDs whose container references were cleared
+/// Clears container references for environments whose Docker containers no longer exist,
+/// and resets environments left `creating`/`stopping` without a container (e.g. the app
+/// was killed mid-start) to `stopped`
+/// Returns a list of environment IDs whose container references or statuses were reset
 #[tauri::command]
 pub async fn sync_all_environments_with_docker() -> Result<Vec<String>, String> {
+    run_sync_all_environments().await
+}
+
+/// Event payload emitted when the launch-time reconciliation reset environments
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EnvironmentsReconciledPayload {
+    pub environment_ids: Vec<String>,
+}
+
+/// Sync all environments with Docker once at app launch, so stale statuses and
+/// orphaned container references are cleaned up before the UI loads them.
+/// Skipped when Docker is unavailable, since every container lookup would fail
+/// and wrongly clear all container references.
+pub async fn reconcile_environments_on_launch(app_handle: tauri::AppHandle) {
+    if !crate::docker::is_docker_available().await {
+        info!("Docker unavailable, skipping launch-time environment reconciliation");
+        return;
+    }
+
+    match run_sync_all_environments().await {
+        Ok(environment_ids) if !environment_ids.is_empty() => {
+            let payload = EnvironmentsReconciledPayload { environment_ids };
+            if let Err(e) = app_handle.emit("environments-reconciled", payload) {
+                warn!(error = %e, "Failed to emit environments-reconciled event");
+            }
+        }
+        Ok(_) => {}
+        Err(e) => warn!(error = %e, "Launch-time environment reconciliation failed"),
+    }
+}
+
+/// Whether an environment is `creating`/`stopping` with no container and no
+/// start in progress, i.e. the operation was interrupted and will never finish
+fn is_stuck_in_transition(environment: &Environment) -> bool {
+    environment.container_id.is_none()
+        && matches!(
+            environment.status,
+            EnvironmentStatus::Creating | EnvironmentStatus::Stopping
+        )
+        && !START_TASKS.is_active(&environment.id)
+}
+
+async fn run_sync_all_environments() -> Result<Vec<String>, String> {
     info!("Syncing all environments with Docker state");
 
     let storage = get_storage().map_err(storage_error_to_string)?;
@@ -988,6 +1588,15 @@ pub async fn sync_all_environments_with_docker() -> Result<Vec<String>, String>
 
     // Check each environment with a container_id against Docker
     for env in &environments {
+        if is_stuck_in_transition(env) {
+            info!(
+                environment_id = %env.id,
+                status = %env.status,
+                "Environment stuck in transition without a container, resetting to stopped"
+            );
+            environments_to_clear.push(env.id.clone());
+            continue;
+        }
         if let Some(container_id) = &env.container_id {
             // Try to get the container status from Docker
             match get_container_environment_status(container_id).await {
@@ -1095,6 +1704,84 @@ pub async fn get_environment(environment_id: String) -> Result<Option<Environmen
     Ok(None)
 }
 
+/// Age and most recent activity of an environment, for finding stale ones
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentActivity {
+    pub environment_id: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    /// Latest `last_activity_at` of the environment's stored sessions
+    pub last_session_activity_at: Option<DateTime<Utc>>,
+    /// When the container was last started (containerized environments only)
+    pub container_started_at: Option<DateTime<Utc>>,
+    /// Latest of the three timestamps above
+    pub last_active_at: DateTime<Utc>,
+    /// Whole days since `last_active_at`
+    pub days_idle: i64,
+}
+
+fn summarize_environment_activity(
+    environment: &Environment,
+    sessions: &[Session],
+    container_started_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> EnvironmentActivity {
+    let last_session_activity_at = sessions
+        .iter()
+        .filter(|s| s.environment_id == environment.id)
+        .map(|s| s.last_activity_at)
+        .max();
+    let last_active_at = [last_session_activity_at, container_started_at]
+        .into_iter()
+        .flatten()
+        .fold(environment.created_at, DateTime::max);
+
+    EnvironmentActivity {
+        environment_id: environment.id.clone(),
+        name: environment.name.clone(),
+        created_at: environment.created_at,
+        last_session_activity_at,
+        container_started_at,
+        last_active_at,
+        days_idle: (now - last_active_at).num_days().max(0),
+    }
+}
+
+/// Age and last activity of every environment in a project. Session activity
+/// comes from stored sessions, not live terminals.
+#[tauri::command]
+pub async fn get_environment_activity(
+    project_id: String,
+) -> Result<Vec<EnvironmentActivity>, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    let environments = storage
+        .get_environments_by_project(&project_id)
+        .map_err(storage_error_to_string)?;
+    let sessions = storage.load_sessions().map_err(storage_error_to_string)?;
+
+    let now = Utc::now();
+    let mut activity = Vec::with_capacity(environments.len());
+    for environment in &environments {
+        let container_started_at = match &environment.container_id {
+            Some(container_id) => get_container_started_at(container_id)
+                .await
+                .unwrap_or_else(|e| {
+                    debug!(environment_id = %environment.id, error = %e, "Could not inspect container start time");
+                    None
+                }),
+            None => None,
+        };
+        activity.push(summarize_environment_activity(
+            environment,
+            &sessions,
+            container_started_at,
+            now,
+        ));
+    }
+    Ok(activity)
+}
+
 /// Update environment status
 #[tauri::command]
 pub async fn update_environment_status(
@@ -1169,7 +1856,14 @@ pub async fn get_setup_commands(environment_id: String) -> Result<Option<Vec<Str
         return Ok(None);
     };
 
-    Ok(fetch_setup_commands(worktree_path, &environment_id).await)
+    let commands = get_setup_local_commands(worktree_path)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(if commands.is_empty() {
+        None
+    } else {
+        Some(commands)
+    })
 }
 
 /// Persist whether setup scripts have completed for an environment.
@@ -1187,6 +1881,66 @@ pub async fn set_environment_setup_complete(
         .map_err(storage_error_to_string)
 }
 
+/// Turn review (read-only) mode on or off for an environment.
+///
+/// Read-only environments refuse file writes and PR merges, and the UI opens
+/// plain shells instead of launching an agent.
+#[tauri::command]
+pub async fn set_environment_read_only(
+    environment_id: String,
+    read_only: bool,
+) -> Result<Environment, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    storage
+        .update_environment(&environment_id, json!({ "readOnly": read_only }))
+        .map_err(storage_error_to_string)
+}
+
+/// Pin or unpin an environment. Pinned environments are listed first.
+#[tauri::command]
+pub async fn set_environment_pinned(
+    environment_id: String,
+    pinned: bool,
+) -> Result<Environment, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    storage
+        .update_environment(&environment_id, json!({ "pinned": pinned }))
+        .map_err(storage_error_to_string)
+}
+
+/// Set an environment's free-form notes. Pass None or blank text to clear them.
+#[tauri::command]
+pub async fn set_environment_notes(
+    environment_id: String,
+    notes: Option<String>,
+) -> Result<Environment, String> {
+    let notes = normalize_environment_notes(notes)?;
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    storage
+        .update_environment(&environment_id, json!({ "notes": notes }))
+        .map_err(storage_error_to_string)
+}
+
+/// Set the OpenCode model for an environment, used instead of the global
+/// `opencode_model` the next time its container or local server starts.
+/// Pass None to go back to the global model.
+#[tauri::command]
+pub async fn set_environment_opencode_model(
+    environment_id: String,
+    model: Option<String>,
+) -> Result<Environment, String> {
+    let model = match model {
+        Some(model) if model.trim().is_empty() => {
+            return Err("OpenCode model cannot be empty".to_string())
+        }
+        model => model.map(|m| m.trim().to_string()),
+    };
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    storage
+        .update_environment(&environment_id, json!({ "opencodeModelOverride": model }))
+        .map_err(storage_error_to_string)
+}
+
 /// Update per-environment agent settings (default agent, claude mode, opencode mode, codex mode)
 /// Pass None for any field to use the global config default
 #[tauri::command]
@@ -1213,6 +1967,68 @@ pub async fn update_environment_agent_settings(
         .map_err(storage_error_to_string)
 }
 
+/// Update the per-environment Docker restart policy override.
+/// Pass None to inherit from the repository config. If the environment already
+/// has a container, the resolved policy is applied to it immediately.
+#[tauri::command]
+pub async fn update_environment_restart_policy(
+    environment_id: String,
+    restart_policy: Option<RestartPolicy>,
+) -> Result<Environment, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    let environment = storage
+        .update_environment(&environment_id, json!({ "restartPolicy": restart_policy }))
+        .map_err(storage_error_to_string)?;
+
+    if let Some(container_id) = &environment.container_id {
+        let config = get_config().map_err(|e| e.to_string())?;
+        let resolved = resolve_restart_policy(&config, &environment);
+        let client = get_container_engine().map_err(|e| e.to_string())?;
+        client
+            .update_restart_policy(container_id, docker_restart_policy(resolved))
+            .await
+            .map_err(|e| format!("Failed to update container restart policy: {}", e))?;
+        info!(
+            environment_id = %environment_id,
+            restart_policy = %resolved,
+            "Updated container restart policy"
+        );
+    }
+
+    Ok(environment)
+}
+
+/// Set whether an environment clones the full history even when its
+/// repository configures a shallow `clone_depth`. Takes effect when the
+/// container is next created; use `unshallow_repo` for an existing one.
+#[tauri::command]
+pub async fn set_environment_full_clone(
+    environment_id: String,
+    full_clone: bool,
+) -> Result<Environment, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    storage
+        .update_environment(&environment_id, json!({ "fullClone": full_clone }))
+        .map_err(storage_error_to_string)
+}
+
+/// Set the sessions `create_default_sessions` opens for an environment,
+/// overriding the repository default. An empty list opens none; pass None to
+/// go back to the repository default.
+#[tauri::command]
+pub async fn set_environment_default_sessions(
+    environment_id: String,
+    sessions: Option<Vec<SessionType>>,
+) -> Result<Environment, String> {
+    if let Some(sessions) = &sessions {
+        validate_default_sessions(sessions)?;
+    }
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    storage
+        .update_environment(&environment_id, json!({ "defaultSessions": sessions }))
+        .map_err(storage_error_to_string)
+}
+
 /// Rename an environment
 #[tauri::command]
 pub async fn rename_environment(
@@ -1234,6 +2050,11 @@ pub async fn rename_environment(
         );
     }
 
+    // A manual rename takes precedence over any name still being generated
+    if NAMING_TASKS.cancel(&environment_id) {
+        debug!(environment_id = %environment_id, "Cancelled background naming in favor of manual rename");
+    }
+
     let storage = get_storage().map_err(storage_error_to_string)?;
 
     // Get the current environment to access old branch name and container info
@@ -1281,15 +2102,19 @@ pub async fn rename_environment(
         }
     } else if let Some(container_id) = &environment.container_id {
         if environment.status == EnvironmentStatus::Running {
-            if let Ok(docker) = get_docker_client() {
+            if let Ok(docker) = get_container_engine() {
                 // Rename the git branch inside the container
+                let workspace_path = docker
+                    .get_workspace_path(container_id)
+                    .await
+                    .unwrap_or_else(|_| DEFAULT_WORKSPACE_PATH.to_string());
                 match docker
                     .exec_command(
                         container_id,
                         vec![
                             "git",
                             "-C",
-                            "/workspace",
+                            &workspace_path,
                             "branch",
                             "-m",
                             "--",
@@ -1359,6 +2184,16 @@ pub async fn rename_environment_from_prompt(
 
     let old_branch = environment.branch.clone();
 
+    // Remember what the environment is about if it was created without a prompt
+    if environment.initial_prompt.is_none() {
+        let stored_prompt = truncate_initial_prompt(&prompt);
+        if let Err(e) =
+            storage.update_environment(&environment_id, json!({ "initialPrompt": stored_prompt }))
+        {
+            warn!(environment_id = %environment_id, error = %e, "Failed to store initial prompt");
+        }
+    }
+
     debug!(environment_id = %environment_id, "Running naming task from first prompt (blocking until complete)");
 
     // Run inline — the frontend awaits this so the prompt is only sent after
@@ -1368,6 +2203,52 @@ pub async fn rename_environment_from_prompt(
     Ok(())
 }
 
+/// Re-roll an environment's AI-generated name from a new prompt, or from the
+/// environment's stored initial prompt when none is given.
+/// Runs the same naming pipeline as background naming (unique slug, storage,
+/// git branch, container, `environment-renamed` event) but waits for it and
+/// reports whether the rename happened.
+#[tauri::command]
+pub async fn regenerate_environment_name(
+    app_handle: tauri::AppHandle,
+    environment_id: String,
+    prompt: Option<String>,
+) -> Result<Environment, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+
+    let prompt = normalize_initial_prompt(prompt.as_deref())
+        .or_else(|| normalize_initial_prompt(environment.initial_prompt.as_deref()))
+        .ok_or_else(|| {
+            "Prompt cannot be empty and the environment has no stored prompt".to_string()
+        })?;
+
+    info!(environment_id = %environment_id, "Regenerating environment name");
+
+    let outcome = background_rename_environment(
+        app_handle,
+        environment_id.clone(),
+        environment.branch.clone(),
+        prompt,
+    )
+    .await;
+
+    match outcome {
+        NamingOutcome::Renamed => storage
+            .get_environment(&environment_id)
+            .map_err(storage_error_to_string)?
+            .ok_or_else(|| format!("Environment not found: {}", environment_id)),
+        NamingOutcome::Cancelled => Err("Name generation was cancelled".to_string()),
+        NamingOutcome::Skipped => {
+            Err("Environment was renamed while generating a new name".to_string())
+        }
+        NamingOutcome::Failed => Err("Failed to generate a new environment name".to_string()),
+    }
+}
+
 /// Get the current status of an environment
 #[tauri::command]
 pub async fn get_environment_status(environment_id: String) -> Result<EnvironmentStatus, String> {
@@ -1401,54 +2282,410 @@ pub async fn get_environment_status(environment_id: String) -> Result<Environmen
     Ok(environment.status)
 }
 
-/// Start an environment - creates and starts Docker container or git worktree
+/// Directories an environment start would write new data to: the worktree
+/// base for a local environment without a worktree, Docker's data root for a
+/// container that still has to be created, and always the app data directory
+async fn disk_space_locations(environment: &Environment) -> Vec<(DiskLocation, PathBuf)> {
+    let mut locations = Vec::new();
+    if let Ok(storage) = get_storage() {
+        locations.push((DiskLocation::DataDir, storage.data_dir().to_path_buf()));
+    }
+    if environment.is_local() {
+        if environment.worktree_path.is_none() {
+            if let Ok(base) = crate::local::worktree::get_worktree_base_path() {
+                locations.push((DiskLocation::WorktreeBase, base));
+            }
+        }
+    } else if environment.container_id.is_none() {
+        // Only a data root on this machine can be measured; Docker Desktop
+        // keeps it inside its VM
+        let root = match crate::docker::get_docker_client() {
+            Ok(client) => client
+                .system_info()
+                .await
+                .ok()
+                .and_then(|i| i.docker_root_dir),
+            Err(_) => None,
+        };
+        if let Some(root) = root.map(PathBuf::from).filter(|p| p.exists()) {
+            locations.push((DiskLocation::DockerRoot, root));
+        }
+    }
+    locations
+}
+
+/// Check free disk space for starting an environment against the configured
+/// minimum, so the UI can warn before the start fails with an opaque error
 #[tauri::command]
-pub async fn start_environment(environment_id: String) -> Result<StartEnvironmentResult, String> {
-    info!(environment_id = %environment_id, "Starting environment");
+pub async fn check_disk_space(environment_id: String) -> Result<Vec<DiskSpaceWarning>, String> {
+    let environment = get_storage()
+        .map_err(storage_error_to_string)?
+        .get_environment(&environment_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+    let config = get_config().map_err(|e| e.to_string())?;
 
+    let locations = disk_space_locations(&environment).await;
+    Ok(disk::check_disk_space(
+        &locations,
+        disk::threshold_bytes(config.global.min_free_disk_gb),
+    ))
+}
+
+/// Configured container resource limits that exceed the CPUs or memory
+/// available to Docker. Empty when Docker can't be queried.
+async fn check_container_resource_limits() -> Vec<ResourceLimitWarning> {
+    let config = match get_config() {
+        Ok(config) => config,
+        Err(e) => {
+            debug!(error = %e, "Could not load config for resource limit check");
+            return Vec::new();
+        }
+    };
+    match get_host_capacity().await {
+        Ok(host) => host_resources::check_resource_limits(&config.global.container_resources, host),
+        Err(e) => {
+            debug!(error = %e, "Could not read Docker host capacity");
+            Vec::new()
+        }
+    }
+}
+
+/// Check the configured container resource limits before a container is
+/// created. Returns the limits that exceed the host's capacity, or an error
+/// listing them when `block_on_excess_resource_limits` is set.
+pub(crate) async fn ensure_container_resource_limits(
+    environment_id: &str,
+) -> Result<Vec<ResourceLimitWarning>, String> {
+    let resource_limit_warnings = check_container_resource_limits().await;
+    if resource_limit_warnings.is_empty() {
+        return Ok(resource_limit_warnings);
+    }
+    for warning in &resource_limit_warnings {
+        warn!(environment_id = %environment_id, resource = ?warning.resource, requested = warning.requested, available = warning.available, "Container resource limit exceeds host capacity");
+    }
+    let block = get_config()
+        .map(|config| config.global.block_on_excess_resource_limits)
+        .unwrap_or(false);
+    if block {
+        let messages: Vec<&str> = resource_limit_warnings
+            .iter()
+            .map(|w| w.message.as_str())
+            .collect();
+        return Err(messages.join("\n"));
+    }
+    Ok(resource_limit_warnings)
+}
+
+/// Start an environment - creates and starts Docker container or git worktree.
+/// The start can be aborted with `cancel_environment_start`.
+#[tauri::command]
+pub async fn start_environment(
+    app_handle: tauri::AppHandle,
+    environment_id: String,
+) -> Result<StartEnvironmentResult, String> {
     let storage = get_storage().map_err(storage_error_to_string)?;
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(storage_error_to_string)?;
+    let previous_container_id = environment
+        .as_ref()
+        .and_then(|env| env.container_id.clone());
 
-    // Get environment and project info
+    // Starting an existing container or worktree writes little new data, so
+    // low disk space only blocks a start that creates one
+    let creates_storage = environment.as_ref().is_some_and(|env| {
+        if env.is_local() {
+            env.worktree_path.is_none()
+        } else {
+            env.container_id.is_none()
+        }
+    });
+    let disk_space_warnings = check_disk_space(environment_id.clone()).await?;
+    if !disk_space_warnings.is_empty() {
+        let block = creates_storage
+            && get_config()
+                .map(|config| config.global.block_on_low_disk_space)
+                .unwrap_or(false);
+        for warning in &disk_space_warnings {
+            warn!(environment_id = %environment_id, path = %warning.path, available_bytes = warning.available_bytes, "Low disk space before environment start");
+        }
+        if block {
+            let messages: Vec<&str> = disk_space_warnings
+                .iter()
+                .map(|w| w.message.as_str())
+                .collect();
+            return Err(messages.join("\n"));
+        }
+    }
+
+    let progress = |stage| emit_start_progress(&app_handle, &environment_id, stage);
+    let (task_id, mut cancel_rx) = START_TASKS.begin(&environment_id);
+    let result = tokio::select! {
+        result = run_start_environment(&progress, environment_id.clone()) => Some(result),
+        _ = cancel_rx.wait_for(|cancelled| *cancelled) => None,
+    };
+    START_TASKS.end(&environment_id, task_id);
+
+    match result {
+        Some(result) => result.map(|result| StartEnvironmentResult {
+            disk_space_warnings,
+            ..result
+        }),
+        None => {
+            let message = "Environment start was cancelled".to_string();
+            warn!(environment_id = %environment_id, "Environment start cancelled");
+            clean_up_cancelled_start(&environment_id, previous_container_id.as_deref()).await;
+
+            let payload = EnvironmentStartCancelledPayload {
+                environment_id: environment_id.clone(),
+                message: message.clone(),
+            };
+            if let Err(e) = app_handle.emit("environment-start-cancelled", payload) {
+                warn!(environment_id = %environment_id, error = %e, "Failed to emit start cancelled event");
+            }
+            Err(message)
+        }
+    }
+}
+
+/// Abort an in-flight `start_environment`. The start task removes any
+/// partially-created container and resets the environment to `Stopped`.
+/// Returns whether a start was in progress.
+#[tauri::command]
+pub async fn cancel_environment_start(environment_id: String) -> Result<bool, String> {
+    let cancelled = START_TASKS.cancel(&environment_id);
+    if cancelled {
+        info!(environment_id = %environment_id, "Cancelling environment start");
+    }
+    Ok(cancelled)
+}
+
+/// Show the container configuration `start_environment` would create a new
+/// container with, including repository and global settings, without
+/// creating anything. Secrets are redacted from the result.
+#[tauri::command]
+pub async fn preview_container_config(
+    environment_id: String,
+) -> Result<ContainerConfigPreview, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
     let environment = storage
         .get_environment(&environment_id)
         .map_err(storage_error_to_string)?
         .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
-
-    debug!(environment_id = %environment_id, environment_name = %environment.name, "Found environment");
-
+    if environment.is_local() {
+        return Err("Local environments do not run in a container".to_string());
+    }
     let project = storage
         .get_project(&environment.project_id)
         .map_err(storage_error_to_string)?
         .ok_or_else(|| format!("Project not found: {}", environment.project_id))?;
+    let config = get_config().map_err(|e| e.to_string())?;
 
-    debug!(environment_id = %environment_id, project_name = %project.name, "Found project");
+    let mut container_config = build_container_config(&config, &environment, &project);
+    // Only variable names are shown, so just check which credentials exist;
+    // `apply_claude_auth` would refresh expiring tokens and rewrite the keychain
+    let global = &config.global;
+    if global.auth_preference != AuthPreference::Oauth {
+        container_config.anthropic_api_key = global.anthropic_api_key.clone();
+    }
+    if global.auth_preference != AuthPreference::ApiKey && credentials::has_claude_credentials() {
+        container_config.oauth_credentials_json = Some(String::new());
+    }
 
-    // Branch based on environment type
-    if environment.is_local() {
-        return start_local_environment(&environment_id, &environment, &project, &storage).await;
+    Ok(describe_environment_container(&container_config, None))
+}
+
+/// Undo whatever a cancelled start left behind. A container created by the
+/// aborted start is removed (it may not have been recorded in storage yet, so
+/// containers are looked up by environment label); a pre-existing container is
+/// only stopped.
+async fn clean_up_cancelled_start(environment_id: &str, previous_container_id: Option<&str>) {
+    let Ok(storage) = get_storage() else {
+        return;
+    };
+
+    if let Some(container_id) = previous_container_id {
+        if let Err(e) = stop_environment_container(container_id).await {
+            debug!(environment_id = %environment_id, error = %e, "Failed to stop container after cancelled start");
+        }
+        let _ = storage.update_environment(environment_id, json!({ "status": "stopped" }));
+        return;
     }
 
-    // Get configuration
-    let config = get_config().map_err(|e| e.to_string())?;
+    let is_local =
+        matches!(storage.get_environment(environment_id), Ok(Some(env)) if env.is_local());
+    if !is_local {
+        match find_environment_containers(environment_id).await {
+            Ok(container_ids) => {
+                for container_id in container_ids {
+                    match remove_environment_container(&container_id).await {
+                        Ok(()) => {
+                            info!(environment_id = %environment_id, container_id = %container_id, "Removed partially-created container");
+                        }
+                        Err(e) => {
+                            warn!(environment_id = %environment_id, container_id = %container_id, error = %e, "Failed to remove partially-created container");
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                warn!(environment_id = %environment_id, error = %e, "Failed to look up containers after cancelled start");
+            }
+        }
+    }
 
-    let base_branch_override = resolve_base_branch_override(&config, &environment.project_id);
+    let _ = storage.update_environment(
+        environment_id,
+        json!({ "status": "stopped", "containerId": null }),
+    );
+}
 
-    if let Some(branch) = &base_branch_override {
-        debug!(
-            environment_id = %environment_id,
-            project_id = %environment.project_id,
-            branch = %branch,
-            "Using repository default branch for container base"
+/// Fail before creating a container when the auth preference can't be satisfied
+fn ensure_claude_auth_available(global: &GlobalConfig) -> Result<(), String> {
+    let has_api_key = global
+        .anthropic_api_key
+        .as_deref()
+        .is_some_and(|key| !key.trim().is_empty());
+    if global.auth_preference == AuthPreference::ApiKey && !has_api_key {
+        return Err(
+            "Auth preference is set to API key, but no Anthropic API key is configured. Add one in Settings or change the auth preference."
+                .to_string(),
         );
     }
+    Ok(())
+}
 
-    // If container already exists, just start it
-    if let Some(container_id) = &environment.container_id {
-        debug!(environment_id = %environment_id, container_id = %container_id, "Container already exists, starting it");
-        storage
+/// Inject Claude credentials into a container config according to the global
+/// auth preference. Keychain OAuth credentials are refreshed if expired or near
+/// expiry; the entrypoint script writes them to `.credentials.json`.
+async fn apply_claude_auth(
+    container_config: &mut ContainerConfig,
+    global: &GlobalConfig,
+    environment_id: &str,
+) {
+    if global.auth_preference != AuthPreference::Oauth {
+        container_config.anthropic_api_key = global.anthropic_api_key.clone();
+    }
+    if global.auth_preference == AuthPreference::ApiKey {
+        debug!(environment_id = %environment_id, "Auth preference is API key; skipping keychain credentials");
+        return;
+    }
+
+    match credentials::get_or_refresh_claude_credentials().await {
+        Ok(creds) => match serde_json::to_string(&creds) {
+            Ok(creds_json) => {
+                debug!(environment_id = %environment_id, "Retrieved OAuth credentials from system keychain");
+                container_config.oauth_credentials_json = Some(creds_json);
+            }
+            Err(e) => {
+                warn!(environment_id = %environment_id, error = ?e, "Failed to serialize credentials");
+            }
+        },
+        Err(e) => {
+            warn!(environment_id = %environment_id, error = ?e, "Failed to read/refresh keychain credentials; Claude auth in container may fail");
+        }
+    }
+}
+
+/// Build the container configuration for a containerized environment from
+/// its project, repository settings and global settings. Claude auth is
+/// applied separately by `apply_claude_auth`, since it reads the keychain.
+fn build_container_config(
+    config: &crate::models::AppConfig,
+    environment: &Environment,
+    project: &crate::models::Project,
+) -> ContainerConfig {
+    let repo_config = config.repositories.get(&environment.project_id);
+
+    let mut container_config = ContainerConfig::new(environment, &project.git_url)
+        .with_project_local_path(project.local_path.clone())
+        .with_branch(&environment.branch)
+        .with_workspace_path(config.workspace_path(&environment.project_id))
+        .with_network(repo_config.and_then(RepositoryConfig::docker_network));
+
+    if let Some(base_branch) = resolve_environment_base_branch(Some(config), environment) {
+        container_config = container_config.with_base_branch(&base_branch);
+    }
+    if let Some(remote) = &environment.base_remote {
+        container_config = container_config.with_base_remote(remote);
+    }
+
+    // Apply repository config settings
+    if let Some(files) = repo_config.and_then(|rc| rc.files_to_copy.as_ref()) {
+        container_config = container_config.with_files_to_copy(files.clone());
+    }
+    // Set entry port for dynamic host port allocation
+    container_config.entry_port = repo_config.and_then(|rc| rc.entry_port);
+    container_config.restart_policy = resolve_restart_policy(config, environment);
+    container_config.clone_depth = resolve_clone_depth(config, environment);
+
+    // Apply settings from global config
+    container_config.cpu_limit = Some(config.global.container_resources.cpu_cores as f64);
+    container_config.memory_limit =
+        Some(config.global.container_resources.memory_gb as i64 * 1024 * 1024 * 1024);
+    container_config.github_token =
+        resolve_container_github_token(config.global.github_token.as_deref(), &environment.id);
+    container_config.opencode_model = resolve_opencode_model(config, environment);
+
+    // Set allowed domains from global config (for restricted network mode)
+    container_config.allowed_domains = config.global.allowed_domains.clone();
+
+    container_config
+}
+
+/// Start an environment without registering it for cancellation.
+/// `progress` is called as each start stage begins.
+async fn run_start_environment(
+    progress: &(dyn Fn(StartProgressStage) + Sync),
+    environment_id: String,
+) -> Result<StartEnvironmentResult, String> {
+    info!(environment_id = %environment_id, "Starting environment");
+
+    let storage = get_storage().map_err(storage_error_to_string)?;
+
+    // Get environment and project info
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+
+    debug!(environment_id = %environment_id, environment_name = %environment.name, "Found environment");
+
+    let project = storage
+        .get_project(&environment.project_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Project not found: {}", environment.project_id))?;
+
+    debug!(environment_id = %environment_id, project_name = %project.name, "Found project");
+
+    // Branch based on environment type
+    if environment.is_local() {
+        return start_local_environment(&environment_id, &environment, &project, &storage).await;
+    }
+
+    // Get configuration
+    let config = get_config().map_err(|e| e.to_string())?;
+
+    let base_branch_override = resolve_environment_base_branch(Some(&config), &environment);
+
+    if let Some(branch) = &base_branch_override {
+        debug!(
+            environment_id = %environment_id,
+            project_id = %environment.project_id,
+            branch = %branch,
+            "Using base branch for container"
+        );
+    }
+
+    // If container already exists, just start it
+    if let Some(container_id) = &environment.container_id {
+        debug!(environment_id = %environment_id, container_id = %container_id, "Container already exists, starting it");
+        storage
             .update_environment(&environment_id, json!({ "status": "creating" }))
             .map_err(storage_error_to_string)?;
 
+        progress(StartProgressStage::StartingContainer);
         let start_result: Result<(), DockerError> = start_environment_container(container_id).await;
         start_result.map_err(|e: DockerError| {
             let err_msg = e.to_string();
@@ -1456,6 +2693,7 @@ pub async fn start_environment(environment_id: String) -> Result<StartEnvironmen
             let _ = storage.update_environment(&environment_id, json!({ "status": "error" }));
             err_msg
         })?;
+        progress(StartProgressStage::RunningSetup);
 
         // Re-resolve dynamic entry port (may change on restart)
         let has_entry_port = config
@@ -1465,13 +2703,29 @@ pub async fn start_environment(environment_id: String) -> Result<StartEnvironmen
         resolve_and_store_entry_port(storage, &environment_id, container_id, has_entry_port).await;
 
         storage
-            .update_environment(&environment_id, json!({ "status": "running" }))
+            .update_environment(
+                &environment_id,
+                json!({ "status": "running", "firewallBypassed": false }),
+            )
             .map_err(storage_error_to_string)?;
 
         info!(environment_id = %environment_id, "Container started successfully");
-        return Ok(StartEnvironmentResult::default());
+        let setup_commands = fetch_container_setup_commands_for_start(
+            &project,
+            &environment_id,
+            environment.setup_scripts_complete,
+        )
+        .await;
+        return Ok(StartEnvironmentResult {
+            setup_commands,
+            ..Default::default()
+        });
     }
 
+    ensure_claude_auth_available(&config.global)?;
+    // Limits only apply when a new container is created
+    let resource_limit_warnings = ensure_container_resource_limits(&environment_id).await?;
+
     // Update status to creating
     debug!(environment_id = %environment_id, "Creating new container");
     storage
@@ -1479,56 +2733,9 @@ pub async fn start_environment(environment_id: String) -> Result<StartEnvironmen
         .map_err(storage_error_to_string)?;
 
     // Build container configuration from settings
-    let mut container_config = ContainerConfig::new(&environment, &project.git_url)
-        .with_project_local_path(project.local_path.clone())
-        .with_branch(&environment.branch);
-
-    if let Some(base_branch) = base_branch_override.as_deref() {
-        container_config = container_config.with_base_branch(base_branch);
-    }
-
-    // Apply repository config settings
-    let entry_port = if let Some(repo_config) = config.repositories.get(&environment.project_id) {
-        if let Some(files) = &repo_config.files_to_copy {
-            container_config = container_config.with_files_to_copy(files.clone());
-        }
-        repo_config.entry_port
-    } else {
-        None
-    };
-
-    // Set entry port for dynamic host port allocation
-    container_config.entry_port = entry_port;
-
-    // Apply settings from global config
-    container_config.cpu_limit = Some(config.global.container_resources.cpu_cores as f64);
-    container_config.memory_limit =
-        Some(config.global.container_resources.memory_gb as i64 * 1024 * 1024 * 1024);
-    container_config.anthropic_api_key = config.global.anthropic_api_key.clone();
-    container_config.github_token =
-        resolve_container_github_token(config.global.github_token.as_deref(), &environment_id);
-    container_config.opencode_model = config.global.opencode_model.clone();
-
-    // Set allowed domains from global config (for restricted network mode)
-    container_config.allowed_domains = config.global.allowed_domains.clone();
-
-    // Try to get OAuth credentials from system keychain (preferred), refreshing
-    // if the token is expired or near expiry. This creates the .credentials.json
-    // file in the Linux container via the entrypoint script.
-    match credentials::get_or_refresh_claude_credentials().await {
-        Ok(creds) => match serde_json::to_string(&creds) {
-            Ok(creds_json) => {
-                debug!(environment_id = %environment_id, "Retrieved OAuth credentials from system keychain");
-                container_config.oauth_credentials_json = Some(creds_json);
-            }
-            Err(e) => {
-                warn!(environment_id = %environment_id, error = ?e, "Failed to serialize credentials");
-            }
-        },
-        Err(e) => {
-            warn!(environment_id = %environment_id, error = ?e, "Failed to read/refresh keychain credentials; Claude auth in container may fail");
-        }
-    }
+    let mut container_config = build_container_config(&config, &environment, &project);
+    let entry_port = container_config.entry_port;
+    apply_claude_auth(&mut container_config, &config.global, &environment_id).await;
 
     debug!(
         environment_id = %environment_id,
@@ -1537,7 +2744,8 @@ pub async fn start_environment(environment_id: String) -> Result<StartEnvironmen
         "Container config prepared"
     );
 
-    // Create the container
+    // Create the container (checks the base image is available first)
+    progress(StartProgressStage::CreatingContainer);
     let create_result: Result<String, DockerError> =
         create_environment_container(&container_config, None).await;
     let container_id = create_result.map_err(|e: DockerError| {
@@ -1552,11 +2760,18 @@ pub async fn start_environment(environment_id: String) -> Result<StartEnvironmen
 
     // Update environment with container ID
     storage
-        .update_environment(&environment_id, json!({ "containerId": container_id }))
+        .update_environment(
+            &environment_id,
+            json!({
+                "containerId": container_id,
+                "shallowHistory": container_config.clone_depth.is_some(),
+            }),
+        )
         .map_err(storage_error_to_string)?;
 
     // Start the container
     debug!(environment_id = %environment_id, "Starting container");
+    progress(StartProgressStage::StartingContainer);
     let start_result: Result<(), DockerError> = start_environment_container(&container_id).await;
     start_result.map_err(|e: DockerError| {
         let err_msg = e.to_string();
@@ -1564,17 +2779,31 @@ pub async fn start_environment(environment_id: String) -> Result<StartEnvironmen
         let _ = storage.update_environment(&environment_id, json!({ "status": "error" }));
         err_msg
     })?;
+    progress(StartProgressStage::RunningSetup);
 
     // Resolve and store entry port mapping
     resolve_and_store_entry_port(storage, &environment_id, &container_id, entry_port).await;
 
     // Update status to running
     storage
-        .update_environment(&environment_id, json!({ "status": "running" }))
+        .update_environment(
+            &environment_id,
+            json!({ "status": "running", "firewallBypassed": false }),
+        )
         .map_err(storage_error_to_string)?;
 
     info!(environment_id = %environment_id, "Environment started successfully");
-    Ok(StartEnvironmentResult::default())
+    let setup_commands = fetch_container_setup_commands_for_start(
+        &project,
+        &environment_id,
+        environment.setup_scripts_complete,
+    )
+    .await;
+    Ok(StartEnvironmentResult {
+        setup_commands,
+        resource_limit_warnings,
+        ..Default::default()
+    })
 }
 
 /// Start a local (worktree-based) environment
@@ -1621,7 +2850,10 @@ async fn start_local_environment(
                 .update_environment(environment_id, json!({ "status": "running" }))
                 .map_err(storage_error_to_string)?;
             info!(environment_id = %environment_id, "Local environment started (existing worktree)");
-            return Ok(StartEnvironmentResult { setup_commands });
+            return Ok(StartEnvironmentResult {
+                setup_commands,
+                ..Default::default()
+            });
         }
     }
 
@@ -1633,9 +2865,7 @@ async fn start_local_environment(
 
     // Resolve repository-specific default branch for new environment branching.
     let config = storage.load_config().ok();
-    let base_branch_override = config
-        .as_ref()
-        .and_then(|config| resolve_base_branch_override(config, &project.id));
+    let base_branch_override = resolve_environment_base_branch(config.as_ref(), environment);
     let files_to_copy = config
         .as_ref()
         .and_then(|config| config.repositories.get(&project.id))
@@ -1646,7 +2876,7 @@ async fn start_local_environment(
             environment_id = %environment_id,
             project_id = %project.id,
             branch = %branch,
-            "Using repository default branch for worktree base"
+            "Using base branch for worktree"
         );
     }
 
@@ -1666,11 +2896,12 @@ async fn start_local_environment(
     })?;
     let worktree_path = worktree_result.path;
 
-    if worktree_result.branch != environment.branch {
-        debug!(
+    if let Some(conflict) = &worktree_result.conflict {
+        info!(
             environment_id = %environment_id,
-            old_branch = %environment.branch,
-            new_branch = %worktree_result.branch,
+            reason = ?conflict.reason,
+            old_branch = %conflict.requested_branch,
+            new_branch = %conflict.adjusted_branch,
             "Local environment branch was adjusted due to worktree conflict"
         );
     }
@@ -1703,18 +2934,27 @@ async fn start_local_environment(
             json!({
                 "worktreePath": worktree_path,
                 "branch": worktree_result.branch,
+                "worktreeConflict": worktree_result.conflict,
                 "status": "running"
             }),
         )
         .map_err(storage_error_to_string)?;
 
     info!(environment_id = %environment_id, "Local environment started successfully");
-    Ok(StartEnvironmentResult { setup_commands })
+    Ok(StartEnvironmentResult {
+        setup_commands,
+        worktree_conflict: worktree_result.conflict,
+        ..Default::default()
+    })
 }
 
 /// Sync environment status with actual Docker container state
 #[tauri::command]
 pub async fn sync_environment_status(environment_id: String) -> Result<Environment, String> {
+    run_sync_environment_status(environment_id).await
+}
+
+async fn run_sync_environment_status(environment_id: String) -> Result<Environment, String> {
     let storage = get_storage().map_err(storage_error_to_string)?;
 
     let mut environment = storage
@@ -1772,9 +3012,23 @@ pub async fn sync_environment_status(environment_id: String) -> Result<Environme
     Ok(environment)
 }
 
-/// Stop an environment - stops Docker container or local servers
+/// Stop an environment - stops Docker container or local servers.
+/// The environment is `stopping` while this runs, then `stopped` (or `error`
+/// if the container fails to stop in time); each transition is emitted as
+/// `environment-status-changed`.
 #[tauri::command]
-pub async fn stop_environment(environment_id: String) -> Result<(), String> {
+pub async fn stop_environment(
+    app_handle: tauri::AppHandle,
+    environment_id: String,
+) -> Result<(), String> {
+    let on_status = |status| emit_status_changed(&app_handle, &environment_id, status);
+    run_stop_environment(&on_status, environment_id.clone()).await
+}
+
+async fn run_stop_environment(
+    on_status: &impl Fn(EnvironmentStatus),
+    environment_id: String,
+) -> Result<(), String> {
     info!(environment_id = %environment_id, "Stopping environment");
 
     let storage = get_storage().map_err(storage_error_to_string)?;
@@ -1792,6 +3046,11 @@ pub async fn stop_environment(environment_id: String) -> Result<(), String> {
         "Found environment"
     );
 
+    storage
+        .update_environment(&environment_id, json!({ "status": "stopping" }))
+        .map_err(storage_error_to_string)?;
+    on_status(EnvironmentStatus::Stopping);
+
     // Close terminal/tmux sessions before either-backend container stop.
     // Local terminal cleanup is a no-op for container envs; tmux cleanup
     // uses the backend stored on each tracked session.
@@ -1817,6 +3076,7 @@ pub async fn stop_environment(environment_id: String) -> Result<(), String> {
                 }),
             )
             .map_err(storage_error_to_string)?;
+        on_status(EnvironmentStatus::Stopped);
 
         info!(environment_id = %environment_id, "Local environment stopped");
         return Ok(());
@@ -1825,77 +3085,563 @@ pub async fn stop_environment(environment_id: String) -> Result<(), String> {
     // Stop the container if it exists (containerized environments)
     if let Some(container_id) = &environment.container_id {
         debug!(environment_id = %environment_id, container_id = %container_id, "Stopping container");
-        let stop_result: Result<(), DockerError> = stop_environment_container(container_id).await;
-        stop_result.map_err(|e: DockerError| {
+        let stop_result = match tokio::time::timeout(
+            STOP_TIMEOUT,
+            stop_environment_container(container_id),
+        )
+        .await
+        {
+            Ok(result) => result.map_err(|e: DockerError| e.to_string()),
+            Err(_) => Err(format!(
+                "Container did not stop within {} seconds",
+                STOP_TIMEOUT.as_secs()
+            )),
+        };
+        if let Err(e) = stop_result {
             warn!(environment_id = %environment_id, error = %e, "Error stopping container");
-            e.to_string()
-        })?;
+            if let Err(update_err) =
+                storage.update_environment(&environment_id, json!({ "status": "error" }))
+            {
+                warn!(environment_id = %environment_id, error = %update_err, "Failed to mark environment as errored");
+            }
+            on_status(EnvironmentStatus::Error);
+            return Err(e);
+        }
         debug!(environment_id = %environment_id, "Container stopped successfully");
     } else {
         debug!(environment_id = %environment_id, "No container to stop");
     }
 
     storage
-        .update_environment(&environment_id, json!({ "status": "stopped" }))
+        .update_environment(
+            &environment_id,
+            json!({ "status": "stopped", "firewallBypassed": false }),
+        )
         .map_err(storage_error_to_string)?;
+    on_status(EnvironmentStatus::Stopped);
 
     info!(environment_id = %environment_id, "Environment stopped");
     Ok(())
 }
 
-/// Recreate an environment - preserves filesystem state via docker commit, then creates new container with updated port mappings
-/// This is needed when port mappings change, as Docker port bindings are set at container creation time
-/// Note: All running processes will be terminated, but installed packages and file changes are preserved
-/// Note: This operation does not apply to local environments - they don't have containers to restart
+/// Event payload emitted when an environment's container was force-killed
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EnvironmentForceKilledPayload {
+    pub environment_id: String,
+    pub container_id: String,
+}
+
+/// Kill a wedged environment container with SIGKILL, skipping the graceful
+/// stop (and its timeout) of `stop_environment`. Meant as an explicit user
+/// choice when a stop hangs; emits `environment-force-killed`.
 #[tauri::command]
-pub async fn recreate_environment(environment_id: String) -> Result<(), String> {
-    info!(environment_id = %environment_id, "Recreating environment with docker commit (preserving filesystem state)");
+pub async fn force_kill_environment(
+    app_handle: tauri::AppHandle,
+    environment_id: String,
+) -> Result<(), String> {
+    let container_id = run_force_kill_environment(&environment_id).await?;
+    emit_status_changed(&app_handle, &environment_id, EnvironmentStatus::Stopped);
+    let payload = EnvironmentForceKilledPayload {
+        environment_id: environment_id.clone(),
+        container_id,
+    };
+    if let Err(e) = app_handle.emit("environment-force-killed", payload) {
+        warn!(environment_id = %environment_id, error = %e, "Failed to emit force killed event");
+    }
+    Ok(())
+}
 
+/// Returns the ID of the killed container
+async fn run_force_kill_environment(environment_id: &str) -> Result<String, String> {
     let storage = get_storage().map_err(storage_error_to_string)?;
-    let docker = get_docker_client().map_err(|e| e.to_string())?;
+    let environment = storage
+        .get_environment(environment_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+    let container_id = environment
+        .container_id
+        .ok_or("Environment has no container to kill")?;
+
+    warn!(environment_id = %environment_id, container_id = %container_id, "Force killing container");
+    if let Err(e) = kill_environment_container(&container_id).await {
+        // Killing a container that already exited fails; that's the goal anyway
+        let stopped = matches!(
+            get_container_environment_status(&container_id).await,
+            Ok(EnvironmentStatus::Stopped)
+        );
+        if !stopped {
+            return Err(format!("Failed to kill container: {}", e));
+        }
+    }
 
-    // Get environment and project info
+    // Clean up sessions as a normal stop does, but only once the container is
+    // gone so a wedged container can't hold up the kill
+    close_local_terminal_sessions_for_environment(environment_id);
+    stop_tmux_sessions_for_environment(environment_id).await;
+
+    storage
+        .update_environment(
+            environment_id,
+            json!({ "status": "stopped", "firewallBypassed": false }),
+        )
+        .map_err(storage_error_to_string)?;
+    info!(environment_id = %environment_id, "Container force killed");
+    Ok(container_id)
+}
+
+/// Resolve the worktree destination for `relocate_worktree`. The new base
+/// must be an existing directory outside the source repository.
+fn relocated_worktree_path(
+    worktree_path: &Path,
+    new_base: &Path,
+    source_repo_path: &Path,
+) -> Result<PathBuf, String> {
+    let new_base = new_base.canonicalize().map_err(|e| {
+        format!(
+            "New worktree base {} is not accessible: {}",
+            new_base.display(),
+            e
+        )
+    })?;
+    if !new_base.is_dir() {
+        return Err(format!(
+            "New worktree base is not a directory: {}",
+            new_base.display()
+        ));
+    }
+
+    let source_repo = source_repo_path
+        .canonicalize()
+        .unwrap_or_else(|_| source_repo_path.to_path_buf());
+    if new_base.starts_with(&source_repo) {
+        return Err("New worktree base cannot be inside the source repository".to_string());
+    }
+
+    let name = worktree_path
+        .file_name()
+        .ok_or_else(|| format!("Invalid worktree path: {}", worktree_path.display()))?;
+    let new_path = new_base.join(name);
+    if new_path.exists() {
+        return Err(format!("{} already exists", new_path.display()));
+    }
+    Ok(new_path)
+}
+
+/// Start a local environment again after a worktree operation stopped it.
+/// The start is registered like `start_environment` so sync doesn't treat its
+/// `creating` status as interrupted, and it can be aborted with
+/// `cancel_environment_start`.
+async fn restart_local_environment(
+    environment_id: &str,
+    environment: &Environment,
+    project: &crate::models::Project,
+    storage: &crate::storage::Storage,
+) -> Result<StartEnvironmentResult, String> {
+    let (task_id, mut cancel_rx) = START_TASKS.begin(environment_id);
+    let result = tokio::select! {
+        result = start_local_environment(environment_id, environment, project, storage) => Some(result),
+        _ = cancel_rx.wait_for(|cancelled| *cancelled) => None,
+    };
+    START_TASKS.end(environment_id, task_id);
+
+    match result {
+        Some(result) => result,
+        None => {
+            warn!(environment_id = %environment_id, "Environment restart cancelled");
+            clean_up_cancelled_start(environment_id, None).await;
+            Err("Environment start was cancelled".to_string())
+        }
+    }
+}
+
+/// Move a local environment's worktree into a new base directory.
+/// Local servers are stopped for the move and the environment is started
+/// again afterwards if it was running.
+#[tauri::command]
+pub async fn relocate_worktree(
+    environment_id: String,
+    new_base: String,
+) -> Result<Environment, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
     let environment = storage
         .get_environment(&environment_id)
         .map_err(storage_error_to_string)?
         .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
 
-    // Local environments don't support recreate/restart - they always "exist" as worktrees
-    if environment.is_local() {
-        debug!(environment_id = %environment_id, "Ignoring recreate request for local environment");
-        return Ok(());
+    if !environment.is_local() {
+        return Err("Only local environments have a worktree to relocate".to_string());
     }
+    let worktree_path = environment
+        .worktree_path
+        .clone()
+        .ok_or("Local environment has no worktree yet")?;
 
     let project = storage
         .get_project(&environment.project_id)
         .map_err(storage_error_to_string)?
         .ok_or_else(|| format!("Project not found: {}", environment.project_id))?;
+    let source_repo_path = project
+        .local_path
+        .clone()
+        .ok_or("Project has no local path - cannot move worktree")?;
 
-    let config = get_config().map_err(|e| e.to_string())?;
+    let new_path = relocated_worktree_path(
+        Path::new(&worktree_path),
+        Path::new(&new_base),
+        Path::new(&source_repo_path),
+    )?;
+    let new_path = new_path.to_string_lossy().to_string();
 
-    let base_branch_override = resolve_base_branch_override(&config, &environment.project_id);
+    info!(
+        environment_id = %environment_id,
+        from = %worktree_path,
+        to = %new_path,
+        "Relocating worktree"
+    );
 
-    if let Some(branch) = &base_branch_override {
-        debug!(
-            environment_id = %environment_id,
-            project_id = %environment.project_id,
-            branch = %branch,
-            "Using repository default branch for recreated container base"
-        );
+    let was_running = environment.status == EnvironmentStatus::Running;
+    if was_running {
+        run_stop_environment(&|_| {}, environment_id.clone()).await?;
     }
 
-    // If no container exists, just start a new one
-    let container_id = match &environment.container_id {
-        Some(id) => id.clone(),
-        None => {
-            info!(environment_id = %environment_id, "No existing container, creating fresh");
-            return start_environment(environment_id).await.map(|_| ());
+    if let Err(e) = move_worktree(&source_repo_path, &worktree_path, &new_path).await {
+        // The worktree is still in place, so bring the environment back up there
+        if was_running {
+            let restarted = match storage.get_environment(&environment_id) {
+                Ok(Some(environment)) => {
+                    restart_local_environment(&environment_id, &environment, &project, &storage)
+                        .await
+                }
+                Ok(None) => Err(format!("Environment not found: {}", environment_id)),
+                Err(e) => Err(storage_error_to_string(e)),
+            };
+            if let Err(restart_error) = restarted {
+                warn!(environment_id = %environment_id, error = %restart_error, "Failed to restart environment after worktree move failed");
+            }
         }
-    };
+        return Err(e.to_string());
+    }
 
-    // Update status to creating
-    storage
-        .update_environment(&environment_id, json!({ "status": "creating" }))
+    let environment = storage
+        .update_environment(&environment_id, json!({ "worktreePath": new_path }))
+        .map_err(storage_error_to_string)?;
+
+    if was_running {
+        restart_local_environment(&environment_id, &environment, &project, &storage).await?;
+    }
+
+    info!(environment_id = %environment_id, "Worktree relocated");
+
+    storage
+        .get_environment(&environment_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))
+}
+
+/// How to settle a worktree branch conflict recorded on a local environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WorktreeConflictStrategy {
+    /// Keep the adjusted branch and dismiss the conflict
+    KeepAdjusted,
+    /// Point the environment at the worktree that already has the requested
+    /// branch, removing the one created for it (refused if that one has
+    /// uncommitted or untracked changes)
+    ReuseExisting,
+    /// Rename the adjusted branch to the requested name, for a requested
+    /// branch that only exists on the remote
+    ForceNewBranch,
+    /// Remove the stale worktree holding the requested branch and check that
+    /// branch out in the environment's own worktree (refused if the stale
+    /// worktree has uncommitted or untracked changes)
+    DeleteStaleWorktree,
+}
+
+/// Check that a strategy can settle a conflict of the given kind. Renaming to
+/// a branch another worktree has checked out fails, and there is no worktree
+/// to reuse or delete when the branch only exists on the remote.
+fn check_conflict_strategy(
+    strategy: WorktreeConflictStrategy,
+    conflict: &WorktreeConflict,
+) -> Result<(), String> {
+    match (strategy, &conflict.reason) {
+        (WorktreeConflictStrategy::KeepAdjusted, _)
+        | (WorktreeConflictStrategy::ForceNewBranch, WorktreeConflictReason::BranchOnRemote)
+        | (
+            WorktreeConflictStrategy::ReuseExisting | WorktreeConflictStrategy::DeleteStaleWorktree,
+            WorktreeConflictReason::BranchCheckedOut,
+        ) => Ok(()),
+        (WorktreeConflictStrategy::ForceNewBranch, WorktreeConflictReason::BranchCheckedOut) => {
+            Err(format!(
+                "Branch '{}' is checked out in another worktree; reuse or delete that worktree instead",
+                conflict.requested_branch
+            ))
+        }
+        (_, WorktreeConflictReason::BranchOnRemote) => Err(format!(
+            "No worktree has branch '{}' checked out; keep the adjusted branch or rename it instead",
+            conflict.requested_branch
+        )),
+    }
+}
+
+/// Worktree holding the requested branch of a conflict, provided no other
+/// environment and not the source repository itself uses it
+fn stale_worktree_for_conflict(
+    conflict: &WorktreeConflict,
+    environment_id: &str,
+    environments: &[Environment],
+    source_repo_path: &str,
+) -> Result<String, String> {
+    let existing = match (&conflict.reason, &conflict.existing_worktree) {
+        (WorktreeConflictReason::BranchCheckedOut, Some(existing)) => existing,
+        _ => {
+            return Err(format!(
+                "No worktree has branch '{}' checked out",
+                conflict.requested_branch
+            ))
+        }
+    };
+
+    let same_path = |a: &str, b: &str| {
+        let a = Path::new(a)
+            .canonicalize()
+            .unwrap_or_else(|_| PathBuf::from(a));
+        let b = Path::new(b)
+            .canonicalize()
+            .unwrap_or_else(|_| PathBuf::from(b));
+        a == b
+    };
+    if same_path(existing, source_repo_path) {
+        return Err(format!(
+            "Branch '{}' is checked out in the source repository",
+            conflict.requested_branch
+        ));
+    }
+    if let Some(owner) = environments.iter().find(|env| {
+        env.id != environment_id
+            && env
+                .worktree_path
+                .as_deref()
+                .is_some_and(|path| same_path(path, existing))
+    }) {
+        return Err(format!(
+            "Worktree {} belongs to environment '{}'",
+            existing, owner.name
+        ));
+    }
+    Ok(existing.clone())
+}
+
+/// Settle the worktree branch conflict recorded when a local environment was
+/// created on a different branch than it asked for
+#[tauri::command]
+pub async fn resolve_worktree_conflict(
+    environment_id: String,
+    strategy: WorktreeConflictStrategy,
+) -> Result<Environment, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+    let conflict = environment
+        .worktree_conflict
+        .clone()
+        .ok_or("Environment has no worktree conflict to resolve")?;
+    check_conflict_strategy(strategy, &conflict)?;
+    let worktree_path = environment
+        .worktree_path
+        .clone()
+        .ok_or("Local environment has no worktree")?;
+    let project = storage
+        .get_project(&environment.project_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Project not found: {}", environment.project_id))?;
+    let source_repo_path = project
+        .local_path
+        .clone()
+        .ok_or("Project has no local path")?;
+
+    info!(
+        environment_id = %environment_id,
+        strategy = ?strategy,
+        requested_branch = %conflict.requested_branch,
+        adjusted_branch = %conflict.adjusted_branch,
+        "Resolving worktree conflict"
+    );
+
+    let mut updates = json!({ "worktreeConflict": null });
+    match strategy {
+        WorktreeConflictStrategy::KeepAdjusted => {}
+        WorktreeConflictStrategy::ForceNewBranch => {
+            rename_branch(
+                &worktree_path,
+                &conflict.adjusted_branch,
+                &conflict.requested_branch,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            updates["branch"] = json!(conflict.requested_branch);
+        }
+        WorktreeConflictStrategy::ReuseExisting => {
+            let environments = storage
+                .load_environments()
+                .map_err(storage_error_to_string)?;
+            let existing = stale_worktree_for_conflict(
+                &conflict,
+                &environment_id,
+                &environments,
+                &source_repo_path,
+            )?;
+
+            let was_running = environment.status == EnvironmentStatus::Running;
+            if was_running {
+                run_stop_environment(&|_| {}, environment_id.clone()).await?;
+            }
+            if let Err(e) = remove_clean_worktree(&source_repo_path, &worktree_path).await {
+                // The worktree is still in place, so bring the environment back up there
+                if was_running {
+                    let restarted = match storage.get_environment(&environment_id) {
+                        Ok(Some(environment)) => {
+                            restart_local_environment(
+                                &environment_id,
+                                &environment,
+                                &project,
+                                &storage,
+                            )
+                            .await
+                        }
+                        Ok(None) => Err(format!("Environment not found: {}", environment_id)),
+                        Err(e) => Err(storage_error_to_string(e)),
+                    };
+                    if let Err(restart_error) = restarted {
+                        warn!(environment_id = %environment_id, error = %restart_error, "Failed to restart environment after worktree removal failed");
+                    }
+                }
+                return Err(e.to_string());
+            }
+            if let Err(e) = delete_branch(&source_repo_path, &conflict.adjusted_branch).await {
+                warn!(environment_id = %environment_id, error = %e, "Kept adjusted branch");
+            }
+
+            let environment = storage
+                .update_environment(
+                    &environment_id,
+                    json!({
+                        "worktreePath": existing,
+                        "branch": conflict.requested_branch,
+                        "worktreeConflict": null
+                    }),
+                )
+                .map_err(storage_error_to_string)?;
+            if was_running {
+                restart_local_environment(&environment_id, &environment, &project, &storage)
+                    .await?;
+            }
+        }
+        WorktreeConflictStrategy::DeleteStaleWorktree => {
+            let environments = storage
+                .load_environments()
+                .map_err(storage_error_to_string)?;
+            let stale = stale_worktree_for_conflict(
+                &conflict,
+                &environment_id,
+                &environments,
+                &source_repo_path,
+            )?;
+
+            remove_clean_worktree(&source_repo_path, &stale)
+                .await
+                .map_err(|e| e.to_string())?;
+            switch_branch(&worktree_path, &conflict.requested_branch)
+                .await
+                .map_err(|e| e.to_string())?;
+            if let Err(e) = delete_branch(&source_repo_path, &conflict.adjusted_branch).await {
+                warn!(environment_id = %environment_id, error = %e, "Kept adjusted branch");
+            }
+            updates["branch"] = json!(conflict.requested_branch);
+        }
+    }
+
+    let environment = storage
+        .update_environment(&environment_id, updates)
+        .map_err(storage_error_to_string)?;
+    info!(environment_id = %environment_id, branch = %environment.branch, "Worktree conflict resolved");
+    Ok(environment)
+}
+
+/// Recreate an environment - preserves filesystem state via docker commit, then creates new container with updated port mappings
+/// This is needed when port mappings change, as Docker port bindings are set at container creation time
+/// Note: All running processes will be terminated, but installed packages and file changes are preserved
+/// Note: This operation does not apply to local environments - they don't have containers to restart
+#[tauri::command]
+pub async fn recreate_environment(
+    app_handle: tauri::AppHandle,
+    environment_id: String,
+) -> Result<(), String> {
+    let progress = |stage| emit_start_progress(&app_handle, &environment_id, stage);
+    run_recreate_environment(&progress, environment_id.clone()).await
+}
+
+async fn run_recreate_environment(
+    progress: &(dyn Fn(StartProgressStage) + Sync),
+    environment_id: String,
+) -> Result<(), String> {
+    info!(environment_id = %environment_id, "Recreating environment with docker commit (preserving filesystem state)");
+
+    let storage = get_storage().map_err(storage_error_to_string)?;
+
+    // Get environment and project info
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+
+    // Local environments don't support recreate/restart - they always "exist" as worktrees
+    if environment.is_local() {
+        debug!(environment_id = %environment_id, "Ignoring recreate request for local environment");
+        return Ok(());
+    }
+
+    let project = storage
+        .get_project(&environment.project_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Project not found: {}", environment.project_id))?;
+
+    let config = get_config().map_err(|e| e.to_string())?;
+    ensure_claude_auth_available(&config.global)?;
+
+    let base_branch_override = resolve_environment_base_branch(Some(&config), &environment);
+
+    if let Some(branch) = &base_branch_override {
+        debug!(
+            environment_id = %environment_id,
+            project_id = %environment.project_id,
+            branch = %branch,
+            "Using base branch for recreated container"
+        );
+    }
+
+    let engine = get_container_engine().map_err(|e| e.to_string())?;
+
+    // If no container exists, just start a new one
+    let container_id = match &environment.container_id {
+        Some(id) => id.clone(),
+        None => {
+            info!(environment_id = %environment_id, "No existing container, creating fresh");
+            return run_start_environment(progress, environment_id)
+                .await
+                .map(|_| ());
+        }
+    };
+
+    // Checked before the old container is stopped, so a blocked recreate
+    // leaves it untouched
+    ensure_container_resource_limits(&environment_id).await?;
+
+    // Update status to creating
+    storage
+        .update_environment(&environment_id, json!({ "status": "creating" }))
         .map_err(storage_error_to_string)?;
 
     // Step 1: Stop the container if running (processes will be terminated)
@@ -1911,7 +3657,7 @@ pub async fn recreate_environment(environment_id: String) -> Result<(), String>
     let temp_image_tag = "recreate";
     debug!(environment_id = %environment_id, image = %temp_image_name, "Committing container to temporary image");
 
-    let commit_result = docker
+    let commit_result = engine
         .commit_container(&container_id, &temp_image_name, temp_image_tag)
         .await;
     if let Err(e) = &commit_result {
@@ -1926,7 +3672,9 @@ pub async fn recreate_environment(environment_id: String) -> Result<(), String>
                 json!({ "containerId": null, "status": "stopped" }),
             )
             .map_err(storage_error_to_string)?;
-        return start_environment(environment_id).await.map(|_| ());
+        return run_start_environment(progress, environment_id)
+            .await
+            .map(|_| ());
     }
 
     let temp_image_full = format!("{}:{}", temp_image_name, temp_image_tag);
@@ -1939,51 +3687,13 @@ pub async fn recreate_environment(environment_id: String) -> Result<(), String>
     }
 
     // Step 4: Build container configuration (same as start_environment)
-    let mut container_config = ContainerConfig::new(&environment, &project.git_url)
-        .with_project_local_path(project.local_path.clone())
-        .with_branch(&environment.branch);
-
-    if let Some(base_branch) = base_branch_override.as_deref() {
-        container_config = container_config.with_base_branch(base_branch);
-    }
-
-    // Apply repository config settings
-    let entry_port = if let Some(repo_config) = config.repositories.get(&environment.project_id) {
-        if let Some(files) = &repo_config.files_to_copy {
-            container_config = container_config.with_files_to_copy(files.clone());
-        }
-        repo_config.entry_port
-    } else {
-        None
-    };
-
-    // Set entry port for dynamic host port allocation
-    container_config.entry_port = entry_port;
-
-    container_config.cpu_limit = Some(config.global.container_resources.cpu_cores as f64);
-    container_config.memory_limit =
-        Some(config.global.container_resources.memory_gb as i64 * 1024 * 1024 * 1024);
-    container_config.anthropic_api_key = config.global.anthropic_api_key.clone();
-    container_config.github_token =
-        resolve_container_github_token(config.global.github_token.as_deref(), &environment_id);
-    container_config.opencode_model = config.global.opencode_model.clone();
-    container_config.allowed_domains = config.global.allowed_domains.clone();
-
-    // Get OAuth credentials (refresh if near expiry so the rehydrated container
-    // doesn't start with a stale access token).
-    match credentials::get_or_refresh_claude_credentials().await {
-        Ok(creds) => {
-            if let Ok(creds_json) = serde_json::to_string(&creds) {
-                container_config.oauth_credentials_json = Some(creds_json);
-            }
-        }
-        Err(e) => {
-            warn!(environment_id = %environment_id, error = ?e, "Failed to read/refresh keychain credentials; Claude auth in recreated container may fail");
-        }
-    }
+    let mut container_config = build_container_config(&config, &environment, &project);
+    let entry_port = container_config.entry_port;
+    apply_claude_auth(&mut container_config, &config.global, &environment_id).await;
 
     // Step 5: Create new container from the committed image (with new port mappings)
     debug!(environment_id = %environment_id, "Creating new container from committed image");
+    progress(StartProgressStage::CreatingContainer);
     let create_result =
         create_environment_container(&container_config, Some(&temp_image_full)).await;
 
@@ -1993,7 +3703,7 @@ pub async fn recreate_environment(environment_id: String) -> Result<(), String>
             let err_msg = e.to_string();
             warn!(environment_id = %environment_id, error = %err_msg, "Failed to create container from committed image");
             // Clean up temp image
-            let _ = docker.remove_image(&temp_image_full, true).await;
+            let _ = engine.remove_image(&temp_image_full, true).await;
             let _ = storage.update_environment(
                 &environment_id,
                 json!({ "containerId": null, "status": "error" }),
@@ -2009,27 +3719,37 @@ pub async fn recreate_environment(environment_id: String) -> Result<(), String>
         .update_environment(&environment_id, json!({ "containerId": new_container_id }))
         .map_err(storage_error_to_string)?;
 
+    // Open terminals reattach to the new container once it's running
+    if let Some(manager) = crate::pty::get_terminal_manager() {
+        manager.retarget_container(&container_id, &new_container_id);
+    }
+
     // Step 6: Start the new container
     debug!(environment_id = %environment_id, "Starting new container");
+    progress(StartProgressStage::StartingContainer);
     if let Err(e) = start_environment_container(&new_container_id).await {
         let err_msg = e.to_string();
         warn!(environment_id = %environment_id, error = %err_msg, "Failed to start new container");
-        let _ = docker.remove_image(&temp_image_full, true).await;
+        let _ = engine.remove_image(&temp_image_full, true).await;
         let _ = storage.update_environment(&environment_id, json!({ "status": "error" }));
         return Err(err_msg);
     }
+    progress(StartProgressStage::RunningSetup);
 
     // Resolve and store entry port mapping
     resolve_and_store_entry_port(storage, &environment_id, &new_container_id, entry_port).await;
 
     // Update status to running
     storage
-        .update_environment(&environment_id, json!({ "status": "running" }))
+        .update_environment(
+            &environment_id,
+            json!({ "status": "running", "firewallBypassed": false }),
+        )
         .map_err(storage_error_to_string)?;
 
     // Step 7: Clean up the temporary image
     debug!(environment_id = %environment_id, image = %temp_image_full, "Cleaning up temporary image");
-    if let Err(e) = docker.remove_image(&temp_image_full, true).await {
+    if let Err(e) = engine.remove_image(&temp_image_full, true).await {
         // Non-fatal - just log it
         warn!(environment_id = %environment_id, error = %e, "Failed to remove temporary image (non-fatal)");
     }
@@ -2062,6 +3782,9 @@ pub async fn add_environment_domains(
         return Err("Cannot add domains to an environment with full network access".to_string());
     }
 
+    // Domains, raw IPv4 addresses, and CIDR ranges are all accepted
+    validate_firewall_entries(&domains)?;
+
     // Get container ID
     let container_id = environment
         .container_id
@@ -2070,7 +3793,7 @@ pub async fn add_environment_domains(
 
     // Execute the update-firewall.sh script in the container
     let domains_csv = domains.join(",");
-    let docker = get_docker_client().map_err(|e| e.to_string())?;
+    let docker = get_container_engine().map_err(|e| e.to_string())?;
 
     let output = docker
         .exec_command(
@@ -2128,6 +3851,8 @@ pub async fn remove_environment_domains(
         );
     }
 
+    validate_firewall_entries(&domains)?;
+
     // Get container ID
     let container_id = environment
         .container_id
@@ -2136,7 +3861,7 @@ pub async fn remove_environment_domains(
 
     // Execute the update-firewall.sh script in the container
     let domains_csv = domains.join(",");
-    let docker = get_docker_client().map_err(|e| e.to_string())?;
+    let docker = get_container_engine().map_err(|e| e.to_string())?;
 
     let output = docker
         .exec_command(
@@ -2171,6 +3896,8 @@ pub async fn update_environment_allowed_domains(
     environment_id: String,
     domains: Vec<String>,
 ) -> Result<Environment, String> {
+    validate_firewall_entries(&domains)?;
+
     let storage = get_storage().map_err(storage_error_to_string)?;
 
     let environment = storage
@@ -2188,7 +3915,7 @@ pub async fn update_environment_allowed_domains(
         && environment.network_access_mode == NetworkAccessMode::Restricted
     {
         if let Some(container_id) = &environment.container_id {
-            let docker = get_docker_client().map_err(|e| e.to_string())?;
+            let docker = get_container_engine().map_err(|e| e.to_string())?;
 
             // First, we'd need to figure out what changed. For simplicity,
             // just add all the new domains (ipset ignores duplicates)
@@ -2211,6 +3938,444 @@ pub async fn update_environment_allowed_domains(
     Ok(updated)
 }
 
+/// Outcome of applying new allowed domains to one environment's firewall
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentFirewallUpdate {
+    pub environment_id: String,
+    pub name: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// Result of `add_domains_to_all_environments`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkDomainUpdateResult {
+    /// Global allowed domains after the update
+    pub allowed_domains: Vec<String>,
+    /// One entry per running restricted environment
+    pub environments: Vec<EnvironmentFirewallUpdate>,
+}
+
+/// Append entries to an allowed-domains list, trimming them and skipping
+/// blanks and duplicates. Returns the entries that were actually new.
+fn merge_allowed_domains(existing: &mut Vec<String>, domains: &[String]) -> Vec<String> {
+    let mut added = Vec::new();
+    for domain in domains.iter().map(|d| d.trim()).filter(|d| !d.is_empty()) {
+        if !existing.iter().any(|e| e == domain) {
+            existing.push(domain.to_string());
+            added.push(domain.to_string());
+        }
+    }
+    added
+}
+
+/// Add domains to the global allowed domains and apply them to the firewall
+/// of every running restricted environment, reporting each one's outcome
+#[tauri::command]
+pub async fn add_domains_to_all_environments(
+    domains: Vec<String>,
+) -> Result<BulkDomainUpdateResult, String> {
+    validate_firewall_entries(&domains)?;
+
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    let (config, added) = storage
+        .update_config(|config| {
+            Ok(merge_allowed_domains(
+                &mut config.global.allowed_domains,
+                &domains,
+            ))
+        })
+        .map_err(storage_error_to_string)?;
+    if !added.is_empty() {
+        info!(domains = ?added, "Added domains to global allowed domains");
+    }
+
+    let environments: Vec<Environment> = storage
+        .get_all_environments()
+        .map_err(storage_error_to_string)?
+        .into_iter()
+        .filter(crate::docker::firewall::is_running_restricted)
+        .collect();
+
+    let mut results = Vec::with_capacity(environments.len());
+    if !environments.is_empty() {
+        // Apply every requested entry, not just the newly added ones, since an
+        // environment may predate a domain that was already in the global list
+        let entries: Vec<&str> = domains
+            .iter()
+            .map(|d| d.trim())
+            .filter(|d| !d.is_empty())
+            .collect();
+        let domains_csv = entries.join(",");
+        let engine = get_container_engine().map_err(|e| e.to_string())?;
+
+        for environment in environments {
+            let Some(container_id) = &environment.container_id else {
+                continue;
+            };
+            let error = match engine
+                .exec_command_with_status(
+                    container_id,
+                    vec![
+                        "sudo",
+                        crate::docker::firewall::UPDATE_FIREWALL_SCRIPT,
+                        "--add",
+                        &domains_csv,
+                    ],
+                )
+                .await
+            {
+                Ok((_, _, 0)) => None,
+                Ok((_, stderr, code)) => Some(format!(
+                    "Firewall update exited with {}: {}",
+                    code,
+                    stderr.trim()
+                )),
+                Err(e) => Some(e.to_string()),
+            };
+            if let Some(error) = &error {
+                warn!(environment_id = %environment.id, error = %error, "Failed to add domains to environment firewall");
+            }
+            results.push(EnvironmentFirewallUpdate {
+                environment_id: environment.id,
+                name: environment.name,
+                success: error.is_none(),
+                error,
+            });
+        }
+    }
+
+    Ok(BulkDomainUpdateResult {
+        allowed_domains: config.global.allowed_domains,
+        environments: results,
+    })
+}
+
+/// Where `import_allowed_domains` merges the imported entries
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DomainImportScope {
+    /// The global allowed domains
+    Global,
+    /// One environment's custom allowed domains
+    #[serde(rename_all = "camelCase")]
+    Environment { environment_id: String },
+}
+
+/// Result of `import_allowed_domains`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DomainImportResult {
+    /// Entries that weren't in the list yet
+    pub added: usize,
+    /// Entries that aren't valid domains, IPs, or CIDR ranges
+    pub rejected: Vec<String>,
+}
+
+/// Import allowed domains from a newline- or JSON-formatted file into the
+/// global config or one environment. Invalid entries are skipped and reported.
+#[tauri::command]
+pub async fn import_allowed_domains(
+    path: String,
+    scope: DomainImportScope,
+) -> Result<DomainImportResult, String> {
+    let contents =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let (valid, rejected): (Vec<String>, Vec<String>) = parse_domain_list(&contents)?
+        .into_iter()
+        .partition(|entry| is_valid_firewall_entry(entry));
+
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    let added = match scope {
+        DomainImportScope::Global => {
+            let (_, added) = storage
+                .update_config(|config| {
+                    let added = merge_allowed_domains(&mut config.global.allowed_domains, &valid);
+                    ensure_valid_config(config).map_err(StorageError::InvalidConfig)?;
+                    Ok(added)
+                })
+                .map_err(storage_error_to_string)?;
+            added.len()
+        }
+        DomainImportScope::Environment { environment_id } => {
+            let environment = storage
+                .get_environment(&environment_id)
+                .map_err(storage_error_to_string)?
+                .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+            let mut domains = environment.allowed_domains.unwrap_or_default();
+            let added = merge_allowed_domains(&mut domains, &valid);
+            if !added.is_empty() {
+                // Also pushes the new entries to a running restricted container
+                update_environment_allowed_domains(environment_id, domains).await?;
+            }
+            added.len()
+        }
+    };
+
+    info!(path = %path, added, rejected = rejected.len(), "Imported allowed domains");
+    Ok(DomainImportResult { added, rejected })
+}
+
+/// Which side `reconcile_allowed_domains` treats as the source of truth
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReconcileDirection {
+    /// Apply the stored entries to the container's firewall
+    ToContainer,
+    /// Update the environment's stored entries from the container's firewall
+    ToStorage,
+}
+
+/// Compare an environment's effective allowed domains against the entries
+/// applied to its container's firewall and against its live ipset. Returns
+/// the environment and container ID with the diff.
+async fn load_allowed_domains_diff(
+    environment_id: &str,
+) -> Result<(Environment, String, AllowedDomainsDiff), String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    let environment = storage
+        .get_environment(environment_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+    if !crate::docker::firewall::is_running_restricted(&environment) {
+        return Err(
+            "Environment must be running in restricted mode to inspect its firewall".to_string(),
+        );
+    }
+    let container_id = environment
+        .container_id
+        .clone()
+        .ok_or("Environment has no container")?;
+
+    let engine = get_container_engine().map_err(|e| e.to_string())?;
+    let (listing, stderr, code) = engine
+        .exec_command_with_status(
+            &container_id,
+            vec![
+                "sudo",
+                crate::docker::firewall::UPDATE_FIREWALL_SCRIPT,
+                "--list-domains",
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to list firewall entries: {}", e))?;
+    // Containers from older images don't record their applied entries
+    let configured = if code == 0 {
+        Some(crate::docker::firewall::parse_configured_entries(&listing))
+    } else {
+        debug!(
+            environment_id = %environment_id,
+            error = %stderr.trim(),
+            "Container doesn't track its firewall entries, checking the ipset only"
+        );
+        None
+    };
+
+    let (ipset_listing, stderr, code) = engine
+        .exec_command_with_status(
+            &container_id,
+            vec![
+                "sudo",
+                crate::docker::firewall::UPDATE_FIREWALL_SCRIPT,
+                "--list",
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to list firewall ipset: {}", e))?;
+    if code != 0 {
+        return Err(format!("Failed to list firewall ipset: {}", stderr.trim()));
+    }
+    let members = crate::docker::firewall::parse_ipset_members(&ipset_listing);
+
+    let global_domains = get_config()
+        .map(|config| config.global.allowed_domains)
+        .unwrap_or_default();
+    let stored = crate::docker::firewall::effective_allowed_domains(&environment, &global_domains);
+
+    let mut diff =
+        crate::docker::firewall::diff_allowed_domain_entries(&stored, configured.as_deref());
+
+    // Resolve like the container does to check the applied entries are still
+    // in the ipset; entries that don't resolve are left out rather than guessed
+    let applied: Vec<String> = stored
+        .into_iter()
+        .filter(|entry| !diff.only_in_storage.contains(entry))
+        .collect();
+    let resolved = tokio::task::spawn_blocking(move || {
+        applied
+            .into_iter()
+            .map(|entry| {
+                let ips = if is_ip_or_cidr(&entry) {
+                    vec![entry.clone()]
+                } else {
+                    resolve_domain(&entry)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|ip| ip.parse::<std::net::Ipv4Addr>().is_ok())
+                        .collect()
+                };
+                (entry, ips)
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| format!("Failed to resolve allowed domains: {}", e))?;
+    diff.missing_from_ipset =
+        crate::docker::firewall::entries_missing_from_ipset(&resolved, &members);
+
+    Ok((environment, container_id, diff))
+}
+
+/// Report entries that differ between an environment's stored allowed domains
+/// and the entries configured in its running restricted container
+#[tauri::command]
+pub async fn diff_allowed_domains(environment_id: String) -> Result<AllowedDomainsDiff, String> {
+    let (_, _, diff) = load_allowed_domains_diff(&environment_id).await?;
+    Ok(diff)
+}
+
+/// Resync an environment's allowed domains with its container's firewall, in
+/// either direction. Returns the remaining diff; global domains missing from
+/// the container, and entries missing from its ipset, are only fixed by
+/// `ToContainer`, since `ToStorage` only edits the environment's own entries.
+#[tauri::command]
+pub async fn reconcile_allowed_domains(
+    environment_id: String,
+    direction: ReconcileDirection,
+) -> Result<AllowedDomainsDiff, String> {
+    let (environment, container_id, diff) = load_allowed_domains_diff(&environment_id).await?;
+
+    match direction {
+        ReconcileDirection::ToContainer => {
+            let engine = get_container_engine().map_err(|e| e.to_string())?;
+            let to_add: Vec<String> = diff
+                .only_in_storage
+                .iter()
+                .chain(&diff.missing_from_ipset)
+                .cloned()
+                .collect();
+            for (flag, entries) in [("--add", &to_add), ("--remove", &diff.only_in_container)] {
+                if entries.is_empty() {
+                    continue;
+                }
+                let entries_csv = entries.join(",");
+                let (_, stderr, code) = engine
+                    .exec_command_with_status(
+                        &container_id,
+                        vec![
+                            "sudo",
+                            crate::docker::firewall::UPDATE_FIREWALL_SCRIPT,
+                            flag,
+                            &entries_csv,
+                        ],
+                    )
+                    .await
+                    .map_err(|e| format!("Failed to execute firewall update: {}", e))?;
+                if code != 0 {
+                    return Err(format!(
+                        "Firewall update exited with {}: {}",
+                        code,
+                        stderr.trim()
+                    ));
+                }
+            }
+        }
+        ReconcileDirection::ToStorage => {
+            let mut domains = environment.allowed_domains.unwrap_or_default();
+            domains.retain(|d| !diff.only_in_storage.contains(d));
+            merge_allowed_domains(&mut domains, &diff.only_in_container);
+            get_storage()
+                .map_err(storage_error_to_string)?
+                .update_environment(&environment_id, json!({ "allowedDomains": domains }))
+                .map_err(storage_error_to_string)?;
+        }
+    }
+
+    info!(
+        environment_id = %environment_id,
+        direction = ?direction,
+        only_in_storage = diff.only_in_storage.len(),
+        only_in_container = diff.only_in_container.len(),
+        missing_from_ipset = diff.missing_from_ipset.len(),
+        "Reconciled allowed domains"
+    );
+    let (_, _, remaining) = load_allowed_domains_diff(&environment_id).await?;
+    Ok(remaining)
+}
+
+/// Temporarily bypass (or restore) the firewall of a running restricted environment
+/// The stored network access mode stays Restricted, so the whitelist is re-applied
+/// automatically when the container restarts
+#[tauri::command]
+pub async fn set_firewall_bypass(
+    app_handle: tauri::AppHandle,
+    environment_id: String,
+    enabled: bool,
+) -> Result<Environment, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+
+    if environment.status != EnvironmentStatus::Running {
+        return Err("Environment must be running to change firewall rules".to_string());
+    }
+
+    if environment.network_access_mode != NetworkAccessMode::Restricted {
+        return Err("Firewall bypass only applies to restricted environments".to_string());
+    }
+
+    let container_id = environment
+        .container_id
+        .as_ref()
+        .ok_or("Environment has no container")?;
+
+    let flag = if enabled { "--bypass" } else { "--restore" };
+    let docker = get_container_engine().map_err(|e| e.to_string())?;
+    let (_, stderr, code) = docker
+        .exec_command_with_status(
+            container_id,
+            vec![
+                "sudo",
+                crate::docker::firewall::UPDATE_FIREWALL_SCRIPT,
+                flag,
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to execute firewall update: {}", e))?;
+    if code != 0 {
+        return Err(format!(
+            "Firewall update exited with {}: {}",
+            code,
+            stderr.trim()
+        ));
+    }
+
+    let updated = storage
+        .update_environment(&environment_id, json!({ "firewallBypassed": enabled }))
+        .map_err(storage_error_to_string)?;
+
+    if enabled {
+        warn!(environment_id = %environment_id, "Firewall bypassed - all traffic is allowed");
+    } else {
+        info!(environment_id = %environment_id, "Firewall restored");
+    }
+
+    let payload = FirewallBypassPayload {
+        environment_id: environment_id.clone(),
+        bypassed: enabled,
+    };
+    if let Err(e) = app_handle.emit("environment-firewall-bypass", payload) {
+        warn!(environment_id = %environment_id, error = %e, "Failed to emit event");
+    }
+
+    Ok(updated)
+}
+
 /// Update port mappings for an environment
 /// If the environment has a container, this will require a restart to take effect
 #[tauri::command]
@@ -2256,7 +4421,7 @@ pub async fn reattach_container(
         .ok_or_else(|| format!("Project not found: {}", project_id))?;
 
     // Get container info to verify it exists and get its name/status
-    let docker = get_docker_client().map_err(|e| e.to_string())?;
+    let docker = get_container_engine().map_err(|e| e.to_string())?;
     let container_info = docker
         .inspect_container(&container_id)
         .await
@@ -2268,90 +4433,374 @@ pub async fn reattach_container(
         .as_ref()
         .and_then(|c| c.labels.as_ref());
 
-    let is_orkestrator = labels
-        .map(|l| l.get("app").map(|v| v == "orkestrator-ai").unwrap_or(false))
-        .unwrap_or(false);
+    let is_orkestrator = labels
+        .map(|l| l.get("app").map(|v| v == "orkestrator-ai").unwrap_or(false))
+        .unwrap_or(false);
+
+    if !is_orkestrator {
+        return Err("Container is not an Orkestrator-managed container".to_string());
+    }
+
+    // Get the container name (strip leading '/' if present)
+    let container_name = container_info
+        .name
+        .as_ref()
+        .map(|n| n.trim_start_matches('/').to_string())
+        .unwrap_or_else(|| format!("reattached-{}", &container_id[..12.min(container_id.len())]));
+
+    // Determine environment name: use provided name, or fall back to container name
+    let env_name = sanitize_environment_name(&name.unwrap_or_else(|| container_name.clone()));
+
+    // Load existing environments to check for duplicate names and existing attachments
+    let existing_environments = storage
+        .load_environments()
+        .map_err(storage_error_to_string)?;
+
+    // Check if this container is already attached to an environment
+    let already_attached = existing_environments
+        .iter()
+        .find(|e| e.container_id.as_ref() == Some(&container_id));
+
+    if let Some(existing_env) = already_attached {
+        return Err(format!(
+            "Container is already attached to environment '{}' (ID: {})",
+            existing_env.name, existing_env.id
+        ));
+    }
+
+    // Make one slug unique for both name and branch.
+    let unique_name = make_unique_environment_slug(&env_name, &existing_environments, &[]);
+    if unique_name != env_name {
+        debug!(
+            requested_name = %env_name,
+            assigned_name = %unique_name,
+            "Name already in use, using unique variant"
+        );
+    }
+
+    // Determine the container's current status
+    let status = match get_container_environment_status(&container_id).await {
+        Ok(s) => s,
+        Err(_) => EnvironmentStatus::Stopped,
+    };
+
+    // Create the environment with the container already attached
+    // Note: The branch field will be auto-generated from the environment name.
+    // This branch may not exist in the container's git repository - the container
+    // retains whatever git state it had when orphaned. The branch field serves as
+    // a placeholder identifier for the reattached environment.
+    let mut environment = Environment::with_name(project_id.clone(), unique_name.clone());
+    environment.container_id = Some(container_id.clone());
+    environment.status = status;
+
+    // Save to storage
+    let created_environment = storage
+        .add_environment(environment)
+        .map_err(storage_error_to_string)?;
+
+    info!(
+        environment_id = %created_environment.id,
+        container_id = %container_id,
+        "Container reattached successfully"
+    );
+
+    Ok(created_environment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docker::mock::MockEngine;
+    use crate::docker::{set_container_engine_for_test, BASE_IMAGE};
+    use crate::models::{AppConfig, RepositoryConfig, SessionType};
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    fn create_test_storage() -> Storage {
+        let temp_dir = tempdir().unwrap();
+        Storage::new_for_tests(temp_dir.keep())
+    }
+
+    #[test]
+    fn test_resolve_clone_depth_honors_full_clone_override() {
+        let mut config = AppConfig::default();
+        let mut environment = Environment::new("project-1".to_string());
+        assert_eq!(resolve_clone_depth(&config, &environment), None);
+
+        config.repositories.insert(
+            "project-1".to_string(),
+            RepositoryConfig {
+                clone_depth: Some(50),
+                ..RepositoryConfig::default()
+            },
+        );
+        assert_eq!(resolve_clone_depth(&config, &environment), Some(50));
+
+        environment.full_clone = true;
+        assert_eq!(resolve_clone_depth(&config, &environment), None);
+    }
+
+    #[test]
+    fn test_validate_default_sessions() {
+        assert!(validate_default_sessions(&[]).is_ok());
+        assert!(validate_default_sessions(&[SessionType::Claude, SessionType::Claude]).is_ok());
+
+        let max = crate::storage::Storage::MAX_SESSIONS_PER_ENVIRONMENT;
+        assert!(validate_default_sessions(&vec![SessionType::Plain; max]).is_ok());
+        assert!(validate_default_sessions(&vec![SessionType::Plain; max + 1]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_set_environment_default_sessions_saves_and_clears_override() {
+        let guard = crate::storage::set_storage_for_test(create_test_storage());
+        let environment = guard
+            .storage
+            .add_environment(Environment::new("project-1".to_string()))
+            .unwrap();
+
+        let updated = set_environment_default_sessions(
+            environment.id.clone(),
+            Some(vec![SessionType::Claude, SessionType::Plain]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            updated.default_sessions,
+            Some(vec![SessionType::Claude, SessionType::Plain])
+        );
+
+        let cleared = set_environment_default_sessions(environment.id.clone(), None)
+            .await
+            .unwrap();
+        assert_eq!(cleared.default_sessions, None);
+    }
+
+    #[tokio::test]
+    async fn test_import_allowed_domains_validates_global_config() {
+        let guard = crate::storage::set_storage_for_test(create_test_storage());
+        let file = tempdir().unwrap().keep().join("domains.txt");
+        std::fs::write(&file, "example.com\nnot a domain\n").unwrap();
+        let path = file.to_string_lossy().to_string();
+
+        let result = import_allowed_domains(path.clone(), DomainImportScope::Global)
+            .await
+            .unwrap();
+        assert_eq!(result.added, 1);
+        assert_eq!(result.rejected, vec!["not a domain".to_string()]);
+        let config = guard.storage.load_config().unwrap();
+        assert!(config
+            .global
+            .allowed_domains
+            .contains(&"example.com".to_string()));
+
+        // A config that wouldn't pass validation isn't saved
+        let mut invalid = config;
+        invalid.global.allowed_domains.clear();
+        invalid.global.container_resources.cpu_cores = 0;
+        guard.storage.save_config(&invalid).unwrap();
+        assert!(import_allowed_domains(path, DomainImportScope::Global)
+            .await
+            .is_err());
+        assert!(guard
+            .storage
+            .load_config()
+            .unwrap()
+            .global
+            .allowed_domains
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_set_environment_full_clone_saves_flag() {
+        let guard = crate::storage::set_storage_for_test(create_test_storage());
+        let environment = guard
+            .storage
+            .add_environment(Environment::new("project-1".to_string()))
+            .unwrap();
+
+        let updated = set_environment_full_clone(environment.id.clone(), true)
+            .await
+            .unwrap();
+        assert!(updated.full_clone);
+        let stored = guard
+            .storage
+            .get_environment(&environment.id)
+            .unwrap()
+            .unwrap();
+        assert!(stored.full_clone);
+    }
+
+    #[test]
+    fn test_resolve_default_sessions_prefers_environment_override() {
+        let mut config = AppConfig::default();
+        let mut environment = Environment::new("project-1".to_string());
+        assert!(resolve_default_sessions(&config, &environment).is_empty());
+
+        config.repositories.insert(
+            "project-1".to_string(),
+            RepositoryConfig {
+                default_sessions: Some(vec![SessionType::Claude, SessionType::Plain]),
+                ..RepositoryConfig::default()
+            },
+        );
+        assert_eq!(
+            resolve_default_sessions(&config, &environment),
+            vec![SessionType::Claude, SessionType::Plain]
+        );
+
+        environment.default_sessions = Some(vec![]);
+        assert!(resolve_default_sessions(&config, &environment).is_empty());
+
+        environment.default_sessions = Some(vec![SessionType::Codex]);
+        assert_eq!(
+            resolve_default_sessions(&config, &environment),
+            vec![SessionType::Codex]
+        );
+    }
+
+    #[test]
+    fn test_resolve_opencode_model_prefers_environment_override() {
+        let mut config = AppConfig::default();
+        config.global.opencode_model = "opencode/grok-code".to_string();
+        let mut environment = Environment::new("project-1".to_string());
+
+        assert_eq!(
+            resolve_opencode_model(&config, &environment),
+            "opencode/grok-code"
+        );
 
-    if !is_orkestrator {
-        return Err("Container is not an Orkestrator-managed container".to_string());
+        environment.opencode_model_override = Some("anthropic/claude-opus-4-1".to_string());
+        assert_eq!(
+            resolve_opencode_model(&config, &environment),
+            "anthropic/claude-opus-4-1"
+        );
+
+        environment.opencode_model_override = Some("  ".to_string());
+        assert_eq!(
+            resolve_opencode_model(&config, &environment),
+            "opencode/grok-code"
+        );
     }
 
-    // Get the container name (strip leading '/' if present)
-    let container_name = container_info
-        .name
-        .as_ref()
-        .map(|n| n.trim_start_matches('/').to_string())
-        .unwrap_or_else(|| format!("reattached-{}", &container_id[..12.min(container_id.len())]));
+    #[test]
+    fn test_stale_worktree_for_conflict() {
+        let conflict = WorktreeConflict {
+            reason: WorktreeConflictReason::BranchCheckedOut,
+            requested_branch: "feature".to_string(),
+            adjusted_branch: "feature-2".to_string(),
+            existing_worktree: Some("/ws/project-abc123".to_string()),
+        };
+        let mut other = Environment::new_local("project-1".to_string(), "other".to_string());
+        other.worktree_path = Some("/ws/project-abc123".to_string());
 
-    // Determine environment name: use provided name, or fall back to container name
-    let env_name = sanitize_environment_name(&name.unwrap_or_else(|| container_name.clone()));
+        assert_eq!(
+            stale_worktree_for_conflict(&conflict, "env-1", &[], "/repo").unwrap(),
+            "/ws/project-abc123"
+        );
+        assert!(stale_worktree_for_conflict(&conflict, "env-1", &[other], "/repo").is_err());
+        assert!(
+            stale_worktree_for_conflict(&conflict, "env-1", &[], "/ws/project-abc123").is_err()
+        );
 
-    // Load existing environments to check for duplicate names and existing attachments
-    let existing_environments = storage
-        .load_environments()
-        .map_err(storage_error_to_string)?;
+        let remote_only = WorktreeConflict {
+            reason: WorktreeConflictReason::BranchOnRemote,
+            existing_worktree: None,
+            ..conflict
+        };
+        assert!(stale_worktree_for_conflict(&remote_only, "env-1", &[], "/repo").is_err());
+    }
 
-    // Check if this container is already attached to an environment
-    let already_attached = existing_environments
-        .iter()
-        .find(|e| e.container_id.as_ref() == Some(&container_id));
+    #[test]
+    fn test_check_conflict_strategy() {
+        let checked_out = WorktreeConflict {
+            reason: WorktreeConflictReason::BranchCheckedOut,
+            requested_branch: "feature".to_string(),
+            adjusted_branch: "feature-2".to_string(),
+            existing_worktree: Some("/ws/project-abc123".to_string()),
+        };
+        let remote_only = WorktreeConflict {
+            reason: WorktreeConflictReason::BranchOnRemote,
+            existing_worktree: None,
+            ..checked_out.clone()
+        };
+
+        for strategy in [
+            WorktreeConflictStrategy::KeepAdjusted,
+            WorktreeConflictStrategy::ReuseExisting,
+            WorktreeConflictStrategy::DeleteStaleWorktree,
+        ] {
+            assert!(check_conflict_strategy(strategy, &checked_out).is_ok());
+        }
+        assert!(
+            check_conflict_strategy(WorktreeConflictStrategy::ForceNewBranch, &checked_out)
+                .unwrap_err()
+                .contains("checked out in another worktree")
+        );
 
-    if let Some(existing_env) = already_attached {
-        return Err(format!(
-            "Container is already attached to environment '{}' (ID: {})",
-            existing_env.name, existing_env.id
-        ));
+        for strategy in [
+            WorktreeConflictStrategy::KeepAdjusted,
+            WorktreeConflictStrategy::ForceNewBranch,
+        ] {
+            assert!(check_conflict_strategy(strategy, &remote_only).is_ok());
+        }
+        for strategy in [
+            WorktreeConflictStrategy::ReuseExisting,
+            WorktreeConflictStrategy::DeleteStaleWorktree,
+        ] {
+            assert!(check_conflict_strategy(strategy, &remote_only).is_err());
+        }
     }
 
-    // Make one slug unique for both name and branch.
-    let unique_name = make_unique_environment_slug(&env_name, &existing_environments, &[]);
-    if unique_name != env_name {
-        debug!(
-            requested_name = %env_name,
-            assigned_name = %unique_name,
-            "Name already in use, using unique variant"
+    #[test]
+    fn test_relocated_worktree_path() {
+        let repo = tempdir().unwrap();
+        let base = tempdir().unwrap();
+        let worktree = Path::new("/old/base/my-project-abc123");
+
+        let new_path = relocated_worktree_path(worktree, base.path(), repo.path()).unwrap();
+        assert_eq!(
+            new_path,
+            base.path()
+                .canonicalize()
+                .unwrap()
+                .join("my-project-abc123")
         );
-    }
 
-    // Determine the container's current status
-    let status = match get_container_environment_status(&container_id).await {
-        Ok(s) => s,
-        Err(_) => EnvironmentStatus::Stopped,
-    };
+        std::fs::create_dir(&new_path).unwrap();
+        assert!(relocated_worktree_path(worktree, base.path(), repo.path()).is_err());
 
-    // Create the environment with the container already attached
-    // Note: The branch field will be auto-generated from the environment name.
-    // This branch may not exist in the container's git repository - the container
-    // retains whatever git state it had when orphaned. The branch field serves as
-    // a placeholder identifier for the reattached environment.
-    let mut environment = Environment::with_name(project_id.clone(), unique_name.clone());
-    environment.container_id = Some(container_id.clone());
-    environment.status = status;
+        let inside_repo = repo.path().join("worktrees");
+        std::fs::create_dir(&inside_repo).unwrap();
+        assert!(relocated_worktree_path(worktree, &inside_repo, repo.path()).is_err());
 
-    // Save to storage
-    let created_environment = storage
-        .add_environment(environment)
-        .map_err(storage_error_to_string)?;
+        assert!(
+            relocated_worktree_path(worktree, &base.path().join("missing"), repo.path()).is_err()
+        );
+    }
 
-    info!(
-        environment_id = %created_environment.id,
-        container_id = %container_id,
-        "Container reattached successfully"
-    );
+    #[test]
+    fn test_cancellation_registry() {
+        let registry = CancellationRegistry::new();
+        let env_id = "registry-test-env";
 
-    Ok(created_environment)
-}
+        let (first_id, first_rx) = registry.begin(env_id);
+        assert!(!*first_rx.borrow());
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::models::{AppConfig, RepositoryConfig};
-    use std::collections::HashMap;
-    use tempfile::tempdir;
+        // Starting a new task cancels the previous one
+        let (second_id, second_rx) = registry.begin(env_id);
+        assert!(*first_rx.borrow());
+        assert!(!*second_rx.borrow());
 
-    fn create_test_storage() -> Storage {
-        let temp_dir = tempdir().unwrap();
-        Storage::new_for_tests(temp_dir.keep())
+        // A stale task finishing must not unregister the newer one
+        registry.end(env_id, first_id);
+        assert!(registry.is_active(env_id));
+
+        assert!(registry.cancel(env_id));
+        assert!(*second_rx.borrow());
+        assert!(!registry.cancel(env_id));
+
+        registry.end(env_id, second_id);
+        assert!(!registry.is_active(env_id));
     }
 
     #[test]
@@ -2377,6 +4826,34 @@ mod tests {
         assert_eq!(branch, Some("develop".to_string()));
     }
 
+    #[test]
+    fn test_resolve_environment_base_branch_prefers_environment_base_ref() {
+        let mut config = AppConfig::default();
+        config.repositories = HashMap::from([(
+            "project-123".to_string(),
+            RepositoryConfig {
+                default_branch: "develop".to_string(),
+                ..RepositoryConfig::default()
+            },
+        )]);
+        let mut environment = Environment::new("project-123".to_string());
+
+        assert_eq!(
+            resolve_environment_base_branch(Some(&config), &environment),
+            Some("develop".to_string())
+        );
+
+        environment.base_ref = Some("feature/pr-42".to_string());
+        assert_eq!(
+            resolve_environment_base_branch(Some(&config), &environment),
+            Some("feature/pr-42".to_string())
+        );
+        assert_eq!(
+            resolve_environment_base_branch(None, &environment),
+            Some("feature/pr-42".to_string())
+        );
+    }
+
     #[test]
     fn test_resolve_base_branch_override_returns_none_for_missing_or_empty() {
         let mut config = AppConfig::default();
@@ -2572,6 +5049,96 @@ mod tests {
         assert_eq!(normalize_initial_prompt(None), None);
     }
 
+    #[test]
+    fn test_summarize_environment_activity_uses_latest_timestamp() {
+        let now = Utc::now();
+        let mut env = Environment::new("project-1".to_string());
+        env.created_at = now - chrono::Duration::days(60);
+
+        let mut old = Session::new(
+            env.id.clone(),
+            "container-1".to_string(),
+            "tab-1".to_string(),
+            SessionType::Plain,
+        );
+        old.last_activity_at = now - chrono::Duration::days(40);
+        let mut recent = old.clone();
+        recent.last_activity_at = now - chrono::Duration::days(31);
+        let mut other_env = old.clone();
+        other_env.environment_id = "other".to_string();
+        other_env.last_activity_at = now;
+
+        let activity = summarize_environment_activity(
+            &env,
+            &[old, recent.clone(), other_env],
+            Some(now - chrono::Duration::days(45)),
+            now,
+        );
+        assert_eq!(
+            activity.last_session_activity_at,
+            Some(recent.last_activity_at)
+        );
+        assert_eq!(activity.last_active_at, recent.last_activity_at);
+        assert_eq!(activity.days_idle, 31);
+
+        let untouched = summarize_environment_activity(&env, &[], None, now);
+        assert_eq!(untouched.last_active_at, env.created_at);
+        assert_eq!(untouched.days_idle, 60);
+    }
+
+    #[test]
+    fn test_merge_allowed_domains_skips_blanks_and_duplicates() {
+        let mut existing = vec!["github.com".to_string()];
+        let added = merge_allowed_domains(
+            &mut existing,
+            &[
+                " api.example.com ".to_string(),
+                "github.com".to_string(),
+                "".to_string(),
+                "api.example.com".to_string(),
+            ],
+        );
+        assert_eq!(added, vec!["api.example.com".to_string()]);
+        assert_eq!(
+            existing,
+            vec!["github.com".to_string(), "api.example.com".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_normalize_environment_notes_clears_blank_and_caps_length() {
+        assert_eq!(
+            normalize_environment_notes(Some("- finish migration\n".to_string())),
+            Ok(Some("- finish migration\n".to_string()))
+        );
+        assert_eq!(
+            normalize_environment_notes(Some(" \n".to_string())),
+            Ok(None)
+        );
+        assert_eq!(normalize_environment_notes(None), Ok(None));
+
+        let max = "a".repeat(MAX_ENVIRONMENT_NOTES_BYTES);
+        assert!(normalize_environment_notes(Some(max)).is_ok());
+        let too_long = "a".repeat(MAX_ENVIRONMENT_NOTES_BYTES + 1);
+        assert!(normalize_environment_notes(Some(too_long)).is_err());
+    }
+
+    #[test]
+    fn test_truncate_initial_prompt_limits_length_at_char_boundary() {
+        assert_eq!(truncate_initial_prompt("Fix the bug"), "Fix the bug");
+
+        let exact = "a".repeat(MAX_STORED_INITIAL_PROMPT_CHARS);
+        assert_eq!(truncate_initial_prompt(&exact), exact);
+
+        let long = "é".repeat(MAX_STORED_INITIAL_PROMPT_CHARS + 10);
+        let truncated = truncate_initial_prompt(&long);
+        assert_eq!(
+            truncated.chars().count(),
+            MAX_STORED_INITIAL_PROMPT_CHARS + 3
+        );
+        assert!(truncated.ends_with("..."));
+    }
+
     #[test]
     fn test_sanitize_then_unique_branch_handles_collision() {
         // Two different display names that sanitize to the same branch
@@ -2742,4 +5309,336 @@ mod tests {
     async fn run_git_at(dir: &str, args: &[&str]) {
         run_git(dir, args).await;
     }
+
+    /// Install global storage holding one containerized environment. The
+    /// config uses an API key and GitHub token so starting needs neither the
+    /// keychain nor the host `gh` CLI.
+    fn setup_container_environment(
+        container_id: Option<&str>,
+        status: EnvironmentStatus,
+    ) -> (crate::storage::TestStorageGuard, Environment) {
+        let guard = crate::storage::set_storage_for_test(create_test_storage());
+
+        let mut config = AppConfig::default();
+        config.global.auth_preference = AuthPreference::ApiKey;
+        config.global.anthropic_api_key = Some("sk-test".to_string());
+        config.global.github_token = Some("ghp_test".to_string());
+        guard.storage.save_config(&config).unwrap();
+
+        let project = guard
+            .storage
+            .add_project(crate::models::Project::new(
+                "https://github.com/org/repo.git".to_string(),
+                None,
+            ))
+            .unwrap();
+        let mut environment = Environment::new(project.id);
+        environment.container_id = container_id.map(str::to_string);
+        environment.status = status;
+        let environment = guard.storage.add_environment(environment).unwrap();
+
+        (guard, environment)
+    }
+
+    fn stored_environment(
+        guard: &crate::storage::TestStorageGuard,
+        environment_id: &str,
+    ) -> Environment {
+        guard
+            .storage
+            .get_environment(environment_id)
+            .unwrap()
+            .expect("environment should exist")
+    }
+
+    #[tokio::test]
+    async fn test_start_environment_creates_and_runs_container() {
+        let (guard, environment) = setup_container_environment(None, EnvironmentStatus::Stopped);
+        let mock = set_container_engine_for_test(MockEngine::default());
+        let stages = Mutex::new(Vec::new());
+
+        run_start_environment(
+            &|stage| stages.lock().unwrap().push(stage),
+            environment.id.clone(),
+        )
+        .await
+        .expect("start should succeed");
+
+        assert_eq!(
+            mock.engine.calls(),
+            vec![
+                format!("create {}", BASE_IMAGE),
+                "start container-1".to_string()
+            ]
+        );
+        assert_eq!(
+            *stages.lock().unwrap(),
+            vec![
+                StartProgressStage::CreatingContainer,
+                StartProgressStage::StartingContainer,
+                StartProgressStage::RunningSetup,
+            ]
+        );
+        assert_eq!(
+            get_container_environment_status("container-1")
+                .await
+                .unwrap(),
+            EnvironmentStatus::Running
+        );
+
+        let stored = stored_environment(&guard, &environment.id);
+        assert_eq!(stored.status, EnvironmentStatus::Running);
+        assert_eq!(stored.container_id.as_deref(), Some("container-1"));
+    }
+
+    #[tokio::test]
+    async fn test_start_environment_restarts_existing_container() {
+        let (guard, environment) =
+            setup_container_environment(Some("existing"), EnvironmentStatus::Stopped);
+        let mock = set_container_engine_for_test(MockEngine::with_container(
+            "existing",
+            EnvironmentStatus::Stopped,
+        ));
+
+        run_start_environment(&|_| {}, environment.id.clone())
+            .await
+            .expect("start should succeed");
+
+        assert_eq!(mock.engine.calls(), vec!["start existing"]);
+        let stored = stored_environment(&guard, &environment.id);
+        assert_eq!(stored.status, EnvironmentStatus::Running);
+        assert_eq!(stored.container_id.as_deref(), Some("existing"));
+    }
+
+    #[tokio::test]
+    async fn test_sync_environment_status_clears_missing_container() {
+        let (guard, environment) =
+            setup_container_environment(Some("gone"), EnvironmentStatus::Running);
+        let mock = set_container_engine_for_test(MockEngine::default());
+
+        let synced = run_sync_environment_status(environment.id.clone())
+            .await
+            .expect("sync should succeed");
+
+        assert_eq!(synced.status, EnvironmentStatus::Stopped);
+        assert!(synced.container_id.is_none());
+        let stored = stored_environment(&guard, &environment.id);
+        assert_eq!(stored.status, EnvironmentStatus::Stopped);
+        assert!(stored.container_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sync_all_environments_reconciles_with_engine() {
+        let (guard, missing) =
+            setup_container_environment(Some("gone"), EnvironmentStatus::Running);
+        let mut running = Environment::new(missing.project_id.clone());
+        running.container_id = Some("alive".to_string());
+        running.status = EnvironmentStatus::Stopped;
+        let running = guard.storage.add_environment(running).unwrap();
+        let mock = set_container_engine_for_test(MockEngine::with_container(
+            "alive",
+            EnvironmentStatus::Running,
+        ));
+
+        let cleared = run_sync_all_environments()
+            .await
+            .expect("sync should succeed");
+
+        assert_eq!(cleared, vec![missing.id.clone()]);
+        let stored_missing = stored_environment(&guard, &missing.id);
+        assert_eq!(stored_missing.status, EnvironmentStatus::Stopped);
+        assert!(stored_missing.container_id.is_none());
+        let stored_running = stored_environment(&guard, &running.id);
+        assert_eq!(stored_running.status, EnvironmentStatus::Running);
+        assert_eq!(stored_running.container_id.as_deref(), Some("alive"));
+    }
+
+    #[tokio::test]
+    async fn test_stop_environment_reports_status_transitions() {
+        let (guard, environment) =
+            setup_container_environment(Some("existing"), EnvironmentStatus::Running);
+        let mock = set_container_engine_for_test(MockEngine::with_container(
+            "existing",
+            EnvironmentStatus::Running,
+        ));
+        let statuses = Mutex::new(Vec::new());
+
+        run_stop_environment(
+            &|status| statuses.lock().unwrap().push(status),
+            environment.id.clone(),
+        )
+        .await
+        .expect("stop should succeed");
+
+        assert_eq!(
+            *statuses.lock().unwrap(),
+            vec![EnvironmentStatus::Stopping, EnvironmentStatus::Stopped]
+        );
+        assert_eq!(
+            stored_environment(&guard, &environment.id).status,
+            EnvironmentStatus::Stopped
+        );
+    }
+
+    #[tokio::test]
+    async fn test_force_kill_environment_marks_stopped() {
+        let (guard, environment) =
+            setup_container_environment(Some("wedged"), EnvironmentStatus::Stopping);
+        let mock = set_container_engine_for_test(MockEngine::with_container(
+            "wedged",
+            EnvironmentStatus::Running,
+        ));
+
+        let killed = run_force_kill_environment(&environment.id)
+            .await
+            .expect("kill should succeed");
+
+        assert_eq!(killed, "wedged");
+        assert_eq!(mock.engine.calls(), vec!["kill wedged"]);
+        assert_eq!(
+            stored_environment(&guard, &environment.id).status,
+            EnvironmentStatus::Stopped
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stop_environment_failure_marks_error() {
+        let (guard, environment) =
+            setup_container_environment(Some("gone"), EnvironmentStatus::Running);
+        let mock = set_container_engine_for_test(MockEngine::default());
+        let statuses = Mutex::new(Vec::new());
+
+        let result = run_stop_environment(
+            &|status| statuses.lock().unwrap().push(status),
+            environment.id.clone(),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            *statuses.lock().unwrap(),
+            vec![EnvironmentStatus::Stopping, EnvironmentStatus::Error]
+        );
+        assert_eq!(
+            stored_environment(&guard, &environment.id).status,
+            EnvironmentStatus::Error
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_all_environments_resets_containerless_creating() {
+        let (guard, stuck) = setup_container_environment(None, EnvironmentStatus::Creating);
+        let mut stopped = Environment::new(stuck.project_id.clone());
+        stopped.status = EnvironmentStatus::Stopped;
+        let stopped = guard.storage.add_environment(stopped).unwrap();
+        let mock = set_container_engine_for_test(MockEngine::default());
+
+        let cleared = run_sync_all_environments()
+            .await
+            .expect("sync should succeed");
+
+        assert_eq!(cleared, vec![stuck.id.clone()]);
+        assert_eq!(
+            stored_environment(&guard, &stuck.id).status,
+            EnvironmentStatus::Stopped
+        );
+        assert_eq!(
+            stored_environment(&guard, &stopped.id).status,
+            EnvironmentStatus::Stopped
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recreate_environment_restores_from_committed_image() {
+        let (guard, environment) =
+            setup_container_environment(Some("old"), EnvironmentStatus::Running);
+        let mock = set_container_engine_for_test(MockEngine::with_container(
+            "old",
+            EnvironmentStatus::Running,
+        ));
+        let temp_image = format!("orkestrator-temp-{}:recreate", environment.id);
+
+        run_recreate_environment(&|_| {}, environment.id.clone())
+            .await
+            .expect("recreate should succeed");
+
+        assert_eq!(
+            mock.engine.calls(),
+            vec![
+                "stop old".to_string(),
+                format!("commit old {}", temp_image),
+                "remove old".to_string(),
+                format!("create {}", temp_image),
+                "start container-1".to_string(),
+                format!("remove-image {}", temp_image),
+            ]
+        );
+        assert!(mock.engine.state().images.is_empty());
+
+        let stored = stored_environment(&guard, &environment.id);
+        assert_eq!(stored.status, EnvironmentStatus::Running);
+        assert_eq!(stored.container_id.as_deref(), Some("container-1"));
+    }
+
+    #[tokio::test]
+    async fn test_recreate_environment_starts_fresh_when_commit_fails() {
+        let (guard, environment) =
+            setup_container_environment(Some("old"), EnvironmentStatus::Stopped);
+        let mock = set_container_engine_for_test(MockEngine::with_container(
+            "old",
+            EnvironmentStatus::Stopped,
+        ));
+        mock.engine.state().fail_commit = true;
+
+        run_recreate_environment(&|_| {}, environment.id.clone())
+            .await
+            .expect("recreate should fall back to a fresh start");
+
+        assert_eq!(
+            mock.engine.calls(),
+            vec![
+                format!("commit old orkestrator-temp-{}:recreate", environment.id),
+                "remove old".to_string(),
+                format!("create {}", BASE_IMAGE),
+                "start container-1".to_string(),
+            ]
+        );
+        let stored = stored_environment(&guard, &environment.id);
+        assert_eq!(stored.status, EnvironmentStatus::Running);
+        assert_eq!(stored.container_id.as_deref(), Some("container-1"));
+    }
+
+    #[tokio::test]
+    async fn test_diff_allowed_domains_falls_back_to_ipset_for_untracked_entries() {
+        let guard = crate::storage::set_storage_for_test(create_test_storage());
+        let mut config = AppConfig::default();
+        config.global.allowed_domains = Vec::new();
+        guard.storage.save_config(&config).unwrap();
+        let mut environment = Environment::new("project-1".to_string());
+        environment.container_id = Some("abc".to_string());
+        environment.status = EnvironmentStatus::Running;
+        environment.network_access_mode = NetworkAccessMode::Restricted;
+        environment.allowed_domains = Some(vec!["10.0.0.7".to_string(), "10.1.0.0/16".to_string()]);
+        let environment = guard.storage.add_environment(environment).unwrap();
+        let _mock = set_container_engine_for_test(
+            MockEngine::with_container("abc", EnvironmentStatus::Running).with_exec(|cmd| {
+                match cmd.last().copied() {
+                    // Older images have no record of the applied entries
+                    Some("--list-domains") => (String::new(), "no such file".to_string(), 1),
+                    Some("--list") => (
+                        "Name: allowed-domains\nMembers:\n10.0.0.0/24\n".to_string(),
+                        String::new(),
+                        0,
+                    ),
+                    _ => (String::new(), String::new(), 0),
+                }
+            }),
+        );
+
+        let diff = diff_allowed_domains(environment.id.clone()).await.unwrap();
+        assert!(!diff.entries_tracked);
+        assert!(diff.only_in_storage.is_empty());
+        assert!(diff.only_in_container.is_empty());
+        assert_eq!(diff.missing_from_ipset, vec!["10.1.0.0/16"]);
+    }
 }
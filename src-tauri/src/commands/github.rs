@@ -1,7 +1,10 @@
 // GitHub integration Tauri commands
 
 use crate::docker::client::get_docker_client;
-use crate::models::PrState;
+use crate::models::{
+    is_valid_branch_name, sanitize_branch_name, CiStatus, Environment, EnvironmentType, GitRemote,
+    PrState, Project, RepositoryMetadata,
+};
 use crate::storage::Storage;
 
 /// PR detection result containing both URL and state
@@ -22,6 +25,117 @@ struct GhPrListEntry {
     updated_at: Option<String>,
 }
 
+/// Open pull request of a project's repository
+#[derive(Debug, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectPullRequest {
+    pub number: u64,
+    pub title: String,
+    /// Head branch, usable as `base_ref` when creating an environment
+    pub head_ref_name: String,
+    /// GitHub login of the author
+    pub author: String,
+    pub url: String,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhPrSummaryEntry {
+    number: u64,
+    title: String,
+    head_ref_name: String,
+    author: Option<GhAuthor>,
+    url: String,
+}
+
+#[derive(serde::Deserialize)]
+struct GhAuthor {
+    login: String,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhPrViewEntry {
+    title: String,
+    head_ref_name: String,
+    url: String,
+    state: String,
+    #[serde(default)]
+    is_cross_repository: bool,
+    head_repository: Option<GhRepository>,
+    head_repository_owner: Option<GhAuthor>,
+}
+
+#[derive(serde::Deserialize)]
+struct GhRepository {
+    name: String,
+}
+
+/// How long cached repository metadata is served before it is fetched again
+const REPOSITORY_METADATA_TTL: chrono::Duration = chrono::Duration::hours(6);
+
+/// How long a gh or git call fetching repository metadata may take
+const REPOSITORY_METADATA_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhRepoViewEntry {
+    name: String,
+    description: Option<String>,
+    default_branch_ref: Option<GhBranchRef>,
+    repository_topics: Option<Vec<GhTopic>>,
+    visibility: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct GhBranchRef {
+    name: String,
+}
+
+#[derive(serde::Deserialize)]
+struct GhTopic {
+    name: String,
+}
+
+/// A single CI check run of a pull request
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrCheck {
+    pub name: String,
+    /// Raw check state, e.g. "SUCCESS", "FAILURE" or "IN_PROGRESS"
+    pub state: String,
+    #[serde(default)]
+    pub link: String,
+    /// gh's categorization of the state: pass, fail, pending, skipping or cancel
+    #[serde(default)]
+    pub bucket: String,
+}
+
+/// A label or reviewer that gh refused to attach to a new pull request
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectedPrMetadata {
+    pub value: String,
+    pub reason: String,
+}
+
+/// Result of `create_pull_request`
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedPullRequest {
+    pub url: String,
+    pub rejected_labels: Vec<RejectedPrMetadata>,
+    pub rejected_reviewers: Vec<RejectedPrMetadata>,
+}
+
+/// Check runs of an environment's pull request plus their summarized state
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrChecks {
+    pub ci_status: Option<CiStatus>,
+    pub checks: Vec<PrCheck>,
+}
+
 struct DetectionCandidate {
     rank: u8,
     updated_at: Option<String>,
@@ -148,6 +262,266 @@ fn parse_local_pr_list_output(
     parse_pr_list_output(trimmed, branch)
 }
 
+fn is_gh_auth_error(text: &str) -> bool {
+    let lowered = text.to_lowercase();
+    lowered.contains("gh auth login")
+        || lowered.contains("not logged in")
+        || lowered.contains("authentication required")
+        || lowered.contains("bad credentials")
+}
+
+fn parse_project_pr_list_output(
+    stdout: &str,
+    stderr: &str,
+    success: bool,
+) -> Result<Vec<ProjectPullRequest>, String> {
+    let stderr_trimmed = stderr.trim();
+
+    if !success {
+        if is_gh_auth_error(stderr_trimmed) {
+            return Err(
+                "GitHub CLI is not authenticated. Run `gh auth login` and try again.".to_string(),
+            );
+        }
+        let error_msg = if stderr_trimmed.is_empty() {
+            "gh pr list failed"
+        } else {
+            stderr_trimmed
+        };
+        return Err(format!("Failed to list pull requests: {}", error_msg));
+    }
+
+    let entries: Vec<GhPrSummaryEntry> = serde_json::from_str(stdout.trim()).map_err(|e| {
+        tracing::debug!(output = %stdout.trim(), error = %e, "Unexpected output from gh pr list");
+        "Failed to parse gh pr list output".to_string()
+    })?;
+
+    Ok(entries
+        .into_iter()
+        .map(|entry| ProjectPullRequest {
+            number: entry.number,
+            title: entry.title,
+            head_ref_name: entry.head_ref_name,
+            author: entry.author.map(|author| author.login).unwrap_or_default(),
+            url: entry.url,
+        })
+        .collect())
+}
+
+fn parse_repo_view_output(stdout: &str) -> Option<RepositoryMetadata> {
+    let entry: GhRepoViewEntry = serde_json::from_str(stdout.trim()).ok()?;
+    Some(RepositoryMetadata {
+        name: entry.name,
+        description: entry.description.filter(|d| !d.trim().is_empty()),
+        default_branch: entry
+            .default_branch_ref
+            .map(|branch| branch.name)
+            .filter(|name| !name.is_empty()),
+        topics: entry
+            .repository_topics
+            .unwrap_or_default()
+            .into_iter()
+            .map(|topic| topic.name)
+            .collect(),
+        visibility: entry.visibility,
+        fetched_at: chrono::Utc::now(),
+    })
+}
+
+/// Default branch from `git ls-remote --symref <remote> HEAD` output
+fn parse_symref_default_branch(stdout: &str) -> Option<String> {
+    stdout.lines().find_map(|line| {
+        let (reference, target) = line.strip_prefix("ref: ")?.split_once('\t')?;
+        if target.trim() != "HEAD" {
+            return None;
+        }
+        reference.strip_prefix("refs/heads/").map(String::from)
+    })
+}
+
+/// Read repository metadata with gh, or None if gh is missing or fails
+async fn fetch_repository_metadata_gh(project: &Project) -> Option<RepositoryMetadata> {
+    let output = tokio::process::Command::new("gh")
+        .args([
+            "repo",
+            "view",
+            &project.git_url,
+            "--json",
+            "name,description,defaultBranchRef,repositoryTopics,visibility",
+        ])
+        .kill_on_drop(true)
+        .output();
+    let output = match tokio::time::timeout(REPOSITORY_METADATA_TIMEOUT, output).await {
+        Ok(output) => output.ok()?,
+        Err(_) => {
+            tracing::debug!(project_id = %project.id, "gh repo view timed out, falling back to git");
+            return None;
+        }
+    };
+    if !output.status.success() {
+        tracing::debug!(
+            project_id = %project.id,
+            stderr = %String::from_utf8_lossy(&output.stderr).trim(),
+            "gh repo view failed, falling back to git"
+        );
+        return None;
+    }
+    parse_repo_view_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Read what git alone can tell: the project name and the remote's default branch
+async fn fetch_repository_metadata_git(project: &Project) -> Result<RepositoryMetadata, String> {
+    let mut command = tokio::process::Command::new("git");
+    command
+        .args(["ls-remote", "--symref"])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .kill_on_drop(true);
+    match project.local_path.as_deref() {
+        Some(local_path) => {
+            command.arg("origin").current_dir(local_path);
+        }
+        None => {
+            command.arg(&project.git_url);
+        }
+    }
+    let output = tokio::time::timeout(REPOSITORY_METADATA_TIMEOUT, command.arg("HEAD").output())
+        .await
+        .map_err(|_| "Timed out reading repository metadata".to_string())?
+        .map_err(|e| format!("Failed to execute git command: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to read repository metadata: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(RepositoryMetadata {
+        name: project.name.clone(),
+        description: None,
+        default_branch: parse_symref_default_branch(&String::from_utf8_lossy(&output.stdout)),
+        topics: Vec::new(),
+        visibility: None,
+        fetched_at: chrono::Utc::now(),
+    })
+}
+
+/// Get a project's repository metadata (description, topics, default branch,
+/// visibility). Read with gh, falling back to git for users without it, and
+/// cached in the data dir for a few hours; `refresh` bypasses the cache.
+#[tauri::command]
+pub async fn get_repository_metadata(
+    project_id: String,
+    refresh: Option<bool>,
+) -> Result<RepositoryMetadata, String> {
+    use crate::storage::get_storage;
+
+    let storage = get_storage().map_err(|e| e.to_string())?;
+    let project = storage
+        .get_project(&project_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+
+    if !refresh.unwrap_or(false) {
+        let cached = storage
+            .get_cached_repository_metadata(&project_id)
+            .map_err(|e| e.to_string())?;
+        if let Some(cached) = cached {
+            if chrono::Utc::now() - cached.fetched_at < REPOSITORY_METADATA_TTL {
+                return Ok(cached);
+            }
+        }
+    }
+
+    let metadata = match fetch_repository_metadata_gh(&project).await {
+        Some(metadata) => metadata,
+        None => fetch_repository_metadata_git(&project).await?,
+    };
+    if let Err(e) = storage.save_repository_metadata(&project_id, &metadata) {
+        tracing::warn!(project_id = %project_id, error = %e, "Failed to cache repository metadata");
+    }
+    Ok(metadata)
+}
+
+fn parse_pr_view_output(
+    stdout: &str,
+    stderr: &str,
+    success: bool,
+) -> Result<GhPrViewEntry, String> {
+    let stderr_trimmed = stderr.trim();
+
+    if !success {
+        if is_gh_auth_error(stderr_trimmed) {
+            return Err(
+                "GitHub CLI is not authenticated. Run `gh auth login` and try again.".to_string(),
+            );
+        }
+        let error_msg = if stderr_trimmed.is_empty() {
+            "gh pr view failed"
+        } else {
+            stderr_trimmed
+        };
+        return Err(format!("Failed to load pull request: {}", error_msg));
+    }
+
+    serde_json::from_str(stdout.trim()).map_err(|e| {
+        tracing::debug!(output = %stdout.trim(), error = %e, "Unexpected output from gh pr view");
+        "Failed to parse gh pr view output".to_string()
+    })
+}
+
+/// Build the remote for the fork a cross-repository pull request comes from.
+/// The clone URL is derived from the PR URL so it points at the same host.
+fn fork_remote(pr_url: &str, owner: &str, repo: &str) -> Option<GitRemote> {
+    let host = pr_url.strip_prefix("https://")?.split('/').next()?;
+    Some(GitRemote {
+        name: format!("fork-{}", sanitize_branch_name(owner)),
+        url: format!("https://{}/{}/{}.git", host, owner, repo),
+    })
+}
+
+/// Summarize check runs: any failure wins, then any pending check.
+/// Returns None when there are no checks.
+fn summarize_pr_checks(checks: &[PrCheck]) -> Option<CiStatus> {
+    if checks.is_empty() {
+        return None;
+    }
+    let has_bucket = |bucket: &str| checks.iter().any(|check| check.bucket == bucket);
+    if has_bucket("fail") || has_bucket("cancel") {
+        Some(CiStatus::Failing)
+    } else if has_bucket("pending") {
+        Some(CiStatus::Pending)
+    } else {
+        Some(CiStatus::Passing)
+    }
+}
+
+/// Parse `gh pr checks --json` output. gh exits non-zero while checks are
+/// failing or pending, so JSON on stdout is used regardless of the exit status.
+fn parse_pr_checks_output(stdout: &str, stderr: &str) -> Result<Vec<PrCheck>, String> {
+    let trimmed = stdout.trim();
+    if let Ok(checks) = serde_json::from_str::<Vec<PrCheck>>(trimmed) {
+        return Ok(checks);
+    }
+
+    let stderr_trimmed = stderr.trim();
+    if stderr_trimmed.to_lowercase().contains("no checks reported") {
+        return Ok(Vec::new());
+    }
+    if is_gh_auth_error(stderr_trimmed) {
+        return Err(
+            "GitHub CLI is not authenticated. Run `gh auth login` and try again.".to_string(),
+        );
+    }
+
+    tracing::debug!(stdout = %trimmed, stderr = %stderr_trimmed, "Unexpected output from gh pr checks");
+    let error_msg = if stderr_trimmed.is_empty() {
+        "unexpected output"
+    } else {
+        stderr_trimmed
+    };
+    Err(format!("Failed to load PR checks: {}", error_msg))
+}
+
 fn get_environment_pr_url_from_storage(
     storage: &Storage,
     environment_id: &str,
@@ -165,7 +539,7 @@ fn clear_environment_pr_in_storage(storage: &Storage, environment_id: &str) -> R
     storage
         .update_environment(
             environment_id,
-            json!({ "prUrl": null, "prState": null, "hasMergeConflicts": null }),
+            json!({ "prUrl": null, "prState": null, "hasMergeConflicts": null, "ciStatus": null }),
         )
         .map_err(|e| e.to_string())?;
 
@@ -204,6 +578,66 @@ fn parse_merge_command_result(stdout: &str, stderr: &str, success: bool) -> Resu
     Err(format!("Failed to merge PR: {}", error_msg))
 }
 
+/// Trim labels or reviewers and reject blank entries
+fn validate_pr_metadata(kind: &str, values: Vec<String>) -> Result<Vec<String>, String> {
+    values
+        .into_iter()
+        .map(|value| {
+            let trimmed = value.trim();
+            if trimmed.is_empty() {
+                Err(format!("{} must be non-empty strings", kind))
+            } else {
+                Ok(trimmed.to_string())
+            }
+        })
+        .collect()
+}
+
+/// Find the URL `gh pr create` prints for the new pull request
+fn parse_created_pr_url(stdout: &str) -> Option<String> {
+    stdout
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| is_valid_pr_url(line))
+        .map(str::to_string)
+}
+
+fn gh_error_message(stdout: &str, stderr: &str) -> String {
+    let stdout_trimmed = stdout.trim();
+    let stderr_trimmed = stderr.trim();
+    if !stderr_trimmed.is_empty() {
+        stderr_trimmed.to_string()
+    } else if !stdout_trimmed.is_empty() {
+        stdout_trimmed.to_string()
+    } else {
+        "Unknown error".to_string()
+    }
+}
+
+/// Map the result of `gh pr close`/`gh pr reopen`. A merged PR can be neither
+/// closed nor reopened, so that case gets a dedicated message.
+fn parse_pr_state_change_result(
+    action: &str,
+    stdout: &str,
+    stderr: &str,
+    success: bool,
+) -> Result<(), String> {
+    if success {
+        return Ok(());
+    }
+
+    let error_msg = gh_error_message(stdout, stderr);
+    if error_msg.to_lowercase().contains("already merged") {
+        return Err(format!(
+            "Cannot {} the pull request because it was already merged",
+            action
+        ));
+    }
+
+    Err(format!("Failed to {} PR: {}", action, error_msg))
+}
+
 /// Detect PR URL and state for the environment's branch by querying GitHub for that head branch
 /// Uses the stored branch explicitly so detection is independent of the current checkout
 #[tauri::command]
@@ -313,6 +747,257 @@ pub async fn detect_pr_local(
     parse_local_pr_list_output(&stdout, &stderr, output.status.success(), &branch)
 }
 
+/// List open pull requests of a project's repository.
+/// The head branch of a result can be passed as `base_ref` to `create_environment`
+/// to start an environment from that pull request.
+#[tauri::command]
+pub async fn list_project_pull_requests(
+    project_id: String,
+) -> Result<Vec<ProjectPullRequest>, String> {
+    use crate::storage::get_storage;
+    use tokio::process::Command;
+    use tracing::debug;
+
+    let storage = get_storage().map_err(|e| e.to_string())?;
+    let project = storage
+        .get_project(&project_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+
+    // Run: gh pr list --repo <git url> --state open --json ...
+    let output = Command::new("gh")
+        .args([
+            "pr",
+            "list",
+            "--repo",
+            &project.git_url,
+            "--state",
+            "open",
+            "--limit",
+            "100",
+            "--json",
+            "number,title,headRefName,author,url",
+        ])
+        .output()
+        .await
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                "GitHub CLI (gh) is not installed".to_string()
+            } else {
+                format!("Failed to execute gh command: {}", e)
+            }
+        })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    debug!(project_id = %project_id, stderr = %stderr.trim(), "gh pr list output");
+
+    parse_project_pr_list_output(&stdout, &stderr, output.status.success())
+}
+
+/// Create an environment that checks out a pull request's head branch.
+/// The environment is named after the PR title and starts out linked to the PR.
+/// For pull requests from forks, the fork is added as a remote so the branch
+/// is fetched from, and pushed back to, the fork.
+#[tauri::command]
+pub async fn create_environment_from_pr(
+    app_handle: tauri::AppHandle,
+    project_id: String,
+    pr_number: u64,
+) -> Result<Environment, String> {
+    use crate::storage::get_storage;
+    use serde_json::json;
+    use tokio::process::Command;
+    use tracing::{debug, info};
+
+    let storage = get_storage().map_err(|e| e.to_string())?;
+    let project = storage
+        .get_project(&project_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+
+    // Run: gh pr view <number> --repo <git url> --json ...
+    let output = Command::new("gh")
+        .args([
+            "pr",
+            "view",
+            &pr_number.to_string(),
+            "--repo",
+            &project.git_url,
+            "--json",
+            "title,headRefName,url,state,isCrossRepository,headRepository,headRepositoryOwner",
+        ])
+        .output()
+        .await
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                "GitHub CLI (gh) is not installed".to_string()
+            } else {
+                format!("Failed to execute gh command: {}", e)
+            }
+        })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    debug!(project_id = %project_id, pr_number, stderr = %stderr.trim(), "gh pr view output");
+
+    let pr = parse_pr_view_output(&stdout, &stderr, output.status.success())?;
+
+    if parse_pr_state(&pr.state) != Some(PrState::Open) {
+        return Err(format!("Pull request #{} is not open", pr_number));
+    }
+    if !is_valid_branch_name(&pr.head_ref_name) || !is_valid_pr_url(&pr.url) {
+        return Err(format!(
+            "Pull request #{} has an unsupported head branch",
+            pr_number
+        ));
+    }
+
+    let base_remote = if pr.is_cross_repository {
+        let owner = pr
+            .head_repository_owner
+            .as_ref()
+            .map(|owner| owner.login.as_str());
+        let repo = pr.head_repository.as_ref().map(|repo| repo.name.as_str());
+        match (owner, repo) {
+            (Some(owner), Some(repo)) => fork_remote(&pr.url, owner, repo),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            format!(
+                "Pull request #{} comes from a fork that is no longer available",
+                pr_number
+            )
+        })
+        .map(Some)?
+    } else {
+        None
+    };
+
+    let environment = super::environments::create_environment(
+        app_handle,
+        project_id,
+        Some(pr.title.clone()),
+        None,
+        None,
+        None,
+        None,
+        Some(pr.head_ref_name.clone()),
+    )
+    .await?;
+
+    // Same-repository PRs check out the head branch itself so pushes update the PR.
+    // Fork branches get a local name prefixed with the remote to avoid clashing with
+    // origin's branches (a fork's head is often its `main`); a "remote/branch" name
+    // would make `git checkout` detach at the remote-tracking ref instead.
+    let branch = match &base_remote {
+        Some(remote) => sanitize_branch_name(&format!("{}-{}", remote.name, pr.head_ref_name)),
+        None => pr.head_ref_name.clone(),
+    };
+
+    let environment = storage
+        .update_environment(
+            &environment.id,
+            json!({
+                "branch": branch,
+                "baseRemote": base_remote,
+                "prUrl": pr.url,
+                "prState": PrState::Open,
+            }),
+        )
+        .map_err(|e| e.to_string())?;
+
+    info!(environment_id = %environment.id, pr_number, branch = %environment.branch, "Created environment from pull request");
+
+    Ok(environment)
+}
+
+/// Run a gh command for an environment and return (stdout, stderr, success).
+/// Containers carry their own GitHub auth; local environments use the host's gh.
+async fn run_gh_for_environment(
+    environment: &Environment,
+    args: &[&str],
+) -> Result<(String, String, bool), String> {
+    use tokio::process::Command;
+
+    match (&environment.environment_type, &environment.container_id) {
+        (EnvironmentType::Containerized, Some(container_id)) => {
+            let client = get_docker_client().map_err(|e| e.to_string())?;
+            let is_running = client
+                .is_container_running(container_id)
+                .await
+                .map_err(|e| e.to_string())?;
+            if !is_running {
+                return Err("Container is not running".to_string());
+            }
+            let mut cmd = vec!["gh"];
+            cmd.extend(args);
+            let (stdout, stderr, exit_code) = client
+                .exec_command_with_status(container_id, cmd)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok((stdout, stderr, exit_code == 0))
+        }
+        _ => {
+            let mut command = Command::new("gh");
+            command.args(args);
+            if let Some(worktree_path) = &environment.worktree_path {
+                command.current_dir(worktree_path);
+            }
+            let output = command
+                .output()
+                .await
+                .map_err(|e| format!("Failed to execute gh command: {}", e))?;
+            Ok((
+                String::from_utf8_lossy(&output.stdout).to_string(),
+                String::from_utf8_lossy(&output.stderr).to_string(),
+                output.status.success(),
+            ))
+        }
+    }
+}
+
+/// Get the CI check runs of an environment's pull request.
+/// The summarized status is also stored on the environment for the sidebar badge.
+#[tauri::command]
+pub async fn get_pr_checks(environment_id: String) -> Result<PrChecks, String> {
+    use crate::storage::get_storage;
+    use serde_json::json;
+
+    let storage = get_storage().map_err(|e| e.to_string())?;
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+
+    let pr_url = environment
+        .pr_url
+        .clone()
+        .filter(|url| is_valid_pr_url(url))
+        .ok_or_else(|| "Environment has no pull request".to_string())?;
+
+    let args = [
+        "pr",
+        "checks",
+        pr_url.as_str(),
+        "--json",
+        "name,state,link,bucket",
+    ];
+
+    let (stdout, stderr, _) = run_gh_for_environment(&environment, &args).await?;
+
+    let checks = parse_pr_checks_output(&stdout, &stderr)?;
+    let ci_status = summarize_pr_checks(&checks);
+
+    if environment.ci_status != ci_status {
+        storage
+            .update_environment(&environment_id, json!({ "ciStatus": ci_status }))
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(PrChecks { ci_status, checks })
+}
+
 /// Open a URL in the default browser
 /// This uses Tauri's opener plugin
 #[tauri::command]
@@ -352,6 +1037,237 @@ pub async fn clear_environment_pr(environment_id: String) -> Result<(), String>
     clear_environment_pr_in_storage(storage, &environment_id)
 }
 
+/// Create a pull request for the environment's branch with gh pr create.
+/// Labels and reviewers are passed through to `--label`/`--reviewer`. gh refuses
+/// to create the PR when one of them is unknown, so in that case the PR is created
+/// without them and each is added separately to report exactly which were rejected.
+#[tauri::command]
+pub async fn create_pull_request(
+    environment_id: String,
+    title: String,
+    body: Option<String>,
+    base: Option<String>,
+    draft: Option<bool>,
+    labels: Vec<String>,
+    reviewers: Vec<String>,
+) -> Result<CreatedPullRequest, String> {
+    use crate::storage::{get_config, get_storage};
+    use serde_json::json;
+    use tracing::{info, warn};
+
+    let title = title.trim().to_string();
+    if title.is_empty() {
+        return Err("Pull request title cannot be empty".to_string());
+    }
+    let labels = validate_pr_metadata("Labels", labels)?;
+    let reviewers = validate_pr_metadata("Reviewers", reviewers)?;
+
+    let storage = get_storage().map_err(|e| e.to_string())?;
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+
+    if environment.read_only {
+        return Err("Environment is read-only".to_string());
+    }
+
+    let base = match base.map(|b| b.trim().to_string()).filter(|b| !b.is_empty()) {
+        Some(base) => base,
+        None => get_config()
+            .ok()
+            .and_then(|config| config.repositories.get(&environment.project_id).cloned())
+            .map(|repo| repo.pr_base_branch)
+            .unwrap_or_else(|| "main".to_string()),
+    };
+    if !is_valid_branch_name(&base) {
+        return Err(format!("Invalid base branch: {}", base));
+    }
+
+    let body = body.unwrap_or_default();
+    let mut args = vec![
+        "pr",
+        "create",
+        "--head",
+        environment.branch.as_str(),
+        "--base",
+        base.as_str(),
+        "--title",
+        title.as_str(),
+        "--body",
+        body.as_str(),
+    ];
+    if draft.unwrap_or(false) {
+        args.push("--draft");
+    }
+    let base_arg_count = args.len();
+    for label in &labels {
+        args.extend(["--label", label.as_str()]);
+    }
+    for reviewer in &reviewers {
+        args.extend(["--reviewer", reviewer.as_str()]);
+    }
+
+    info!(
+        environment_id = %environment_id,
+        branch = %environment.branch,
+        base = %base,
+        labels = labels.len(),
+        reviewers = reviewers.len(),
+        "Creating PR"
+    );
+
+    let (mut stdout, mut stderr, mut success) = run_gh_for_environment(&environment, &args).await?;
+    let mut metadata_applied = true;
+
+    if !success && (!labels.is_empty() || !reviewers.is_empty()) {
+        warn!(
+            environment_id = %environment_id,
+            stderr = %stderr.trim(),
+            "gh pr create failed with labels/reviewers, retrying without them"
+        );
+        (stdout, stderr, success) =
+            run_gh_for_environment(&environment, &args[..base_arg_count]).await?;
+        metadata_applied = false;
+    }
+
+    if !success {
+        return Err(format!(
+            "Failed to create PR: {}",
+            gh_error_message(&stdout, &stderr)
+        ));
+    }
+
+    let url = parse_created_pr_url(&stdout)
+        .ok_or_else(|| "gh pr create did not return a pull request URL".to_string())?;
+
+    let mut rejected_labels = Vec::new();
+    let mut rejected_reviewers = Vec::new();
+    if !metadata_applied {
+        for (flag, values, rejected) in [
+            ("--add-label", &labels, &mut rejected_labels),
+            ("--add-reviewer", &reviewers, &mut rejected_reviewers),
+        ] {
+            for value in values {
+                let (stdout, stderr, success) = run_gh_for_environment(
+                    &environment,
+                    &["pr", "edit", url.as_str(), flag, value.as_str()],
+                )
+                .await?;
+                if !success {
+                    rejected.push(RejectedPrMetadata {
+                        value: value.clone(),
+                        reason: gh_error_message(&stdout, &stderr),
+                    });
+                }
+            }
+        }
+    }
+
+    storage
+        .update_environment(
+            &environment_id,
+            json!({ "prUrl": url, "prState": PrState::Open, "hasMergeConflicts": null }),
+        )
+        .map_err(|e| e.to_string())?;
+
+    info!(
+        environment_id = %environment_id,
+        url = %url,
+        rejected_labels = rejected_labels.len(),
+        rejected_reviewers = rejected_reviewers.len(),
+        "PR created"
+    );
+
+    Ok(CreatedPullRequest {
+        url,
+        rejected_labels,
+        rejected_reviewers,
+    })
+}
+
+/// Close or reopen an environment's pull request and store the new state
+async fn set_pull_request_open(environment_id: &str, open: bool) -> Result<Environment, String> {
+    use crate::storage::get_storage;
+    use serde_json::json;
+    use tracing::info;
+
+    let action = if open { "reopen" } else { "close" };
+    let storage = get_storage().map_err(|e| e.to_string())?;
+    let environment = storage
+        .get_environment(environment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+
+    if environment.read_only {
+        return Err("Environment is read-only".to_string());
+    }
+
+    let pr_url = environment
+        .pr_url
+        .clone()
+        .filter(|url| is_valid_pr_url(url))
+        .ok_or_else(|| "Environment has no pull request".to_string())?;
+
+    if environment.pr_state == Some(PrState::Merged) {
+        return Err(format!(
+            "Cannot {} the pull request because it was already merged",
+            action
+        ));
+    }
+
+    info!(environment_id = %environment_id, pr_url = %pr_url, action, "Changing PR state");
+
+    let (stdout, stderr, success) =
+        run_gh_for_environment(&environment, &["pr", action, pr_url.as_str()]).await?;
+
+    tracing::debug!(
+        environment_id = %environment_id,
+        stdout = %stdout.trim(),
+        stderr = %stderr.trim(),
+        success,
+        "gh pr {} output",
+        action
+    );
+
+    if let Err(error) = parse_pr_state_change_result(action, &stdout, &stderr, success) {
+        // Keep the stored state in sync when GitHub reports the PR as merged
+        if error.contains("already merged") {
+            let _ = storage.update_environment(
+                environment_id,
+                json!({ "prState": PrState::Merged, "hasMergeConflicts": null }),
+            );
+        }
+        return Err(error);
+    }
+
+    let updates = if open {
+        json!({ "prState": PrState::Open })
+    } else {
+        json!({ "prState": PrState::Closed, "hasMergeConflicts": null })
+    };
+    let updated = storage
+        .update_environment(environment_id, updates)
+        .map_err(|e| e.to_string())?;
+
+    info!(environment_id = %environment_id, action, "PR state changed");
+
+    Ok(updated)
+}
+
+/// Close an environment's pull request without merging (gh pr close)
+#[tauri::command]
+pub async fn close_pull_request(environment_id: String) -> Result<Environment, String> {
+    set_pull_request_open(&environment_id, false).await
+}
+
+/// Reopen an environment's closed pull request (gh pr reopen).
+/// Merged pull requests cannot be reopened.
+#[tauri::command]
+pub async fn reopen_pull_request(environment_id: String) -> Result<Environment, String> {
+    set_pull_request_open(&environment_id, true).await
+}
+
 /// Merge method for PR merging
 #[derive(serde::Deserialize, Default, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
@@ -393,6 +1309,10 @@ pub async fn merge_pr(
         return Err("Container is not running".to_string());
     }
 
+    super::ensure_environment_writable(|env| {
+        env.container_id.as_deref() == Some(container_id.as_str())
+    })?;
+
     let merge_method = method.unwrap_or_default();
     let should_delete_branch = delete_branch.unwrap_or(true);
 
@@ -449,6 +1369,10 @@ pub async fn merge_pr_local(
         .map_err(|e| e.to_string())?
         .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
 
+    if environment.read_only {
+        return Err("Environment is read-only".to_string());
+    }
+
     // Get the worktree path - this only works for local environments
     let worktree_path = environment
         .worktree_path
@@ -499,10 +1423,14 @@ pub async fn merge_pr_local(
 mod tests {
     use super::{
         build_local_merge_args, build_merge_command, clear_environment_pr_in_storage, detect_pr,
-        detect_pr_local, get_environment_pr_url_from_storage, is_expected_absence_output,
-        parse_local_pr_list_output, parse_merge_command_result, parse_pr_detection_output,
-        parse_pr_list_output, MergeMethod,
+        detect_pr_local, fork_remote, get_environment_pr_url_from_storage,
+        is_expected_absence_output, parse_created_pr_url, parse_local_pr_list_output,
+        parse_merge_command_result, parse_pr_checks_output, parse_pr_detection_output,
+        parse_pr_list_output, parse_pr_state_change_result, parse_pr_view_output,
+        parse_project_pr_list_output, summarize_pr_checks, validate_pr_metadata, MergeMethod,
+        PrCheck, ProjectPullRequest,
     };
+    use crate::models::{CiStatus, GitRemote};
     use crate::models::{Environment, PrState};
     use crate::storage::Storage;
     use tempfile::tempdir;
@@ -595,6 +1523,113 @@ mod tests {
         assert!(error.contains("authentication failed"));
     }
 
+    #[test]
+    fn parse_project_pr_list_output_reads_entries() {
+        let stdout = r#"[{"number":42,"title":"Add login","headRefName":"feature/login","author":{"login":"octocat","name":"Octo"},"url":"https://github.com/org/repo/pull/42"}]"#;
+        let prs = parse_project_pr_list_output(stdout, "", true).unwrap();
+        assert_eq!(
+            prs,
+            vec![ProjectPullRequest {
+                number: 42,
+                title: "Add login".to_string(),
+                head_ref_name: "feature/login".to_string(),
+                author: "octocat".to_string(),
+                url: "https://github.com/org/repo/pull/42".to_string(),
+            }]
+        );
+        assert!(parse_project_pr_list_output("[]", "", true)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn parse_project_pr_list_output_reports_missing_auth() {
+        let err = parse_project_pr_list_output(
+            "",
+            "To get started with GitHub CLI, please run:  gh auth login",
+            false,
+        )
+        .unwrap_err();
+        assert!(err.contains("not authenticated"));
+
+        let err = parse_project_pr_list_output("", "HTTP 404: Not Found", false).unwrap_err();
+        assert_eq!(err, "Failed to list pull requests: HTTP 404: Not Found");
+    }
+
+    #[test]
+    fn parse_repo_view_output_reads_metadata() {
+        let stdout = r#"{"defaultBranchRef":{"name":"develop"},"description":"","name":"repo","repositoryTopics":[{"name":"rust"},{"name":"tauri"}],"visibility":"PUBLIC"}"#;
+        let metadata = parse_repo_view_output(stdout).unwrap();
+        assert_eq!(metadata.name, "repo");
+        assert_eq!(metadata.description, None);
+        assert_eq!(metadata.default_branch.as_deref(), Some("develop"));
+        assert_eq!(metadata.topics, vec!["rust", "tauri"]);
+        assert_eq!(metadata.visibility.as_deref(), Some("PUBLIC"));
+
+        let stdout = r#"{"defaultBranchRef":null,"description":"A repo","name":"repo","repositoryTopics":null,"visibility":"PRIVATE"}"#;
+        let metadata = parse_repo_view_output(stdout).unwrap();
+        assert_eq!(metadata.description.as_deref(), Some("A repo"));
+        assert!(metadata.topics.is_empty());
+        assert!(parse_repo_view_output("not json").is_none());
+    }
+
+    #[test]
+    fn parse_symref_default_branch_reads_head() {
+        let stdout = "ref: refs/heads/main\tHEAD\n1a2b3c\tHEAD\n";
+        assert_eq!(parse_symref_default_branch(stdout).as_deref(), Some("main"));
+        assert_eq!(parse_symref_default_branch("1a2b3c\tHEAD\n"), None);
+    }
+
+    #[test]
+    fn parse_pr_view_output_reads_fork_details() {
+        let stdout = r#"{"title":"Fix typo","headRefName":"main","url":"https://github.com/org/repo/pull/7","state":"OPEN","isCrossRepository":true,"headRepository":{"id":"R_1","name":"repo-fork"},"headRepositoryOwner":{"id":"U_1","login":"OctoCat"}}"#;
+        let pr = parse_pr_view_output(stdout, "", true).unwrap();
+        assert_eq!(pr.head_ref_name, "main");
+        assert!(pr.is_cross_repository);
+
+        assert_eq!(
+            fork_remote(&pr.url, "OctoCat", "repo-fork"),
+            Some(GitRemote {
+                name: "fork-octocat".to_string(),
+                url: "https://github.com/OctoCat/repo-fork.git".to_string(),
+            })
+        );
+
+        let err = parse_pr_view_output("", "not logged in to any hosts", false)
+            .err()
+            .expect("missing auth should fail");
+        assert!(err.contains("not authenticated"));
+    }
+
+    #[test]
+    fn parse_pr_checks_output_reads_checks_and_summarizes() {
+        let stdout = r#"[{"name":"build","state":"SUCCESS","link":"https://ci/1","bucket":"pass"},{"name":"lint","state":"IN_PROGRESS","link":"https://ci/2","bucket":"pending"}]"#;
+        let checks = parse_pr_checks_output(stdout, "").unwrap();
+        assert_eq!(checks.len(), 2);
+        assert_eq!(summarize_pr_checks(&checks), Some(CiStatus::Pending));
+
+        let failed = vec![
+            PrCheck {
+                name: "build".to_string(),
+                state: "FAILURE".to_string(),
+                link: String::new(),
+                bucket: "fail".to_string(),
+            },
+            checks[1].clone(),
+        ];
+        assert_eq!(summarize_pr_checks(&failed), Some(CiStatus::Failing));
+        assert_eq!(summarize_pr_checks(&checks[..1]), Some(CiStatus::Passing));
+        assert_eq!(summarize_pr_checks(&[]), None);
+    }
+
+    #[test]
+    fn parse_pr_checks_output_treats_no_checks_as_empty() {
+        let checks =
+            parse_pr_checks_output("", "no checks reported on the 'feature' branch").unwrap();
+        assert!(checks.is_empty());
+        assert!(parse_pr_checks_output("", "HTTP 502").is_err());
+    }
+
     #[test]
     fn get_environment_pr_url_from_storage_returns_stored_url() {
         let storage = create_test_storage();
@@ -620,6 +1655,7 @@ mod tests {
         environment.pr_url = Some("https://github.com/org/repo/pull/42".to_string());
         environment.pr_state = Some(PrState::Open);
         environment.has_merge_conflicts = Some(true);
+        environment.ci_status = Some(CiStatus::Failing);
         storage
             .add_environment(environment.clone())
             .expect("environment should save");
@@ -635,6 +1671,7 @@ mod tests {
         assert!(updated.pr_url.is_none());
         assert!(updated.pr_state.is_none());
         assert!(updated.has_merge_conflicts.is_none());
+        assert!(updated.ci_status.is_none());
     }
 
     #[test]
@@ -660,6 +1697,73 @@ mod tests {
         assert_eq!(error, "Failed to merge PR: stderr message");
     }
 
+    #[test]
+    fn validate_pr_metadata_trims_and_rejects_blank_entries() {
+        assert_eq!(
+            validate_pr_metadata("Labels", vec![" bug ".to_string(), "ui".to_string()]),
+            Ok(vec!["bug".to_string(), "ui".to_string()])
+        );
+        assert_eq!(
+            validate_pr_metadata("Reviewers", vec!["octocat".to_string(), "  ".to_string()]),
+            Err("Reviewers must be non-empty strings".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_created_pr_url_finds_url_line() {
+        let stdout = "\nCreating pull request for feature into main in org/repo\n\nhttps://github.com/org/repo/pull/7\n";
+        assert_eq!(
+            parse_created_pr_url(stdout),
+            Some("https://github.com/org/repo/pull/7".to_string())
+        );
+        assert_eq!(parse_created_pr_url("no url here"), None);
+    }
+
+    #[test]
+    fn parse_pr_state_change_result_reports_merged_prs() {
+        assert!(parse_pr_state_change_result("close", "", "", true).is_ok());
+
+        let error = parse_pr_state_change_result(
+            "reopen",
+            "",
+            "X Pull request org/repo#42 can't be reopened because it was already merged",
+            false,
+        )
+        .expect_err("merged PR should be rejected");
+        assert_eq!(
+            error,
+            "Cannot reopen the pull request because it was already merged"
+        );
+
+        let error = parse_pr_state_change_result("close", "", "HTTP 403", false)
+            .expect_err("failure should surface stderr");
+        assert_eq!(error, "Failed to close PR: HTTP 403");
+    }
+
+    #[tokio::test]
+    async fn clear_environment_pr_command_uses_global_storage() {
+        let guard = crate::storage::set_storage_for_test(create_test_storage());
+        let mut environment = Environment::new("project-1".to_string());
+        environment.pr_url = Some("https://github.com/org/repo/pull/42".to_string());
+        environment.pr_state = Some(PrState::Open);
+        guard
+            .storage
+            .add_environment(environment.clone())
+            .expect("environment should save");
+
+        super::clear_environment_pr(environment.id.clone())
+            .await
+            .expect("command should clear PR metadata");
+
+        let updated = guard
+            .storage
+            .get_environment(&environment.id)
+            .expect("environment should load")
+            .expect("environment should exist");
+        assert!(updated.pr_url.is_none());
+        assert!(updated.pr_state.is_none());
+    }
+
     #[tokio::test]
     async fn detect_pr_rejects_empty_branch() {
         let error = detect_pr("container-1".to_string(), "   ".to_string())
@@ -3,20 +3,23 @@
 //! These commands manage OpenCode, Claude-bridge, and Codex-bridge servers
 //! for local (non-Docker) environments.
 
+use crate::local::logs::{follow_server_log, read_server_log, server_log_path};
 use crate::local::ports::{allocate_ports, is_port_available};
 use crate::local::process::{get_process_manager, is_process_alive, ProcessType};
 use crate::local::{
     get_local_claude_status, get_local_codex_status, get_local_opencode_status,
-    start_local_claude_bridge, start_local_codex_bridge, start_local_opencode_server,
-    stop_local_claude_bridge, stop_local_codex_bridge, stop_local_opencode_server,
-    LocalServerStartResult, LocalServerStatus,
+    probe_server_health, start_local_claude_bridge, start_local_codex_bridge,
+    start_local_opencode_server, stop_local_claude_bridge, stop_local_codex_bridge,
+    stop_local_opencode_server, LocalServerStartResult, LocalServerStatus,
 };
 use crate::storage::get_storage;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex as StdMutex, OnceLock};
+use std::time::Duration;
 use tauri::Manager;
 use tracing::{debug, info, warn};
 
@@ -53,7 +56,7 @@ fn get_codex_start_lock(environment_id: &str) -> Arc<tokio::sync::Mutex<()>> {
         .clone()
 }
 
-use super::load_codex_bridge_raw_event_logging;
+use super::{load_codex_bridge_raw_event_logging, load_native_server_host};
 
 /// Result type for local server start commands
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -208,7 +211,7 @@ pub async fn start_local_opencode_server_cmd(
 
         if !is_port_available(port) {
             let all_envs = storage.get_all_environments().map_err(|e| e.to_string())?;
-            let allocation = allocate_ports(&all_envs)?;
+            let allocation = allocate_ports(&all_envs, &[])?;
             let new_port = allocation.opencode_port;
             warn!(
                 environment_id = %environment_id,
@@ -236,10 +239,13 @@ pub async fn start_local_opencode_server_cmd(
     }
 
     // Start the server
+    let bind_host = load_native_server_host()?;
     let result = start_local_opencode_server(
         &environment_id,
         worktree_path,
         port,
+        &bind_host,
+        environment.opencode_model_override.as_deref(),
         bundled_opencode_path.as_deref(),
     )
     .await?;
@@ -443,7 +449,7 @@ pub async fn start_local_claude_server_cmd(
 
         if !is_port_available(port) {
             let all_envs = storage.get_all_environments().map_err(|e| e.to_string())?;
-            let allocation = allocate_ports(&all_envs)?;
+            let allocation = allocate_ports(&all_envs, &[])?;
             let new_port = allocation.claude_port;
             warn!(
                 environment_id = %environment_id,
@@ -477,10 +483,12 @@ pub async fn start_local_claude_server_cmd(
     }
 
     // Start the server
+    let bind_host = load_native_server_host()?;
     let result = start_local_claude_bridge(
         &environment_id,
         worktree_path,
         port,
+        &bind_host,
         &bridge_path,
         bundled_bun_path.as_deref(),
     )
@@ -905,7 +913,7 @@ pub async fn start_local_codex_server_cmd(
 
         if !is_port_available(port) {
             let all_envs = storage.get_all_environments().map_err(|e| e.to_string())?;
-            let allocation = allocate_ports(&all_envs)?;
+            let allocation = allocate_ports(&all_envs, &[])?;
             let new_port = allocation.codex_port;
             warn!(
                 environment_id = %environment_id,
@@ -930,7 +938,7 @@ pub async fn start_local_codex_server_cmd(
     debug!(environment_id = %environment_id, bridge_path = %bridge_path, "Resolved codex-bridge path");
     let raw_log_dir = if load_codex_bridge_raw_event_logging()? {
         Some(
-            crate::log_dir_path()
+            crate::logging::log_dir_path()
                 .join("codex-raw")
                 .to_string_lossy()
                 .to_string(),
@@ -949,10 +957,12 @@ pub async fn start_local_codex_server_cmd(
         debug!(environment_id = %environment_id, codex_path = %codex_path, "Resolved bundled codex path");
     }
 
+    let bind_host = load_native_server_host()?;
     let result = start_local_codex_bridge(
         &environment_id,
         worktree_path,
         port,
+        &bind_host,
         &bridge_path,
         bundled_bun_path.as_deref(),
         bundled_codex_path.as_deref(),
@@ -1040,3 +1050,514 @@ pub async fn get_local_codex_server_status(
 
     Ok(status.into())
 }
+
+/// How long `ping_local_servers` waits for each health endpoint
+const PING_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Agent server of a local environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LocalServerKind {
+    Opencode,
+    Claude,
+    Codex,
+}
+
+impl From<LocalServerKind> for ProcessType {
+    fn from(kind: LocalServerKind) -> Self {
+        match kind {
+            LocalServerKind::Opencode => ProcessType::OpenCode,
+            LocalServerKind::Claude => ProcessType::ClaudeBridge,
+            LocalServerKind::Codex => ProcessType::CodexBridge,
+        }
+    }
+}
+
+/// Whether one local agent server answered its health check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalServerPing {
+    pub server: LocalServerKind,
+    pub port: u16,
+    pub reachable: bool,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Probe the health endpoint of each agent server with an allocated port,
+/// so the UI can show whether servers actually respond rather than trusting
+/// the stored PIDs
+#[tauri::command]
+pub async fn ping_local_servers(environment_id: String) -> Result<Vec<LocalServerPing>, String> {
+    let storage = get_storage().map_err(|e| e.to_string())?;
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+
+    if !environment.is_local() {
+        return Err("Only local environments run agent servers on the host".to_string());
+    }
+
+    let servers = [
+        (LocalServerKind::Opencode, environment.local_opencode_port),
+        (LocalServerKind::Claude, environment.local_claude_port),
+        (LocalServerKind::Codex, environment.local_codex_port),
+    ];
+    let pings = servers
+        .into_iter()
+        .filter_map(|(server, port)| port.map(|port| (server, port)))
+        .map(|(server, port)| async move {
+            let result = probe_server_health(port, PING_TIMEOUT).await;
+            LocalServerPing {
+                server,
+                port,
+                reachable: result.is_ok(),
+                latency_ms: result.as_ref().ok().map(|d| d.as_millis() as u64),
+                error: result.err(),
+            }
+        });
+    let pings = futures::future::join_all(pings).await;
+
+    debug!(
+        environment_id = %environment_id,
+        reachable = pings.iter().filter(|p| p.reachable).count(),
+        total = pings.len(),
+        "Pinged local servers"
+    );
+    Ok(pings)
+}
+
+/// Restart one agent server of a local environment on its allocated port,
+/// leaving the other agent servers running
+async fn restart_local_server(
+    app_handle: tauri::AppHandle,
+    environment_id: String,
+    kind: LocalServerKind,
+) -> Result<LocalServerStatusResult, String> {
+    debug!(environment_id = %environment_id, server = ?kind, "Restarting local server");
+
+    let storage = get_storage().map_err(|e| e.to_string())?;
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+
+    if environment.is_containerized() {
+        return Err("Cannot restart local server for containerized environment".to_string());
+    }
+
+    // A healthy server left over from a previous app session is not tracked by
+    // the process manager; adopt it so the stop below kills it rather than the
+    // start path simply reusing it
+    let manager = get_process_manager();
+    let process_type = ProcessType::from(kind);
+    let (port, pid) = match kind {
+        LocalServerKind::Opencode => (environment.local_opencode_port, environment.opencode_pid),
+        LocalServerKind::Claude => (environment.local_claude_port, environment.claude_bridge_pid),
+        LocalServerKind::Codex => (environment.local_codex_port, environment.codex_bridge_pid),
+    };
+    if let Some(pid) = pid {
+        if !manager.is_running(&environment_id, process_type).await {
+            let status = match kind {
+                LocalServerKind::Opencode => {
+                    get_local_opencode_status(&environment_id, port, Some(pid)).await
+                }
+                LocalServerKind::Claude => {
+                    get_local_claude_status(&environment_id, port, Some(pid)).await
+                }
+                LocalServerKind::Codex => {
+                    get_local_codex_status(&environment_id, port, Some(pid)).await
+                }
+            };
+            if status.running {
+                manager
+                    .recover_from_pid(&environment_id, process_type, pid)
+                    .await;
+            }
+        }
+    }
+
+    match kind {
+        LocalServerKind::Opencode => {
+            stop_local_opencode_server_cmd(environment_id.clone()).await?;
+            start_local_opencode_server_cmd(app_handle, environment_id.clone()).await?;
+        }
+        LocalServerKind::Claude => {
+            stop_local_claude_server_cmd(environment_id.clone()).await?;
+            start_local_claude_server_cmd(app_handle, environment_id.clone()).await?;
+        }
+        LocalServerKind::Codex => {
+            stop_local_codex_server_cmd(environment_id.clone()).await?;
+            start_local_codex_server_cmd(app_handle, environment_id.clone()).await?;
+        }
+    }
+
+    info!(environment_id = %environment_id, server = ?kind, "Local server restarted");
+
+    match kind {
+        LocalServerKind::Opencode => get_local_opencode_server_status(environment_id).await,
+        LocalServerKind::Claude => get_local_claude_server_status(environment_id).await,
+        LocalServerKind::Codex => get_local_codex_server_status(environment_id).await,
+    }
+}
+
+/// Restart only the OpenCode server of a local environment on its allocated
+/// port, leaving the other agent servers running
+#[tauri::command]
+pub async fn restart_local_opencode(
+    app_handle: tauri::AppHandle,
+    environment_id: String,
+) -> Result<LocalServerStatusResult, String> {
+    restart_local_server(app_handle, environment_id, LocalServerKind::Opencode).await
+}
+
+/// Restart only the Claude-bridge server of a local environment on its
+/// allocated port, leaving the other agent servers running
+#[tauri::command]
+pub async fn restart_local_claude_bridge(
+    app_handle: tauri::AppHandle,
+    environment_id: String,
+) -> Result<LocalServerStatusResult, String> {
+    restart_local_server(app_handle, environment_id, LocalServerKind::Claude).await
+}
+
+/// Payload for local server log events
+#[derive(Clone, Serialize)]
+pub struct LocalServerLogPayload {
+    pub environment_id: String,
+    pub server: LocalServerKind,
+    pub text: String,
+}
+
+/// Get the log of a local agent server (for debugging).
+/// `since` skips the first that many bytes, `tail` keeps the last that many lines.
+#[tauri::command]
+pub async fn get_local_server_log(
+    environment_id: String,
+    which: LocalServerKind,
+    tail: Option<usize>,
+    since: Option<u64>,
+) -> Result<String, String> {
+    debug!(environment_id = %environment_id, server = ?which, "Getting local server log");
+
+    read_server_log(&server_log_path(&environment_id, which.into()), tail, since)
+}
+
+/// Log streams in progress, keyed by environment and server. Each entry holds
+/// a stream ID (so a finished stream can't unregister a newer one) and the
+/// abort handle of its task.
+type LogStreams = HashMap<(String, ProcessType), (u64, tokio::task::AbortHandle)>;
+
+static LOG_STREAMS: OnceLock<StdMutex<LogStreams>> = OnceLock::new();
+static NEXT_LOG_STREAM_ID: AtomicU64 = AtomicU64::new(0);
+
+fn log_streams() -> std::sync::MutexGuard<'static, LogStreams> {
+    LOG_STREAMS
+        .get_or_init(|| StdMutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Start streaming a local agent server's log to the frontend via events.
+/// Emits "local-server-log" events with LocalServerLogPayload until the
+/// server stops or `stop_local_server_log_stream` is called. A server whose
+/// log is already streaming isn't streamed twice.
+#[tauri::command]
+pub async fn stream_local_server_log(
+    app_handle: tauri::AppHandle,
+    environment_id: String,
+    which: LocalServerKind,
+) -> Result<(), String> {
+    use tauri::Emitter;
+
+    debug!(environment_id = %environment_id, server = ?which, "Starting local server log stream");
+
+    let process_type = ProcessType::from(which);
+    let manager = get_process_manager();
+    if !manager.is_running(&environment_id, process_type).await {
+        return Err(format!("Local {} server is not running", process_type));
+    }
+
+    let key = (environment_id.clone(), process_type);
+    let mut streams = log_streams();
+    if streams.contains_key(&key) {
+        debug!(environment_id = %environment_id, server = ?which, "Local server log already streaming");
+        return Ok(());
+    }
+    let stream_id = NEXT_LOG_STREAM_ID.fetch_add(1, Ordering::Relaxed);
+
+    let mut rx = follow_server_log(server_log_path(&environment_id, process_type));
+
+    // Spawn a task to receive log output and emit events, checking
+    // periodically whether the server is still up
+    let task_key = key.clone();
+    let task = tokio::spawn(async move {
+        let mut liveness = tokio::time::interval(Duration::from_secs(5));
+        loop {
+            tokio::select! {
+                text = rx.recv() => {
+                    let Some(text) = text else { break };
+                    let payload = LocalServerLogPayload {
+                        environment_id: environment_id.clone(),
+                        server: which,
+                        text,
+                    };
+                    if let Err(e) = app_handle.emit("local-server-log", payload) {
+                        warn!(error = %e, "Failed to emit local server log event");
+                        break;
+                    }
+                }
+                _ = liveness.tick() => {
+                    if !manager.is_running(&environment_id, process_type).await {
+                        break;
+                    }
+                }
+            }
+        }
+        let mut streams = log_streams();
+        if streams
+            .get(&task_key)
+            .is_some_and(|(id, _)| *id == stream_id)
+        {
+            streams.remove(&task_key);
+        }
+        debug!(environment_id = %environment_id, server = ?which, "Local server log stream ended");
+    });
+    streams.insert(key, (stream_id, task.abort_handle()));
+
+    Ok(())
+}
+
+/// Stop streaming a local agent server's log
+#[tauri::command]
+pub async fn stop_local_server_log_stream(
+    environment_id: String,
+    which: LocalServerKind,
+) -> Result<(), String> {
+    let key = (environment_id, ProcessType::from(which));
+    if let Some((_, handle)) = log_streams().remove(&key) {
+        debug!(environment_id = %key.0, server = ?which, "Stopping local server log stream");
+        handle.abort();
+    }
+    Ok(())
+}
+
+/// Stop streaming the logs of every local server of an environment
+pub(crate) fn stop_local_server_log_streams_for_environment(environment_id: &str) {
+    log_streams().retain(|(stream_environment_id, _), (_, handle)| {
+        if stream_environment_id != environment_id {
+            return true;
+        }
+        handle.abort();
+        false
+    });
+}
+
+/// Timeout for requests forwarded by `proxy_agent_request`
+const PROXY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Requests `proxy_agent_request` forwards to each server, as (method, path)
+/// where `*` matches one path segment. Event streams are not proxied.
+const OPENCODE_PROXY_ROUTES: &[(&str, &str)] = &[
+    ("GET", "/global/health"),
+    ("GET", "/session"),
+    ("POST", "/session"),
+    ("DELETE", "/session/*"),
+    ("GET", "/session/*/message"),
+    ("POST", "/session/*/prompt_async"),
+    ("POST", "/session/*/abort"),
+    ("GET", "/config/providers"),
+    ("GET", "/provider"),
+    ("GET", "/command"),
+    ("GET", "/permission"),
+    ("POST", "/permission/*/reply"),
+    ("GET", "/question"),
+    ("POST", "/question/*/reply"),
+    ("POST", "/question/*/reject"),
+];
+const CLAUDE_PROXY_ROUTES: &[(&str, &str)] = &[
+    ("GET", "/global/health"),
+    ("GET", "/config/models"),
+    ("GET", "/mcp/servers"),
+    ("GET", "/plugins"),
+    ("GET", "/plugins/commands"),
+    ("POST", "/session/create"),
+    ("GET", "/session/list"),
+    ("GET", "/session/*"),
+    ("DELETE", "/session/*"),
+    ("GET", "/session/*/init"),
+    ("GET", "/session/*/messages"),
+    ("POST", "/session/*/prompt"),
+    ("POST", "/session/*/abort"),
+    ("GET", "/session/*/questions"),
+    ("POST", "/session/*/questions/*/answer"),
+    ("GET", "/session/*/plan-approvals"),
+    ("POST", "/session/*/plan-approvals/*/respond"),
+];
+const CODEX_PROXY_ROUTES: &[(&str, &str)] = &[
+    ("GET", "/global/health"),
+    ("GET", "/global/models"),
+    ("GET", "/global/slash-commands"),
+    ("GET", "/session/list"),
+    ("POST", "/session/create"),
+    ("POST", "/session/resume"),
+    ("DELETE", "/session/*"),
+    ("POST", "/session/*/config"),
+    ("GET", "/session/*/messages"),
+    ("GET", "/session/*/status"),
+    ("POST", "/session/*/prompt"),
+    ("POST", "/session/*/abort"),
+];
+
+/// Response of an agent server to a proxied request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentProxyResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: String,
+}
+
+/// Whether a path segment can be forwarded: IDs and route names only, so
+/// `..` or encoded characters can't reach other endpoints
+fn is_valid_path_segment(segment: &str) -> bool {
+    !segment.is_empty()
+        && !segment.chars().all(|c| c == '.')
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Whether `method` and `path` (optionally with a query string) match one of
+/// the routes allowed for `server`
+fn is_allowed_proxy_request(server: LocalServerKind, method: &str, path: &str) -> bool {
+    let routes = match server {
+        LocalServerKind::Opencode => OPENCODE_PROXY_ROUTES,
+        LocalServerKind::Claude => CLAUDE_PROXY_ROUTES,
+        LocalServerKind::Codex => CODEX_PROXY_ROUTES,
+    };
+    let path = path.split_once('?').map_or(path, |(path, _)| path);
+    let Some(segments) = path
+        .strip_prefix('/')
+        .map(|p| p.split('/').collect::<Vec<_>>())
+    else {
+        return false;
+    };
+    if !segments.iter().all(|s| is_valid_path_segment(s)) {
+        return false;
+    }
+
+    routes.iter().any(|(route_method, route)| {
+        let route_segments: Vec<&str> = route[1..].split('/').collect();
+        route_method.eq_ignore_ascii_case(method)
+            && route_segments.len() == segments.len()
+            && route_segments
+                .iter()
+                .zip(&segments)
+                .all(|(route, segment)| *route == "*" || route == segment)
+    })
+}
+
+/// Forward an HTTP request to one of a local environment's agent servers on
+/// its allocated port, so the frontend doesn't depend on reaching the port
+/// directly. Only allowlisted methods and paths are forwarded.
+#[tauri::command]
+pub async fn proxy_agent_request(
+    environment_id: String,
+    which: LocalServerKind,
+    method: String,
+    path: String,
+    body: Option<serde_json::Value>,
+) -> Result<AgentProxyResponse, String> {
+    if !is_allowed_proxy_request(which, &method, &path) {
+        return Err(format!(
+            "{} {} is not an allowed {:?} server request",
+            method, path, which
+        ));
+    }
+
+    let storage = get_storage().map_err(|e| e.to_string())?;
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+
+    if !environment.is_local() {
+        return Err("Only local environments run agent servers on the host".to_string());
+    }
+    let port = match which {
+        LocalServerKind::Opencode => environment.local_opencode_port,
+        LocalServerKind::Claude => environment.local_claude_port,
+        LocalServerKind::Codex => environment.local_codex_port,
+    }
+    .ok_or_else(|| format!("No port allocated for the local {:?} server", which))?;
+
+    let method = reqwest::Method::from_bytes(method.to_ascii_uppercase().as_bytes())
+        .map_err(|e| format!("Invalid method: {}", e))?;
+    let client = reqwest::Client::builder()
+        .timeout(PROXY_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let mut request = client.request(method, format!("http://127.0.0.1:{}{}", port, path));
+    if let Some(body) = body {
+        request = request.json(&body);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Request to local {:?} server failed: {}", which, e))?;
+    let status = response.status().as_u16();
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+
+    debug!(environment_id = %environment_id, server = ?which, path = %path, status = status, "Proxied agent request");
+    Ok(AgentProxyResponse {
+        status,
+        content_type,
+        body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_allowed_proxy_request() {
+        use LocalServerKind::*;
+
+        assert!(is_allowed_proxy_request(Claude, "GET", "/global/health"));
+        assert!(is_allowed_proxy_request(
+            Claude,
+            "post",
+            "/session/abc-123/prompt"
+        ));
+        assert!(is_allowed_proxy_request(
+            Opencode,
+            "GET",
+            "/session/ses_1/message?limit=50"
+        ));
+        assert!(is_allowed_proxy_request(Codex, "DELETE", "/session/abc"));
+
+        assert!(!is_allowed_proxy_request(Codex, "GET", "/event/subscribe"));
+        assert!(!is_allowed_proxy_request(
+            Claude,
+            "DELETE",
+            "/session/list/x"
+        ));
+        assert!(!is_allowed_proxy_request(Opencode, "PUT", "/session"));
+        assert!(!is_allowed_proxy_request(Claude, "GET", "/session/../mcp"));
+        assert!(!is_allowed_proxy_request(Claude, "GET", "/session/%2e%2e"));
+        assert!(!is_allowed_proxy_request(Claude, "GET", "global/health"));
+        assert!(!is_allowed_proxy_request(Claude, "GET", "/session//init"));
+    }
+}
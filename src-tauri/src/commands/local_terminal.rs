@@ -5,6 +5,8 @@
 
 use crate::commands::claude_tmux::{bundled_resource_dir_candidates, find_bundled_dir_containing};
 use crate::local::get_local_terminal_manager;
+use crate::local::project_config::read_project_config;
+use crate::models::Environment;
 use crate::storage::get_storage;
 use tauri::{AppHandle, Emitter, Runtime};
 use tracing::{debug, info, instrument, warn};
@@ -70,6 +72,104 @@ pub async fn create_local_terminal_session(
     Ok(session_id)
 }
 
+/// Create a plain host shell session not tied to any environment or
+/// container. Starts in `cwd`, or the home directory when omitted. Use the
+/// other `local_terminal_*` commands to start, write to, resize and close it.
+#[tauri::command]
+#[instrument(skip(app), fields(cwd = ?cwd, cols, rows))]
+pub async fn create_host_terminal(
+    app: AppHandle,
+    cwd: Option<String>,
+    cols: u16,
+    rows: u16,
+) -> Result<String, String> {
+    debug!("Creating host terminal session");
+
+    let manager = get_local_terminal_manager()
+        .ok_or_else(|| "Local terminal manager not initialized".to_string())?;
+
+    let session_id = manager
+        .create_host_session(cwd.as_deref(), cols, rows, resolve_bundled_bin_dir(&app))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    info!(session_id = %session_id, "Host terminal session created");
+    Ok(session_id)
+}
+
+/// Create a host shell in a local environment's worktree, with the same git
+/// variables container environments get plus the `env` of the worktree's
+/// `orkestrator-ai.json`. The returned session id works with the
+/// `environment_terminal` commands like any other local session.
+#[tauri::command]
+#[instrument(skip(app), fields(environment_id = %environment_id, cols, rows))]
+pub async fn create_worktree_terminal(
+    app: AppHandle,
+    environment_id: String,
+    cols: u16,
+    rows: u16,
+) -> Result<String, String> {
+    debug!("Creating worktree terminal session");
+
+    let manager = get_local_terminal_manager()
+        .ok_or_else(|| "Local terminal manager not initialized".to_string())?;
+
+    let storage = get_storage().map_err(|e| e.to_string())?;
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+    if !environment.is_local() {
+        return Err("Worktree terminals are only available for local environments".to_string());
+    }
+    let worktree_path = environment
+        .worktree_path
+        .clone()
+        .ok_or_else(|| "Environment has no worktree path".to_string())?;
+
+    let project_env = match read_project_config(&worktree_path).await {
+        Ok(config) => config.map(|config| config.env).unwrap_or_default(),
+        Err(e) => {
+            warn!(error = %e, "Ignoring invalid orkestrator-ai.json for worktree terminal");
+            Default::default()
+        }
+    };
+    let env_vars = worktree_env_vars(&environment, &worktree_path, project_env);
+
+    let session_id = manager
+        .create_worktree_session(
+            &environment_id,
+            &worktree_path,
+            env_vars,
+            cols,
+            rows,
+            resolve_bundled_bin_dir(&app),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    info!(session_id = %session_id, environment_id = %environment_id, "Worktree terminal session created");
+    Ok(session_id)
+}
+
+/// Environment variables of a worktree shell. Project variables come last so
+/// they can override the defaults.
+fn worktree_env_vars(
+    environment: &Environment,
+    worktree_path: &str,
+    project_env: std::collections::BTreeMap<String, String>,
+) -> Vec<(String, String)> {
+    let mut env_vars = vec![
+        ("GIT_BRANCH".to_string(), environment.branch.clone()),
+        ("WORKSPACE_DIR".to_string(), worktree_path.to_string()),
+    ];
+    if let Some(base_ref) = &environment.base_ref {
+        env_vars.push(("GIT_BASE_BRANCH".to_string(), base_ref.clone()));
+    }
+    env_vars.extend(project_env);
+    env_vars
+}
+
 fn resolve_bundled_bin_dir(app_handle: &tauri::AppHandle) -> Option<String> {
     let candidates = bundled_resource_dir_candidates(app_handle);
     find_bundled_dir_containing(&candidates, "claude").map(|p| p.to_string_lossy().into_owned())
@@ -135,3 +235,29 @@ pub fn close_local_terminal_session(session_id: String) -> Result<(), String> {
         .close_session(&session_id)
         .map_err(|e| e.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn worktree_env_vars_include_git_context_and_project_env() {
+        let mut environment = Environment::new("project-1".to_string());
+        environment.branch = "feature/x".to_string();
+        environment.base_ref = Some("develop".to_string());
+        let project_env = [("NODE_ENV".to_string(), "development".to_string())]
+            .into_iter()
+            .collect();
+
+        let env_vars = worktree_env_vars(&environment, "/work/tree", project_env);
+        assert_eq!(
+            env_vars,
+            vec![
+                ("GIT_BRANCH".to_string(), "feature/x".to_string()),
+                ("WORKSPACE_DIR".to_string(), "/work/tree".to_string()),
+                ("GIT_BASE_BRANCH".to_string(), "develop".to_string()),
+                ("NODE_ENV".to_string(), "development".to_string()),
+            ]
+        );
+    }
+}
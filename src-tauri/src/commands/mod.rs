@@ -8,8 +8,10 @@ mod claude_tmux;
 mod codex;
 mod config;
 pub mod credentials;
+mod diagnostics;
 mod docker;
 mod editor;
+mod environment_terminal;
 mod environments;
 mod files;
 mod github;
@@ -21,6 +23,7 @@ mod opencode;
 mod projects;
 mod sessions;
 mod terminal;
+mod window;
 
 pub use claude::*;
 pub use claude_cli::*;
@@ -28,9 +31,14 @@ pub use claude_state::*;
 pub use claude_tmux::*;
 pub use codex::*;
 pub use config::*;
-pub use credentials::{get_credential_status, has_claude_credentials};
+pub use credentials::{
+    get_credential_status, has_claude_credentials, refresh_container_credentials,
+    refresh_credentials,
+};
+pub use diagnostics::*;
 pub use docker::*;
 pub use editor::*;
+pub use environment_terminal::*;
 pub use environments::*;
 pub use files::*;
 pub use github::*;
@@ -42,6 +50,7 @@ pub use opencode::*;
 pub use projects::*;
 pub use sessions::*;
 pub use terminal::*;
+pub use window::*;
 
 /// Load the runtime setting that enables Codex raw event logging. Shared by
 /// both the container and local server start commands.
@@ -51,8 +60,38 @@ fn load_codex_bridge_raw_event_logging() -> Result<bool, String> {
     Ok(config.global.experimental_codex_raw_event_logging)
 }
 
-/// Simple greeting command for testing
-#[tauri::command]
-pub fn greet(name: &str) -> String {
-    format!("Hello, {}! You've been greeted from Rust!", name)
+/// Load the address local native-mode servers bind to, falling back to
+/// loopback when the setting is invalid
+fn load_native_server_host() -> Result<String, String> {
+    let storage = crate::storage::get_storage().map_err(|e| e.to_string())?;
+    let config = storage.load_config().map_err(|e| e.to_string())?;
+    Ok(crate::local::servers::resolve_bind_host(
+        &config.global.native_server_host,
+    ))
+}
+
+/// Shell command printing a server log file inside a container. `since` skips
+/// the first that many bytes and `tail` keeps only the last that many lines.
+fn server_log_command(path: &str, tail: Option<usize>, since: Option<u64>) -> String {
+    let mut command = match since {
+        Some(offset) => format!("tail -c +{} {}", offset + 1, path),
+        None => format!("cat {}", path),
+    };
+    if let Some(count) = tail {
+        command.push_str(&format!(" | tail -n {}", count));
+    }
+    command
+}
+
+/// Refuse a write when the environment it targets is in read-only (review) mode.
+/// `targets` selects the environment the write is aimed at.
+fn ensure_environment_writable(
+    targets: impl Fn(&crate::models::Environment) -> bool,
+) -> Result<(), String> {
+    let storage = crate::storage::get_storage().map_err(|e| e.to_string())?;
+    let environments = storage.load_environments().map_err(|e| e.to_string())?;
+    if environments.iter().any(|env| env.read_only && targets(env)) {
+        return Err("Environment is read-only".to_string());
+    }
+    Ok(())
 }
@@ -2,7 +2,7 @@
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::net::ToSocketAddrs;
+use std::net::{Ipv4Addr, ToSocketAddrs};
 
 /// Result of testing a single domain
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,8 +29,79 @@ fn is_valid_domain_format(domain: &str) -> bool {
     domain_regex.is_match(domain)
 }
 
+/// Check whether an entry is a raw IPv4 address or an IPv4 CIDR range.
+///
+/// The container firewall uses an `inet` ipset, so only IPv4 is accepted.
+/// Such entries are added to the ipset as-is without DNS resolution.
+pub(crate) fn is_ip_or_cidr(entry: &str) -> bool {
+    let (addr, prefix) = match entry.split_once('/') {
+        Some((addr, prefix)) => (addr, Some(prefix)),
+        None => (entry, None),
+    };
+
+    if addr.parse::<Ipv4Addr>().is_err() {
+        return false;
+    }
+
+    match prefix {
+        None => true,
+        Some(prefix) => {
+            !prefix.is_empty()
+                && prefix.chars().all(|c| c.is_ascii_digit())
+                && prefix.parse::<u8>().is_ok_and(|p| p <= 32)
+        }
+    }
+}
+
+/// Check whether an entry is acceptable for an allowed-domains list
+/// (a domain name, a raw IPv4 address, or an IPv4 CIDR range).
+pub(crate) fn is_valid_firewall_entry(entry: &str) -> bool {
+    is_valid_domain_format(entry) || is_ip_or_cidr(entry)
+}
+
+/// Validate a list of allowed-domains entries, returning an error that names
+/// every invalid entry. Blank entries are ignored.
+pub(crate) fn validate_firewall_entries(entries: &[String]) -> Result<(), String> {
+    let invalid: Vec<&str> = entries
+        .iter()
+        .map(|e| e.trim())
+        .filter(|e| !e.is_empty() && !is_valid_firewall_entry(e))
+        .collect();
+
+    if invalid.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid domain, IP, or CIDR entries: {}",
+            invalid.join(", ")
+        ))
+    }
+}
+
+/// Parse a shared allowlist file: either a JSON array of strings or one entry
+/// per line, where blank lines and `#` comments are ignored
+pub(crate) fn parse_domain_list(contents: &str) -> Result<Vec<String>, String> {
+    let trimmed = contents.trim_start();
+    if trimmed.starts_with('[') {
+        let entries: Vec<String> = serde_json::from_str(trimmed)
+            .map_err(|e| format!("Invalid JSON domain list: {}", e))?;
+        return Ok(entries
+            .into_iter()
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty())
+            .collect());
+    }
+
+    Ok(contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
 /// Resolve a domain to IP addresses
-fn resolve_domain(domain: &str) -> Result<Vec<String>, String> {
+pub(crate) fn resolve_domain(domain: &str) -> Result<Vec<String>, String> {
     // Use port 80 for resolution (the port doesn't matter, we just need the IPs)
     let addr = format!("{}:80", domain);
 
@@ -60,6 +131,19 @@ pub async fn test_domain_resolution(domains: Vec<String>) -> Result<Vec<DomainTe
             continue;
         }
 
+        // Raw IPs and CIDR ranges are passed to the firewall as-is, so there
+        // is nothing to resolve
+        if is_ip_or_cidr(&domain) {
+            results.push(DomainTestResult {
+                ips: vec![domain.clone()],
+                domain,
+                valid: true,
+                resolvable: Some(true),
+                error: None,
+            });
+            continue;
+        }
+
         // First check format validity
         let valid = is_valid_domain_format(&domain);
 
@@ -101,6 +185,7 @@ pub async fn test_domain_resolution(domains: Vec<String>) -> Result<Vec<DomainTe
 }
 
 /// Validate domain format only (no DNS resolution)
+/// Raw IPv4 addresses and CIDR ranges are accepted as valid entries
 /// Returns a list of invalid domains with error messages
 #[tauri::command]
 pub fn validate_domains(domains: Vec<String>) -> Vec<DomainTestResult> {
@@ -109,7 +194,7 @@ pub fn validate_domains(domains: Vec<String>) -> Vec<DomainTestResult> {
         .filter(|d| !d.trim().is_empty())
         .map(|domain| {
             let domain = domain.trim().to_string();
-            let valid = is_valid_domain_format(&domain);
+            let valid = is_valid_firewall_entry(&domain);
 
             DomainTestResult {
                 domain,
@@ -119,7 +204,7 @@ pub fn validate_domains(domains: Vec<String>) -> Vec<DomainTestResult> {
                 error: if valid {
                     None
                 } else {
-                    Some("Invalid domain format".to_string())
+                    Some("Invalid domain, IP, or CIDR format".to_string())
                 },
             }
         })
@@ -151,4 +236,58 @@ mod tests {
         assert!(!is_valid_domain_format("example.com/path")); // Has path
         assert!(!is_valid_domain_format("*.example.com")); // Wildcard
     }
+
+    #[test]
+    fn test_ip_and_cidr_entries() {
+        assert!(is_ip_or_cidr("10.0.0.5"));
+        assert!(is_ip_or_cidr("192.168.0.0/16"));
+        assert!(is_ip_or_cidr("0.0.0.0/0"));
+        assert!(is_ip_or_cidr("172.16.4.1/32"));
+        assert!(!is_ip_or_cidr("10.0.0.256"));
+        assert!(!is_ip_or_cidr("10.0.0.0/33"));
+        assert!(!is_ip_or_cidr("10.0.0.0/"));
+        assert!(!is_ip_or_cidr("10.0.0.0/+8"));
+        assert!(!is_ip_or_cidr("::1"));
+        assert!(!is_ip_or_cidr("example.com"));
+    }
+
+    #[test]
+    fn test_validate_firewall_entries() {
+        let entries = vec![
+            "example.com".to_string(),
+            "10.1.2.3".to_string(),
+            "10.0.0.0/8".to_string(),
+            "  ".to_string(),
+        ];
+        assert!(validate_firewall_entries(&entries).is_ok());
+
+        let entries = vec!["example.com".to_string(), "10.0.0.0/40".to_string()];
+        let err = validate_firewall_entries(&entries).unwrap_err();
+        assert!(err.contains("10.0.0.0/40"));
+        assert!(!err.contains("example.com"));
+    }
+
+    #[test]
+    fn test_validate_domains_accepts_ip_entries() {
+        let results = validate_domains(vec!["10.0.0.1".to_string(), "bad_domain".to_string()]);
+        assert!(results[0].valid);
+        assert!(!results[1].valid);
+    }
+
+    #[test]
+    fn test_parse_domain_list_formats() {
+        let lines = "# team allowlist\ngithub.com\n\n  api.example.com  # staging\n10.0.0.0/8\n";
+        assert_eq!(
+            parse_domain_list(lines).unwrap(),
+            vec!["github.com", "api.example.com", "10.0.0.0/8"]
+        );
+
+        let json = r#"["github.com", " ", "registry.npmjs.org"]"#;
+        assert_eq!(
+            parse_domain_list(json).unwrap(),
+            vec!["github.com", "registry.npmjs.org"]
+        );
+
+        assert!(parse_domain_list("[\"unterminated").is_err());
+    }
 }
@@ -17,7 +17,7 @@ const SERVER_STARTUP_POLL_INTERVAL_MS: u64 = 200;
 
 fn build_opencode_server_start_command() -> &'static str {
     r#"
-        cd /workspace
+        cd "${WORKSPACE_DIR:-/workspace}"
         rm -f /tmp/opencode-serve.log
         source /etc/profile 2>/dev/null || true
         source ~/.profile 2>/dev/null || true
@@ -320,10 +320,15 @@ pub async fn stop_opencode_server(container_id: String) -> Result<(), String> {
     Ok(())
 }
 
-/// Get the OpenCode server log from a container (for debugging)
+/// Get the OpenCode server log from a container (for debugging).
+/// `since` skips the first that many bytes, `tail` keeps the last that many lines.
 #[tauri::command]
-pub async fn get_opencode_server_log(container_id: String) -> Result<String, String> {
-    debug!(container_id = %container_id, "Getting OpenCode server log");
+pub async fn get_opencode_server_log(
+    container_id: String,
+    tail: Option<usize>,
+    since: Option<u64>,
+) -> Result<String, String> {
+    debug!(container_id = %container_id, tail = ?tail, since = ?since, "Getting OpenCode server log");
 
     let client = docker::client::get_docker_client().map_err(|e| e.to_string())?;
 
@@ -338,8 +343,9 @@ pub async fn get_opencode_server_log(container_id: String) -> Result<String, Str
     }
 
     // Read the log file
+    let command = super::server_log_command("/tmp/opencode-serve.log", tail, since);
     let log_content = client
-        .exec_in_container(&container_id, vec!["cat", "/tmp/opencode-serve.log"], None)
+        .exec_in_container(&container_id, vec!["sh", "-c", &command], None)
         .await
         .map_err(|e| format!("Failed to read log: {}", e))?;
 
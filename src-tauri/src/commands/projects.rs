@@ -1,7 +1,16 @@
 // Project management Tauri commands
 
-use crate::models::Project;
+use crate::docker::compose::{detect_compose_project, ComposeProject};
+use crate::local::project_config::{
+    parse_project_config, ProjectConfigError, ProjectConfigValidation, PROJECT_CONFIG_FILE_NAME,
+};
+use crate::models::{Project, ProjectGroup};
 use crate::storage::{get_storage, StorageError};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// How long `git ls-remote` may take before branch validation gives up
+const LS_REMOTE_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Convert storage errors to string for Tauri
 fn storage_error_to_string(err: StorageError) -> String {
@@ -73,6 +82,77 @@ pub async fn reorder_projects(project_ids: Vec<String>) -> Result<Vec<Project>,
         .map_err(storage_error_to_string)
 }
 
+/// Trim a group name, rejecting blank names
+fn normalize_group_name(name: &str) -> Result<&str, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Group name cannot be empty".to_string());
+    }
+    Ok(name)
+}
+
+/// Get all project groups by group ID
+#[tauri::command]
+pub async fn get_project_groups() -> Result<HashMap<String, ProjectGroup>, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    storage
+        .load_project_groups()
+        .map_err(storage_error_to_string)
+}
+
+/// Create a project group at the end of the sidebar, returning its ID
+#[tauri::command]
+pub async fn create_project_group(name: String) -> Result<String, String> {
+    let name = normalize_group_name(&name)?;
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    storage
+        .create_project_group(name)
+        .map_err(storage_error_to_string)
+}
+
+/// Rename a project group
+#[tauri::command]
+pub async fn rename_project_group(group_id: String, name: String) -> Result<ProjectGroup, String> {
+    let name = normalize_group_name(&name)?;
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    storage
+        .rename_project_group(&group_id, name)
+        .map_err(storage_error_to_string)
+}
+
+/// Delete a project group. Its projects are ungrouped, not removed; returns
+/// their IDs.
+#[tauri::command]
+pub async fn delete_project_group(group_id: String) -> Result<Vec<String>, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    storage
+        .delete_project_group(&group_id)
+        .map_err(storage_error_to_string)
+}
+
+/// Reorder project groups based on the provided array of group IDs
+#[tauri::command]
+pub async fn reorder_project_groups(
+    group_ids: Vec<String>,
+) -> Result<HashMap<String, ProjectGroup>, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    storage
+        .reorder_project_groups(&group_ids)
+        .map_err(storage_error_to_string)
+}
+
+/// Assign a project to a group, or ungroup it with None
+#[tauri::command]
+pub async fn set_project_group(
+    project_id: String,
+    group_id: Option<String>,
+) -> Result<Project, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    storage
+        .set_project_group(&project_id, group_id.as_deref())
+        .map_err(storage_error_to_string)
+}
+
 /// Validate a Git URL format
 #[tauri::command]
 pub fn validate_git_url(url: String) -> bool {
@@ -105,6 +185,123 @@ pub async fn get_git_remote_url(path: String) -> Result<Option<String>, String>
     }
 }
 
+/// Detect a Docker Compose file in a project's local path
+///
+/// Returns the declared services and their ports so the UI can warn that only
+/// a single container runs per environment and suggest a service/port to use.
+/// Returns `None` when the project has no local path or no compose file.
+#[tauri::command]
+pub async fn detect_compose(project_id: String) -> Result<Option<ComposeProject>, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    let project = storage
+        .get_project(&project_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+
+    let Some(local_path) = project.local_path.as_deref() else {
+        return Ok(None);
+    };
+
+    detect_compose_project(std::path::Path::new(local_path)).await
+}
+
+/// Check that the default and PR base branches of a project's repository
+/// config exist on its remote, so a misconfiguration is caught before it
+/// breaks PR creation. Returns the names of the missing branches.
+///
+/// Uses the `origin` remote of the local checkout when the project has one
+/// (so the user's git credentials apply), otherwise the project's git URL.
+#[tauri::command]
+pub async fn validate_repository_branches(project_id: String) -> Result<Vec<String>, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    let project = storage
+        .get_project(&project_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+    let repo_config = storage
+        .load_config()
+        .map_err(storage_error_to_string)?
+        .repositories
+        .remove(&project_id)
+        .unwrap_or_default();
+
+    let mut branches = vec![repo_config.default_branch.as_str()];
+    if repo_config.pr_base_branch != repo_config.default_branch {
+        branches.push(repo_config.pr_base_branch.as_str());
+    }
+
+    let mut command = tokio::process::Command::new("git");
+    command
+        .arg("ls-remote")
+        .arg("--heads")
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .kill_on_drop(true);
+    match project.local_path.as_deref() {
+        Some(local_path) => {
+            command.arg("origin").current_dir(local_path);
+        }
+        None => {
+            command.arg(&project.git_url);
+        }
+    }
+    command.args(&branches);
+
+    let output = tokio::time::timeout(LS_REMOTE_TIMEOUT, command.output())
+        .await
+        .map_err(|_| "Timed out listing remote branches".to_string())?
+        .map_err(|e| format!("Failed to execute git command: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to list remote branches: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let remote_heads = parse_ls_remote_heads(&String::from_utf8_lossy(&output.stdout));
+    Ok(branches
+        .into_iter()
+        .filter(|branch| !remote_heads.contains(*branch))
+        .map(String::from)
+        .collect())
+}
+
+/// Branch names in `git ls-remote --heads` output
+fn parse_ls_remote_heads(stdout: &str) -> HashSet<String> {
+    stdout
+        .lines()
+        .filter_map(|line| line.split('\t').nth(1))
+        .filter_map(|reference| reference.trim().strip_prefix("refs/heads/"))
+        .map(String::from)
+        .collect()
+}
+
+/// Lint an `orkestrator-ai.json` file against the project config schema
+///
+/// `path` may be the config file itself or the repository directory containing it.
+/// Malformed files are reported in the returned validation result; an error is
+/// only returned when no config file exists at `path`.
+#[tauri::command]
+pub async fn validate_orkestrator_config(path: String) -> Result<ProjectConfigValidation, String> {
+    let mut config_path = std::path::PathBuf::from(&path);
+    if config_path.is_dir() {
+        config_path.push(PROJECT_CONFIG_FILE_NAME);
+    }
+
+    let result = match tokio::fs::read_to_string(&config_path).await {
+        Ok(content) => parse_project_config(&content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(format!(
+                "No {} found at {}",
+                PROJECT_CONFIG_FILE_NAME,
+                config_path.display()
+            ));
+        }
+        Err(e) => Err(ProjectConfigError::from(e)),
+    };
+
+    Ok(result.into())
+}
+
 /// Check if a string is a valid Git URL
 fn is_valid_git_url(url: &str) -> bool {
     let url = url.trim();
@@ -127,7 +324,7 @@ fn is_valid_git_url(url: &str) -> bool {
 
 /// Convert SSH git URL to HTTPS format for token-based authentication
 /// Supports: git@host:user/repo.git -> https://host/user/repo.git
-fn convert_ssh_to_https(url: &str) -> String {
+pub(crate) fn convert_ssh_to_https(url: &str) -> String {
     let url = url.trim();
 
     // Already HTTPS - return as-is
@@ -249,6 +446,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_ls_remote_heads() {
+        let heads =
+            parse_ls_remote_heads("1a2b3c\trefs/heads/main\n4d5e6f\trefs/heads/feature/main\n\n");
+        assert!(heads.contains("main"));
+        assert!(heads.contains("feature/main"));
+        assert!(!heads.contains("develop"));
+        assert_eq!(heads.len(), 2);
+    }
+
     #[test]
     fn test_whitespace_handling() {
         assert_eq!(
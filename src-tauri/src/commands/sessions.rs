@@ -1,9 +1,12 @@
 // Session management Tauri commands
 // Commands for creating, updating, and querying terminal sessions
 
-use tracing::{debug, info};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tracing::{debug, info, warn};
 
-use crate::models::{Session, SessionStatus, SessionType};
+use super::environments::resolve_default_sessions;
+use crate::models::{is_valid_model_id, Session, SessionLaunchConfig, SessionStatus, SessionType};
 use crate::storage::{get_storage, StorageError};
 
 /// Convert storage errors to string for Tauri
@@ -11,14 +14,27 @@ fn storage_error_to_string(err: StorageError) -> String {
     err.to_string()
 }
 
-/// Create a new session for an environment
+/// Create a new session for an environment.
+///
+/// `launch_config` picks the agent, model and permission flags the session
+/// launches with; its session type replaces `session_type`.
 #[tauri::command]
 pub async fn create_session(
     environment_id: String,
     container_id: String,
     tab_id: String,
     session_type: SessionType,
+    launch_config: Option<SessionLaunchConfig>,
 ) -> Result<Session, String> {
+    if let Some(model) = launch_config.as_ref().and_then(|c| c.model.as_deref()) {
+        if !is_valid_model_id(model) {
+            return Err(format!("Invalid model: {:?}", model));
+        }
+    }
+    let session_type = launch_config
+        .as_ref()
+        .map_or(session_type, |c| c.session_type.clone());
+
     debug!(
         environment_id = %environment_id,
         container_id = %container_id,
@@ -29,7 +45,8 @@ pub async fn create_session(
 
     let storage = get_storage().map_err(storage_error_to_string)?;
 
-    let session = Session::new(environment_id, container_id, tab_id, session_type);
+    let mut session = Session::new(environment_id, container_id, tab_id, session_type);
+    session.launch_config = launch_config;
     let created = storage
         .add_session(session)
         .map_err(storage_error_to_string)?;
@@ -38,6 +55,66 @@ pub async fn create_session(
     Ok(created)
 }
 
+/// Command that launches the agent of a session created with a launch
+/// config, so reconnects start the same agent and model. None for sessions
+/// without one.
+#[tauri::command]
+pub async fn get_session_launch_command(session_id: String) -> Result<Option<String>, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    let session = storage
+        .get_session(&session_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+    Ok(session
+        .launch_config
+        .and_then(|config| config.launch_command()))
+}
+
+/// Create the session records of an environment's default sessions
+/// (environment override, then repository default) so the frontend can open
+/// a terminal for each. Does nothing if the environment already has sessions,
+/// so restarting an environment does not duplicate its tabs.
+#[tauri::command]
+pub async fn create_default_sessions(environment_id: String) -> Result<Vec<Session>, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    let environment = storage
+        .get_environment(&environment_id)
+        .map_err(storage_error_to_string)?
+        .ok_or_else(|| format!("Environment not found: {}", environment_id))?;
+
+    let existing = storage
+        .get_sessions_by_environment(&environment_id)
+        .map_err(storage_error_to_string)?;
+    if !existing.is_empty() {
+        debug!(environment_id = %environment_id, "Environment already has sessions, skipping defaults");
+        return Ok(Vec::new());
+    }
+
+    let config = storage.load_config().map_err(storage_error_to_string)?;
+    let session_types = resolve_default_sessions(&config, &environment);
+    let container_id = environment.container_id.unwrap_or_default();
+
+    let mut created = Vec::with_capacity(session_types.len());
+    for session_type in session_types {
+        let session = Session::new(
+            environment_id.clone(),
+            container_id.clone(),
+            uuid::Uuid::new_v4().to_string(),
+            session_type,
+        );
+        created.push(
+            storage
+                .add_session(session)
+                .map_err(storage_error_to_string)?,
+        );
+    }
+
+    if !created.is_empty() {
+        info!(environment_id = %environment_id, count = created.len(), "Default sessions created");
+    }
+    Ok(created)
+}
+
 /// Get all sessions for an environment
 #[tauri::command]
 pub async fn get_sessions_by_environment(environment_id: String) -> Result<Vec<Session>, String> {
@@ -107,17 +184,41 @@ pub async fn delete_session(session_id: String) -> Result<(), String> {
     Ok(())
 }
 
-/// Rename a session
+/// Payload of the "session-renamed" event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionRenamedPayload {
+    pub session_id: String,
+    pub environment_id: String,
+    /// New name, or None when cleared back to the default label
+    pub name: Option<String>,
+}
+
+/// Rename a session and notify every window via a "session-renamed" event.
+/// Pass None (or a blank name) to revert to the default label.
 #[tauri::command]
-pub async fn rename_session(session_id: String, name: Option<String>) -> Result<Session, String> {
+pub async fn rename_session(
+    app_handle: AppHandle,
+    session_id: String,
+    name: Option<String>,
+) -> Result<Session, String> {
     debug!(session_id = %session_id, name = ?name, "Renaming session");
 
     let storage = get_storage().map_err(storage_error_to_string)?;
     let updated = storage
-        .rename_session(&session_id, name.clone())
+        .rename_session(&session_id, name)
         .map_err(storage_error_to_string)?;
 
-    info!(session_id = %session_id, name = ?name, "Session renamed");
+    info!(session_id = %session_id, name = ?updated.name, "Session renamed");
+
+    let payload = SessionRenamedPayload {
+        session_id,
+        environment_id: updated.environment_id.clone(),
+        name: updated.name.clone(),
+    };
+    if let Err(e) = app_handle.emit("session-renamed", payload) {
+        warn!(session_id = %updated.id, error = %e, "Failed to emit session-renamed event");
+    }
     Ok(updated)
 }
 
@@ -146,6 +247,24 @@ pub async fn set_session_has_launched_command(
     Ok(updated)
 }
 
+/// Link a session to the terminal session currently backing it
+#[tauri::command]
+pub async fn set_session_terminal_id(
+    session_id: String,
+    terminal_session_id: Option<String>,
+) -> Result<Session, String> {
+    debug!(
+        session_id = %session_id,
+        terminal_session_id = ?terminal_session_id,
+        "Setting session terminal ID"
+    );
+
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    storage
+        .set_session_terminal_id(&session_id, terminal_session_id)
+        .map_err(storage_error_to_string)
+}
+
 /// Delete all sessions for an environment
 #[tauri::command]
 pub async fn delete_sessions_by_environment(environment_id: String) -> Result<Vec<String>, String> {
@@ -220,6 +339,65 @@ pub async fn load_session_buffer(session_id: String) -> Result<Option<String>, S
     Ok(buffer)
 }
 
+/// A disconnected session with what the frontend needs to reopen its tab
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestorableSession {
+    pub session_id: String,
+    pub tab_id: String,
+    pub order: i32,
+    pub session_type: SessionType,
+    pub name: Option<String>,
+    /// Whether a saved terminal buffer can be replayed
+    pub has_buffer: bool,
+    /// Whether the agent command already ran (so it must not be relaunched)
+    pub has_launched_command: bool,
+    pub launch_config: Option<SessionLaunchConfig>,
+}
+
+/// Disconnected sessions as restorable tabs, in tab order
+fn restorable_sessions(
+    sessions: Vec<Session>,
+    has_buffer: impl Fn(&str) -> bool,
+) -> Vec<RestorableSession> {
+    let mut restorable: Vec<RestorableSession> = sessions
+        .into_iter()
+        .filter(|s| s.status == SessionStatus::Disconnected)
+        .map(|s| RestorableSession {
+            has_buffer: has_buffer(&s.id),
+            session_id: s.id,
+            tab_id: s.tab_id,
+            order: s.order,
+            session_type: s.session_type,
+            name: s.name,
+            has_launched_command: s.has_launched_command,
+            launch_config: s.launch_config,
+        })
+        .collect();
+    restorable.sort_by_key(|s| s.order);
+    restorable
+}
+
+/// Get the disconnected sessions of an environment in tab order, so the
+/// frontend can reopen the same tabs after an app restart
+#[tauri::command]
+pub async fn get_restorable_sessions(
+    environment_id: String,
+) -> Result<Vec<RestorableSession>, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    let sessions = storage
+        .get_sessions_by_environment(&environment_id)
+        .map_err(storage_error_to_string)?;
+
+    let restorable = restorable_sessions(sessions, |id| storage.has_session_buffer(id));
+    debug!(
+        environment_id = %environment_id,
+        restorable_count = restorable.len(),
+        "Found restorable sessions"
+    );
+    Ok(restorable)
+}
+
 /// Sync sessions for an environment with container state
 /// If container is not running, marks all sessions as disconnected
 #[tauri::command]
@@ -314,4 +492,30 @@ mod tests {
         assert_eq!(SessionStatus::Connected.to_string(), "connected");
         assert_eq!(SessionStatus::Disconnected.to_string(), "disconnected");
     }
+
+    #[test]
+    fn test_restorable_sessions_are_disconnected_in_tab_order() {
+        let session = |tab_id: &str, order, status| {
+            let mut session = Session::new(
+                "env-1".to_string(),
+                "container-1".to_string(),
+                tab_id.to_string(),
+                SessionType::Plain,
+            );
+            session.order = order;
+            session.status = status;
+            session
+        };
+        let second = session("tab-2", 1, SessionStatus::Disconnected);
+        let first = session("tab-1", 0, SessionStatus::Disconnected);
+        let open = session("tab-3", 2, SessionStatus::Connected);
+        let buffered = first.id.clone();
+
+        let restorable = restorable_sessions(vec![second, open, first], |id| id == buffered);
+
+        let tabs: Vec<&str> = restorable.iter().map(|s| s.tab_id.as_str()).collect();
+        assert_eq!(tabs, vec!["tab-1", "tab-2"]);
+        assert!(restorable[0].has_buffer);
+        assert!(!restorable[1].has_buffer);
+    }
 }
@@ -1,10 +1,21 @@
 // Terminal-related Tauri commands
 // Exposes PTY operations to the frontend via events
 
-use crate::pty::get_terminal_manager;
+use crate::models::{Session, SessionStatus, SessionType};
+use crate::pty::{get_terminal_manager, TerminalSessionMetrics, TerminalSessionSnapshot};
+use crate::storage::get_storage;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 use tauri::{AppHandle, Emitter, Runtime};
 use tracing::{debug, instrument, warn};
 
+/// Payload of the "terminal-disconnected" event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalDisconnectedPayload {
+    pub session_id: String,
+}
+
 fn spawn_output_forwarder<R: Runtime>(
     app: AppHandle<R>,
     session_id: String,
@@ -24,6 +35,22 @@ fn spawn_output_forwarder<R: Runtime>(
             }
         }
         debug!(session_id = %session_id_clone, "Output forwarder task ended");
+
+        // Let the frontend reattach if the exec died with its container (e.g.
+        // a restart or recreate). A closed session or a shell the user exited
+        // stays closed.
+        let lost_container = match get_terminal_manager() {
+            Some(manager) => manager.lost_container(&session_id_clone).await,
+            None => false,
+        };
+        if lost_container {
+            let payload = TerminalDisconnectedPayload {
+                session_id: session_id_clone.clone(),
+            };
+            if let Err(e) = app_clone.emit("terminal-disconnected", payload) {
+                warn!(session_id = %session_id_clone, error = ?e, "Failed to emit terminal disconnected event");
+            }
+        }
     });
 }
 
@@ -151,6 +178,185 @@ pub fn list_terminal_sessions() -> Result<Vec<String>, String> {
     Ok(manager.list_sessions())
 }
 
+/// A terminal session as seen by both the terminal manager and storage.
+/// Live-only entries have no persisted fields; stored-only entries have no
+/// terminal (the session is disconnected or its terminal was never linked).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalSessionDetails {
+    /// Terminal manager session ID
+    pub terminal_session_id: Option<String>,
+    /// Stored session ID
+    pub persistent_session_id: Option<String>,
+    pub environment_id: Option<String>,
+    pub container_id: String,
+    pub cols: Option<u16>,
+    pub rows: Option<u16>,
+    /// The terminal has a running exec
+    pub is_active: bool,
+    pub session_type: Option<SessionType>,
+    pub status: Option<SessionStatus>,
+    pub last_activity_at: Option<DateTime<Utc>>,
+}
+
+/// Join live terminal sessions with stored sessions via the stored
+/// `terminal_session_id`. Live sessions come first, then unmatched stored ones.
+fn join_session_details(
+    live: Vec<TerminalSessionSnapshot>,
+    stored: Vec<Session>,
+) -> Vec<TerminalSessionDetails> {
+    let mut unmatched = stored;
+    let mut details: Vec<TerminalSessionDetails> = live
+        .into_iter()
+        .map(|terminal| {
+            let stored = unmatched
+                .iter()
+                .position(|s| s.terminal_session_id.as_deref() == Some(&terminal.session_id))
+                .map(|index| unmatched.remove(index));
+            TerminalSessionDetails {
+                terminal_session_id: Some(terminal.session_id),
+                persistent_session_id: stored.as_ref().map(|s| s.id.clone()),
+                environment_id: stored.as_ref().map(|s| s.environment_id.clone()),
+                container_id: terminal.container_id,
+                cols: Some(terminal.cols),
+                rows: Some(terminal.rows),
+                is_active: terminal.is_active,
+                session_type: stored.as_ref().map(|s| s.session_type.clone()),
+                status: stored.as_ref().map(|s| s.status.clone()),
+                last_activity_at: stored.as_ref().map(|s| s.last_activity_at),
+            }
+        })
+        .collect();
+
+    details.extend(unmatched.into_iter().map(|s| TerminalSessionDetails {
+        terminal_session_id: None,
+        persistent_session_id: Some(s.id),
+        environment_id: Some(s.environment_id),
+        container_id: s.container_id,
+        cols: None,
+        rows: None,
+        is_active: false,
+        session_type: Some(s.session_type),
+        status: Some(s.status),
+        last_activity_at: Some(s.last_activity_at),
+    }));
+    details
+}
+
+/// List terminal sessions from the terminal manager joined with stored
+/// session records, to reconcile the in-memory and persisted views
+#[tauri::command]
+#[instrument]
+pub fn list_terminal_sessions_detailed() -> Result<Vec<TerminalSessionDetails>, String> {
+    let manager =
+        get_terminal_manager().ok_or_else(|| "Terminal manager not initialized".to_string())?;
+    let stored = get_storage()
+        .map_err(|e| e.to_string())?
+        .load_sessions()
+        .map_err(|e| e.to_string())?;
+
+    Ok(join_session_details(manager.snapshot_sessions(), stored))
+}
+
+/// Reattach a disconnected terminal session with a fresh exec in its
+/// (possibly recreated) container. Returns the output saved before the
+/// disconnect so the frontend can restore the scrollback.
+#[tauri::command]
+#[instrument(skip(app), fields(session_id = %session_id))]
+pub async fn reattach_session<R: Runtime>(
+    app: AppHandle<R>,
+    session_id: String,
+) -> Result<Vec<u8>, String> {
+    debug!("Reattaching terminal session");
+    let manager =
+        get_terminal_manager().ok_or_else(|| "Terminal manager not initialized".to_string())?;
+
+    let saved_output = manager.saved_output(&session_id).unwrap_or_default();
+    let output_rx = manager
+        .reattach_session(&session_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    spawn_output_forwarder(app, session_id, output_rx);
+
+    Ok(saved_output)
+}
+
+/// Result of restoring a stored session
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoredSession {
+    pub session: Session,
+    /// Terminal that was reattached, or None when the frontend must create a
+    /// new terminal (e.g. after an app restart) and link it to the session
+    pub terminal_session_id: Option<String>,
+    /// Output to replay: the live terminal's saved output when reattached,
+    /// otherwise the saved buffer
+    pub buffer: Option<String>,
+}
+
+/// Restore a stored session: reattach its terminal if the terminal manager
+/// still has it, mark the session connected and return the output to replay
+#[tauri::command]
+#[instrument(skip(app), fields(session_id = %session_id))]
+pub async fn restore_session<R: Runtime>(
+    app: AppHandle<R>,
+    session_id: String,
+) -> Result<RestoredSession, String> {
+    debug!("Restoring session");
+    let storage = get_storage().map_err(|e| e.to_string())?;
+    let session = storage
+        .get_session(&session_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+
+    let live_terminal = session
+        .terminal_session_id
+        .clone()
+        .filter(|id| get_terminal_manager().is_some_and(|m| m.has_session(id)));
+
+    let (terminal_session_id, buffer) = match live_terminal {
+        Some(terminal_id) => {
+            let output = reattach_session(app, terminal_id.clone()).await?;
+            (
+                Some(terminal_id),
+                Some(String::from_utf8_lossy(&output).into_owned()),
+            )
+        }
+        None => {
+            let buffer = storage
+                .load_session_buffer(&session_id)
+                .map_err(|e| e.to_string())?;
+            (None, buffer)
+        }
+    };
+
+    storage
+        .update_session_status(&session_id, SessionStatus::Connected)
+        .map_err(|e| e.to_string())?;
+    let session = storage
+        .touch_session(&session_id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(RestoredSession {
+        session,
+        terminal_session_id,
+        buffer,
+    })
+}
+
+/// Get byte counts, uptime and throughput of a terminal session, to spot a
+/// session flooding the output channel or a stuck stream
+#[tauri::command]
+#[instrument(fields(session_id = %session_id))]
+pub fn get_session_metrics(session_id: String) -> Result<TerminalSessionMetrics, String> {
+    let manager =
+        get_terminal_manager().ok_or_else(|| "Terminal manager not initialized".to_string())?;
+
+    manager
+        .session_metrics(&session_id)
+        .ok_or_else(|| format!("Session not found: {}", session_id))
+}
+
 /// Get terminal session info
 #[tauri::command]
 #[instrument(fields(session_id = %session_id))]
@@ -160,3 +366,56 @@ pub fn get_terminal_session(session_id: String) -> Result<Option<(String, u16, u
 
     Ok(manager.get_session(&session_id))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(session_id: &str, container_id: &str) -> TerminalSessionSnapshot {
+        TerminalSessionSnapshot {
+            session_id: session_id.to_string(),
+            container_id: container_id.to_string(),
+            cols: 120,
+            rows: 40,
+            is_active: true,
+        }
+    }
+
+    #[test]
+    fn join_session_details_matches_by_terminal_session_id() {
+        let mut linked = Session::new(
+            "env-1".to_string(),
+            "container-1".to_string(),
+            "tab-1".to_string(),
+            SessionType::Claude,
+        );
+        linked.terminal_session_id = Some("pty-1".to_string());
+        let orphan = Session::new(
+            "env-1".to_string(),
+            "container-1".to_string(),
+            "tab-2".to_string(),
+            SessionType::Plain,
+        );
+
+        let details = join_session_details(
+            vec![
+                snapshot("pty-1", "container-1"),
+                snapshot("pty-2", "container-2"),
+            ],
+            vec![orphan.clone(), linked.clone()],
+        );
+
+        assert_eq!(details.len(), 3);
+        assert_eq!(details[0].terminal_session_id.as_deref(), Some("pty-1"));
+        assert_eq!(details[0].persistent_session_id, Some(linked.id));
+        assert_eq!(details[0].session_type, Some(SessionType::Claude));
+        assert_eq!(details[0].cols, Some(120));
+
+        assert_eq!(details[1].terminal_session_id.as_deref(), Some("pty-2"));
+        assert_eq!(details[1].persistent_session_id, None);
+
+        assert_eq!(details[2].terminal_session_id, None);
+        assert_eq!(details[2].persistent_session_id, Some(orphan.id));
+        assert!(!details[2].is_active);
+    }
+}
@@ -0,0 +1,85 @@
+// Window state Tauri commands
+// Persists the zoom level and main window geometry across restarts
+
+use tauri::{Manager, PhysicalPosition, PhysicalSize, WebviewWindow, WindowEvent};
+use tracing::{debug, warn};
+
+use crate::models::WindowState;
+use crate::storage::{get_storage, StorageError};
+
+fn storage_error_to_string(err: StorageError) -> String {
+    err.to_string()
+}
+
+/// Get the saved window state
+#[tauri::command]
+pub async fn get_window_state() -> Result<WindowState, String> {
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    storage.load_window_state().map_err(storage_error_to_string)
+}
+
+/// Save window state. Fields left unset keep their saved values.
+#[tauri::command]
+pub async fn save_window_state(state: WindowState) -> Result<WindowState, String> {
+    debug!(?state, "Saving window state");
+    let storage = get_storage().map_err(storage_error_to_string)?;
+    storage
+        .update_window_state(state)
+        .map_err(storage_error_to_string)
+}
+
+/// Restore the saved size and position of the main window, and save them
+/// again whenever the window is closed.
+pub fn restore_main_window(app: &tauri::App) {
+    let Some(window) = app.get_webview_window("main") else {
+        warn!("Main window not found, skipping window state restore");
+        return;
+    };
+
+    match get_storage().and_then(|storage| storage.load_window_state()) {
+        Ok(state) => apply_window_geometry(&window, &state),
+        Err(e) => warn!(error = %e, "Failed to load window state"),
+    }
+
+    let tracked = window.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::CloseRequested { .. } = event {
+            save_window_geometry(&tracked);
+        }
+    });
+}
+
+fn apply_window_geometry(window: &WebviewWindow, state: &WindowState) {
+    if let (Some(width), Some(height)) = (state.width, state.height) {
+        if let Err(e) = window.set_size(PhysicalSize::new(width, height)) {
+            warn!(error = %e, "Failed to restore window size");
+        }
+    }
+    if let (Some(x), Some(y)) = (state.x, state.y) {
+        if let Err(e) = window.set_position(PhysicalPosition::new(x, y)) {
+            warn!(error = %e, "Failed to restore window position");
+        }
+    }
+}
+
+fn save_window_geometry(window: &WebviewWindow) {
+    // Maximized/fullscreen geometry would be restored as a normal window
+    if window.is_maximized().unwrap_or(false) || window.is_fullscreen().unwrap_or(false) {
+        return;
+    }
+
+    let (Ok(size), Ok(position)) = (window.outer_size(), window.outer_position()) else {
+        return;
+    };
+    let update = WindowState {
+        width: Some(size.width),
+        height: Some(size.height),
+        x: Some(position.x),
+        y: Some(position.y),
+        ..Default::default()
+    };
+
+    if let Err(e) = get_storage().and_then(|storage| storage.update_window_state(update)) {
+        warn!(error = %e, "Failed to save window geometry");
+    }
+}
@@ -5,6 +5,7 @@
 pub mod sync;
 
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::sync::Mutex;
 use tracing::{debug, error, warn};
@@ -23,15 +24,32 @@ const DEFAULT_SCOPES: &[&str] = &[
 ];
 /// Refresh the token if it expires within this window (ms).
 const REFRESH_SKEW_MS: i64 = 5 * 60 * 1000;
+/// A `security` call taking longer than this is waiting on a locked keychain.
+const KEYCHAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Credentials read from the keychain are reused for this long before the
+/// keychain is asked again (each read may show an access prompt).
+const CACHE_TTL: Duration = Duration::from_secs(5 * 60);
 
 /// Serializes the read-refresh-write critical section across the process so
 /// concurrent callers can't both spend the same single-use refresh token.
 static REFRESH_LOCK: Mutex<()> = Mutex::const_new(());
 
+/// Last credentials read from (or written to) the keychain. Memory only; the
+/// decrypted tokens are never written anywhere else.
+static CACHE: std::sync::Mutex<Option<CachedCredentials>> = std::sync::Mutex::new(None);
+
+struct CachedCredentials {
+    credentials: ClaudeCredentials,
+    cached_at: Instant,
+}
+
 #[derive(Error, Debug)]
 pub enum CredentialsError {
     #[error("Keychain access error: {0}")]
     KeychainError(String),
+    #[error("Keychain timed out or is locked: {0}")]
+    KeychainLocked(String),
     #[error("Credentials not found")]
     NotFound,
     #[error("Failed to parse credentials: {0}")]
@@ -78,21 +96,97 @@ fn now_ms() -> i64 {
         .unwrap_or(0)
 }
 
+/// Wait for a child process, killing it once `timeout` elapses.
+///
+/// Same approach as `claude_cli::wait_with_timeout`, except that the process is
+/// killed by pid so a hung child can't keep the waiting thread (and us) blocked.
+#[cfg(unix)]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn wait_with_timeout(
+    child: std::process::Child,
+    timeout: Duration,
+) -> Result<std::process::Output, CredentialsError> {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+    use std::sync::mpsc;
+    use std::thread;
+
+    let pid = Pid::from_raw(child.id() as i32);
+    let (tx, rx) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        let _ = tx.send(child.wait_with_output());
+    });
+
+    match rx.recv_timeout(timeout) {
+        Ok(result) => {
+            let _ = handle.join();
+            result.map_err(|e| {
+                CredentialsError::KeychainError(format!("Failed to read security output: {}", e))
+            })
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            warn!(
+                timeout_secs = timeout.as_secs(),
+                "Keychain access timed out, killing security process"
+            );
+            let _ = kill(pid, Signal::SIGKILL);
+            let _ = handle.join();
+            Err(CredentialsError::KeychainLocked(format!(
+                "no response after {} seconds",
+                timeout.as_secs()
+            )))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(CredentialsError::KeychainError(
+            "security process monitoring thread disconnected".to_string(),
+        )),
+    }
+}
+
+/// Run the `security` CLI with `args`, giving up after `KEYCHAIN_TIMEOUT`.
+///
+/// A locked keychain either makes `security` wait for an unlock prompt or fail
+/// with "User interaction is not allowed"; both are reported as `KeychainLocked`.
+#[cfg(target_os = "macos")]
+fn run_security(args: &[&str]) -> Result<std::process::Output, CredentialsError> {
+    use std::process::{Command, Stdio};
+
+    let child = Command::new("security")
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            CredentialsError::KeychainError(format!("Failed to run security command: {}", e))
+        })?;
+
+    let output = wait_with_timeout(child, KEYCHAIN_TIMEOUT)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if is_keychain_locked_error(&stderr) {
+            return Err(CredentialsError::KeychainLocked(stderr.trim().to_string()));
+        }
+    }
+    Ok(output)
+}
+
+/// Whether `security` stderr reports that the keychain is locked
+/// (errSecInteractionNotAllowed / errSecAuthFailed)
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn is_keychain_locked_error(stderr: &str) -> bool {
+    stderr.contains("User interaction is not allowed")
+        || stderr.contains("-25308")
+        || stderr.contains("-25293")
+}
+
 /// Read Claude Code credentials from the system keychain.
 ///
 /// Uses the macOS `security` CLI tool instead of the `security-framework` crate.
 /// This is a deliberate tradeoff: the CLI is slightly slower (spawns a subprocess)
 /// but doesn't require knowing the account name - only the service name is needed.
 #[cfg(target_os = "macos")]
-pub fn get_claude_credentials() -> Result<ClaudeCredentials, CredentialsError> {
-    use std::process::Command;
-
-    let output = Command::new("security")
-        .args(["find-generic-password", "-s", KEYCHAIN_SERVICE, "-w"])
-        .output()
-        .map_err(|e| {
-            CredentialsError::KeychainError(format!("Failed to run security command: {}", e))
-        })?;
+fn read_keychain_credentials() -> Result<ClaudeCredentials, CredentialsError> {
+    let output = run_security(&["find-generic-password", "-s", KEYCHAIN_SERVICE, "-w"])?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -122,10 +216,47 @@ pub fn get_claude_credentials() -> Result<ClaudeCredentials, CredentialsError> {
 }
 
 #[cfg(not(target_os = "macos"))]
-pub fn get_claude_credentials() -> Result<ClaudeCredentials, CredentialsError> {
+fn read_keychain_credentials() -> Result<ClaudeCredentials, CredentialsError> {
     Err(CredentialsError::UnsupportedPlatform)
 }
 
+/// Cached credentials, unless they are older than `CACHE_TTL` or due for a refresh
+fn cached_credentials() -> Option<ClaudeCredentials> {
+    let cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let cached = cache.as_ref()?;
+    let fresh = cached.cached_at.elapsed() < CACHE_TTL
+        && !should_refresh(cached.credentials.claude_ai_oauth.expires_at, now_ms());
+    fresh.then(|| cached.credentials.clone())
+}
+
+fn cache_credentials(credentials: &ClaudeCredentials) {
+    *CACHE.lock().unwrap_or_else(|e| e.into_inner()) = Some(CachedCredentials {
+        credentials: credentials.clone(),
+        cached_at: Instant::now(),
+    });
+}
+
+/// Drop the cached credentials so the next read goes to the keychain
+pub fn invalidate_cached_credentials() {
+    *CACHE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Read credentials from the keychain, bypassing (and refreshing) the cache
+fn read_and_cache_credentials() -> Result<ClaudeCredentials, CredentialsError> {
+    let credentials = read_keychain_credentials()?;
+    cache_credentials(&credentials);
+    Ok(credentials)
+}
+
+/// Claude Code credentials, reusing a recent keychain read when the tokens
+/// aren't close to expiring.
+pub fn get_claude_credentials() -> Result<ClaudeCredentials, CredentialsError> {
+    match cached_credentials() {
+        Some(credentials) => Ok(credentials),
+        None => read_and_cache_credentials(),
+    }
+}
+
 /// Parse the account ("acct") attribute from `security find-generic-password`
 /// output. `security` emits the value either as a quoted string
 /// (`"acct"<blob>="me@example.com"`) or, when non-UTF8 or containing control
@@ -181,14 +312,7 @@ fn unescape_security_quoted(s: &str) -> String {
 /// Needed to update the entry in place via `security add-generic-password -U`.
 #[cfg(target_os = "macos")]
 fn get_claude_credentials_account() -> Result<String, CredentialsError> {
-    use std::process::Command;
-
-    let output = Command::new("security")
-        .args(["find-generic-password", "-s", KEYCHAIN_SERVICE])
-        .output()
-        .map_err(|e| {
-            CredentialsError::KeychainError(format!("Failed to run security command: {}", e))
-        })?;
+    let output = run_security(&["find-generic-password", "-s", KEYCHAIN_SERVICE])?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -220,28 +344,21 @@ fn get_claude_credentials_account() -> Result<String, CredentialsError> {
 /// note in `Cargo.toml`).
 #[cfg(target_os = "macos")]
 fn write_claude_credentials(credentials: &ClaudeCredentials) -> Result<(), CredentialsError> {
-    use std::process::Command;
-
     let account = get_claude_credentials_account()?;
     let json = serde_json::to_string(credentials).map_err(|e| {
         CredentialsError::ParseError(format!("Failed to serialize credentials: {}", e))
     })?;
 
-    let output = Command::new("security")
-        .args([
-            "add-generic-password",
-            "-U",
-            "-a",
-            &account,
-            "-s",
-            KEYCHAIN_SERVICE,
-            "-w",
-            &json,
-        ])
-        .output()
-        .map_err(|e| {
-            CredentialsError::KeychainError(format!("Failed to run security command: {}", e))
-        })?;
+    let output = run_security(&[
+        "add-generic-password",
+        "-U",
+        "-a",
+        &account,
+        "-s",
+        KEYCHAIN_SERVICE,
+        "-w",
+        &json,
+    ])?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -358,9 +475,10 @@ pub async fn get_or_refresh_claude_credentials() -> Result<ClaudeCredentials, Cr
 
     let _guard = REFRESH_LOCK.lock().await;
 
-    // Re-read after acquiring the lock: another task may have already
-    // refreshed while we were waiting.
-    let current = get_claude_credentials()?;
+    // Re-read after acquiring the lock: another task (or the host's `claude`
+    // CLI) may have already refreshed while we were waiting. This skips the
+    // cache so we never spend a refresh token that has since been rotated.
+    let current = read_and_cache_credentials()?;
     if !should_refresh(current.claude_ai_oauth.expires_at, now_ms()) {
         return Ok(current);
     }
@@ -403,12 +521,12 @@ pub async fn get_or_refresh_claude_credentials() -> Result<ClaudeCredentials, Cr
         )));
     }
 
+    cache_credentials(&updated);
     debug!("Refreshed Claude credentials and updated keychain");
     Ok(updated)
 }
 
-/// Check if Claude credentials are available
-#[allow(dead_code)]
+/// Check if Claude credentials are available, without refreshing them
 pub fn has_claude_credentials() -> bool {
     get_claude_credentials().is_ok()
 }
@@ -452,6 +570,63 @@ mod tests {
         );
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_wait_with_timeout_kills_hung_process() {
+        use std::process::{Command, Stdio};
+
+        let child = Command::new("sleep")
+            .arg("30")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let started = std::time::Instant::now();
+        let result = wait_with_timeout(child, Duration::from_millis(100));
+        assert!(matches!(result, Err(CredentialsError::KeychainLocked(_))));
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let child = Command::new("echo")
+            .arg("ok")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let output = wait_with_timeout(child, Duration::from_secs(5)).unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ok");
+    }
+
+    #[test]
+    fn test_is_keychain_locked_error() {
+        assert!(is_keychain_locked_error(
+            "security: SecKeychainSearchCopyNext: User interaction is not allowed."
+        ));
+        assert!(!is_keychain_locked_error(
+            "security: SecKeychainSearchCopyNext: The specified item could not be found in the keychain."
+        ));
+    }
+
+    #[test]
+    fn test_cached_credentials_respect_expiry() {
+        let mut creds = ClaudeCredentials {
+            claude_ai_oauth: sample_creds(),
+        };
+        creds.claude_ai_oauth.expires_at = now_ms() + 60 * 60 * 1000;
+        cache_credentials(&creds);
+        assert_eq!(
+            cached_credentials().map(|c| c.claude_ai_oauth.access_token),
+            Some("old-access".to_string())
+        );
+
+        invalidate_cached_credentials();
+        assert!(cached_credentials().is_none());
+
+        creds.claude_ai_oauth.expires_at = now_ms() + REFRESH_SKEW_MS - 1000;
+        cache_credentials(&creds);
+        assert!(cached_credentials().is_none(), "near expiry is not reused");
+        invalidate_cached_credentials();
+    }
+
     #[test]
     fn test_should_refresh_skew() {
         let now = 1_000_000_000i64;
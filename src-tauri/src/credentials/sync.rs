@@ -16,6 +16,7 @@ use crate::docker::{
     container::{CONTAINER_LABEL_APP, CONTAINER_LABEL_APP_VALUE},
     get_docker_client,
 };
+use crate::models::AuthPreference;
 
 const SYNC_INTERVAL: Duration = Duration::from_secs(60);
 const CREDENTIALS_PATH_IN_CONTAINER: &str = "/home/node/.claude/.credentials.json";
@@ -65,7 +66,7 @@ fn emit_error(app: &AppHandle, kind: &'static str, message: impl Into<String>) {
 }
 
 /// Push a credentials JSON blob into a single container.
-async fn push_to_container(container_id: &str, creds_json: &[u8]) -> Result<(), String> {
+pub(crate) async fn push_to_container(container_id: &str, creds_json: &[u8]) -> Result<(), String> {
     let client = get_docker_client().map_err(|e| e.to_string())?;
     client
         .upload_file_to_container_with_metadata(
@@ -202,6 +203,14 @@ pub async fn run_sync_loop(app: AppHandle) {
     loop {
         interval.tick().await;
 
+        // Containers get no keychain credentials when the API key is forced
+        let api_key_only = crate::storage::get_config()
+            .map(|config| config.global.auth_preference == AuthPreference::ApiKey)
+            .unwrap_or(false);
+        if api_key_only {
+            continue;
+        }
+
         let outcome = sync_once_with(Some(&app), &mut last_synced_token, list, refresh, push).await;
 
         match outcome {
@@ -0,0 +1,187 @@
+//! Free disk space checks.
+//!
+//! Creating a container or worktree on a nearly full disk fails with opaque
+//! Docker or git errors. These helpers check the directories that operation
+//! writes to so the UI can show an actionable warning first.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+const BYTES_PER_GB: u64 = 1024 * 1024 * 1024;
+
+/// A directory whose filesystem is checked for free space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiskLocation {
+    /// App data directory (config, sessions, buffers)
+    DataDir,
+    /// Docker's data root, when it lives on the host filesystem
+    DockerRoot,
+    /// Base directory of local environment worktrees
+    WorktreeBase,
+}
+
+impl DiskLocation {
+    fn label(self) -> &'static str {
+        match self {
+            DiskLocation::DataDir => "app data directory",
+            DiskLocation::DockerRoot => "Docker data root",
+            DiskLocation::WorktreeBase => "worktree directory",
+        }
+    }
+}
+
+/// A location with less free space than the configured minimum
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskSpaceWarning {
+    pub location: DiskLocation,
+    pub path: String,
+    pub available_bytes: u64,
+    pub threshold_bytes: u64,
+    /// Human-readable summary for the UI
+    pub message: String,
+}
+
+impl DiskSpaceWarning {
+    fn new(
+        location: DiskLocation,
+        path: &Path,
+        available_bytes: u64,
+        threshold_bytes: u64,
+    ) -> Self {
+        let message = format!(
+            "Low disk space: the {} ({}) has {:.1} GB free, below the {:.1} GB minimum",
+            location.label(),
+            path.display(),
+            available_bytes as f64 / BYTES_PER_GB as f64,
+            threshold_bytes as f64 / BYTES_PER_GB as f64,
+        );
+        Self {
+            location,
+            path: path.display().to_string(),
+            available_bytes,
+            threshold_bytes,
+            message,
+        }
+    }
+}
+
+/// Minimum free space in bytes for a threshold in GB
+pub fn threshold_bytes(min_free_gb: u32) -> u64 {
+    u64::from(min_free_gb) * BYTES_PER_GB
+}
+
+/// Bytes available to unprivileged users on the filesystem holding `path`.
+/// Paths that don't exist yet are measured at their nearest existing ancestor.
+pub fn available_space(path: &Path) -> io::Result<u64> {
+    let existing = path
+        .ancestors()
+        .find(|p| p.exists())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no existing ancestor"))?;
+    filesystem_available(existing)
+}
+
+#[cfg(unix)]
+fn filesystem_available(path: &Path) -> io::Result<u64> {
+    let stats = nix::sys::statvfs::statvfs(path).map_err(io::Error::from)?;
+    Ok(stats.blocks_available() as u64 * stats.fragment_size() as u64)
+}
+
+#[cfg(windows)]
+fn filesystem_available(path: &Path) -> io::Result<u64> {
+    use std::iter;
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let wide: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(iter::once(0))
+        .collect();
+    let mut available: u64 = 0;
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(
+            wide.as_ptr(),
+            &mut available,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    if ok == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(available)
+}
+
+/// Check each location against `threshold_bytes` and return a warning for
+/// every one below it. Locations that can't be measured are skipped.
+pub fn check_disk_space(
+    locations: &[(DiskLocation, PathBuf)],
+    threshold_bytes: u64,
+) -> Vec<DiskSpaceWarning> {
+    check_with(locations, threshold_bytes, available_space)
+}
+
+fn check_with(
+    locations: &[(DiskLocation, PathBuf)],
+    threshold_bytes: u64,
+    available: impl Fn(&Path) -> io::Result<u64>,
+) -> Vec<DiskSpaceWarning> {
+    if threshold_bytes == 0 {
+        return Vec::new();
+    }
+    locations
+        .iter()
+        .filter_map(|(location, path)| match available(path) {
+            Ok(bytes) if bytes < threshold_bytes => Some(DiskSpaceWarning::new(
+                *location,
+                path,
+                bytes,
+                threshold_bytes,
+            )),
+            Ok(_) => None,
+            Err(e) => {
+                debug!(path = %path.display(), error = %e, "Could not measure free disk space");
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn warns_only_for_locations_below_threshold() {
+        let locations = vec![
+            (DiskLocation::DataDir, PathBuf::from("/full")),
+            (DiskLocation::WorktreeBase, PathBuf::from("/roomy")),
+            (DiskLocation::DockerRoot, PathBuf::from("/unreadable")),
+        ];
+        let available = |path: &Path| match path.to_str() {
+            Some("/full") => Ok(BYTES_PER_GB),
+            Some("/roomy") => Ok(100 * BYTES_PER_GB),
+            _ => Err(io::Error::new(io::ErrorKind::NotFound, "gone")),
+        };
+
+        let warnings = check_with(&locations, threshold_bytes(5), available);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].location, DiskLocation::DataDir);
+        assert_eq!(warnings[0].available_bytes, BYTES_PER_GB);
+        assert!(warnings[0].message.contains("1.0 GB free"));
+
+        assert!(check_with(&locations, 0, available).is_empty());
+    }
+
+    #[test]
+    fn available_space_measures_nearest_existing_ancestor() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("not/created/yet");
+        assert!(available_space(&missing).unwrap() > 0);
+    }
+}
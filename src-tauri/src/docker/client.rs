@@ -2,28 +2,30 @@
 // Provides high-level API for container operations
 
 use bollard::container::{
-    Config, CreateContainerOptions, InspectContainerOptions, ListContainersOptions, LogOutput,
-    LogsOptions, PruneContainersOptions, RemoveContainerOptions, RenameContainerOptions,
-    StartContainerOptions, StopContainerOptions,
+    Config, CreateContainerOptions, InspectContainerOptions, KillContainerOptions,
+    ListContainersOptions, LogOutput, LogsOptions, PruneContainersOptions, RemoveContainerOptions,
+    RenameContainerOptions, StartContainerOptions, StopContainerOptions, UpdateContainerOptions,
 };
 use bollard::exec::{CreateExecOptions, StartExecResults};
 use bollard::image::{
     CommitContainerOptions, ListImagesOptions, PruneImagesOptions, RemoveImageOptions,
 };
 use bollard::models::{
-    ContainerInspectResponse, ContainerSummary, ImageSummary, PortBinding, SystemDataUsageResponse,
-    SystemInfo,
+    ContainerInspectResponse, ContainerSummary, ImageSummary, PortBinding, RestartPolicy,
+    SystemDataUsageResponse, SystemInfo,
 };
-use bollard::network::PruneNetworksOptions;
+use bollard::network::{CreateNetworkOptions, PruneNetworksOptions};
 use bollard::volume::PruneVolumesOptions;
-use bollard::Docker;
+use bollard::{Docker, API_DEFAULT_VERSION};
 use futures::StreamExt;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use thiserror::Error;
 use tokio::sync::mpsc;
-use tracing::debug;
+use tracing::{debug, warn};
 
-use crate::models::sanitize_slug;
+use crate::models::{sanitize_slug, ContainerEngineKind};
 
 /// Maximum length for Docker container names (Docker has no official limit,
 /// but 128 chars keeps names practical in logs, CLI output, and UIs).
@@ -72,6 +74,10 @@ pub struct CreateContainerConfig {
     pub port_bindings: HashMap<String, Option<Vec<PortBinding>>>,
     /// Exposed ports for the container
     pub exposed_ports: HashMap<String, HashMap<(), ()>>,
+    /// Restart policy (None = Docker default, never restart)
+    pub restart_policy: Option<RestartPolicy>,
+    /// Network to attach the container to (None = default bridge)
+    pub network_mode: Option<String>,
 }
 
 /// Result of executing a command inside a container
@@ -81,17 +87,170 @@ struct ExecOutput {
     exec_id: String,
 }
 
-/// Docker client wrapper providing high-level operations
+/// Timeout in seconds for requests over an explicitly chosen socket
+const SOCKET_TIMEOUT_SECS: u64 = 120;
+
+/// How often and how long to wait for an exec's exit code after its output ends
+const EXEC_EXIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const EXEC_EXIT_POLL_ATTEMPTS: u32 = 20;
+
+/// Concrete engine behind a connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineBackend {
+    Docker,
+    Podman,
+}
+
+impl EngineBackend {
+    /// Display name, e.g. for diagnostics
+    pub fn name(self) -> &'static str {
+        match self {
+            EngineBackend::Docker => "Docker",
+            EngineBackend::Podman => "Podman",
+        }
+    }
+
+    /// CLI binary for operations that shell out (`exec`, `compose`)
+    pub fn cli(self) -> &'static str {
+        match self {
+            EngineBackend::Docker => "docker",
+            EngineBackend::Podman => "podman",
+        }
+    }
+}
+
+/// Candidate Podman API sockets, most specific first
+fn podman_socket_candidates(
+    container_host: Option<&str>,
+    runtime_dir: Option<&Path>,
+    home: Option<&Path>,
+) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    // CONTAINER_HOST is Podman's equivalent of DOCKER_HOST
+    if let Some(path) = container_host.and_then(|host| host.strip_prefix("unix://")) {
+        candidates.push(PathBuf::from(path));
+    }
+    // Rootless service started with `systemctl --user start podman.socket`
+    if let Some(dir) = runtime_dir {
+        candidates.push(dir.join("podman/podman.sock"));
+    }
+    // Rootful service
+    candidates.push(PathBuf::from("/run/podman/podman.sock"));
+    // Podman machine (macOS)
+    if let Some(home) = home {
+        let machine_dir = home.join(".local/share/containers/podman/machine");
+        candidates.push(machine_dir.join("podman.sock"));
+        candidates.push(machine_dir.join("podman-machine-default/podman.sock"));
+    }
+    candidates.push(std::env::temp_dir().join("podman/podman-machine-default-api.sock"));
+    candidates
+}
+
+/// First Podman API socket that exists on this machine
+fn find_podman_socket() -> Option<PathBuf> {
+    let container_host = std::env::var("CONTAINER_HOST").ok();
+    let runtime_dir = std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from);
+    podman_socket_candidates(
+        container_host.as_deref(),
+        runtime_dir.as_deref(),
+        dirs::home_dir().as_deref(),
+    )
+    .into_iter()
+    .find(|path| path.exists())
+}
+
+/// Whether Docker looks reachable without talking to it: `DOCKER_HOST` is set
+/// or the default socket exists
+fn docker_socket_present() -> bool {
+    if std::env::var_os("DOCKER_HOST").is_some() {
+        return true;
+    }
+    if cfg!(windows) {
+        return true;
+    }
+    Path::new("/var/run/docker.sock").exists()
+}
+
+/// Resolve the configured engine to a concrete one. `auto` prefers Docker and
+/// falls back to Podman only when no Docker socket is present but a Podman one is.
+pub fn resolve_engine_backend(preference: ContainerEngineKind) -> EngineBackend {
+    match preference {
+        ContainerEngineKind::Docker => EngineBackend::Docker,
+        ContainerEngineKind::Podman => EngineBackend::Podman,
+        ContainerEngineKind::Auto => {
+            if !docker_socket_present() && find_podman_socket().is_some() {
+                EngineBackend::Podman
+            } else {
+                EngineBackend::Docker
+            }
+        }
+    }
+}
+
+/// Engine preference from the saved config (`auto` if it can't be loaded)
+fn configured_engine() -> ContainerEngineKind {
+    crate::storage::get_config()
+        .map(|config| config.global.container_engine)
+        .unwrap_or_default()
+}
+
+fn connect_backend(backend: EngineBackend) -> Result<Docker, DockerError> {
+    let docker = match backend {
+        EngineBackend::Docker => Docker::connect_with_local_defaults(),
+        EngineBackend::Podman => {
+            let socket = find_podman_socket().ok_or_else(|| {
+                DockerError::ConnectionFailed(
+                    "Podman API socket not found. Start it with `podman machine start` or `systemctl --user start podman.socket`".to_string(),
+                )
+            })?;
+            debug!(socket = %socket.display(), "Connecting to Podman socket");
+            Docker::connect_with_socket(
+                &socket.to_string_lossy(),
+                SOCKET_TIMEOUT_SECS,
+                API_DEFAULT_VERSION,
+            )
+        }
+    };
+    docker.map_err(|e| DockerError::ConnectionFailed(e.to_string()))
+}
+
+/// Open a fresh connection to the configured container engine. Use this
+/// instead of the shared client for hijacked connections (terminals, polling).
+pub fn connect_engine() -> Result<Docker, DockerError> {
+    connect_backend(resolve_engine_backend(configured_engine()))
+}
+
+/// Engine in use: the connected client's, or the one that would be picked
+pub fn engine_backend() -> EngineBackend {
+    get_docker_client()
+        .map(|client| client.backend())
+        .unwrap_or_else(|_| resolve_engine_backend(configured_engine()))
+}
+
+/// CLI binary of the configured engine
+pub fn engine_cli() -> &'static str {
+    engine_backend().cli()
+}
+
+/// Docker client wrapper providing high-level operations. Also talks to
+/// Podman through its Docker-compatible API.
 pub struct DockerClient {
     docker: Docker,
+    backend: EngineBackend,
 }
 
 impl DockerClient {
-    /// Create a new Docker client, connecting to the Docker socket
+    /// Create a new client connected to the configured container engine
     pub fn new() -> Result<Self, DockerError> {
-        let docker = Docker::connect_with_local_defaults()
-            .map_err(|e| DockerError::ConnectionFailed(e.to_string()))?;
-        Ok(Self { docker })
+        let backend = resolve_engine_backend(configured_engine());
+        let docker = connect_backend(backend)?;
+        debug!(backend = backend.name(), "Connected to container engine");
+        Ok(Self { docker, backend })
+    }
+
+    /// Engine this client is connected to
+    pub fn backend(&self) -> EngineBackend {
+        self.backend
     }
 
     /// Check if Docker is available and running
@@ -127,6 +286,17 @@ impl DockerClient {
         }))
     }
 
+    /// ID of a local image by tag or ID, or `None` if there is no such image
+    pub async fn image_id(&self, image: &str) -> Result<Option<String>, DockerError> {
+        match self.docker.inspect_image(image).await {
+            Ok(inspect) => Ok(inspect.id),
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     // --- Container Operations ---
 
     /// List containers (optionally filter by label)
@@ -205,6 +375,14 @@ impl DockerClient {
             host_config.port_bindings = Some(config_opts.port_bindings);
         }
 
+        if config_opts.restart_policy.is_some() {
+            host_config.restart_policy = config_opts.restart_policy;
+        }
+
+        if config_opts.network_mode.is_some() {
+            host_config.network_mode = config_opts.network_mode;
+        }
+
         config.host_config = Some(host_config);
 
         let sanitized_name = sanitize_container_name(name);
@@ -244,6 +422,15 @@ impl DockerClient {
         Ok(())
     }
 
+    /// Kill a container with SIGKILL, without waiting for a graceful stop
+    pub async fn kill_container(&self, container_id: &str) -> Result<(), DockerError> {
+        let options = KillContainerOptions { signal: "SIGKILL" };
+        self.docker
+            .kill_container(container_id, Some(options))
+            .await?;
+        Ok(())
+    }
+
     /// Remove a container
     pub async fn remove_container(
         &self,
@@ -290,7 +477,8 @@ impl DockerClient {
             container: container_id,
             repo: image_name,
             tag,
-            pause: true,
+            // Rootless Podman on cgroups v1 can't pause containers
+            pause: self.backend == EngineBackend::Docker,
             ..Default::default()
         };
 
@@ -301,6 +489,35 @@ impl DockerClient {
         Ok(response.id.unwrap_or_default())
     }
 
+    /// Create a bridge network unless one with this name already exists
+    pub async fn ensure_network(&self, name: &str) -> Result<(), DockerError> {
+        match self.docker.inspect_network::<String>(name, None).await {
+            Ok(_) => return Ok(()),
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        debug!(network = %name, "Creating Docker network");
+        let mut labels = HashMap::new();
+        labels.insert("orkestrator.managed", "true");
+        let options = CreateNetworkOptions {
+            name,
+            driver: "bridge",
+            labels,
+            ..Default::default()
+        };
+        match self.docker.create_network(options).await {
+            Ok(_) => Ok(()),
+            // Another environment created it concurrently
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 409, ..
+            }) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// Remove an image
     pub async fn remove_image(&self, image_name: &str, force: bool) -> Result<(), DockerError> {
         let options = RemoveImageOptions {
@@ -352,6 +569,20 @@ impl DockerClient {
         Ok(output)
     }
 
+    /// Change the restart policy of an existing container
+    pub async fn update_restart_policy(
+        &self,
+        container_id: &str,
+        restart_policy: RestartPolicy,
+    ) -> Result<(), DockerError> {
+        let options = UpdateContainerOptions::<String> {
+            restart_policy: Some(restart_policy),
+            ..Default::default()
+        };
+        self.docker.update_container(container_id, options).await?;
+        Ok(())
+    }
+
     /// Inspect a container
     pub async fn inspect_container(
         &self,
@@ -383,6 +614,17 @@ impl DockerClient {
         Ok(status.to_lowercase() == "running")
     }
 
+    /// Get the workspace directory of a container (its configured working
+    /// directory), falling back to the default workspace path
+    pub async fn get_workspace_path(&self, container_id: &str) -> Result<String, DockerError> {
+        let info = self.inspect_container(container_id).await?;
+        Ok(info
+            .config
+            .and_then(|c| c.working_dir)
+            .filter(|dir| !dir.is_empty())
+            .unwrap_or_else(|| crate::models::DEFAULT_WORKSPACE_PATH.to_string()))
+    }
+
     /// Get the host port mapped to a specific container port
     /// Returns None if the port is not mapped or the container is not running
     pub async fn get_host_port(
@@ -649,14 +891,70 @@ impl DockerClient {
         cmd: Vec<&str>,
     ) -> Result<(String, String, i64), DockerError> {
         let output = self.exec_command_internal(container_id, cmd, false).await?;
-
-        // Inspect the exec to get the exit code
-        let inspect = self.docker.inspect_exec(&output.exec_id).await?;
-        let exit_code = inspect.exit_code.unwrap_or(-1);
+        let exit_code = self.exec_exit_code(&output.exec_id).await?;
 
         Ok((output.stdout, output.stderr, exit_code))
     }
 
+    /// Execute a command in a running container, passing stdout and stderr
+    /// chunks to `on_output` as they arrive. Returns the exit code.
+    pub async fn exec_command_streaming(
+        &self,
+        container_id: &str,
+        cmd: Vec<&str>,
+        mut on_output: impl FnMut(String),
+    ) -> Result<i64, DockerError> {
+        let config = CreateExecOptions {
+            cmd: Some(cmd),
+            attach_stdout: Some(true),
+            attach_stderr: Some(true),
+            ..Default::default()
+        };
+
+        let exec = self.docker.create_exec(container_id, config).await?;
+
+        match self.docker.start_exec(&exec.id, None).await? {
+            StartExecResults::Attached { mut output, .. } => {
+                while let Some(msg) = output.next().await {
+                    match msg {
+                        Ok(bollard::container::LogOutput::StdOut { message })
+                        | Ok(bollard::container::LogOutput::StdErr { message }) => {
+                            on_output(String::from_utf8_lossy(&message).to_string());
+                        }
+                        Ok(_) => {}
+                        Err(e) => {
+                            return Err(DockerError::OperationFailed(format!(
+                                "Error reading exec output: {}",
+                                e
+                            )));
+                        }
+                    }
+                }
+            }
+            StartExecResults::Detached => {
+                return Err(DockerError::OperationFailed(
+                    "Exec started in detached mode".to_string(),
+                ));
+            }
+        }
+
+        self.exec_exit_code(&exec.id).await
+    }
+
+    /// Exit code of a finished exec. Podman can still report the exec as
+    /// running for a moment after its output stream closes.
+    async fn exec_exit_code(&self, exec_id: &str) -> Result<i64, DockerError> {
+        let mut inspect = self.docker.inspect_exec(exec_id).await?;
+        for _ in 0..EXEC_EXIT_POLL_ATTEMPTS {
+            if inspect.exit_code.is_some() && inspect.running != Some(true) {
+                break;
+            }
+            tokio::time::sleep(EXEC_EXIT_POLL_INTERVAL).await;
+            inspect = self.docker.inspect_exec(exec_id).await?;
+        }
+        Ok(inspect.exit_code.unwrap_or(-1))
+    }
+
     /// Internal helper for executing commands in a container
     /// Returns an ExecOutput with stdout, stderr, and exec_id. If include_stderr is true, stderr is appended to stdout.
     async fn exec_command_internal(
@@ -808,39 +1106,28 @@ impl DockerClient {
         Ok(result)
     }
 
-    /// Perform Docker system prune - removes unused containers, images, networks, and volumes
+    /// Perform Docker system prune - removes unused containers, images, networks, and volumes.
+    /// Pinned containers and images are skipped.
     /// Returns the total space reclaimed in bytes
     pub async fn system_prune(
         &self,
         prune_volumes: bool,
+        pinned_containers: &[String],
+        pinned_images: &[String],
     ) -> Result<SystemPruneResult, DockerError> {
         let mut result = SystemPruneResult::default();
 
         // Prune stopped containers
-        let container_prune = self
-            .docker
-            .prune_containers(None::<PruneContainersOptions<String>>)
-            .await?;
-        result.containers_deleted = container_prune
-            .containers_deleted
-            .map(|v| v.len() as u32)
-            .unwrap_or(0);
-        if let Some(space) = container_prune.space_reclaimed {
-            result.space_reclaimed += space as u64;
-        }
+        let container_prune = self.prune_stopped_containers(pinned_containers).await?;
+        result.containers_deleted = container_prune.deleted;
+        result.space_reclaimed += container_prune.space_reclaimed;
+        result.skipped_pinned.extend(container_prune.skipped);
 
         // Prune unused images (dangling only for safety)
-        let image_prune = self
-            .docker
-            .prune_images(None::<PruneImagesOptions<String>>)
-            .await?;
-        result.images_deleted = image_prune
-            .images_deleted
-            .map(|v| v.len() as u32)
-            .unwrap_or(0);
-        if let Some(space) = image_prune.space_reclaimed {
-            result.space_reclaimed += space as u64;
-        }
+        let image_prune = self.prune_dangling_images(pinned_images).await?;
+        result.images_deleted = image_prune.deleted;
+        result.space_reclaimed += image_prune.space_reclaimed;
+        result.skipped_pinned.extend(image_prune.skipped);
 
         // Prune unused networks
         let network_prune = self
@@ -872,6 +1159,179 @@ impl DockerClient {
 
         Ok(result)
     }
+
+    /// Remove stopped containers, skipping pinned ones and any that fail to
+    /// be removed
+    pub async fn prune_stopped_containers(
+        &self,
+        pinned: &[String],
+    ) -> Result<PruneOutcome, DockerError> {
+        if pinned.is_empty() {
+            let response = self
+                .docker
+                .prune_containers(None::<PruneContainersOptions<String>>)
+                .await?;
+            return Ok(PruneOutcome {
+                deleted: response.containers_deleted.map_or(0, |v| v.len() as u32),
+                space_reclaimed: response.space_reclaimed.unwrap_or(0).max(0) as u64,
+                skipped: Vec::new(),
+            });
+        }
+
+        // The prune API can't exclude containers, so remove the unpinned
+        // stopped containers one by one. A container that can't be removed
+        // is logged and skipped, so one failure doesn't abort the prune.
+        let options = ListContainersOptions {
+            all: true,
+            size: true,
+            filters: HashMap::from([("status", vec!["created", "exited", "dead"])]),
+            ..Default::default()
+        };
+        let mut outcome = PruneOutcome::default();
+        for container in self.docker.list_containers(Some(options)).await? {
+            let Some(id) = container.id else {
+                continue;
+            };
+            let names: Vec<&str> = container
+                .names
+                .iter()
+                .flatten()
+                .map(|name| name.trim_start_matches('/'))
+                .collect();
+            if is_pinned(pinned, &id, &names) {
+                outcome
+                    .skipped
+                    .push(names.first().map_or_else(|| id.clone(), |n| n.to_string()));
+                continue;
+            }
+            if let Err(e) = self
+                .docker
+                .remove_container(&id, None::<RemoveContainerOptions>)
+                .await
+            {
+                warn!(container_id = %id, error = %e, "Failed to remove stopped container during prune");
+                continue;
+            }
+            outcome.deleted += 1;
+            outcome.space_reclaimed += container.size_rw.unwrap_or(0).max(0) as u64;
+        }
+        Ok(outcome)
+    }
+
+    /// Remove dangling images, skipping pinned ones and any that fail to be
+    /// removed. Dangling images have no tags, so only pins by image ID can
+    /// match them.
+    pub async fn prune_dangling_images(
+        &self,
+        pinned: &[String],
+    ) -> Result<PruneOutcome, DockerError> {
+        if pinned.is_empty() {
+            let response = self
+                .docker
+                .prune_images(None::<PruneImagesOptions<String>>)
+                .await?;
+            return Ok(PruneOutcome {
+                deleted: response.images_deleted.map_or(0, |v| v.len() as u32),
+                space_reclaimed: response.space_reclaimed.unwrap_or(0).max(0) as u64,
+                skipped: Vec::new(),
+            });
+        }
+
+        let options = ListImagesOptions {
+            filters: HashMap::from([("dangling", vec!["true"])]),
+            ..Default::default()
+        };
+        let mut outcome = PruneOutcome::default();
+        for image in self.docker.list_images(Some(options)).await? {
+            // Dangling images have no tags, so pins are stored and matched by ID
+            if is_pinned(pinned, &image.id, &[]) {
+                outcome.skipped.push(image.id);
+                continue;
+            }
+            if let Err(e) = self.remove_image(&image.id, false).await {
+                warn!(image_id = %image.id, error = %e, "Failed to remove dangling image during prune");
+                continue;
+            }
+            outcome.deleted += 1;
+            outcome.space_reclaimed += image.size.max(0) as u64;
+        }
+        Ok(outcome)
+    }
+
+    /// Remove volumes not used by any container
+    pub async fn prune_unused_volumes(&self) -> Result<PruneOutcome, DockerError> {
+        let response = self
+            .docker
+            .prune_volumes(None::<PruneVolumesOptions<String>>)
+            .await?;
+        Ok(PruneOutcome {
+            deleted: response.volumes_deleted.map_or(0, |v| v.len() as u32),
+            space_reclaimed: response.space_reclaimed.unwrap_or(0).max(0) as u64,
+            skipped: Vec::new(),
+        })
+    }
+
+    /// Remove the build cache. Bollard has no build cache prune, so this runs
+    /// `builder prune` through the engine CLI and measures the build cache
+    /// before and after.
+    pub async fn prune_build_cache(&self) -> Result<PruneOutcome, DockerError> {
+        let before = self.disk_usage().await?;
+
+        let cli = self.backend.cli();
+        let output = tokio::process::Command::new(cli)
+            .args(["builder", "prune", "--force"])
+            .output()
+            .await?;
+        if !output.status.success() {
+            return Err(DockerError::OperationFailed(format!(
+                "{} builder prune failed: {}",
+                cli,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        let after = self.disk_usage().await?;
+        let records = |df: &SystemDataUsageResponse| df.build_cache.as_ref().map_or(0, Vec::len);
+        Ok(PruneOutcome {
+            deleted: records(&before).saturating_sub(records(&after)) as u32,
+            space_reclaimed: build_cache_size(&before).saturating_sub(build_cache_size(&after)),
+            skipped: Vec::new(),
+        })
+    }
+}
+
+/// Total size of the build cache records in a `docker system df` response
+pub fn build_cache_size(df: &SystemDataUsageResponse) -> u64 {
+    df.build_cache
+        .iter()
+        .flatten()
+        .filter_map(|cache| cache.size)
+        .map(|size| size.max(0) as u64)
+        .sum()
+}
+
+/// Whether a container or image matches a pinned entry, by full or short
+/// (12+ character) ID or by one of its names or tags
+pub fn is_pinned(pinned: &[String], id: &str, names: &[&str]) -> bool {
+    let id = id.trim_start_matches("sha256:");
+    pinned.iter().any(|entry| {
+        let entry = entry.trim_start_matches("sha256:");
+        (entry.len() >= 12 && id.starts_with(entry))
+            || names
+                .iter()
+                .any(|name| *name == entry || name.strip_suffix(":latest") == Some(entry))
+    })
+}
+
+/// Result of pruning one category of Docker data
+#[derive(Debug, Default)]
+pub struct PruneOutcome {
+    /// Number of items deleted
+    pub deleted: u32,
+    /// Space reclaimed in bytes
+    pub space_reclaimed: u64,
+    /// Pinned items that were left in place
+    pub skipped: Vec<String>,
 }
 
 /// Result of a Docker system prune operation
@@ -887,6 +1347,8 @@ pub struct SystemPruneResult {
     pub volumes_deleted: u32,
     /// Total space reclaimed in bytes
     pub space_reclaimed: u64,
+    /// Pinned containers and images that were left in place
+    pub skipped_pinned: Vec<String>,
 }
 
 // Global Docker client instance
@@ -914,6 +1376,16 @@ pub fn get_docker_client() -> Result<&'static DockerClient, DockerError> {
     Ok(*cached.insert(client))
 }
 
+/// Drop the cached client so the next `get_docker_client` reconnects, e.g.
+/// after the configured container engine changed
+pub fn reset_docker_client() {
+    if let Some(client_slot) = DOCKER_CLIENT.get() {
+        if let Ok(mut cached) = client_slot.lock() {
+            *cached = None;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -926,6 +1398,41 @@ mod tests {
         assert!(client.is_ok() || client.is_err());
     }
 
+    #[test]
+    fn test_podman_socket_candidates() {
+        let candidates = podman_socket_candidates(
+            Some("unix:///custom/podman.sock"),
+            Some(Path::new("/run/user/1000")),
+            Some(Path::new("/home/dev")),
+        );
+        assert_eq!(candidates[0], PathBuf::from("/custom/podman.sock"));
+        assert_eq!(
+            candidates[1],
+            PathBuf::from("/run/user/1000/podman/podman.sock")
+        );
+        assert!(candidates.contains(&PathBuf::from("/run/podman/podman.sock")));
+        assert!(candidates.contains(&PathBuf::from(
+            "/home/dev/.local/share/containers/podman/machine/podman.sock"
+        )));
+
+        // Non-unix CONTAINER_HOST values (e.g. ssh://) are not local sockets
+        let candidates =
+            podman_socket_candidates(Some("ssh://core@host/run/podman.sock"), None, None);
+        assert_eq!(candidates[0], PathBuf::from("/run/podman/podman.sock"));
+    }
+
+    #[test]
+    fn test_resolve_engine_backend_explicit() {
+        assert_eq!(
+            resolve_engine_backend(ContainerEngineKind::Docker),
+            EngineBackend::Docker
+        );
+        assert_eq!(
+            resolve_engine_backend(ContainerEngineKind::Podman),
+            EngineBackend::Podman
+        );
+    }
+
     #[test]
     fn test_sanitize_container_name_with_spaces() {
         assert_eq!(
@@ -996,4 +1503,23 @@ mod tests {
         assert!(!result.ends_with('-'));
         assert!(result.len() <= MAX_CONTAINER_NAME_LEN);
     }
+
+    #[test]
+    fn test_is_pinned_matches_ids_names_and_tags() {
+        let pinned = vec![
+            "shared-postgres".to_string(),
+            "0123456789ab".to_string(),
+            "redis".to_string(),
+        ];
+        let full_id = "sha256:0123456789abcdef0123456789abcdef";
+
+        assert!(is_pinned(&pinned, full_id, &[]));
+        // Pinned images are stored by their full ID
+        assert!(is_pinned(&[full_id.to_string()], full_id, &[]));
+        assert!(is_pinned(&pinned, "ffff", &["shared-postgres"]));
+        assert!(is_pinned(&pinned, "ffff", &["redis:latest"]));
+        assert!(!is_pinned(&pinned, "ffff", &["redis:7"]));
+        // Short prefixes would match too many IDs
+        assert!(!is_pinned(&["0123".to_string()], full_id, &[]));
+    }
 }
@@ -0,0 +1,442 @@
+// Docker Compose support
+// Finds a compose file in a project checkout and extracts its services and ports
+// (from `docker compose config`, so compose does the YAML parsing), and runs
+// `docker compose` up/down for repositories that opt in to compose support.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+
+use crate::models::{EnvironmentStatus, PortProtocol};
+
+/// Compose file names, in the order Docker Compose looks for them
+pub const COMPOSE_FILE_NAMES: [&str; 4] = [
+    "compose.yaml",
+    "compose.yml",
+    "docker-compose.yaml",
+    "docker-compose.yml",
+];
+
+/// A compose project found in a repository
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComposeProject {
+    /// Path of the compose file that was read
+    pub file_path: String,
+    pub services: Vec<ComposeService>,
+}
+
+/// A service declared in a compose file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComposeService {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    pub ports: Vec<ComposePort>,
+}
+
+/// A published port of a compose service
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComposePort {
+    /// Host port, if one is fixed in the compose file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_port: Option<u16>,
+    pub container_port: u16,
+    pub protocol: PortProtocol,
+}
+
+/// Path of the compose file in `dir`, if there is one
+pub async fn find_compose_file(dir: &Path) -> std::io::Result<Option<PathBuf>> {
+    for file_name in COMPOSE_FILE_NAMES {
+        let path = dir.join(file_name);
+        if tokio::fs::try_exists(&path).await? {
+            return Ok(Some(path));
+        }
+    }
+    Ok(None)
+}
+
+/// Look for a compose file in `dir` and read its services through
+/// `docker compose config`. Returns `Ok(None)` when the directory has no
+/// compose file.
+pub async fn detect_compose_project(dir: &Path) -> Result<Option<ComposeProject>, String> {
+    let Some(path) = find_compose_file(dir)
+        .await
+        .map_err(|e| format!("Failed to read compose file: {}", e))?
+    else {
+        return Ok(None);
+    };
+
+    let output = Command::new(super::engine_cli())
+        .arg("compose")
+        .arg("-f")
+        .arg(&path)
+        .args(["config", "--format", "json"])
+        .current_dir(dir)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run docker compose config: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "docker compose config failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(Some(ComposeProject {
+        file_path: path.to_string_lossy().to_string(),
+        services: parse_compose_config(&String::from_utf8_lossy(&output.stdout))?,
+    }))
+}
+
+/// Compose project name (`docker compose -p`) for an environment.
+/// Compose only accepts lowercase letters, digits, `-` and `_`.
+pub fn compose_project_name(environment_id: &str) -> String {
+    let name: String = environment_id
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    format!("orkestrator-{}", name)
+}
+
+/// Which output stream a line of `docker compose` output came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComposeOutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Run `docker compose -p <project_name> <args>` in `context_dir`, passing each
+/// output line to `on_line` as it arrives
+pub async fn run_compose(
+    context_dir: &Path,
+    project_name: &str,
+    args: &[&str],
+    mut on_line: impl FnMut(ComposeOutputStream, String),
+) -> Result<(), String> {
+    let mut child = Command::new(super::engine_cli())
+        .arg("compose")
+        .arg("-p")
+        .arg(project_name)
+        .args(args)
+        .current_dir(context_dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to run docker compose: {}", e))?;
+
+    let mut stdout = child.stdout.take().map(|out| BufReader::new(out).lines());
+    let mut stderr = child.stderr.take().map(|err| BufReader::new(err).lines());
+
+    while stdout.is_some() || stderr.is_some() {
+        tokio::select! {
+            line = async { stdout.as_mut().unwrap().next_line().await }, if stdout.is_some() => {
+                match line {
+                    Ok(Some(line)) => on_line(ComposeOutputStream::Stdout, line),
+                    _ => stdout = None,
+                }
+            }
+            line = async { stderr.as_mut().unwrap().next_line().await }, if stderr.is_some() => {
+                match line {
+                    Ok(Some(line)) => on_line(ComposeOutputStream::Stderr, line),
+                    _ => stderr = None,
+                }
+            }
+        }
+    }
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| format!("Failed to wait for docker compose: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "docker compose {} failed ({})",
+            args.join(" "),
+            status
+        ))
+    }
+}
+
+/// State of one container in a compose project, from `docker compose ps`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ComposeContainerState {
+    #[serde(rename = "Service", default)]
+    pub service: String,
+    /// running, exited, created, restarting, paused or dead
+    #[serde(rename = "State", default)]
+    pub state: String,
+    #[serde(rename = "ExitCode", default)]
+    pub exit_code: i64,
+}
+
+/// List the containers of a compose project
+pub async fn compose_ps(
+    context_dir: &Path,
+    project_name: &str,
+) -> Result<Vec<ComposeContainerState>, String> {
+    let output = Command::new(super::engine_cli())
+        .args([
+            "compose",
+            "-p",
+            project_name,
+            "ps",
+            "--all",
+            "--format",
+            "json",
+        ])
+        .current_dir(context_dir)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run docker compose ps: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "docker compose ps failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    parse_compose_ps(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parse `docker compose ps --format json`, which is a JSON array in older
+/// Compose releases and one JSON object per line in newer ones
+fn parse_compose_ps(output: &str) -> Result<Vec<ComposeContainerState>, String> {
+    let output = output.trim();
+    if output.starts_with('[') {
+        return serde_json::from_str(output)
+            .map_err(|e| format!("Failed to parse docker compose ps output: {}", e));
+    }
+    output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line)
+                .map_err(|e| format!("Failed to parse docker compose ps output: {}", e))
+        })
+        .collect()
+}
+
+/// Map the states of a compose project's containers to a single environment status
+pub fn aggregate_compose_status(containers: &[ComposeContainerState]) -> EnvironmentStatus {
+    let failed = containers
+        .iter()
+        .any(|c| c.state == "dead" || (c.state == "exited" && c.exit_code != 0));
+    if failed {
+        return EnvironmentStatus::Error;
+    }
+    if !containers.is_empty() && containers.iter().all(|c| c.state == "running") {
+        return EnvironmentStatus::Running;
+    }
+    if containers
+        .iter()
+        .any(|c| matches!(c.state.as_str(), "running" | "created" | "restarting"))
+    {
+        return EnvironmentStatus::Creating;
+    }
+    EnvironmentStatus::Stopped
+}
+
+/// The parts of `docker compose config --format json` output we read
+#[derive(Deserialize)]
+struct ComposeConfig {
+    #[serde(default)]
+    services: BTreeMap<String, ComposeConfigService>,
+}
+
+#[derive(Deserialize)]
+struct ComposeConfigService {
+    #[serde(default)]
+    image: Option<String>,
+    #[serde(default)]
+    ports: Vec<ComposeConfigPort>,
+}
+
+/// A port in the normalized long syntax `docker compose config` emits
+#[derive(Deserialize)]
+struct ComposeConfigPort {
+    target: u16,
+    /// A string ("8080" or a range "8080-8090") in current Compose releases,
+    /// a number in older ones
+    #[serde(default)]
+    published: Option<serde_json::Value>,
+    #[serde(default)]
+    protocol: Option<String>,
+}
+
+/// Extract services and their published ports from the JSON printed by
+/// `docker compose config --format json`
+fn parse_compose_config(output: &str) -> Result<Vec<ComposeService>, String> {
+    let config: ComposeConfig = serde_json::from_str(output)
+        .map_err(|e| format!("Failed to parse docker compose config output: {}", e))?;
+
+    Ok(config
+        .services
+        .into_iter()
+        .map(|(name, service)| ComposeService {
+            name,
+            image: service.image,
+            ports: service
+                .ports
+                .into_iter()
+                .map(|port| ComposePort {
+                    host_port: port.published.as_ref().and_then(published_port),
+                    container_port: port.target,
+                    protocol: match port.protocol.as_deref() {
+                        Some("udp") => PortProtocol::Udp,
+                        _ => PortProtocol::Tcp,
+                    },
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+/// First host port of a `published` value
+fn published_port(value: &serde_json::Value) -> Option<u16> {
+    match value {
+        serde_json::Value::String(published) => parse_port_range(published).map(|(start, _)| start),
+        serde_json::Value::Number(published) => published
+            .as_u64()
+            .and_then(|port| u16::try_from(port).ok())
+            .filter(|port| *port > 0),
+        _ => None,
+    }
+}
+
+/// Parse `8080` or `8080-8090` into an inclusive range
+fn parse_port_range(value: &str) -> Option<(u16, u16)> {
+    let (start, end) = match value.split_once('-') {
+        Some((start, end)) => (start.parse().ok()?, end.parse().ok()?),
+        None => {
+            let port = value.parse().ok()?;
+            (port, port)
+        }
+    };
+    (start > 0 && start <= end).then_some((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn port(host_port: Option<u16>, container_port: u16, protocol: PortProtocol) -> ComposePort {
+        ComposePort {
+            host_port,
+            container_port,
+            protocol,
+        }
+    }
+
+    #[test]
+    fn test_parse_compose_config_services_and_ports() {
+        let output = r#"{
+            "name": "app",
+            "services": {
+                "web": {
+                    "image": "node:20",
+                    "ports": [
+                        {"mode": "ingress", "target": 3000, "published": "3000", "protocol": "tcp"},
+                        {"mode": "ingress", "host_ip": "127.0.0.1", "target": 9229, "published": "9229-9230"},
+                        {"mode": "ingress", "target": 53, "published": 5353, "protocol": "udp"},
+                        {"mode": "ingress", "target": 8080}
+                    ]
+                },
+                "db": {"image": "postgres:16"},
+                "worker": {"build": {"context": "."}}
+            },
+            "volumes": {"data": {}}
+        }"#;
+
+        let services = parse_compose_config(output).unwrap();
+
+        assert_eq!(services.len(), 3);
+        assert_eq!(services[0].name, "db");
+        assert_eq!(services[0].image.as_deref(), Some("postgres:16"));
+        assert!(services[0].ports.is_empty());
+        assert_eq!(services[1].name, "web");
+        assert_eq!(
+            services[1].ports,
+            vec![
+                port(Some(3000), 3000, PortProtocol::Tcp),
+                port(Some(9229), 9229, PortProtocol::Tcp),
+                port(Some(5353), 53, PortProtocol::Udp),
+                port(None, 8080, PortProtocol::Tcp),
+            ]
+        );
+        assert_eq!(services[2].name, "worker");
+        assert!(services[2].image.is_none());
+
+        assert!(parse_compose_config("{}").unwrap().is_empty());
+        assert!(parse_compose_config("not json").is_err());
+    }
+
+    #[test]
+    fn test_published_port_rejects_invalid_values() {
+        assert_eq!(published_port(&serde_json::json!("8080")), Some(8080));
+        assert_eq!(published_port(&serde_json::json!("9000-8000")), None);
+        assert_eq!(published_port(&serde_json::json!(70000)), None);
+        assert_eq!(published_port(&serde_json::json!(0)), None);
+        assert_eq!(published_port(&serde_json::json!("")), None);
+    }
+
+    fn container(state: &str, exit_code: i64) -> ComposeContainerState {
+        ComposeContainerState {
+            service: "web".to_string(),
+            state: state.to_string(),
+            exit_code,
+        }
+    }
+
+    #[test]
+    fn test_compose_project_name_is_valid() {
+        assert_eq!(
+            compose_project_name("ABC-123_x.y"),
+            "orkestrator-abc-123_xy"
+        );
+    }
+
+    #[test]
+    fn test_parse_compose_ps_formats() {
+        let lines = "{\"Service\":\"web\",\"State\":\"running\",\"ExitCode\":0}\n{\"Service\":\"db\",\"State\":\"exited\",\"ExitCode\":1}\n";
+        let parsed = parse_compose_ps(lines).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].service, "db");
+        assert_eq!(parsed[1].exit_code, 1);
+
+        let array = r#"[{"Service":"web","State":"running"}]"#;
+        assert_eq!(parse_compose_ps(array).unwrap()[0].state, "running");
+        assert!(parse_compose_ps("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_aggregate_compose_status() {
+        assert_eq!(aggregate_compose_status(&[]), EnvironmentStatus::Stopped);
+        assert_eq!(
+            aggregate_compose_status(&[container("running", 0), container("running", 0)]),
+            EnvironmentStatus::Running
+        );
+        assert_eq!(
+            aggregate_compose_status(&[container("running", 0), container("created", 0)]),
+            EnvironmentStatus::Creating
+        );
+        assert_eq!(
+            aggregate_compose_status(&[container("running", 0), container("exited", 137)]),
+            EnvironmentStatus::Error
+        );
+        assert_eq!(
+            aggregate_compose_status(&[container("exited", 0)]),
+            EnvironmentStatus::Stopped
+        );
+    }
+}
@@ -2,8 +2,15 @@
 // Handles creating environments with proper configuration
 
 use super::client::{get_docker_client, CreateContainerConfig, DockerError};
-use crate::models::{Environment, EnvironmentStatus, NetworkAccessMode, PortMapping};
-use bollard::models::PortBinding;
+use super::engine::get_container_engine;
+use crate::host_resources::HostCapacity;
+use crate::models::{
+    Environment, EnvironmentStatus, GitRemote, NetworkAccessMode, PortMapping, PortProtocol,
+    RestartPolicy, DEFAULT_WORKSPACE_PATH,
+};
+use bollard::models::{PortBinding, RestartPolicy as DockerRestartPolicy, RestartPolicyNameEnum};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 use std::collections::HashMap;
 use tracing::{debug, warn};
 
@@ -16,6 +23,17 @@ pub const CONTAINER_LABEL_APP_VALUE: &str = "orkestrator-ai";
 pub const CONTAINER_LABEL_ENV_ID: &str = "environment-id";
 pub const CONTAINER_LABEL_PROJECT_ID: &str = "project-id";
 
+/// Marker file created by workspace-setup.sh once the workspace is ready
+pub const WORKSPACE_SETUP_COMPLETE_MARKER: &str = "/tmp/.workspace-setup-complete";
+/// Progress log written by the entrypoint while the container initializes
+pub const ENTRYPOINT_PROGRESS_LOG: &str = "/tmp/.entrypoint-progress";
+
+/// Ports every container exposes with a dynamic host port, for the OpenCode
+/// server and the Claude and Codex bridge servers
+const OPENCODE_SERVER_PORT: u16 = 4096;
+const CLAUDE_BRIDGE_PORT: u16 = 4097;
+const CODEX_BRIDGE_PORT: u16 = 4098;
+
 /// Configuration for creating a new container
 #[derive(Debug, Clone)]
 pub struct ContainerConfig {
@@ -31,6 +49,8 @@ pub struct ContainerConfig {
     pub branch: String,
     /// Base branch to use when creating new environment branches
     pub base_branch: Option<String>,
+    /// Remote the base branch is fetched from when it is not `origin`
+    pub base_remote: Option<GitRemote>,
     /// Path to .env file on host (legacy single file)
     pub env_file_path: Option<String>,
     /// Path to project's local source folder (for mounting .env files)
@@ -75,6 +95,14 @@ pub struct ContainerConfig {
     pub opencode_model: String,
     /// Entry port inside the container to expose with dynamic host port allocation
     pub entry_port: Option<u16>,
+    /// Restart policy applied to the container's HostConfig
+    pub restart_policy: RestartPolicy,
+    /// Working directory the repository is checked out into
+    pub workspace_path: String,
+    /// Docker network to join instead of the default bridge
+    pub network: Option<String>,
+    /// Commits of history to clone (None = full history)
+    pub clone_depth: Option<u32>,
 }
 
 impl ContainerConfig {
@@ -149,6 +177,7 @@ impl ContainerConfig {
             git_url: git_url.to_string(),
             branch: "main".to_string(),
             base_branch: None,
+            base_remote: None,
             env_file_path: None,
             project_local_path: None,
             claude_dir_path: claude_dir.to_string_lossy().to_string(),
@@ -171,6 +200,10 @@ impl ContainerConfig {
             files_to_copy: Vec::new(),
             opencode_model: String::new(),
             entry_port: None,
+            restart_policy: environment.restart_policy.unwrap_or_default(),
+            workspace_path: DEFAULT_WORKSPACE_PATH.to_string(),
+            network: None,
+            clone_depth: None,
         }
     }
 
@@ -192,10 +225,25 @@ impl ContainerConfig {
         self
     }
 
+    pub fn with_base_remote(mut self, remote: &GitRemote) -> Self {
+        self.base_remote = Some(remote.clone());
+        self
+    }
+
     pub fn with_files_to_copy(mut self, files: Vec<String>) -> Self {
         self.files_to_copy = files;
         self
     }
+
+    pub fn with_workspace_path(mut self, workspace_path: &str) -> Self {
+        self.workspace_path = workspace_path.to_string();
+        self
+    }
+
+    pub fn with_network(mut self, network: Option<&str>) -> Self {
+        self.network = network.map(str::to_string);
+        self
+    }
 }
 
 /// Create a new container for an environment
@@ -204,22 +252,157 @@ pub async fn create_environment_container(
     config: &ContainerConfig,
     custom_image: Option<&str>,
 ) -> Result<String, DockerError> {
-    let client = get_docker_client()?;
+    let engine = get_container_engine()?;
 
     let image_name = custom_image.unwrap_or(BASE_IMAGE);
 
     // Check if image exists
-    if !client.image_exists(image_name).await? {
+    if !engine.image_exists(image_name).await? {
         return Err(DockerError::ImageNotFound(format!(
             "Image {} not found. Please build it first.",
             image_name
         )));
     }
 
+    // Join the project's network, creating it on first use. Port bindings
+    // below still publish to the host on any bridge network.
+    if let Some(network) = config.network.as_deref() {
+        engine.ensure_network(network).await?;
+    }
+
     // Prepare environment variables
     let env = build_container_env(config);
 
-    // Prepare bind mounts - use /home/node paths for non-root container user
+    let binds = build_container_binds(config);
+    debug!(binds = ?binds, "Final container binds");
+
+    // Prepare labels
+    let mut labels = HashMap::new();
+    labels.insert(
+        CONTAINER_LABEL_APP.to_string(),
+        CONTAINER_LABEL_APP_VALUE.to_string(),
+    );
+    labels.insert(
+        CONTAINER_LABEL_ENV_ID.to_string(),
+        config.environment_id.clone(),
+    );
+    labels.insert(
+        CONTAINER_LABEL_PROJECT_ID.to_string(),
+        config.project_id.clone(),
+    );
+
+    // Build port bindings for Docker
+    let mut port_bindings: HashMap<String, Option<Vec<PortBinding>>> = HashMap::new();
+    let mut exposed_ports: HashMap<String, HashMap<(), ()>> = HashMap::new();
+
+    for mapping in &config.port_mappings {
+        let key = format!("{}/{}", mapping.container_port, mapping.protocol);
+
+        // Add to exposed ports
+        exposed_ports.insert(key.clone(), HashMap::new());
+
+        // Add to port bindings - bind to localhost only for security
+        // This prevents container ports from being accessible from other machines
+        let binding = PortBinding {
+            host_ip: Some("127.0.0.1".to_string()),
+            host_port: Some(mapping.host_port.to_string()),
+        };
+        port_bindings.insert(key, Some(vec![binding]));
+    }
+
+    // Always expose port 4096 for OpenCode server (native mode)
+    // Use dynamic host port allocation (empty string) to allow multiple environments
+    let opencode_key = format!("{}/tcp", OPENCODE_SERVER_PORT);
+    exposed_ports.insert(opencode_key.clone(), HashMap::new());
+    let opencode_binding = PortBinding {
+        host_ip: Some("127.0.0.1".to_string()),
+        host_port: Some("".to_string()), // Empty string = dynamic allocation
+    };
+    port_bindings.insert(opencode_key, Some(vec![opencode_binding]));
+    debug!(
+        "Added OpenCode server port {} with dynamic host allocation",
+        OPENCODE_SERVER_PORT
+    );
+
+    // Always expose port 4097 for Claude Bridge server (Claude native mode)
+    // Use dynamic host port allocation (empty string) to allow multiple environments
+    let claude_key = format!("{}/tcp", CLAUDE_BRIDGE_PORT);
+    exposed_ports.insert(claude_key.clone(), HashMap::new());
+    let claude_binding = PortBinding {
+        host_ip: Some("127.0.0.1".to_string()),
+        host_port: Some("".to_string()), // Empty string = dynamic allocation
+    };
+    port_bindings.insert(claude_key, Some(vec![claude_binding]));
+    debug!(
+        "Added Claude Bridge server port {} with dynamic host allocation",
+        CLAUDE_BRIDGE_PORT
+    );
+
+    // Always expose port 4098 for Codex Bridge server (Codex native mode)
+    let codex_key = format!("{}/tcp", CODEX_BRIDGE_PORT);
+    exposed_ports.insert(codex_key.clone(), HashMap::new());
+    let codex_binding = PortBinding {
+        host_ip: Some("127.0.0.1".to_string()),
+        host_port: Some("".to_string()),
+    };
+    port_bindings.insert(codex_key, Some(vec![codex_binding]));
+    debug!(
+        "Added Codex Bridge server port {} with dynamic host allocation",
+        CODEX_BRIDGE_PORT
+    );
+
+    // Expose entry port with dynamic host port allocation if configured
+    if let Some(entry_port) = config.entry_port {
+        let entry_key = format!("{}/tcp", entry_port);
+        if port_bindings.contains_key(&entry_key) {
+            warn!(
+                "Entry port {} conflicts with an existing port mapping, skipping dynamic allocation",
+                entry_port
+            );
+        } else {
+            exposed_ports.insert(entry_key.clone(), HashMap::new());
+            let entry_binding = PortBinding {
+                host_ip: Some("127.0.0.1".to_string()),
+                host_port: Some("".to_string()), // Empty string = dynamic allocation
+            };
+            port_bindings.insert(entry_key, Some(vec![entry_binding]));
+            debug!(
+                "Added entry port {} with dynamic host allocation",
+                entry_port
+            );
+        }
+    }
+
+    if !config.port_mappings.is_empty() {
+        debug!("User port mappings: {:?}", config.port_mappings);
+    }
+
+    // Build container configuration with capabilities and resource limits
+    let container_config = CreateContainerConfig {
+        env,
+        binds,
+        labels,
+        working_dir: Some(config.workspace_path.clone()),
+        cpu_limit: config.cpu_limit,
+        memory_limit: config.memory_limit,
+        // Add NET_ADMIN capability for firewall initialization
+        cap_add: vec!["NET_ADMIN".to_string()],
+        port_bindings,
+        exposed_ports,
+        restart_policy: Some(docker_restart_policy(config.restart_policy)),
+        network_mode: config.network.clone(),
+    };
+
+    // Create the container
+    let container_id = engine
+        .create_container(&config.name, image_name, container_config)
+        .await?;
+
+    Ok(container_id)
+}
+
+/// Bind mounts for a container - use /home/node paths for non-root container user
+fn build_container_binds(config: &ContainerConfig) -> Vec<String> {
     let mut binds = vec![
         // Mount ~/.claude read-only to a separate location for copying config
         // The entrypoint will copy necessary files to the writable ~/.claude directory
@@ -276,43 +459,31 @@ pub async fn create_environment_container(
 
     // Mount .env and .env.local from project's local source folder if available
     if let Some(local_path) = &config.project_local_path {
-        println!("[create_container] Project local path: {}", local_path);
+        debug!(path = %local_path, "Project local path");
         let local_path = std::path::Path::new(local_path);
 
         // Mount .env if it exists
         let env_file = local_path.join(".env");
         if env_file.exists() {
-            println!(
-                "[create_container] Mounting .env from: {}",
-                env_file.display()
-            );
+            debug!(path = %env_file.display(), "Mounting project .env");
             binds.push(format!("{}:/project-env/.env:ro", env_file.display()));
         } else {
-            println!(
-                "[create_container] .env not found at: {}",
-                env_file.display()
-            );
+            debug!(path = %env_file.display(), "Project .env not found");
         }
 
         // Mount .env.local if it exists
         let env_local_file = local_path.join(".env.local");
         if env_local_file.exists() {
-            println!(
-                "[create_container] Mounting .env.local from: {}",
-                env_local_file.display()
-            );
+            debug!(path = %env_local_file.display(), "Mounting project .env.local");
             binds.push(format!(
                 "{}:/project-env/.env.local:ro",
                 env_local_file.display()
             ));
         } else {
-            println!(
-                "[create_container] .env.local not found at: {}",
-                env_local_file.display()
-            );
+            debug!(path = %env_local_file.display(), "Project .env.local not found");
         }
     } else {
-        println!("[create_container] No project local path provided");
+        debug!("No project local path provided");
     }
 
     // Mount additional files to copy from project local path
@@ -361,132 +532,84 @@ pub async fn create_environment_container(
         }
     }
 
-    println!("[create_container] Final binds: {:?}", binds);
-
-    // Prepare labels
-    let mut labels = HashMap::new();
-    labels.insert(
-        CONTAINER_LABEL_APP.to_string(),
-        CONTAINER_LABEL_APP_VALUE.to_string(),
-    );
-    labels.insert(
-        CONTAINER_LABEL_ENV_ID.to_string(),
-        config.environment_id.clone(),
-    );
-    labels.insert(
-        CONTAINER_LABEL_PROJECT_ID.to_string(),
-        config.project_id.clone(),
-    );
-
-    // Build port bindings for Docker
-    let mut port_bindings: HashMap<String, Option<Vec<PortBinding>>> = HashMap::new();
-    let mut exposed_ports: HashMap<String, HashMap<(), ()>> = HashMap::new();
-
-    for mapping in &config.port_mappings {
-        let key = format!("{}/{}", mapping.container_port, mapping.protocol);
-
-        // Add to exposed ports
-        exposed_ports.insert(key.clone(), HashMap::new());
-
-        // Add to port bindings - bind to localhost only for security
-        // This prevents container ports from being accessible from other machines
-        let binding = PortBinding {
-            host_ip: Some("127.0.0.1".to_string()),
-            host_port: Some(mapping.host_port.to_string()),
-        };
-        port_bindings.insert(key, Some(vec![binding]));
-    }
-
-    // Always expose port 4096 for OpenCode server (native mode)
-    // Use dynamic host port allocation (empty string) to allow multiple environments
-    const OPENCODE_SERVER_PORT: u16 = 4096;
-    let opencode_key = format!("{}/tcp", OPENCODE_SERVER_PORT);
-    exposed_ports.insert(opencode_key.clone(), HashMap::new());
-    let opencode_binding = PortBinding {
-        host_ip: Some("127.0.0.1".to_string()),
-        host_port: Some("".to_string()), // Empty string = dynamic allocation
-    };
-    port_bindings.insert(opencode_key, Some(vec![opencode_binding]));
-    debug!(
-        "Added OpenCode server port {} with dynamic host allocation",
-        OPENCODE_SERVER_PORT
-    );
-
-    // Always expose port 4097 for Claude Bridge server (Claude native mode)
-    // Use dynamic host port allocation (empty string) to allow multiple environments
-    const CLAUDE_BRIDGE_PORT: u16 = 4097;
-    let claude_key = format!("{}/tcp", CLAUDE_BRIDGE_PORT);
-    exposed_ports.insert(claude_key.clone(), HashMap::new());
-    let claude_binding = PortBinding {
-        host_ip: Some("127.0.0.1".to_string()),
-        host_port: Some("".to_string()), // Empty string = dynamic allocation
-    };
-    port_bindings.insert(claude_key, Some(vec![claude_binding]));
-    debug!(
-        "Added Claude Bridge server port {} with dynamic host allocation",
-        CLAUDE_BRIDGE_PORT
-    );
+    binds
+}
 
-    // Always expose port 4098 for Codex Bridge server (Codex native mode)
-    const CODEX_BRIDGE_PORT: u16 = 4098;
-    let codex_key = format!("{}/tcp", CODEX_BRIDGE_PORT);
-    exposed_ports.insert(codex_key.clone(), HashMap::new());
-    let codex_binding = PortBinding {
-        host_ip: Some("127.0.0.1".to_string()),
-        host_port: Some("".to_string()),
-    };
-    port_bindings.insert(codex_key, Some(vec![codex_binding]));
-    debug!(
-        "Added Codex Bridge server port {} with dynamic host allocation",
-        CODEX_BRIDGE_PORT
-    );
+/// Serializable view of the container an environment would be created with.
+/// Environment variables are listed by name only, since their values carry
+/// tokens and credentials.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerConfigPreview {
+    pub name: String,
+    pub image: String,
+    pub branch: String,
+    pub base_branch: Option<String>,
+    pub working_dir: String,
+    pub cpu_limit: Option<f64>,
+    pub memory_limit: Option<i64>,
+    pub port_mappings: Vec<PortMapping>,
+    /// Container ports published with a dynamically allocated host port
+    pub dynamic_ports: Vec<u16>,
+    pub mounts: Vec<String>,
+    pub env_var_names: Vec<String>,
+    pub network: Option<String>,
+    pub network_access_mode: NetworkAccessMode,
+    pub allowed_domains: Vec<String>,
+    pub restart_policy: RestartPolicy,
+    /// Commits of history cloned (None = full history)
+    pub clone_depth: Option<u32>,
+}
 
-    // Expose entry port with dynamic host port allocation if configured
+/// Describe the container `create_environment_container` would create from
+/// `config`, without touching Docker
+pub fn describe_environment_container(
+    config: &ContainerConfig,
+    custom_image: Option<&str>,
+) -> ContainerConfigPreview {
+    let mut dynamic_ports = vec![OPENCODE_SERVER_PORT, CLAUDE_BRIDGE_PORT, CODEX_BRIDGE_PORT];
     if let Some(entry_port) = config.entry_port {
-        let entry_key = format!("{}/tcp", entry_port);
-        if port_bindings.contains_key(&entry_key) {
-            warn!(
-                "Entry port {} conflicts with an existing port mapping, skipping dynamic allocation",
-                entry_port
-            );
-        } else {
-            exposed_ports.insert(entry_key.clone(), HashMap::new());
-            let entry_binding = PortBinding {
-                host_ip: Some("127.0.0.1".to_string()),
-                host_port: Some("".to_string()), // Empty string = dynamic allocation
-            };
-            port_bindings.insert(entry_key, Some(vec![entry_binding]));
-            debug!(
-                "Added entry port {} with dynamic host allocation",
-                entry_port
-            );
+        let conflicts = dynamic_ports.contains(&entry_port)
+            || config
+                .port_mappings
+                .iter()
+                .any(|m| m.container_port == entry_port && m.protocol == PortProtocol::Tcp);
+        if !conflicts {
+            dynamic_ports.push(entry_port);
         }
     }
 
-    if !config.port_mappings.is_empty() {
-        debug!("User port mappings: {:?}", config.port_mappings);
-    }
-
-    // Build container configuration with capabilities and resource limits
-    let container_config = CreateContainerConfig {
-        env,
-        binds,
-        labels,
-        working_dir: Some("/workspace".to_string()),
+    ContainerConfigPreview {
+        name: config.name.clone(),
+        image: custom_image.unwrap_or(BASE_IMAGE).to_string(),
+        branch: config.branch.clone(),
+        base_branch: config.base_branch.clone(),
+        working_dir: config.workspace_path.clone(),
         cpu_limit: config.cpu_limit,
         memory_limit: config.memory_limit,
-        // Add NET_ADMIN capability for firewall initialization
-        cap_add: vec!["NET_ADMIN".to_string()],
-        port_bindings,
-        exposed_ports,
-    };
-
-    // Create the container
-    let container_id = client
-        .create_container(&config.name, image_name, container_config)
-        .await?;
+        port_mappings: config.port_mappings.clone(),
+        dynamic_ports,
+        mounts: build_container_binds(config),
+        env_var_names: env_var_names(&build_container_env(config)),
+        network: config.network.clone(),
+        network_access_mode: config.network_access_mode.clone(),
+        allowed_domains: config.allowed_domains.clone(),
+        restart_policy: config.restart_policy,
+        clone_depth: config.clone_depth,
+    }
+}
 
-    Ok(container_id)
+/// Strip values from `KEY=value` env entries, keeping only the names
+pub fn env_var_names(env: &[String]) -> Vec<String> {
+    let mut names: Vec<String> = env
+        .iter()
+        .filter_map(|entry| entry.split('=').next())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect();
+    names.sort();
+    names.dedup();
+    names
 }
 
 fn build_container_env(config: &ContainerConfig) -> Vec<String> {
@@ -494,12 +617,22 @@ fn build_container_env(config: &ContainerConfig) -> Vec<String> {
         format!("GIT_URL={}", config.git_url),
         format!("GIT_BRANCH={}", config.branch),
         "TERM=xterm-256color".to_string(),
+        format!("WORKSPACE_DIR={}", config.workspace_path),
     ];
 
     if let Some(base_branch) = &config.base_branch {
         env.push(format!("GIT_BASE_BRANCH={}", base_branch));
     }
 
+    if let Some(remote) = &config.base_remote {
+        env.push(format!("GIT_BASE_REMOTE_NAME={}", remote.name));
+        env.push(format!("GIT_BASE_REMOTE_URL={}", remote.url));
+    }
+
+    if let Some(depth) = config.clone_depth {
+        env.push(format!("GIT_CLONE_DEPTH={}", depth));
+    }
+
     // Add OAuth credentials JSON if available (preferred for Claude Code auth)
     // This is used by the entrypoint to create ~/.claude/.credentials.json
     // which is how Linux containers authenticate with Claude Code
@@ -556,36 +689,100 @@ fn build_container_env(config: &ContainerConfig) -> Vec<String> {
 
 /// Start an environment container
 pub async fn start_environment_container(container_id: &str) -> Result<(), DockerError> {
-    let client = get_docker_client()?;
-    client.start_container(container_id).await
+    let engine = get_container_engine()?;
+    engine.start_container(container_id).await
 }
 
 /// Stop an environment container
 pub async fn stop_environment_container(container_id: &str) -> Result<(), DockerError> {
-    let client = get_docker_client()?;
-    client.stop_container(container_id, Some(10)).await
+    let engine = get_container_engine()?;
+    engine.stop_container(container_id, Some(10)).await
+}
+
+/// Kill an environment container with SIGKILL
+pub async fn kill_environment_container(container_id: &str) -> Result<(), DockerError> {
+    let engine = get_container_engine()?;
+    engine.kill_container(container_id).await
 }
 
 /// Remove an environment container
 pub async fn remove_environment_container(container_id: &str) -> Result<(), DockerError> {
-    let client = get_docker_client()?;
+    let engine = get_container_engine()?;
     // Force remove to ensure it's gone
-    client.remove_container(container_id, true).await
+    engine.remove_container(container_id, true).await
+}
+
+/// Map the app-level restart policy to Docker's HostConfig representation
+pub fn docker_restart_policy(policy: RestartPolicy) -> DockerRestartPolicy {
+    let name = match policy {
+        RestartPolicy::No => RestartPolicyNameEnum::NO,
+        RestartPolicy::OnFailure => RestartPolicyNameEnum::ON_FAILURE,
+        RestartPolicy::UnlessStopped => RestartPolicyNameEnum::UNLESS_STOPPED,
+    };
+    DockerRestartPolicy {
+        name: Some(name),
+        maximum_retry_count: None,
+    }
 }
 
 /// Get the status of an environment container
 pub async fn get_container_environment_status(
     container_id: &str,
 ) -> Result<EnvironmentStatus, DockerError> {
-    let client = get_docker_client()?;
-    let status = client.get_container_status(container_id).await?;
+    let engine = get_container_engine()?;
+    let info = engine.inspect_container(container_id).await?;
+    let state = info
+        .state
+        .ok_or_else(|| DockerError::OperationFailed("Container state not available".to_string()))?;
+
+    let status = state
+        .status
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    // Docker reports the zero time for containers that have never started
+    let has_started = state
+        .started_at
+        .as_deref()
+        .is_some_and(|t| !t.is_empty() && !t.starts_with("0001-"));
+
+    Ok(environment_status_from_docker(&status, has_started))
+}
 
-    Ok(match status.to_lowercase().as_str() {
+/// When a container was last started, or `None` if it has never started
+pub async fn get_container_started_at(
+    container_id: &str,
+) -> Result<Option<DateTime<Utc>>, DockerError> {
+    let engine = get_container_engine()?;
+    let info = engine.inspect_container(container_id).await?;
+    Ok(info
+        .state
+        .and_then(|state| state.started_at)
+        .and_then(|t| parse_docker_timestamp(&t)))
+}
+
+/// Parse a Docker RFC 3339 timestamp, treating the zero time as unset
+fn parse_docker_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
+    if timestamp.starts_with("0001-") {
+        return None;
+    }
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Map a Docker container state to an environment status.
+///
+/// A container that is `restarting` after having run before is being brought
+/// back by its restart policy, so it stays `Running` rather than flapping
+/// through `Creating`. One that has never started is still coming up.
+fn environment_status_from_docker(status: &str, has_started: bool) -> EnvironmentStatus {
+    match status.to_lowercase().as_str() {
         "running" => EnvironmentStatus::Running,
+        "restarting" if has_started => EnvironmentStatus::Running,
         "created" | "restarting" => EnvironmentStatus::Creating,
         "exited" | "dead" | "paused" => EnvironmentStatus::Stopped,
         _ => EnvironmentStatus::Error,
-    })
+    }
 }
 
 /// Check if Docker is available
@@ -602,6 +799,69 @@ pub async fn get_docker_version() -> Result<String, DockerError> {
     client.version().await
 }
 
+/// Get the CPUs and memory available to Docker
+pub async fn get_host_capacity() -> Result<HostCapacity, DockerError> {
+    let info = get_docker_client()?.system_info().await?;
+    Ok(HostCapacity {
+        cpus: info.ncpu.unwrap_or(0).max(0) as u32,
+        memory_bytes: info.mem_total.unwrap_or(0).max(0) as u64,
+    })
+}
+
+/// Shell loop that polls for the workspace setup marker every 0.5s, exiting 0
+/// once it exists or non-zero after `timeout_secs`.
+fn workspace_ready_wait_script(timeout_secs: u64) -> String {
+    format!(
+        r#"count=0
+while [ ! -f {marker} ] && [ $count -lt {max_polls} ]; do
+    sleep 0.5
+    count=$((count + 1))
+done
+[ -f {marker} ]"#,
+        marker = WORKSPACE_SETUP_COMPLETE_MARKER,
+        max_polls = timeout_secs.saturating_mul(2),
+    )
+}
+
+/// Wait for workspace setup inside a container to finish.
+/// Returns `Ok(true)` once the setup marker exists, `Ok(false)` on timeout.
+pub async fn wait_for_workspace_setup(
+    container_id: &str,
+    timeout_secs: u64,
+) -> Result<bool, DockerError> {
+    let client = get_docker_client()?;
+    let script = workspace_ready_wait_script(timeout_secs);
+    let (_, _, exit_code) = client
+        .exec_command_with_status(container_id, vec!["sh", "-c", &script])
+        .await?;
+    Ok(exit_code == 0)
+}
+
+/// Last `lines` lines of the container's entrypoint/setup progress log
+pub async fn workspace_setup_log_tail(
+    container_id: &str,
+    lines: usize,
+) -> Result<String, DockerError> {
+    let client = get_docker_client()?;
+    let lines = lines.to_string();
+    let (stdout, _, _) = client
+        .exec_command_with_status(
+            container_id,
+            vec!["tail", "-n", &lines, ENTRYPOINT_PROGRESS_LOG],
+        )
+        .await?;
+    Ok(stdout)
+}
+
+/// List the IDs of all containers (running or not) labelled with an environment ID
+pub async fn find_environment_containers(environment_id: &str) -> Result<Vec<String>, DockerError> {
+    let client = get_docker_client()?;
+    let label = format!("{}={}", CONTAINER_LABEL_ENV_ID, environment_id);
+    let containers = client.list_containers(true, Some(&label)).await?;
+
+    Ok(containers.into_iter().filter_map(|c| c.id).collect())
+}
+
 /// List all orchestrator-managed containers
 pub async fn list_managed_containers() -> Result<Vec<(String, String)>, DockerError> {
     let client = get_docker_client()?;
@@ -631,6 +891,14 @@ mod tests {
     use std::fs;
     use tempfile::tempdir;
 
+    #[test]
+    fn parse_docker_timestamp_ignores_zero_time() {
+        let parsed = parse_docker_timestamp("2026-03-01T12:30:00.123456789Z").unwrap();
+        assert_eq!(parsed.to_rfc3339(), "2026-03-01T12:30:00.123456789+00:00");
+        assert_eq!(parse_docker_timestamp("0001-01-01T00:00:00Z"), None);
+        assert_eq!(parse_docker_timestamp(""), None);
+    }
+
     #[test]
     fn test_container_config() {
         let env = Environment::new("project-123".to_string());
@@ -643,6 +911,93 @@ mod tests {
         assert_eq!(config.git_url, "https://github.com/test/repo.git");
     }
 
+    #[test]
+    fn test_env_var_names_drops_values() {
+        let env = vec![
+            "GITHUB_TOKEN=ghp_secret".to_string(),
+            "ANTHROPIC_API_KEY=sk-ant-123".to_string(),
+            "PATH=/usr/bin:/bin".to_string(),
+            "EMPTY=".to_string(),
+            "NO_VALUE".to_string(),
+        ];
+        let names = env_var_names(&env);
+        assert_eq!(
+            names,
+            vec![
+                "ANTHROPIC_API_KEY",
+                "EMPTY",
+                "GITHUB_TOKEN",
+                "NO_VALUE",
+                "PATH"
+            ]
+        );
+        assert!(names.iter().all(|n| !n.contains('=')));
+    }
+
+    #[test]
+    fn test_describe_environment_container_redacts_env_values() {
+        let env = Environment::new("project-123".to_string());
+        let mut config = ContainerConfig::new(&env, "https://github.com/test/repo.git");
+        config.github_token = Some("ghp_secret".to_string());
+        config.anthropic_api_key = Some("sk-ant-secret".to_string());
+        config.entry_port = Some(3000);
+
+        let preview = describe_environment_container(&config, None);
+
+        assert_eq!(preview.image, BASE_IMAGE);
+        assert!(preview.env_var_names.contains(&"GITHUB_TOKEN".to_string()));
+        assert!(preview
+            .env_var_names
+            .contains(&"ANTHROPIC_API_KEY".to_string()));
+        let serialized = serde_json::to_string(&preview).unwrap();
+        assert!(!serialized.contains("ghp_secret"));
+        assert!(!serialized.contains("sk-ant-secret"));
+        assert_eq!(preview.dynamic_ports, vec![4096, 4097, 4098, 3000]);
+    }
+
+    #[test]
+    fn test_workspace_ready_wait_script_polls_for_marker() {
+        let script = workspace_ready_wait_script(30);
+        assert!(script.contains("[ $count -lt 60 ]"));
+        assert!(script.ends_with(&format!("[ -f {} ]", WORKSPACE_SETUP_COMPLETE_MARKER)));
+    }
+
+    #[test]
+    fn test_environment_status_from_docker_restarting() {
+        assert_eq!(
+            environment_status_from_docker("restarting", true),
+            EnvironmentStatus::Running
+        );
+        assert_eq!(
+            environment_status_from_docker("restarting", false),
+            EnvironmentStatus::Creating
+        );
+        assert_eq!(
+            environment_status_from_docker("exited", true),
+            EnvironmentStatus::Stopped
+        );
+        assert_eq!(
+            environment_status_from_docker("removing", true),
+            EnvironmentStatus::Error
+        );
+    }
+
+    #[test]
+    fn test_docker_restart_policy_mapping() {
+        assert_eq!(
+            docker_restart_policy(RestartPolicy::No).name,
+            Some(RestartPolicyNameEnum::NO)
+        );
+        assert_eq!(
+            docker_restart_policy(RestartPolicy::UnlessStopped).name,
+            Some(RestartPolicyNameEnum::UNLESS_STOPPED)
+        );
+        assert_eq!(
+            docker_restart_policy(RestartPolicy::OnFailure).name,
+            Some(RestartPolicyNameEnum::ON_FAILURE)
+        );
+    }
+
     #[test]
     fn test_build_container_env_includes_base_branch() {
         let env = Environment::new("project-123".to_string());
@@ -657,6 +1012,24 @@ mod tests {
         assert!(vars.contains(&"GIT_BASE_BRANCH=develop".to_string()));
     }
 
+    #[test]
+    fn test_build_container_env_includes_base_remote() {
+        let env = Environment::new("project-123".to_string());
+        let config = ContainerConfig::new(&env, "https://github.com/test/repo.git")
+            .with_base_branch("fix-typo")
+            .with_base_remote(&GitRemote {
+                name: "fork-octocat".to_string(),
+                url: "https://github.com/octocat/repo.git".to_string(),
+            });
+
+        let vars = build_container_env(&config);
+
+        assert!(vars.contains(&"GIT_BASE_REMOTE_NAME=fork-octocat".to_string()));
+        assert!(
+            vars.contains(&"GIT_BASE_REMOTE_URL=https://github.com/octocat/repo.git".to_string())
+        );
+    }
+
     #[test]
     fn test_build_container_env_omits_empty_base_branch() {
         let env = Environment::new("project-123".to_string());
@@ -672,6 +1045,18 @@ mod tests {
             .any(|entry| entry.starts_with("GIT_BASE_BRANCH=")));
     }
 
+    #[test]
+    fn test_build_container_env_includes_clone_depth_when_shallow() {
+        let env = Environment::new("project-123".to_string());
+        let mut config = ContainerConfig::new(&env, "https://github.com/test/repo.git");
+        assert!(!build_container_env(&config)
+            .iter()
+            .any(|entry| entry.starts_with("GIT_CLONE_DEPTH=")));
+
+        config.clone_depth = Some(50);
+        assert!(build_container_env(&config).contains(&"GIT_CLONE_DEPTH=50".to_string()));
+    }
+
     #[test]
     fn test_container_config_detects_opencode_model_json() {
         let tmp = tempdir().unwrap();
@@ -0,0 +1,393 @@
+// Container engine abstraction
+// Commands depend on this trait instead of the Bollard client so they can be
+// tested against the in-memory engine in `mock`
+
+use bollard::models::{ContainerInspectResponse, ContainerSummary, RestartPolicy};
+use futures::future::BoxFuture;
+
+use super::client::{get_docker_client, CreateContainerConfig, DockerClient, DockerError};
+
+/// Low-level container operations the app needs from a container engine.
+/// Methods return boxed futures so the trait can be used as `dyn ContainerEngine`.
+pub trait ContainerEngine: Send + Sync {
+    fn image_exists<'a>(&'a self, image_name: &'a str) -> BoxFuture<'a, Result<bool, DockerError>>;
+
+    /// List containers, including stopped ones if `all`, optionally filtered by label
+    fn list_containers<'a>(
+        &'a self,
+        all: bool,
+        label_filter: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Vec<ContainerSummary>, DockerError>>;
+
+    /// Run a command in a container and return stdout with stderr appended
+    fn exec_command<'a>(
+        &'a self,
+        container_id: &'a str,
+        cmd: Vec<&'a str>,
+    ) -> BoxFuture<'a, Result<String, DockerError>>;
+
+    /// Run a command in a container and return stdout, stderr and the exit code
+    fn exec_command_with_status<'a>(
+        &'a self,
+        container_id: &'a str,
+        cmd: Vec<&'a str>,
+    ) -> BoxFuture<'a, Result<(String, String, i64), DockerError>>;
+
+    /// Run a command in a container, passing stdout and stderr chunks to
+    /// `on_output` as they arrive, and return the exit code
+    fn exec_command_streaming<'a>(
+        &'a self,
+        container_id: &'a str,
+        cmd: Vec<&'a str>,
+        on_output: Box<dyn FnMut(String) + Send + 'a>,
+    ) -> BoxFuture<'a, Result<i64, DockerError>>;
+
+    /// Create a container and return its ID
+    fn create_container<'a>(
+        &'a self,
+        name: &'a str,
+        image: &'a str,
+        config: CreateContainerConfig,
+    ) -> BoxFuture<'a, Result<String, DockerError>>;
+
+    fn start_container<'a>(
+        &'a self,
+        container_id: &'a str,
+    ) -> BoxFuture<'a, Result<(), DockerError>>;
+
+    /// Stop a container, waiting `timeout` seconds (default 10) before killing it
+    fn stop_container<'a>(
+        &'a self,
+        container_id: &'a str,
+        timeout: Option<i64>,
+    ) -> BoxFuture<'a, Result<(), DockerError>>;
+
+    /// Kill a container with SIGKILL, skipping the graceful stop
+    fn kill_container<'a>(
+        &'a self,
+        container_id: &'a str,
+    ) -> BoxFuture<'a, Result<(), DockerError>>;
+
+    fn remove_container<'a>(
+        &'a self,
+        container_id: &'a str,
+        force: bool,
+    ) -> BoxFuture<'a, Result<(), DockerError>>;
+
+    fn rename_container<'a>(
+        &'a self,
+        container_id: &'a str,
+        new_name: &'a str,
+    ) -> BoxFuture<'a, Result<(), DockerError>>;
+
+    fn update_restart_policy<'a>(
+        &'a self,
+        container_id: &'a str,
+        restart_policy: RestartPolicy,
+    ) -> BoxFuture<'a, Result<(), DockerError>>;
+
+    fn inspect_container<'a>(
+        &'a self,
+        container_id: &'a str,
+    ) -> BoxFuture<'a, Result<ContainerInspectResponse, DockerError>>;
+
+    fn is_container_running<'a>(
+        &'a self,
+        container_id: &'a str,
+    ) -> BoxFuture<'a, Result<bool, DockerError>>;
+
+    /// Working directory of a container, falling back to the default workspace path
+    fn get_workspace_path<'a>(
+        &'a self,
+        container_id: &'a str,
+    ) -> BoxFuture<'a, Result<String, DockerError>>;
+
+    /// Host port bound to a container port, if any
+    fn get_host_port<'a>(
+        &'a self,
+        container_id: &'a str,
+        container_port: u16,
+        protocol: &'a str,
+    ) -> BoxFuture<'a, Result<Option<u16>, DockerError>>;
+
+    /// Commit a container to `image_name:tag` and return the image ID
+    fn commit_container<'a>(
+        &'a self,
+        container_id: &'a str,
+        image_name: &'a str,
+        tag: &'a str,
+    ) -> BoxFuture<'a, Result<String, DockerError>>;
+
+    fn remove_image<'a>(
+        &'a self,
+        image_name: &'a str,
+        force: bool,
+    ) -> BoxFuture<'a, Result<(), DockerError>>;
+
+    /// Create a network with this name if it doesn't exist yet
+    fn ensure_network<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Result<(), DockerError>>;
+
+    /// CPU usage of a running container, or `None` if stats are unavailable
+    fn get_container_cpu_percent<'a>(&'a self, container_id: &'a str)
+        -> BoxFuture<'a, Option<f64>>;
+
+    /// Write a file into a container (mode 0644, owned by root)
+    fn upload_file_to_container<'a>(
+        &'a self,
+        container_id: &'a str,
+        file_path: &'a str,
+        file_data: Vec<u8>,
+    ) -> BoxFuture<'a, Result<(), DockerError>>;
+
+    /// Last `tail` lines of a container's logs (100 by default)
+    fn get_container_logs<'a>(
+        &'a self,
+        container_id: &'a str,
+        tail: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<String, DockerError>>;
+}
+
+impl ContainerEngine for DockerClient {
+    fn image_exists<'a>(&'a self, image_name: &'a str) -> BoxFuture<'a, Result<bool, DockerError>> {
+        Box::pin(DockerClient::image_exists(self, image_name))
+    }
+
+    fn list_containers<'a>(
+        &'a self,
+        all: bool,
+        label_filter: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Vec<ContainerSummary>, DockerError>> {
+        Box::pin(DockerClient::list_containers(self, all, label_filter))
+    }
+
+    fn exec_command<'a>(
+        &'a self,
+        container_id: &'a str,
+        cmd: Vec<&'a str>,
+    ) -> BoxFuture<'a, Result<String, DockerError>> {
+        Box::pin(DockerClient::exec_command(self, container_id, cmd))
+    }
+
+    fn exec_command_with_status<'a>(
+        &'a self,
+        container_id: &'a str,
+        cmd: Vec<&'a str>,
+    ) -> BoxFuture<'a, Result<(String, String, i64), DockerError>> {
+        Box::pin(DockerClient::exec_command_with_status(
+            self,
+            container_id,
+            cmd,
+        ))
+    }
+
+    fn exec_command_streaming<'a>(
+        &'a self,
+        container_id: &'a str,
+        cmd: Vec<&'a str>,
+        on_output: Box<dyn FnMut(String) + Send + 'a>,
+    ) -> BoxFuture<'a, Result<i64, DockerError>> {
+        Box::pin(DockerClient::exec_command_streaming(
+            self,
+            container_id,
+            cmd,
+            on_output,
+        ))
+    }
+
+    fn create_container<'a>(
+        &'a self,
+        name: &'a str,
+        image: &'a str,
+        config: CreateContainerConfig,
+    ) -> BoxFuture<'a, Result<String, DockerError>> {
+        Box::pin(DockerClient::create_container(self, name, image, config))
+    }
+
+    fn start_container<'a>(
+        &'a self,
+        container_id: &'a str,
+    ) -> BoxFuture<'a, Result<(), DockerError>> {
+        Box::pin(DockerClient::start_container(self, container_id))
+    }
+
+    fn stop_container<'a>(
+        &'a self,
+        container_id: &'a str,
+        timeout: Option<i64>,
+    ) -> BoxFuture<'a, Result<(), DockerError>> {
+        Box::pin(DockerClient::stop_container(self, container_id, timeout))
+    }
+
+    fn kill_container<'a>(
+        &'a self,
+        container_id: &'a str,
+    ) -> BoxFuture<'a, Result<(), DockerError>> {
+        Box::pin(DockerClient::kill_container(self, container_id))
+    }
+
+    fn remove_container<'a>(
+        &'a self,
+        container_id: &'a str,
+        force: bool,
+    ) -> BoxFuture<'a, Result<(), DockerError>> {
+        Box::pin(DockerClient::remove_container(self, container_id, force))
+    }
+
+    fn rename_container<'a>(
+        &'a self,
+        container_id: &'a str,
+        new_name: &'a str,
+    ) -> BoxFuture<'a, Result<(), DockerError>> {
+        Box::pin(DockerClient::rename_container(self, container_id, new_name))
+    }
+
+    fn update_restart_policy<'a>(
+        &'a self,
+        container_id: &'a str,
+        restart_policy: RestartPolicy,
+    ) -> BoxFuture<'a, Result<(), DockerError>> {
+        Box::pin(DockerClient::update_restart_policy(
+            self,
+            container_id,
+            restart_policy,
+        ))
+    }
+
+    fn inspect_container<'a>(
+        &'a self,
+        container_id: &'a str,
+    ) -> BoxFuture<'a, Result<ContainerInspectResponse, DockerError>> {
+        Box::pin(DockerClient::inspect_container(self, container_id))
+    }
+
+    fn is_container_running<'a>(
+        &'a self,
+        container_id: &'a str,
+    ) -> BoxFuture<'a, Result<bool, DockerError>> {
+        Box::pin(DockerClient::is_container_running(self, container_id))
+    }
+
+    fn get_workspace_path<'a>(
+        &'a self,
+        container_id: &'a str,
+    ) -> BoxFuture<'a, Result<String, DockerError>> {
+        Box::pin(DockerClient::get_workspace_path(self, container_id))
+    }
+
+    fn get_host_port<'a>(
+        &'a self,
+        container_id: &'a str,
+        container_port: u16,
+        protocol: &'a str,
+    ) -> BoxFuture<'a, Result<Option<u16>, DockerError>> {
+        Box::pin(DockerClient::get_host_port(
+            self,
+            container_id,
+            container_port,
+            protocol,
+        ))
+    }
+
+    fn commit_container<'a>(
+        &'a self,
+        container_id: &'a str,
+        image_name: &'a str,
+        tag: &'a str,
+    ) -> BoxFuture<'a, Result<String, DockerError>> {
+        Box::pin(DockerClient::commit_container(
+            self,
+            container_id,
+            image_name,
+            tag,
+        ))
+    }
+
+    fn remove_image<'a>(
+        &'a self,
+        image_name: &'a str,
+        force: bool,
+    ) -> BoxFuture<'a, Result<(), DockerError>> {
+        Box::pin(DockerClient::remove_image(self, image_name, force))
+    }
+
+    fn ensure_network<'a>(&'a self, name: &'a str) -> BoxFuture<'a, Result<(), DockerError>> {
+        Box::pin(DockerClient::ensure_network(self, name))
+    }
+
+    fn get_container_cpu_percent<'a>(
+        &'a self,
+        container_id: &'a str,
+    ) -> BoxFuture<'a, Option<f64>> {
+        Box::pin(DockerClient::get_container_cpu_percent(self, container_id))
+    }
+
+    fn upload_file_to_container<'a>(
+        &'a self,
+        container_id: &'a str,
+        file_path: &'a str,
+        file_data: Vec<u8>,
+    ) -> BoxFuture<'a, Result<(), DockerError>> {
+        Box::pin(DockerClient::upload_file_to_container(
+            self,
+            container_id,
+            file_path,
+            file_data,
+        ))
+    }
+
+    fn get_container_logs<'a>(
+        &'a self,
+        container_id: &'a str,
+        tail: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<String, DockerError>> {
+        Box::pin(DockerClient::get_container_logs(self, container_id, tail))
+    }
+}
+
+/// Get the container engine used by the app (the Docker API client, which
+/// also talks to Podman)
+pub fn get_container_engine() -> Result<&'static dyn ContainerEngine, DockerError> {
+    #[cfg(test)]
+    {
+        if let Some(engine) = *TEST_ENGINE.read().unwrap_or_else(|e| e.into_inner()) {
+            return Ok(engine);
+        }
+    }
+    Ok(get_docker_client()?)
+}
+
+#[cfg(test)]
+static TEST_ENGINE: std::sync::RwLock<Option<&'static dyn ContainerEngine>> =
+    std::sync::RwLock::new(None);
+
+/// Serializes tests that inject a container engine, since it is process-global
+#[cfg(test)]
+static TEST_ENGINE_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Keeps an injected container engine in place until dropped
+#[cfg(test)]
+pub(crate) struct TestEngineGuard<E: 'static> {
+    pub engine: &'static E,
+    _lock: std::sync::MutexGuard<'static, ()>,
+}
+
+#[cfg(test)]
+impl<E: 'static> Drop for TestEngineGuard<E> {
+    fn drop(&mut self) {
+        *TEST_ENGINE.write().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+/// Make `get_container_engine` return `engine` until the guard is dropped
+#[cfg(test)]
+pub(crate) fn set_container_engine_for_test<E: ContainerEngine + 'static>(
+    engine: E,
+) -> TestEngineGuard<E> {
+    let lock = TEST_ENGINE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let engine: &'static E = Box::leak(Box::new(engine));
+    *TEST_ENGINE.write().unwrap_or_else(|e| e.into_inner()) = Some(engine);
+    TestEngineGuard {
+        engine,
+        _lock: lock,
+    }
+}
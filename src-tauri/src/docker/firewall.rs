@@ -0,0 +1,400 @@
+// Background task that keeps the firewall ipset of restricted containers in
+// step with DNS. Domains behind CDNs rotate their IPs, so a container that
+// resolved its allowed domains once at startup would otherwise lose access
+// as soon as the records change.
+
+use std::net::Ipv4Addr;
+use std::time::Duration;
+
+use regex::Regex;
+use serde::Serialize;
+use tracing::{debug, info, warn};
+
+use super::client::get_docker_client;
+use crate::models::{Environment, EnvironmentStatus, NetworkAccessMode};
+use crate::storage::get_storage;
+
+/// Path of the runtime firewall update script inside the container
+pub const UPDATE_FIREWALL_SCRIPT: &str = "/usr/local/bin/update-firewall.sh";
+
+/// Interval used to re-check the configuration while refreshing is disabled
+const DISABLED_POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// Lower bound on the refresh interval so a typo can't hammer DNS
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Read the configured refresh interval. Returns `None` when refreshing is
+/// disabled (interval of 0) or the config cannot be loaded.
+fn load_refresh_interval() -> Option<Duration> {
+    let storage = get_storage().ok()?;
+    let config = storage.load_config().ok()?;
+    match config.global.firewall_refresh_interval_secs {
+        0 => None,
+        secs => Some(Duration::from_secs(secs).max(MIN_REFRESH_INTERVAL)),
+    }
+}
+
+/// Whether an environment has a running container with firewall rules applied
+pub(crate) fn is_running_restricted(env: &Environment) -> bool {
+    env.is_containerized()
+        && env.status == EnvironmentStatus::Running
+        && env.network_access_mode == NetworkAccessMode::Restricted
+        && env.container_id.is_some()
+}
+
+/// Domains whitelisted inside an environment's container: the global list the
+/// container was created with, plus any domains added to this environment.
+pub fn effective_allowed_domains(env: &Environment, global_domains: &[String]) -> Vec<String> {
+    let mut domains: Vec<String> = global_domains.to_vec();
+    for domain in env.allowed_domains.iter().flatten() {
+        if !domains.contains(domain) {
+            domains.push(domain.clone());
+        }
+    }
+    domains
+}
+
+/// Entries that differ between an environment's stored allowed domains and
+/// the entries applied to its container's firewall
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllowedDomainsDiff {
+    /// Whether the container records its applied entries. Containers from
+    /// older images don't, so only `missing_from_ipset` is reported for them.
+    pub entries_tracked: bool,
+    /// Stored entries the container's firewall hasn't applied
+    pub only_in_storage: Vec<String>,
+    /// Entries applied to the container's firewall but not stored
+    pub only_in_container: Vec<String>,
+    /// Stored entries none of whose IPs are in the container's ipset, e.g.
+    /// because they were removed from it by hand
+    pub missing_from_ipset: Vec<String>,
+}
+
+/// Extract the applied entries from `update-firewall.sh --list-domains`
+/// output
+pub fn parse_configured_entries(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Compare stored entries against the entries applied in the container, or
+/// report nothing if the container doesn't track them (`configured` is
+/// `None`). Only the entries themselves are compared, never the IPs they
+/// resolve to, so DNS changes don't show up as drift.
+pub fn diff_allowed_domain_entries(
+    stored: &[String],
+    configured: Option<&[String]>,
+) -> AllowedDomainsDiff {
+    let Some(configured) = configured else {
+        return AllowedDomainsDiff::default();
+    };
+    AllowedDomainsDiff {
+        entries_tracked: true,
+        only_in_storage: stored
+            .iter()
+            .filter(|entry| !configured.contains(entry))
+            .cloned()
+            .collect(),
+        only_in_container: configured
+            .iter()
+            .filter(|entry| !stored.contains(entry))
+            .cloned()
+            .collect(),
+        missing_from_ipset: Vec::new(),
+    }
+}
+
+/// Extract the IP and CIDR members from `update-firewall.sh --list` output
+pub fn parse_ipset_members(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("Members:"))
+        .skip(1)
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Network address and prefix length of an IPv4 address or CIDR range
+fn ipv4_range(entry: &str) -> Option<(u32, u32)> {
+    let (addr, prefix) = match entry.split_once('/') {
+        Some((addr, prefix)) => (addr, prefix.parse::<u32>().ok()?),
+        None => (entry, 32),
+    };
+    if prefix > 32 {
+        return None;
+    }
+    let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+    Some((u32::from(addr.parse::<Ipv4Addr>().ok()?) & mask, prefix))
+}
+
+/// Whether two IPv4 entries share any address (CIDR ranges either nest or
+/// are disjoint)
+fn ranges_overlap(a: &str, b: &str) -> bool {
+    let (Some((a_net, a_prefix)), Some((b_net, b_prefix))) = (ipv4_range(a), ipv4_range(b)) else {
+        return false;
+    };
+    let prefix = a_prefix.min(b_prefix);
+    let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+    a_net & mask == b_net & mask
+}
+
+/// Entries, each paired with the IPs it currently resolves to (raw IPs and
+/// CIDR ranges pair with themselves), that have none of those IPs in the
+/// ipset `members`. Entries that didn't resolve are skipped rather than
+/// reported, and GitHub domains are skipped as the firewall scripts manage
+/// their ranges from GitHub's API.
+pub fn entries_missing_from_ipset(
+    resolved: &[(String, Vec<String>)],
+    members: &[String],
+) -> Vec<String> {
+    resolved
+        .iter()
+        .filter(|(entry, ips)| {
+            !entry.contains("github.com")
+                && !ips.is_empty()
+                && !ips
+                    .iter()
+                    .any(|ip| members.iter().any(|member| ranges_overlap(ip, member)))
+        })
+        .map(|(entry, _)| entry.clone())
+        .collect()
+}
+
+/// Extract the number of newly added IPs from `update-firewall.sh --add` output
+fn parse_added_ip_count(output: &str) -> Option<u32> {
+    let re = Regex::new(r"Summary: Added (\d+) IPs").ok()?;
+    re.captures(output)?.get(1)?.as_str().parse().ok()
+}
+
+/// Re-resolve the allowed domains of every running restricted environment and
+/// add any new IPs to its container's ipset.
+async fn refresh_once() {
+    let storage = match get_storage() {
+        Ok(s) => s,
+        Err(e) => {
+            debug!(error = %e, "Skipping firewall refresh: storage unavailable");
+            return;
+        }
+    };
+
+    let environments: Vec<Environment> = match storage.get_all_environments() {
+        Ok(envs) => envs.into_iter().filter(is_running_restricted).collect(),
+        Err(e) => {
+            warn!(error = %e, "Skipping firewall refresh: failed to load environments");
+            return;
+        }
+    };
+
+    if environments.is_empty() {
+        return;
+    }
+
+    let global_domains = match storage.load_config() {
+        Ok(config) => config.global.allowed_domains,
+        Err(e) => {
+            warn!(error = %e, "Skipping firewall refresh: failed to load config");
+            return;
+        }
+    };
+
+    let docker = match get_docker_client() {
+        Ok(d) => d,
+        Err(e) => {
+            debug!(error = %e, "Skipping firewall refresh: Docker unavailable");
+            return;
+        }
+    };
+
+    for env in &environments {
+        let Some(container_id) = &env.container_id else {
+            continue;
+        };
+
+        let domains = effective_allowed_domains(env, &global_domains);
+        if domains.is_empty() {
+            continue;
+        }
+        let domains_csv = domains.join(",");
+
+        match docker
+            .exec_command(
+                container_id,
+                vec!["sudo", UPDATE_FIREWALL_SCRIPT, "--add", &domains_csv],
+            )
+            .await
+        {
+            Ok(output) => match parse_added_ip_count(&output) {
+                Some(0) => {
+                    debug!(environment_id = %env.id, "Firewall ipset unchanged after refresh");
+                }
+                Some(added) => {
+                    info!(
+                        environment_id = %env.id,
+                        added_ips = added,
+                        "Firewall ipset updated with newly resolved IPs"
+                    );
+                }
+                None => {
+                    debug!(
+                        environment_id = %env.id,
+                        "Could not parse firewall refresh summary"
+                    );
+                }
+            },
+            Err(e) => {
+                warn!(
+                    environment_id = %env.id,
+                    error = %e,
+                    "Failed to refresh firewall ipset"
+                );
+            }
+        }
+    }
+}
+
+/// Run the firewall refresh loop forever. Intended to be spawned as a
+/// background task from the Tauri `setup` hook. The interval is re-read from
+/// config on every tick so settings changes apply without a restart.
+pub async fn run_refresh_loop() {
+    loop {
+        match load_refresh_interval() {
+            Some(interval) => {
+                tokio::time::sleep(interval).await;
+                refresh_once().await;
+            }
+            None => tokio::time::sleep(DISABLED_POLL_INTERVAL).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_added_ip_count() {
+        let output = "Resolving api.example.com...\n\
+                      \x1b[0;32mAdded 1.2.3.4 for api.example.com\x1b[0m\n\n\
+                      \x1b[0;32mSummary: Added 3 IPs, 0 domains failed\x1b[0m\n";
+        assert_eq!(parse_added_ip_count(output), Some(3));
+        assert_eq!(parse_added_ip_count("no summary here"), None);
+    }
+
+    #[test]
+    fn test_effective_allowed_domains_merges_without_duplicates() {
+        let mut env = Environment::new("project-123".to_string());
+        env.allowed_domains = Some(vec![
+            "api.example.com".to_string(),
+            "github.com".to_string(),
+        ]);
+        let global = vec!["github.com".to_string(), "bun.sh".to_string()];
+
+        assert_eq!(
+            effective_allowed_domains(&env, &global),
+            vec!["github.com", "bun.sh", "api.example.com"]
+        );
+    }
+
+    #[test]
+    fn test_parse_configured_entries() {
+        let output = "api.example.com\n10.0.0.0/24\n\n  github.com \n";
+        assert_eq!(
+            parse_configured_entries(output),
+            vec!["api.example.com", "10.0.0.0/24", "github.com"]
+        );
+        assert!(parse_configured_entries("").is_empty());
+    }
+
+    #[test]
+    fn test_diff_allowed_domain_entries() {
+        let stored = vec![
+            "api.example.com".to_string(),
+            "10.0.0.0/24".to_string(),
+            "unresolvable.example.com".to_string(),
+            "missing.example.com".to_string(),
+        ];
+        let configured = vec![
+            "api.example.com".to_string(),
+            "10.0.0.0/24".to_string(),
+            "unresolvable.example.com".to_string(),
+            "extra.example.com".to_string(),
+        ];
+
+        let diff = diff_allowed_domain_entries(&stored, Some(&configured));
+        assert!(diff.entries_tracked);
+        assert_eq!(diff.only_in_storage, vec!["missing.example.com"]);
+        assert_eq!(diff.only_in_container, vec!["extra.example.com"]);
+        assert_eq!(
+            diff_allowed_domain_entries(&stored, Some(&stored)),
+            AllowedDomainsDiff {
+                entries_tracked: true,
+                ..AllowedDomainsDiff::default()
+            }
+        );
+
+        // Untracked entries aren't guessed at
+        assert_eq!(
+            diff_allowed_domain_entries(&stored, None),
+            AllowedDomainsDiff::default()
+        );
+    }
+
+    #[test]
+    fn test_parse_ipset_members() {
+        let output = "Current allowed-domains ipset entries:\n\
+                      ========================================\n\
+                      Name: allowed-domains\n\
+                      Type: hash:net\n\
+                      Number of entries: 2\n\
+                      Members:\n\
+                      140.82.112.0/20\n\
+                      1.2.3.4\n";
+        assert_eq!(
+            parse_ipset_members(output),
+            vec!["140.82.112.0/20", "1.2.3.4"]
+        );
+        assert!(parse_ipset_members("no members").is_empty());
+    }
+
+    #[test]
+    fn test_entries_missing_from_ipset() {
+        let resolved = vec![
+            (
+                "api.example.com".to_string(),
+                vec!["1.2.3.4".to_string(), "1.2.3.5".to_string()],
+            ),
+            ("10.0.0.7".to_string(), vec!["10.0.0.7".to_string()]),
+            (
+                "removed.example.com".to_string(),
+                vec!["5.6.7.8".to_string()],
+            ),
+            ("unresolvable.example.com".to_string(), Vec::new()),
+            ("api.github.com".to_string(), vec!["9.9.9.9".to_string()]),
+        ];
+        let members = vec!["1.2.3.5".to_string(), "10.0.0.0/24".to_string()];
+
+        assert_eq!(
+            entries_missing_from_ipset(&resolved, &members),
+            vec!["removed.example.com"]
+        );
+    }
+
+    #[test]
+    fn test_is_running_restricted() {
+        let mut env = Environment::new("project-123".to_string());
+        env.network_access_mode = NetworkAccessMode::Restricted;
+        env.status = EnvironmentStatus::Running;
+        assert!(!is_running_restricted(&env));
+
+        env.container_id = Some("abc123".to_string());
+        assert!(is_running_restricted(&env));
+
+        env.network_access_mode = NetworkAccessMode::Full;
+        assert!(!is_running_restricted(&env));
+    }
+}
@@ -0,0 +1,365 @@
+// In-memory container engine for tests
+// Records container mutations and answers `exec` through an optional handler,
+// so tests only describe the behaviour they care about
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+use bollard::models::{
+    ContainerInspectResponse, ContainerState, ContainerStateStatusEnum, ContainerSummary,
+    RestartPolicy,
+};
+use futures::future::BoxFuture;
+
+use super::client::{CreateContainerConfig, DockerError};
+use super::container::BASE_IMAGE;
+use super::engine::ContainerEngine;
+use crate::models::{EnvironmentStatus, DEFAULT_WORKSPACE_PATH};
+
+/// Answers `exec` calls with stdout, stderr and the exit code for a command
+type ExecHandler = Box<dyn Fn(&[&str]) -> (String, String, i64) + Send + Sync>;
+
+/// Container engine backed by an in-memory container table. Every mutating
+/// call is recorded in `calls`; `exec` succeeds with no output unless a
+/// handler is installed with [`MockEngine::with_exec`].
+#[derive(Default)]
+pub(crate) struct MockEngine {
+    state: Mutex<MockEngineState>,
+    exec: Option<ExecHandler>,
+}
+
+#[derive(Default)]
+pub(crate) struct MockEngineState {
+    pub containers: HashMap<String, EnvironmentStatus>,
+    pub images: Vec<String>,
+    pub calls: Vec<String>,
+    pub fail_commit: bool,
+    next_id: u32,
+}
+
+impl MockEngine {
+    pub fn with_container(container_id: &str, status: EnvironmentStatus) -> Self {
+        let engine = Self::default();
+        engine
+            .state()
+            .containers
+            .insert(container_id.to_string(), status);
+        engine
+    }
+
+    pub fn with_exec(
+        mut self,
+        handler: impl Fn(&[&str]) -> (String, String, i64) + Send + Sync + 'static,
+    ) -> Self {
+        self.exec = Some(Box::new(handler));
+        self
+    }
+
+    pub fn state(&self) -> MutexGuard<'_, MockEngineState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn calls(&self) -> Vec<String> {
+        self.state().calls.clone()
+    }
+
+    fn set_status(
+        &self,
+        call: String,
+        container_id: &str,
+        status: EnvironmentStatus,
+    ) -> Result<(), DockerError> {
+        let mut state = self.state();
+        state.calls.push(call);
+        match state.containers.get_mut(container_id) {
+            Some(current) => {
+                *current = status;
+                Ok(())
+            }
+            None => Err(no_such_container(container_id)),
+        }
+    }
+
+    fn status(&self, container_id: &str) -> Result<EnvironmentStatus, DockerError> {
+        self.state()
+            .containers
+            .get(container_id)
+            .cloned()
+            .ok_or_else(|| no_such_container(container_id))
+    }
+
+    fn exec(&self, cmd: &[&str]) -> (String, String, i64) {
+        match &self.exec {
+            Some(handler) => handler(cmd),
+            None => (String::new(), String::new(), 0),
+        }
+    }
+}
+
+fn no_such_container(container_id: &str) -> DockerError {
+    DockerError::OperationFailed(format!("No such container: {}", container_id))
+}
+
+fn ready<'a, T: Send + 'a>(value: T) -> BoxFuture<'a, T> {
+    Box::pin(std::future::ready(value))
+}
+
+impl ContainerEngine for MockEngine {
+    fn image_exists<'a>(&'a self, image_name: &'a str) -> BoxFuture<'a, Result<bool, DockerError>> {
+        let exists =
+            image_name == BASE_IMAGE || self.state().images.iter().any(|i| i == image_name);
+        ready(Ok(exists))
+    }
+
+    fn list_containers<'a>(
+        &'a self,
+        _all: bool,
+        _label_filter: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Vec<ContainerSummary>, DockerError>> {
+        let containers = self
+            .state()
+            .containers
+            .keys()
+            .map(|id| ContainerSummary {
+                id: Some(id.clone()),
+                ..Default::default()
+            })
+            .collect();
+        ready(Ok(containers))
+    }
+
+    fn exec_command<'a>(
+        &'a self,
+        _container_id: &'a str,
+        cmd: Vec<&'a str>,
+    ) -> BoxFuture<'a, Result<String, DockerError>> {
+        let (stdout, stderr, _) = self.exec(&cmd);
+        ready(Ok(stdout + &stderr))
+    }
+
+    fn exec_command_with_status<'a>(
+        &'a self,
+        _container_id: &'a str,
+        cmd: Vec<&'a str>,
+    ) -> BoxFuture<'a, Result<(String, String, i64), DockerError>> {
+        ready(Ok(self.exec(&cmd)))
+    }
+
+    fn exec_command_streaming<'a>(
+        &'a self,
+        _container_id: &'a str,
+        cmd: Vec<&'a str>,
+        mut on_output: Box<dyn FnMut(String) + Send + 'a>,
+    ) -> BoxFuture<'a, Result<i64, DockerError>> {
+        let (stdout, stderr, exit_code) = self.exec(&cmd);
+        for chunk in [stdout, stderr] {
+            if !chunk.is_empty() {
+                on_output(chunk);
+            }
+        }
+        ready(Ok(exit_code))
+    }
+
+    fn create_container<'a>(
+        &'a self,
+        _name: &'a str,
+        image: &'a str,
+        _config: CreateContainerConfig,
+    ) -> BoxFuture<'a, Result<String, DockerError>> {
+        let mut state = self.state();
+        state.next_id += 1;
+        let container_id = format!("container-{}", state.next_id);
+        state.calls.push(format!("create {}", image));
+        state
+            .containers
+            .insert(container_id.clone(), EnvironmentStatus::Stopped);
+        ready(Ok(container_id))
+    }
+
+    fn start_container<'a>(
+        &'a self,
+        container_id: &'a str,
+    ) -> BoxFuture<'a, Result<(), DockerError>> {
+        ready(self.set_status(
+            format!("start {}", container_id),
+            container_id,
+            EnvironmentStatus::Running,
+        ))
+    }
+
+    fn stop_container<'a>(
+        &'a self,
+        container_id: &'a str,
+        _timeout: Option<i64>,
+    ) -> BoxFuture<'a, Result<(), DockerError>> {
+        ready(self.set_status(
+            format!("stop {}", container_id),
+            container_id,
+            EnvironmentStatus::Stopped,
+        ))
+    }
+
+    fn kill_container<'a>(
+        &'a self,
+        container_id: &'a str,
+    ) -> BoxFuture<'a, Result<(), DockerError>> {
+        ready(self.set_status(
+            format!("kill {}", container_id),
+            container_id,
+            EnvironmentStatus::Stopped,
+        ))
+    }
+
+    fn remove_container<'a>(
+        &'a self,
+        container_id: &'a str,
+        _force: bool,
+    ) -> BoxFuture<'a, Result<(), DockerError>> {
+        let mut state = self.state();
+        state.calls.push(format!("remove {}", container_id));
+        let result = state
+            .containers
+            .remove(container_id)
+            .map(|_| ())
+            .ok_or_else(|| no_such_container(container_id));
+        ready(result)
+    }
+
+    fn rename_container<'a>(
+        &'a self,
+        container_id: &'a str,
+        new_name: &'a str,
+    ) -> BoxFuture<'a, Result<(), DockerError>> {
+        let result = self.status(container_id).map(|_| ());
+        self.state()
+            .calls
+            .push(format!("rename {} {}", container_id, new_name));
+        ready(result)
+    }
+
+    fn update_restart_policy<'a>(
+        &'a self,
+        container_id: &'a str,
+        _restart_policy: RestartPolicy,
+    ) -> BoxFuture<'a, Result<(), DockerError>> {
+        ready(self.status(container_id).map(|_| ()))
+    }
+
+    fn inspect_container<'a>(
+        &'a self,
+        container_id: &'a str,
+    ) -> BoxFuture<'a, Result<ContainerInspectResponse, DockerError>> {
+        let result = self.status(container_id).map(|status| {
+            let (status, started_at) = match status {
+                EnvironmentStatus::Running => {
+                    (ContainerStateStatusEnum::RUNNING, "2024-01-01T00:00:00Z")
+                }
+                EnvironmentStatus::Creating => {
+                    (ContainerStateStatusEnum::CREATED, "0001-01-01T00:00:00Z")
+                }
+                EnvironmentStatus::Error => {
+                    (ContainerStateStatusEnum::EMPTY, "0001-01-01T00:00:00Z")
+                }
+                _ => (ContainerStateStatusEnum::EXITED, "2024-01-01T00:00:00Z"),
+            };
+            ContainerInspectResponse {
+                id: Some(container_id.to_string()),
+                state: Some(ContainerState {
+                    status: Some(status),
+                    started_at: Some(started_at.to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }
+        });
+        ready(result)
+    }
+
+    fn is_container_running<'a>(
+        &'a self,
+        container_id: &'a str,
+    ) -> BoxFuture<'a, Result<bool, DockerError>> {
+        ready(
+            self.status(container_id)
+                .map(|status| status == EnvironmentStatus::Running),
+        )
+    }
+
+    fn get_workspace_path<'a>(
+        &'a self,
+        _container_id: &'a str,
+    ) -> BoxFuture<'a, Result<String, DockerError>> {
+        ready(Ok(DEFAULT_WORKSPACE_PATH.to_string()))
+    }
+
+    fn get_host_port<'a>(
+        &'a self,
+        _container_id: &'a str,
+        _container_port: u16,
+        _protocol: &'a str,
+    ) -> BoxFuture<'a, Result<Option<u16>, DockerError>> {
+        ready(Ok(None))
+    }
+
+    fn commit_container<'a>(
+        &'a self,
+        container_id: &'a str,
+        image_name: &'a str,
+        tag: &'a str,
+    ) -> BoxFuture<'a, Result<String, DockerError>> {
+        let mut state = self.state();
+        let image = format!("{}:{}", image_name, tag);
+        state
+            .calls
+            .push(format!("commit {} {}", container_id, image));
+        if state.fail_commit {
+            return ready(Err(DockerError::OperationFailed(
+                "commit failed".to_string(),
+            )));
+        }
+        if !state.containers.contains_key(container_id) {
+            return ready(Err(no_such_container(container_id)));
+        }
+        state.images.push(image);
+        ready(Ok("sha256:committed".to_string()))
+    }
+
+    fn remove_image<'a>(
+        &'a self,
+        image_name: &'a str,
+        _force: bool,
+    ) -> BoxFuture<'a, Result<(), DockerError>> {
+        let mut state = self.state();
+        state.calls.push(format!("remove-image {}", image_name));
+        state.images.retain(|existing| existing != image_name);
+        ready(Ok(()))
+    }
+
+    fn ensure_network<'a>(&'a self, _name: &'a str) -> BoxFuture<'a, Result<(), DockerError>> {
+        ready(Ok(()))
+    }
+
+    fn get_container_cpu_percent<'a>(
+        &'a self,
+        _container_id: &'a str,
+    ) -> BoxFuture<'a, Option<f64>> {
+        ready(None)
+    }
+
+    fn upload_file_to_container<'a>(
+        &'a self,
+        container_id: &'a str,
+        _file_path: &'a str,
+        _file_data: Vec<u8>,
+    ) -> BoxFuture<'a, Result<(), DockerError>> {
+        ready(self.status(container_id).map(|_| ()))
+    }
+
+    fn get_container_logs<'a>(
+        &'a self,
+        _container_id: &'a str,
+        _tail: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<String, DockerError>> {
+        ready(Ok(String::new()))
+    }
+}
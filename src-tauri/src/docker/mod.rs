@@ -2,7 +2,18 @@
 // Handles container lifecycle, image management, and terminal sessions
 
 pub mod client;
+pub mod compose;
 pub mod container;
+pub mod engine;
+pub mod firewall;
+#[cfg(test)]
+pub(crate) mod mock;
 
-pub use client::{get_docker_client, DockerError};
+pub use client::{
+    connect_engine, engine_backend, engine_cli, get_docker_client, reset_docker_client,
+    DockerError, EngineBackend,
+};
 pub use container::*;
+#[cfg(test)]
+pub(crate) use engine::set_container_engine_for_test;
+pub use engine::{get_container_engine, ContainerEngine};
@@ -0,0 +1,113 @@
+//! Container resource limit checks.
+//!
+//! Asking Docker for more CPUs or memory than its host has fails container
+//! creation with an opaque error. These helpers compare the configured limits
+//! with the totals Docker reports so the UI can explain the problem first.
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::ContainerResources;
+
+const BYTES_PER_GB: u64 = 1024 * 1024 * 1024;
+
+/// A host resource a container limit is checked against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HostResource {
+    Cpu,
+    Memory,
+}
+
+/// CPUs and memory available to Docker, as reported by `docker info`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostCapacity {
+    pub cpus: u32,
+    pub memory_bytes: u64,
+}
+
+/// A configured container limit above what the host can provide
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceLimitWarning {
+    pub resource: HostResource,
+    /// Requested amount: cores for CPU, bytes for memory
+    pub requested: u64,
+    /// Host total: cores for CPU, bytes for memory
+    pub available: u64,
+    /// Human-readable summary for the UI
+    pub message: String,
+}
+
+/// Compare the configured limits with the host totals and return a warning
+/// for each one that exceeds them. Totals Docker didn't report (0) are skipped.
+pub fn check_resource_limits(
+    resources: &ContainerResources,
+    host: HostCapacity,
+) -> Vec<ResourceLimitWarning> {
+    let mut warnings = Vec::new();
+
+    let requested_cpus = u64::from(resources.cpu_cores);
+    let host_cpus = u64::from(host.cpus);
+    if host_cpus > 0 && requested_cpus > host_cpus {
+        warnings.push(ResourceLimitWarning {
+            resource: HostResource::Cpu,
+            requested: requested_cpus,
+            available: host_cpus,
+            message: format!(
+                "Containers are limited to {} CPU cores, but Docker only has {}",
+                requested_cpus, host_cpus
+            ),
+        });
+    }
+
+    let requested_memory = u64::from(resources.memory_gb) * BYTES_PER_GB;
+    if host.memory_bytes > 0 && requested_memory > host.memory_bytes {
+        warnings.push(ResourceLimitWarning {
+            resource: HostResource::Memory,
+            requested: requested_memory,
+            available: host.memory_bytes,
+            message: format!(
+                "Containers are limited to {} GB of memory, but Docker only has {:.1} GB",
+                resources.memory_gb,
+                host.memory_bytes as f64 / BYTES_PER_GB as f64
+            ),
+        });
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn warns_only_for_limits_above_host_totals() {
+        let host = HostCapacity {
+            cpus: 8,
+            memory_bytes: 16 * BYTES_PER_GB,
+        };
+        let fits = ContainerResources {
+            cpu_cores: 8,
+            memory_gb: 16,
+        };
+        assert!(check_resource_limits(&fits, host).is_empty());
+
+        let too_much_memory = ContainerResources {
+            cpu_cores: 2,
+            memory_gb: 32,
+        };
+        let warnings = check_resource_limits(&too_much_memory, host);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].resource, HostResource::Memory);
+        assert_eq!(warnings[0].available, 16 * BYTES_PER_GB);
+        assert!(warnings[0].message.contains("only has 16.0 GB"));
+
+        let unknown_host = HostCapacity {
+            cpus: 0,
+            memory_bytes: 0,
+        };
+        assert!(check_resource_limits(&too_much_memory, unknown_host).is_empty());
+    }
+}
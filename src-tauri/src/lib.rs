@@ -5,89 +5,19 @@ mod claude_cli;
 mod claude_tmux;
 mod commands;
 mod credentials;
+mod disk;
 mod docker;
 mod fix_path_env;
+mod host_resources;
 mod local;
+mod logging;
+mod menu;
 mod models;
 mod pty;
 mod storage;
 
-use bollard::Docker;
 use commands::*;
-use tauri::menu::{MenuBuilder, MenuItemBuilder, PredefinedMenuItem, SubmenuBuilder};
-use tauri::Emitter;
 use tracing::{info, warn};
-use tracing_subscriber::layer::SubscriberExt;
-use tracing_subscriber::util::SubscriberInitExt;
-use tracing_subscriber::EnvFilter;
-
-/// Check whether debug logging to disk is enabled by reading config.json directly.
-///
-/// This runs before the Tauri runtime is available, so we read the config file
-/// from the well-known app data directory instead of going through the storage layer.
-fn is_debug_logging_enabled() -> bool {
-    let config_path = dirs::data_dir()
-        .or_else(|| dirs::config_dir())
-        .map(|d| d.join("orkestrator-ai").join("config.json"));
-
-    let Some(path) = config_path else {
-        return false;
-    };
-
-    let Ok(contents) = std::fs::read_to_string(&path) else {
-        return false;
-    };
-
-    // Parse just enough to check global.debugLogging
-    let Ok(value) = serde_json::from_str::<serde_json::Value>(&contents) else {
-        return false;
-    };
-
-    value
-        .get("global")
-        .and_then(|g| g.get("debugLogging"))
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false)
-}
-
-/// Return the log directory path.
-///
-/// Used both at startup (to configure the file appender) and by the
-/// `get_log_directory` Tauri command so there is a single source of truth.
-pub fn log_dir_path() -> std::path::PathBuf {
-    dirs::data_dir()
-        .unwrap_or_else(|| std::path::PathBuf::from("."))
-        .join("orkestrator-ai")
-        .join("logs")
-}
-
-/// Delete log files in `dir` that are older than `max_age_days`.
-fn cleanup_old_logs(dir: &std::path::Path, max_age_days: u64) {
-    let cutoff =
-        std::time::SystemTime::now() - std::time::Duration::from_secs(max_age_days * 24 * 60 * 60);
-
-    let Ok(entries) = std::fs::read_dir(dir) else {
-        return;
-    };
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.extension().and_then(|e| e.to_str()) == Some("log")
-            || path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .is_some_and(|n| n.starts_with("orkestrator-ai.log"))
-        {
-            if let Ok(meta) = path.metadata() {
-                if let Ok(modified) = meta.modified() {
-                    if modified < cutoff {
-                        let _ = std::fs::remove_file(&path);
-                    }
-                }
-            }
-        }
-    }
-}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -95,49 +25,10 @@ pub fn run() {
     // This must be called before logging is initialized to ensure CLI tools are found
     fix_path_env::fix_path_env();
 
-    let debug_logging = is_debug_logging_enabled();
-
-    let filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new("info,orkestrator_ai_lib=debug"));
-
-    // Always log to stderr. Optionally also write to a rolling log file
-    // when the user has enabled "Save logs for debugging" in settings.
-    // Log directory: ~/Library/Application Support/orkestrator-ai/logs/ (macOS)
-    if debug_logging {
-        let log_dir = log_dir_path();
-        let _ = std::fs::create_dir_all(&log_dir);
-
-        // Remove log files older than 7 days to prevent unbounded disk usage.
-        cleanup_old_logs(&log_dir, 7);
-
-        let file_appender = tracing_appender::rolling::daily(&log_dir, "orkestrator-ai.log");
-        let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
-
-        // Keep the guard alive for the entire app lifetime by leaking it.
-        // Dropping the guard would stop the background writer thread.
-        // Note: this means the write buffer is not flushed on exit, so
-        // the last few log lines may be lost. Acceptable for debug logging.
-        std::mem::forget(_guard);
-
-        tracing_subscriber::registry()
-            .with(filter)
-            .with(tracing_subscriber::fmt::layer().with_target(false))
-            .with(
-                tracing_subscriber::fmt::layer()
-                    .with_target(false)
-                    .with_ansi(false)
-                    .with_writer(non_blocking),
-            )
-            .init();
-    } else {
-        tracing_subscriber::fmt()
-            .with_env_filter(filter)
-            .with_target(false)
-            .init();
-    }
+    logging::init();
 
     // Initialize terminal manager if Docker is available
-    if Docker::connect_with_local_defaults().is_ok() {
+    if docker::connect_engine().is_ok() {
         pty::init_terminal_manager();
         info!("Terminal manager initialized");
     } else {
@@ -160,70 +51,25 @@ pub fn run() {
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_process::init())
         .setup(|app| {
-            // Create App menu with About and Quit (CMD+Q)
-            let app_menu = SubmenuBuilder::new(app, "Orkestrator AI")
-                .item(&PredefinedMenuItem::about(
-                    app,
-                    Some("About Orkestrator AI"),
-                    None,
-                )?)
-                .separator()
-                .item(&PredefinedMenuItem::hide(app, None)?)
-                .item(&PredefinedMenuItem::hide_others(app, None)?)
-                .item(&PredefinedMenuItem::show_all(app, None)?)
-                .separator()
-                .item(&PredefinedMenuItem::quit(app, None)?)
-                .build()?;
-
-            // Create Edit menu with standard editing shortcuts
-            let edit_menu = SubmenuBuilder::new(app, "Edit")
-                .item(&PredefinedMenuItem::undo(app, None)?)
-                .item(&PredefinedMenuItem::redo(app, None)?)
-                .separator()
-                .item(&PredefinedMenuItem::cut(app, None)?)
-                .item(&PredefinedMenuItem::copy(app, None)?)
-                .item(&PredefinedMenuItem::paste(app, None)?)
-                .item(&PredefinedMenuItem::select_all(app, None)?)
-                .build()?;
-
-            // Create View menu with zoom controls
-            let zoom_in = MenuItemBuilder::with_id("zoom_in", "Zoom In")
-                .accelerator("CmdOrCtrl+=")
-                .build(app)?;
-
-            let zoom_out = MenuItemBuilder::with_id("zoom_out", "Zoom Out")
-                .accelerator("CmdOrCtrl+-")
-                .build(app)?;
-
-            let zoom_reset = MenuItemBuilder::with_id("zoom_reset", "Actual Size")
-                .accelerator("CmdOrCtrl+0")
-                .build(app)?;
+            // Build the app menu from the configured keybindings
+            let menu = menu::build_app_menu(app.handle())?;
+            app.set_menu(menu)?;
 
-            let view_menu = SubmenuBuilder::new(app, "View")
-                .item(&zoom_in)
-                .item(&zoom_out)
-                .separator()
-                .item(&zoom_reset)
-                .build()?;
+            // Restore the main window's size and position from the last session
+            restore_main_window(app);
 
-            let menu = MenuBuilder::new(app)
-                .items(&[&app_menu, &edit_menu, &view_menu])
-                .build()?;
+            // Tell the frontend which project's environments changed on every save
+            emit_environments_invalidated_events(app.handle());
 
-            app.set_menu(menu)?;
+            // Forward menu clicks to the frontend
+            app.on_menu_event(menu::handle_menu_event);
 
-            // Handle menu events
-            app.on_menu_event(move |app_handle, event| match event.id().0.as_str() {
-                "zoom_in" => {
-                    let _ = app_handle.emit("menu-zoom", "in");
-                }
-                "zoom_out" => {
-                    let _ = app_handle.emit("menu-zoom", "out");
-                }
-                "zoom_reset" => {
-                    let _ = app_handle.emit("menu-zoom", "reset");
-                }
-                _ => {}
+            // Reconcile stored environment statuses with Docker without
+            // delaying launch. Runs after the environments listener is set up
+            // so every reset also emits environments-invalidated.
+            let reconcile_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                commands::reconcile_environments_on_launch(reconcile_handle).await;
             });
 
             // Clean up stale local server processes from previous app sessions.
@@ -240,10 +86,21 @@ pub fn run() {
                 credentials::sync::run_sync_loop(sync_handle).await;
             });
 
+            // Periodically re-resolve allowed domains for running restricted
+            // containers so rotating IPs (e.g. CDNs) don't break access.
+            tauri::async_runtime::spawn(async {
+                docker::firewall::run_refresh_loop().await;
+            });
+
+            // Save terminal output buffers on a timer so a crash doesn't
+            // lose recent scrollback
+            tauri::async_runtime::spawn(async {
+                pty::run_buffer_autosave_loop().await;
+            });
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
-            greet,
             // Project commands
             get_projects,
             add_project,
@@ -251,34 +108,66 @@ pub fn run() {
             get_project,
             update_project,
             reorder_projects,
+            validate_repository_branches,
+            get_project_groups,
+            create_project_group,
+            rename_project_group,
+            delete_project_group,
+            reorder_project_groups,
+            set_project_group,
             validate_git_url,
             get_git_remote_url,
+            validate_orkestrator_config,
+            detect_compose,
             // Environment commands
             get_environments,
             reorder_environments,
             create_environment,
+            suggest_environment_name,
             delete_environment,
             get_environment,
+            get_environment_activity,
             update_environment_status,
             set_environment_pr,
             set_environment_debug_mode,
             set_environment_setup_complete,
+            set_environment_read_only,
+            set_environment_notes,
+            set_environment_pinned,
             get_setup_commands,
             rename_environment,
             rename_environment_from_prompt,
+            regenerate_environment_name,
+            cancel_environment_naming,
+            is_environment_naming,
             get_environment_status,
             start_environment,
+            check_disk_space,
+            cancel_environment_start,
+            preview_container_config,
             stop_environment,
+            force_kill_environment,
             recreate_environment,
+            relocate_worktree,
+            resolve_worktree_conflict,
             sync_environment_status,
             sync_all_environments_with_docker,
             reattach_container,
             add_environment_domains,
             remove_environment_domains,
             update_environment_allowed_domains,
+            add_domains_to_all_environments,
+            import_allowed_domains,
+            diff_allowed_domains,
+            reconcile_allowed_domains,
+            set_firewall_bypass,
             // Port mapping commands
             update_port_mappings,
             update_environment_agent_settings,
+            set_environment_opencode_model,
+            update_environment_restart_policy,
+            set_environment_full_clone,
+            set_environment_default_sessions,
             // Docker commands
             check_docker,
             docker_version,
@@ -293,9 +182,21 @@ pub fn run() {
             get_orkestrator_containers,
             cleanup_orphaned_containers,
             docker_system_prune,
+            get_docker_disk_breakdown,
+            prune_docker,
+            pin_container,
+            unpin_container,
+            pin_image,
+            unpin_image,
             get_container_logs,
             stream_container_logs,
             get_container_host_port,
+            inspect_container,
+            list_container_env_keys,
+            wait_for_workspace_ready,
+            compose_up,
+            compose_down,
+            get_compose_status,
             propagate_github_token_to_containers,
             // Terminal commands
             attach_terminal,
@@ -305,9 +206,22 @@ pub fn run() {
             terminal_resize,
             detach_terminal,
             list_terminal_sessions,
+            list_terminal_sessions_detailed,
             get_terminal_session,
+            get_session_metrics,
+            reattach_session,
+            // Environment terminal commands (route to container or local terminals)
+            create_environment_terminal,
+            start_environment_terminal,
+            environment_terminal_write,
+            environment_terminal_resize,
+            close_environment_terminal,
             // Session commands (persistent session tracking)
             create_session,
+            create_default_sessions,
+            get_restorable_sessions,
+            restore_session,
+            get_session_launch_command,
             get_session,
             get_sessions_by_environment,
             update_session_status,
@@ -316,6 +230,7 @@ pub fn run() {
             delete_sessions_by_environment,
             rename_session,
             set_session_has_launched_command,
+            set_session_terminal_id,
             disconnect_environment_sessions,
             save_session_buffer,
             load_session_buffer,
@@ -329,19 +244,37 @@ pub fn run() {
             clear_environment_pr,
             detect_pr,
             detect_pr_local,
+            list_project_pull_requests,
+            get_repository_metadata,
+            create_environment_from_pr,
+            get_pr_checks,
             merge_pr,
             merge_pr_local,
+            create_pull_request,
+            close_pull_request,
+            reopen_pull_request,
             // Config commands
             get_config,
             save_config,
             get_global_config,
             update_global_config,
+            update_global_config_partial,
             get_repository_config,
             update_repository_config,
+            copy_repository_config,
             get_log_directory,
+            get_log_file_path,
+            set_log_level,
+            get_recent_logs,
+            open_data_dir,
+            open_config_file,
+            get_effective_environment_config,
+            check_config,
             // Credentials commands
             has_claude_credentials,
             get_credential_status,
+            refresh_credentials,
+            refresh_container_credentials,
             // CLI detection and onboarding commands
             check_claude_cli,
             check_claude_config,
@@ -350,6 +283,8 @@ pub fn run() {
             check_github_cli,
             check_any_ai_cli,
             get_available_ai_cli,
+            run_diagnostics,
+            app_info,
             // Network commands
             test_domain_resolution,
             validate_domains,
@@ -361,9 +296,20 @@ pub fn run() {
             open_local_in_editor,
             // File commands (container)
             get_git_status,
+            get_diff_summary,
             get_file_tree,
             read_container_file,
+            copy_file_reference,
+            copy_file_between_environments,
+            get_github_permalink,
             read_file_at_branch,
+            list_container_branches,
+            refresh_remote_branches,
+            unshallow_repo,
+            get_repo_operation_state,
+            abort_repo_operation,
+            resolve_conflict,
+            checkout_branch,
             read_container_file_base64,
             write_container_file,
             // File commands (local environments)
@@ -393,12 +339,19 @@ pub fn run() {
             start_local_opencode_server_cmd,
             stop_local_opencode_server_cmd,
             get_local_opencode_server_status,
+            restart_local_opencode,
             start_local_claude_server_cmd,
             stop_local_claude_server_cmd,
             get_local_claude_server_status,
+            restart_local_claude_bridge,
             start_local_codex_server_cmd,
             stop_local_codex_server_cmd,
             get_local_codex_server_status,
+            ping_local_servers,
+            get_local_server_log,
+            stream_local_server_log,
+            stop_local_server_log_stream,
+            proxy_agent_request,
             cleanup_stale_local_servers_cmd,
             // Kanban commands
             get_kanban_tasks,
@@ -414,6 +367,8 @@ pub fn run() {
             save_project_notes,
             // Local terminal commands (for local/worktree environments)
             create_local_terminal_session,
+            create_host_terminal,
+            create_worktree_terminal,
             start_local_terminal_session,
             local_terminal_write,
             local_terminal_resize,
@@ -440,11 +395,21 @@ pub fn run() {
             claude_tmux_answer_pre_tool_use,
             claude_tmux_reply_hook,
             claude_tmux_list_previous_sessions,
+            // Window state commands
+            get_window_state,
+            save_window_state,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
         .run(|_app_handle, event| {
             if let tauri::RunEvent::Exit = event {
+                // Write settings still waiting for a debounced save
+                if let Ok(storage) = storage::get_storage() {
+                    if let Err(e) = storage.flush_pending_config() {
+                        warn!(error = %e, "Failed to write pending config on exit");
+                    }
+                }
+
                 // Kill all tracked local server processes so they don't
                 // linger as orphans after the app closes.
                 // Use a timeout to avoid blocking indefinitely if the
@@ -0,0 +1,267 @@
+//! Log files for local environment servers
+//!
+//! Output of each spawned server is written to a per-environment file in the
+//! app log directory, so it can be read back or followed the same way as the
+//! `/tmp/*.log` files of servers running in containers. Files are rotated once
+//! they reach `MAX_SERVER_LOG_BYTES` and removed with their environment.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use super::process::ProcessType;
+
+/// How often `follow_server_log` checks the file for new output
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Size at which a server log is rotated. The previous file is kept as
+/// `<name>.log.1`, so each server's logs take at most twice this on disk.
+const MAX_SERVER_LOG_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Server kinds that write log files
+const LOGGED_PROCESS_TYPES: [ProcessType; 3] = [
+    ProcessType::OpenCode,
+    ProcessType::ClaudeBridge,
+    ProcessType::CodexBridge,
+];
+
+/// Path of the log file a local server of an environment writes to
+pub fn server_log_path(environment_id: &str, process_type: ProcessType) -> PathBuf {
+    crate::logging::log_dir_path()
+        .join("local-servers")
+        .join(format!("{}-{}.log", environment_id, process_type))
+}
+
+/// Path a log file is moved to when it is rotated
+fn rotated_log_path(path: &Path) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(".1");
+    PathBuf::from(rotated)
+}
+
+/// Log file a local server's output is appended to
+pub struct ServerLog {
+    path: PathBuf,
+    file: File,
+    len: u64,
+    max_bytes: u64,
+}
+
+impl ServerLog {
+    /// Create the log file at `path`, discarding any previous one
+    fn create(path: PathBuf, max_bytes: u64) -> std::io::Result<Self> {
+        let _ = std::fs::remove_file(rotated_log_path(&path));
+        let file = File::create(&path)?;
+        Ok(Self {
+            path,
+            file,
+            len: 0,
+            max_bytes,
+        })
+    }
+
+    /// Append a line of output, rotating the file first if it is full
+    pub fn append_line(&mut self, line: &str) {
+        if self.len >= self.max_bytes {
+            if let Err(e) = self.rotate() {
+                warn!(path = %self.path.display(), error = %e, "Failed to rotate local server log");
+            }
+        }
+        if writeln!(self.file, "{}", line).is_ok() {
+            self.len += line.len() as u64 + 1;
+        }
+    }
+
+    /// Move the current file aside and start a new one. If it can't be moved,
+    /// it is emptied instead so it still can't grow without bound.
+    fn rotate(&mut self) -> std::io::Result<()> {
+        match std::fs::rename(&self.path, rotated_log_path(&self.path)) {
+            Ok(()) => self.file = File::create(&self.path)?,
+            Err(_) => {
+                self.file.set_len(0)?;
+                self.file.seek(SeekFrom::Start(0))?;
+            }
+        }
+        self.len = 0;
+        Ok(())
+    }
+}
+
+/// Create the log file for a server that is about to be spawned, discarding
+/// the output of its previous run
+pub fn create_server_log(
+    environment_id: &str,
+    process_type: ProcessType,
+) -> Option<Arc<Mutex<ServerLog>>> {
+    let path = server_log_path(environment_id, process_type);
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| ServerLog::create(path.clone(), MAX_SERVER_LOG_BYTES));
+
+    match result {
+        Ok(log) => Some(Arc::new(Mutex::new(log))),
+        Err(e) => {
+            warn!(
+                environment_id = %environment_id,
+                process_type = %process_type,
+                path = %path.display(),
+                error = %e,
+                "Failed to create local server log file"
+            );
+            None
+        }
+    }
+}
+
+/// Remove the log files of every server of an environment
+pub fn remove_server_logs(environment_id: &str) {
+    for process_type in LOGGED_PROCESS_TYPES {
+        let path = server_log_path(environment_id, process_type);
+        for path in [rotated_log_path(&path), path] {
+            match std::fs::remove_file(&path) {
+                Ok(()) => debug!(path = %path.display(), "Removed local server log"),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    warn!(path = %path.display(), error = %e, "Failed to remove local server log");
+                }
+            }
+        }
+    }
+}
+
+/// Keep only the last `count` lines of `text`
+fn tail_lines(text: &str, count: usize) -> &str {
+    if count == 0 {
+        return "";
+    }
+    let trimmed = text.strip_suffix('\n').unwrap_or(text);
+    match trimmed.rmatch_indices('\n').nth(count - 1) {
+        Some((index, _)) => &text[index + 1..],
+        None => text,
+    }
+}
+
+/// Read a server log file.
+///
+/// `since` skips the first that many bytes, so a caller that remembers how
+/// much it has already read only gets the output written after it; `tail`
+/// then keeps only the last that many lines.
+pub fn read_server_log(
+    path: &Path,
+    tail: Option<usize>,
+    since: Option<u64>,
+) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to read log: {}", e))?;
+    if let Some(offset) = since {
+        file.seek(SeekFrom::Start(offset))
+            .map_err(|e| format!("Failed to read log: {}", e))?;
+    }
+
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to read log: {}", e))?;
+    let text = String::from_utf8_lossy(&bytes);
+
+    Ok(match tail {
+        Some(count) => tail_lines(&text, count).to_string(),
+        None => text.into_owned(),
+    })
+}
+
+/// Follow a server log file, yielding output appended after the call.
+///
+/// The file is recreated whenever the server restarts or the log is rotated;
+/// reading then picks up from the start of the new file. Following stops once the receiver is
+/// dropped.
+pub fn follow_server_log(path: PathBuf) -> mpsc::Receiver<String> {
+    let (tx, rx) = mpsc::channel::<String>(100);
+
+    tokio::spawn(async move {
+        let mut offset = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+        loop {
+            tokio::time::sleep(FOLLOW_POLL_INTERVAL).await;
+            if tx.is_closed() {
+                break;
+            }
+
+            let len = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            if len < offset {
+                offset = 0;
+            }
+            if len == offset {
+                continue;
+            }
+
+            match read_server_log(&path, None, Some(offset)) {
+                Ok(text) => {
+                    offset = len;
+                    if tx.send(text).await.is_err() {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    debug!(path = %path.display(), error = %e, "Local server log unreadable");
+                }
+            }
+        }
+    });
+
+    rx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tail_lines() {
+        assert_eq!(tail_lines("a\nb\nc\n", 2), "b\nc\n");
+        assert_eq!(tail_lines("a\nb\nc", 2), "b\nc");
+        assert_eq!(tail_lines("a\nb\n", 5), "a\nb\n");
+        assert_eq!(tail_lines("a\nb\n", 0), "");
+    }
+
+    #[test]
+    fn test_read_server_log_since_and_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("server.log");
+        std::fs::write(&path, "one\ntwo\nthree\n").unwrap();
+
+        assert_eq!(
+            read_server_log(&path, None, None).unwrap(),
+            "one\ntwo\nthree\n"
+        );
+        assert_eq!(
+            read_server_log(&path, None, Some(4)).unwrap(),
+            "two\nthree\n"
+        );
+        assert_eq!(read_server_log(&path, Some(1), Some(4)).unwrap(), "three\n");
+        assert_eq!(read_server_log(&path, None, Some(100)).unwrap(), "");
+    }
+
+    #[test]
+    fn test_server_log_rotates_when_full() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("server.log");
+        let mut log = ServerLog::create(path.clone(), 8).unwrap();
+
+        log.append_line("first");
+        log.append_line("second");
+        log.append_line("third");
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "third\n");
+        assert_eq!(
+            std::fs::read_to_string(rotated_log_path(&path)).unwrap(),
+            "first\nsecond\n"
+        );
+
+        // A new run starts without the previous run's rotated output
+        ServerLog::create(path.clone(), 8).unwrap();
+        assert!(!rotated_log_path(&path).exists());
+    }
+}
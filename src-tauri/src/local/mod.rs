@@ -3,8 +3,10 @@
 //! This module handles local (non-Docker) environments that use git worktrees
 //! and run agent servers as native child processes on the host machine.
 
+pub mod logs;
 pub mod ports;
 pub mod process;
+pub mod project_config;
 pub mod pty;
 pub mod servers;
 pub mod worktree;
@@ -17,12 +19,13 @@ pub use pty::{
 };
 pub use servers::{
     cleanup_stale_local_servers, get_local_claude_status, get_local_codex_status,
-    get_local_opencode_status, isolated_opencode_data_home, shutdown_all_local_servers,
-    start_local_claude_bridge, start_local_codex_bridge, start_local_opencode_server,
-    stop_all_local_servers, stop_local_claude_bridge, stop_local_codex_bridge,
-    stop_local_opencode_server, LocalServerStartResult, LocalServerStatus,
+    get_local_opencode_status, isolated_opencode_data_home, probe_server_health,
+    shutdown_all_local_servers, start_local_claude_bridge, start_local_codex_bridge,
+    start_local_opencode_server, stop_all_local_servers, stop_local_claude_bridge,
+    stop_local_codex_bridge, stop_local_opencode_server, LocalServerStartResult, LocalServerStatus,
 };
 pub use worktree::{
     configure_local_git_artifacts, copy_env_files, copy_project_files, create_worktree,
-    delete_worktree, get_setup_local_commands,
+    delete_branch, delete_worktree, find_worktree_for_branch, get_setup_container_commands,
+    get_setup_local_commands, move_worktree, remove_clean_worktree, rename_branch, switch_branch,
 };
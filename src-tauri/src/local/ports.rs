@@ -1,9 +1,10 @@
 //! Port allocation for local environment servers
 //!
 //! Allocates unique ports for OpenCode and Claude-bridge servers
-//! running in local environments.
+//! running in local environments, and reserves the repository's default
+//! app ports so each environment's app gets its own localhost port.
 
-use crate::models::Environment;
+use crate::models::{Environment, LocalAppPort};
 use std::net::TcpListener;
 use tracing::{debug, warn};
 
@@ -21,6 +22,8 @@ pub struct PortAllocation {
     pub claude_port: u16,
     /// Port for Codex bridge server
     pub codex_port: u16,
+    /// Reserved app ports, in the order they were requested
+    pub app_ports: Vec<LocalAppPort>,
 }
 
 /// Check if a port is available for binding.
@@ -47,20 +50,28 @@ fn get_used_ports(environments: &[Environment]) -> Vec<u16> {
         if let Some(port) = env.local_codex_port {
             ports.push(port);
         }
+        if let Some(app_ports) = &env.local_app_ports {
+            ports.extend(app_ports.iter().map(|app_port| app_port.host_port));
+        }
     }
 
     ports
 }
 
-/// Allocate two unique ports for a new local environment
+/// Allocate server ports and reserve app ports for a new local environment
 ///
 /// # Arguments
 /// * `existing_environments` - List of existing environments to check for port conflicts
+/// * `app_ports` - App ports to reserve (e.g. from the repository's default port
+///   mappings). A port that is taken shifts to the next free port above it.
 ///
 /// # Returns
-/// A `PortAllocation` with two unique available ports
-pub fn allocate_ports(existing_environments: &[Environment]) -> Result<PortAllocation, String> {
-    let used_ports = get_used_ports(existing_environments);
+/// A `PortAllocation` with unique available ports
+pub fn allocate_ports(
+    existing_environments: &[Environment],
+    app_ports: &[u16],
+) -> Result<PortAllocation, String> {
+    let mut used_ports = get_used_ports(existing_environments);
     debug!(used_ports = ?used_ports, "Checking existing port allocations");
 
     let mut opencode_port: Option<u16> = None;
@@ -93,11 +104,16 @@ pub fn allocate_ports(existing_environments: &[Environment]) -> Result<PortAlloc
     }
 
     match (opencode_port, claude_port, codex_port) {
-        (Some(oport), Some(cport), Some(xport)) => Ok(PortAllocation {
-            opencode_port: oport,
-            claude_port: cport,
-            codex_port: xport,
-        }),
+        (Some(oport), Some(cport), Some(xport)) => {
+            used_ports.extend([oport, cport, xport]);
+            let app_ports = reserve_app_ports(app_ports, &mut used_ports)?;
+            Ok(PortAllocation {
+                opencode_port: oport,
+                claude_port: cport,
+                codex_port: xport,
+                app_ports,
+            })
+        }
         _ => {
             warn!(
                 "Failed to allocate ports in range {}-{}",
@@ -111,6 +127,46 @@ pub fn allocate_ports(existing_environments: &[Environment]) -> Result<PortAlloc
     }
 }
 
+/// Reserve each requested app port, shifting to the next free port when it is
+/// already used by another environment or bound by another process
+fn reserve_app_ports(
+    requested: &[u16],
+    used_ports: &mut Vec<u16>,
+) -> Result<Vec<LocalAppPort>, String> {
+    let mut reserved = Vec::with_capacity(requested.len());
+
+    for &app_port in requested {
+        if reserved
+            .iter()
+            .any(|existing: &LocalAppPort| existing.app_port == app_port)
+        {
+            continue;
+        }
+
+        let host_port = (app_port..=u16::MAX)
+            .find(|port| !used_ports.contains(port) && is_port_available(*port))
+            .ok_or_else(|| format!("No available port at or above {}", app_port))?;
+
+        if host_port == app_port {
+            debug!(port = host_port, "Reserved app port");
+        } else {
+            debug!(
+                app_port = app_port,
+                host_port = host_port,
+                "App port taken, reserved next free port"
+            );
+        }
+
+        used_ports.push(host_port);
+        reserved.push(LocalAppPort {
+            app_port,
+            host_port,
+        });
+    }
+
+    Ok(reserved)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,7 +200,7 @@ mod tests {
 
     #[test]
     fn test_allocate_ports_empty() {
-        let result = allocate_ports(&[]);
+        let result = allocate_ports(&[], &[]);
         assert!(result.is_ok());
         let allocation = result.unwrap();
         assert!(allocation.opencode_port >= LOCAL_PORT_RANGE_START);
@@ -153,5 +209,25 @@ mod tests {
         assert_ne!(allocation.opencode_port, allocation.claude_port);
         assert_ne!(allocation.opencode_port, allocation.codex_port);
         assert_ne!(allocation.claude_port, allocation.codex_port);
+        assert!(allocation.app_ports.is_empty());
+    }
+
+    #[test]
+    fn test_reserve_app_ports_shifts_past_used_ports() {
+        // Hold a port so it is unavailable, and mark another as used by an env
+        let listener = TcpListener::bind(("0.0.0.0", 0)).unwrap();
+        let bound = listener.local_addr().unwrap().port();
+        let mut used_ports = vec![LOCAL_PORT_RANGE_START];
+
+        let reserved =
+            reserve_app_ports(&[bound, bound, LOCAL_PORT_RANGE_START], &mut used_ports).unwrap();
+
+        assert_eq!(reserved.len(), 2);
+        assert_eq!(reserved[0].app_port, bound);
+        assert!(reserved[0].host_port > bound);
+        assert_eq!(reserved[1].app_port, LOCAL_PORT_RANGE_START);
+        assert!(reserved[1].host_port > LOCAL_PORT_RANGE_START);
+        assert!(used_ports.contains(&reserved[0].host_port));
+        assert!(used_ports.contains(&reserved[1].host_port));
     }
 }
@@ -5,12 +5,14 @@
 
 use std::collections::HashMap;
 use std::process::Stdio;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex as StdMutex};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::{Child, Command};
 use tokio::sync::Mutex;
 use tracing::{debug, error, info, warn};
 
+use super::logs::{create_server_log, ServerLog};
+
 /// Type of server process
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ProcessType {
@@ -121,12 +123,17 @@ impl LocalProcessManager {
 
         let mut child = cmd.spawn()?;
 
+        // Output also goes to a log file so it can be read back from the UI
+        let log_file = create_server_log(environment_id, process_type);
+
         if let Some(stdout) = child.stdout.take() {
             let env_id = environment_id.to_string();
             let proc_name = process_type.to_string();
+            let log_file = log_file.clone();
             tokio::spawn(async move {
                 let mut lines = BufReader::new(stdout).lines();
                 while let Ok(Some(line)) = lines.next_line().await {
+                    append_log_line(log_file.as_ref(), &line);
                     debug!(
                         environment_id = %env_id,
                         process_type = %proc_name,
@@ -144,6 +151,7 @@ impl LocalProcessManager {
             tokio::spawn(async move {
                 let mut lines = BufReader::new(stderr).lines();
                 while let Ok(Some(line)) = lines.next_line().await {
+                    append_log_line(log_file.as_ref(), &line);
                     warn!(
                         environment_id = %env_id,
                         process_type = %proc_name,
@@ -313,6 +321,15 @@ impl Default for LocalProcessManager {
     }
 }
 
+/// Append a line of server output to its log file, if one could be created
+fn append_log_line(log_file: Option<&Arc<StdMutex<ServerLog>>>, line: &str) {
+    if let Some(log) = log_file {
+        if let Ok(mut log) = log.lock() {
+            log.append_line(line);
+        }
+    }
+}
+
 /// Check if a process with the given PID is alive
 #[cfg(unix)]
 pub fn is_process_alive(pid: u32) -> bool {
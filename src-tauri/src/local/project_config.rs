@@ -0,0 +1,377 @@
+//! Typed schema for a repository's `orkestrator-ai.json`
+//!
+//! The file lives at the repository root. Every key is optional:
+//!
+//! ```json
+//! {
+//!   "root": ["apt-get install -y ripgrep"],
+//!   "setupContainer": ["curl -fsSL https://bun.sh/install | bash", "bun install"],
+//!   "setupLocal": ["bun install"],
+//!   "run": ["bun run dev"],
+//!   "ports": [3000],
+//!   "defaultAgent": "claude",
+//!   "env": { "NODE_ENV": "development" }
+//! }
+//! ```
+//!
+//! - `root`, `setupContainer`, `setupLocal`: a command string or an array of
+//!   command strings. Empty strings are ignored.
+//! - `root` runs as root inside container environments before container setup.
+//! - `setupContainer` runs inside containerized environments (workspace-setup.sh
+//!   executes it after cloning the repository).
+//! - `setupLocal` runs in a terminal in the worktree of local environments.
+//! - `run`: array of command strings used by the Run button.
+//! - `ports`: container/app ports (1-65535) the project listens on.
+//! - `defaultAgent`: `claude`, `opencode` or `codex`.
+//! - `env`: object of string environment variables.
+//!
+//! Unknown keys are ignored so newer files keep working with older versions.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::models::DefaultAgent;
+
+/// File name of the per-repository configuration
+pub const PROJECT_CONFIG_FILE_NAME: &str = "orkestrator-ai.json";
+
+/// Parsed and validated `orkestrator-ai.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrkestratorProjectConfig {
+    /// Commands run as root inside containers before container setup
+    #[serde(default)]
+    pub root: Vec<String>,
+    /// Commands run inside containerized environments during workspace setup
+    #[serde(default)]
+    pub setup_container: Vec<String>,
+    /// Commands run in a terminal for local environments
+    #[serde(default)]
+    pub setup_local: Vec<String>,
+    /// Commands run by the Run button
+    #[serde(default)]
+    pub run: Vec<String>,
+    /// Ports the project's app listens on
+    #[serde(default)]
+    pub ports: Vec<u16>,
+    /// Preferred agent for environments of this project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_agent: Option<DefaultAgent>,
+    /// Extra environment variables for the project
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+}
+
+/// A problem with a single field of `orkestrator-ai.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectConfigFieldError {
+    /// Top-level key (with index for array items, e.g. `ports[1]`)
+    pub field_path: String,
+    /// Human-readable description of the problem
+    pub message: String,
+}
+
+/// Result of linting an `orkestrator-ai.json` file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectConfigValidation {
+    pub valid: bool,
+    /// The parsed config when the file is valid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<OrkestratorProjectConfig>,
+    /// Summary of the problem when the file is invalid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Per-field problems (empty for unreadable files or bad JSON)
+    pub field_errors: Vec<ProjectConfigFieldError>,
+}
+
+impl From<Result<OrkestratorProjectConfig, ProjectConfigError>> for ProjectConfigValidation {
+    fn from(result: Result<OrkestratorProjectConfig, ProjectConfigError>) -> Self {
+        match result {
+            Ok(config) => Self {
+                valid: true,
+                config: Some(config),
+                error: None,
+                field_errors: vec![],
+            },
+            Err(e) => Self {
+                valid: false,
+                config: None,
+                error: Some(e.to_string()),
+                field_errors: e.field_errors().to_vec(),
+            },
+        }
+    }
+}
+
+/// Error type for reading `orkestrator-ai.json`
+#[derive(Error, Debug)]
+pub enum ProjectConfigError {
+    #[error("Failed to read orkestrator-ai.json: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("orkestrator-ai.json is not valid JSON: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+
+    #[error("orkestrator-ai.json must contain a JSON object")]
+    NotAnObject,
+
+    #[error("orkestrator-ai.json is invalid: {}", describe_field_errors(.0))]
+    InvalidFields(Vec<ProjectConfigFieldError>),
+}
+
+impl ProjectConfigError {
+    /// Field-level problems, if the file parsed but had invalid values
+    pub fn field_errors(&self) -> &[ProjectConfigFieldError] {
+        match self {
+            ProjectConfigError::InvalidFields(errors) => errors,
+            _ => &[],
+        }
+    }
+}
+
+fn describe_field_errors(errors: &[ProjectConfigFieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field_path, e.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Read and validate `orkestrator-ai.json` from a repository directory.
+/// Returns `Ok(None)` when the file does not exist.
+pub async fn read_project_config(
+    repo_path: &str,
+) -> Result<Option<OrkestratorProjectConfig>, ProjectConfigError> {
+    let config_path = Path::new(repo_path).join(PROJECT_CONFIG_FILE_NAME);
+    match tokio::fs::read_to_string(&config_path).await {
+        Ok(content) => parse_project_config(&content).map(Some),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Parse and validate the contents of `orkestrator-ai.json`
+pub fn parse_project_config(content: &str) -> Result<OrkestratorProjectConfig, ProjectConfigError> {
+    let value: Value = serde_json::from_str(content)?;
+    let Value::Object(object) = value else {
+        return Err(ProjectConfigError::NotAnObject);
+    };
+
+    let mut errors = Vec::new();
+    let mut config = OrkestratorProjectConfig::default();
+
+    if let Some(value) = object.get("root") {
+        config.root = parse_commands("root", value, &mut errors);
+    }
+    if let Some(value) = object.get("setupContainer") {
+        config.setup_container = parse_commands("setupContainer", value, &mut errors);
+    }
+    if let Some(value) = object.get("setupLocal") {
+        config.setup_local = parse_commands("setupLocal", value, &mut errors);
+    }
+    if let Some(value) = object.get("run") {
+        if value.is_array() {
+            config.run = parse_commands("run", value, &mut errors);
+        } else {
+            push_error(&mut errors, "run", "must be an array of command strings");
+        }
+    }
+    if let Some(value) = object.get("ports") {
+        config.ports = parse_ports(value, &mut errors);
+    }
+    if let Some(value) = object.get("defaultAgent") {
+        match serde_json::from_value::<DefaultAgent>(value.clone()) {
+            Ok(agent) => config.default_agent = Some(agent),
+            Err(_) => push_error(
+                &mut errors,
+                "defaultAgent",
+                "must be one of \"claude\", \"opencode\" or \"codex\"",
+            ),
+        }
+    }
+    if let Some(value) = object.get("env") {
+        config.env = parse_env(value, &mut errors);
+    }
+
+    if errors.is_empty() {
+        Ok(config)
+    } else {
+        Err(ProjectConfigError::InvalidFields(errors))
+    }
+}
+
+fn push_error(errors: &mut Vec<ProjectConfigFieldError>, field_path: &str, message: &str) {
+    errors.push(ProjectConfigFieldError {
+        field_path: field_path.to_string(),
+        message: message.to_string(),
+    });
+}
+
+/// A command string or array of command strings; empty strings are dropped
+fn parse_commands(
+    field: &str,
+    value: &Value,
+    errors: &mut Vec<ProjectConfigFieldError>,
+) -> Vec<String> {
+    match value {
+        Value::String(command) if command.is_empty() => vec![],
+        Value::String(command) => vec![command.clone()],
+        Value::Array(items) => {
+            let mut commands = Vec::new();
+            for (index, item) in items.iter().enumerate() {
+                match item.as_str() {
+                    Some("") => {}
+                    Some(command) => commands.push(command.to_string()),
+                    None => push_error(
+                        errors,
+                        &format!("{}[{}]", field, index),
+                        "must be a command string",
+                    ),
+                }
+            }
+            commands
+        }
+        _ => {
+            push_error(
+                errors,
+                field,
+                "must be a command string or an array of command strings",
+            );
+            vec![]
+        }
+    }
+}
+
+fn parse_ports(value: &Value, errors: &mut Vec<ProjectConfigFieldError>) -> Vec<u16> {
+    let Some(items) = value.as_array() else {
+        push_error(errors, "ports", "must be an array of port numbers");
+        return vec![];
+    };
+
+    let mut ports = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        match item.as_u64().and_then(|port| u16::try_from(port).ok()) {
+            Some(port) if port > 0 => ports.push(port),
+            _ => push_error(
+                errors,
+                &format!("ports[{}]", index),
+                "must be a port number between 1 and 65535",
+            ),
+        }
+    }
+    ports
+}
+
+fn parse_env(value: &Value, errors: &mut Vec<ProjectConfigFieldError>) -> BTreeMap<String, String> {
+    let Some(object) = value.as_object() else {
+        push_error(errors, "env", "must be an object of string values");
+        return BTreeMap::new();
+    };
+
+    let mut env = BTreeMap::new();
+    for (key, item) in object {
+        match item.as_str() {
+            Some(value) => {
+                env.insert(key.clone(), value.to_string());
+            }
+            None => push_error(errors, &format!("env.{}", key), "must be a string"),
+        }
+    }
+    env
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_project_config_full() {
+        let config = parse_project_config(
+            r#"{
+                "root": "apt-get update",
+                "setupContainer": ["bun install", ""],
+                "setupLocal": ["bun install"],
+                "run": ["bun run dev"],
+                "ports": [3000, 5173],
+                "defaultAgent": "codex",
+                "env": { "NODE_ENV": "development" },
+                "model": "ignored-unknown-key"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.root, vec!["apt-get update"]);
+        assert_eq!(config.setup_container, vec!["bun install"]);
+        assert_eq!(config.setup_local, vec!["bun install"]);
+        assert_eq!(config.run, vec!["bun run dev"]);
+        assert_eq!(config.ports, vec![3000, 5173]);
+        assert_eq!(config.default_agent, Some(DefaultAgent::Codex));
+        assert_eq!(
+            config.env.get("NODE_ENV").map(String::as_str),
+            Some("development")
+        );
+    }
+
+    #[test]
+    fn test_parse_project_config_empty_object() {
+        assert_eq!(
+            parse_project_config("{}").unwrap(),
+            OrkestratorProjectConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_parse_project_config_rejects_bad_json() {
+        assert!(matches!(
+            parse_project_config("{ not json"),
+            Err(ProjectConfigError::InvalidJson(_))
+        ));
+        assert!(matches!(
+            parse_project_config(r#"["bun install"]"#),
+            Err(ProjectConfigError::NotAnObject)
+        ));
+    }
+
+    #[test]
+    fn test_parse_project_config_reports_wrong_types() {
+        let err = parse_project_config(
+            r#"{
+                "setupLocal": 42,
+                "setupContainer": ["ok", 1],
+                "run": "bun run dev",
+                "ports": [3000, 70000, "80"],
+                "defaultAgent": "gpt",
+                "env": { "A": "1", "B": 2 }
+            }"#,
+        )
+        .unwrap_err();
+
+        let paths: Vec<&str> = err
+            .field_errors()
+            .iter()
+            .map(|e| e.field_path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "setupContainer[1]",
+                "setupLocal",
+                "run",
+                "ports[1]",
+                "ports[2]",
+                "defaultAgent",
+                "env.B",
+            ]
+        );
+        assert!(err
+            .to_string()
+            .contains("setupLocal: must be a command string"));
+    }
+}
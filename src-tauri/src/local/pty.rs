@@ -4,6 +4,7 @@
 //! as opposed to Docker exec sessions for containerized environments.
 
 use super::process::kill_process;
+use crate::pty::output::{output_channel, terminal_channel_settings};
 use portable_pty::{native_pty_system, ChildKiller, CommandBuilder, MasterPty, PtyPair, PtySize};
 use std::collections::HashMap;
 use std::io::{Read, Write};
@@ -13,6 +14,9 @@ use thiserror::Error;
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
+/// Environment ID recorded on host terminals, which belong to no environment
+pub const HOST_TERMINAL_ENVIRONMENT_ID: &str = "host";
+
 #[derive(Error, Debug)]
 pub enum LocalPtyError {
     #[error("PTY error: {0}")]
@@ -42,6 +46,8 @@ pub struct LocalTerminalSession {
     pub is_active: bool,
     pub bundled_bin_dir: Option<String>,
     pub command: Option<Vec<String>>,
+    /// Extra environment variables set on the shell
+    pub env_vars: Vec<(String, String)>,
     pty_handle: Option<PtyHandle>,
     child_pid: Option<u32>,
     child_killer: Option<Box<dyn ChildKiller + Send + Sync>>,
@@ -82,6 +88,7 @@ impl LocalTerminalSession {
             is_active: false,
             bundled_bin_dir,
             command,
+            env_vars: Vec::new(),
             pty_handle: None,
             child_pid: None,
             child_killer: None,
@@ -186,6 +193,65 @@ impl LocalTerminalManager {
         Ok(session_id)
     }
 
+    /// Create a plain host shell session not tied to any environment, e.g. for
+    /// `gh auth login` during onboarding. Starts in `cwd` (home dir by default).
+    pub async fn create_host_session(
+        &self,
+        cwd: Option<&str>,
+        cols: u16,
+        rows: u16,
+        bundled_bin_dir: Option<String>,
+    ) -> Result<String, LocalPtyError> {
+        let cwd = match cwd.map(str::trim).filter(|cwd| !cwd.is_empty()) {
+            Some(cwd) => std::path::PathBuf::from(cwd),
+            None => dirs::home_dir()
+                .ok_or_else(|| LocalPtyError::Pty("Home directory not found".to_string()))?,
+        };
+        if !cwd.is_dir() {
+            return Err(LocalPtyError::Pty(format!(
+                "Not a directory: {}",
+                cwd.display()
+            )));
+        }
+
+        self.create_session(
+            HOST_TERMINAL_ENVIRONMENT_ID,
+            &cwd.to_string_lossy(),
+            cols,
+            rows,
+            bundled_bin_dir,
+        )
+        .await
+    }
+
+    /// Create a shell session in a local environment's worktree with the
+    /// environment's variables set. The session belongs to `environment_id`,
+    /// so it is closed along with the environment's other terminals.
+    pub async fn create_worktree_session(
+        &self,
+        environment_id: &str,
+        worktree_path: &str,
+        env_vars: Vec<(String, String)>,
+        cols: u16,
+        rows: u16,
+        bundled_bin_dir: Option<String>,
+    ) -> Result<String, LocalPtyError> {
+        if !Path::new(worktree_path).is_dir() {
+            return Err(LocalPtyError::Pty(format!(
+                "Worktree path does not exist: {}",
+                worktree_path
+            )));
+        }
+
+        let session_id = self
+            .create_session(environment_id, worktree_path, cols, rows, bundled_bin_dir)
+            .await?;
+        if let Some(session) = self.sessions.lock().unwrap().get_mut(&session_id) {
+            session.env_vars = env_vars;
+        }
+        Ok(session_id)
+    }
+
     /// Start a local terminal session and return output receiver
     pub async fn start_session(
         &self,
@@ -193,7 +259,7 @@ impl LocalTerminalManager {
     ) -> Result<mpsc::Receiver<Vec<u8>>, LocalPtyError> {
         debug!(session_id = %session_id, "Starting local terminal session");
 
-        let (worktree_path, bundled_bin_dir, command, pair) = {
+        let (worktree_path, bundled_bin_dir, command, env_vars, pair) = {
             let mut sessions = self.sessions.lock().unwrap();
             let session = sessions
                 .get_mut(session_id)
@@ -215,6 +281,7 @@ impl LocalTerminalManager {
                 session.worktree_path.clone(),
                 session.bundled_bin_dir.clone(),
                 session.command.clone(),
+                session.env_vars.clone(),
                 pair,
             )
         };
@@ -237,7 +304,14 @@ impl LocalTerminalManager {
         // Set up environment variables
         cmd.env("TERM", "xterm-256color");
         cmd.env("COLORTERM", "truecolor");
-        let current_path = std::env::var("PATH").unwrap_or_default();
+        for (key, value) in &env_vars {
+            cmd.env(key, value);
+        }
+        let current_path = env_vars
+            .iter()
+            .find(|(key, _)| key == "PATH")
+            .map(|(_, value)| value.clone())
+            .unwrap_or_else(|| std::env::var("PATH").unwrap_or_default());
         for (key, value) in build_bundled_bin_env(bundled_bin_dir.as_deref(), &current_path) {
             cmd.env(key, value);
         }
@@ -253,9 +327,11 @@ impl LocalTerminalManager {
         // Drop the slave - we don't need it after spawning
         drop(pair.slave);
 
-        // Create channels for input/output
-        let (input_tx, mut input_rx) = mpsc::channel::<Vec<u8>>(1024);
-        let (output_tx, output_rx) = mpsc::channel::<Vec<u8>>(1024);
+        // Create channels for input/output, applying the configured overflow
+        // policy to output
+        let (capacity, overflow_policy) = terminal_channel_settings();
+        let (input_tx, mut input_rx) = mpsc::channel::<Vec<u8>>(capacity);
+        let (output_tx, output_rx) = output_channel(capacity, overflow_policy);
 
         // Store the input sender
         {
@@ -636,6 +712,62 @@ mod tests {
         assert!(String::from_utf8_lossy(&output).contains("custom-command"));
     }
 
+    #[tokio::test]
+    async fn create_host_session_uses_cwd_and_rejects_missing_dirs() {
+        let tmp = TempDir::new().unwrap();
+        let manager = LocalTerminalManager::new();
+        let cwd = tmp.path().to_string_lossy();
+
+        let session_id = manager
+            .create_host_session(Some(&cwd), 80, 24, None)
+            .await
+            .unwrap();
+        let (environment_id, path, _, _) = manager.get_session(&session_id).unwrap();
+        assert_eq!(environment_id, HOST_TERMINAL_ENVIRONMENT_ID);
+        assert_eq!(path, cwd);
+
+        let missing = tmp.path().join("missing");
+        assert!(manager
+            .create_host_session(Some(&missing.to_string_lossy()), 80, 24, None)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn worktree_session_sets_env_vars_and_rejects_missing_worktrees() {
+        let tmp = TempDir::new().unwrap();
+        let manager = LocalTerminalManager::new();
+        let worktree = tmp.path().to_string_lossy();
+
+        let session_id = manager
+            .create_worktree_session(
+                "env-1",
+                &worktree,
+                vec![("GIT_BRANCH".to_string(), "feature/x".to_string())],
+                80,
+                24,
+                None,
+            )
+            .await
+            .unwrap();
+        {
+            let sessions = manager.sessions.lock().unwrap();
+            let session = sessions.get(&session_id).unwrap();
+            assert_eq!(session.environment_id, "env-1");
+            assert_eq!(session.worktree_path, worktree);
+            assert_eq!(
+                session.env_vars,
+                vec![("GIT_BRANCH".to_string(), "feature/x".to_string())]
+            );
+        }
+
+        let missing = tmp.path().join("missing");
+        assert!(manager
+            .create_worktree_session("env-1", &missing.to_string_lossy(), vec![], 80, 24, None)
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn close_sessions_for_environment_removes_only_matching_sessions() {
         let tmp = TempDir::new().unwrap();
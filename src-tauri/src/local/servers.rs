@@ -67,22 +67,60 @@ const SERVER_STARTUP_MAX_ATTEMPTS: u32 = 75;
 /// Interval between health check attempts (200ms)
 const SERVER_STARTUP_POLL_INTERVAL_MS: u64 = 200;
 
-/// Check if a server is healthy by making a request to its health endpoint
-async fn check_server_health(port: u16) -> bool {
+/// Bind address for a local server from the `native_server_host` setting.
+///
+/// Invalid values fall back to loopback. Binding to all interfaces is allowed
+/// but logged as a warning, since the servers have no authentication.
+pub fn resolve_bind_host(host: &str) -> String {
+    match crate::models::parse_native_server_host(host) {
+        Some(addr) if addr.is_loopback() => addr.to_string(),
+        Some(addr) => {
+            warn!(
+                host = %addr,
+                "Local servers are binding to all network interfaces; anyone who can reach this machine can control its agents"
+            );
+            addr.to_string()
+        }
+        None => {
+            warn!(
+                host = %host,
+                fallback = crate::models::DEFAULT_NATIVE_SERVER_HOST,
+                "Invalid native server host; binding local servers to loopback"
+            );
+            crate::models::DEFAULT_NATIVE_SERVER_HOST.to_string()
+        }
+    }
+}
+
+/// Request a server's health endpoint, returning how long it took to answer
+pub async fn probe_server_health(port: u16, timeout: Duration) -> Result<Duration, String> {
     let client = Client::builder()
-        .timeout(Duration::from_secs(2))
+        .timeout(timeout)
         .build()
-        .ok();
-
-    if let Some(client) = client {
-        let url = format!("http://127.0.0.1:{}/global/health", port);
-        match client.get(&url).send().await {
-            Ok(response) => response.status().is_success(),
-            Err(_) => false,
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let url = format!("http://127.0.0.1:{}/global/health", port);
+    let started = std::time::Instant::now();
+    let response = client.get(&url).send().await.map_err(|e| {
+        if e.is_timeout() {
+            format!("No response within {}ms", timeout.as_millis())
+        } else if e.is_connect() {
+            "Connection refused".to_string()
+        } else {
+            e.to_string()
         }
-    } else {
-        false
+    })?;
+    if !response.status().is_success() {
+        return Err(format!("Health check returned {}", response.status()));
     }
+    Ok(started.elapsed())
+}
+
+/// Check if a server is healthy by making a request to its health endpoint
+async fn check_server_health(port: u16) -> bool {
+    probe_server_health(port, Duration::from_secs(2))
+        .await
+        .is_ok()
 }
 
 /// Wait for a server to become healthy
@@ -240,6 +278,8 @@ fn is_expected_opencode_process(_environment_id: &str, _pid: u32) -> bool {
 /// * `environment_id` - The environment ID
 /// * `worktree_path` - Path to the git worktree (working directory)
 /// * `port` - Port to run the server on
+/// * `bind_host` - Address to bind to (see [`resolve_bind_host`])
+/// * `model` - Model overriding the user's OpenCode config, if any
 ///
 /// # Returns
 /// Result with server start information
@@ -247,6 +287,8 @@ pub async fn start_local_opencode_server(
     environment_id: &str,
     worktree_path: &str,
     port: u16,
+    bind_host: &str,
+    model: Option<&str>,
     bundled_opencode_path: Option<&str>,
 ) -> Result<LocalServerStartResult, String> {
     wait_for_startup_cleanup().await;
@@ -283,6 +325,14 @@ pub async fn start_local_opencode_server(
     let path = build_comprehensive_path(None);
     env_vars.insert("PATH".to_string(), path.clone());
 
+    // OpenCode merges OPENCODE_CONFIG_CONTENT over its config files
+    if let Some(model) = model {
+        env_vars.insert(
+            "OPENCODE_CONFIG_CONTENT".to_string(),
+            serde_json::json!({ "model": model }).to_string(),
+        );
+    }
+
     // Isolate each OpenCode instance's SQLite database by giving it a unique
     // XDG_DATA_HOME.  OpenCode stores its database at
     // $XDG_DATA_HOME/opencode/opencode.db and uses SQLite locking that does
@@ -336,6 +386,7 @@ pub async fn start_local_opencode_server(
         environment_id,
         worktree_path,
         port,
+        bind_host,
         &opencode_cmd,
         env_vars.clone(),
     )
@@ -368,6 +419,7 @@ pub async fn start_local_opencode_server(
             environment_id,
             worktree_path,
             port,
+            bind_host,
             &opencode_cmd,
             env_vars,
         )
@@ -408,6 +460,7 @@ async fn spawn_local_opencode_process(
     environment_id: &str,
     worktree_path: &str,
     port: u16,
+    bind_host: &str,
     opencode_cmd: &str,
     env_vars: HashMap<String, String>,
 ) -> Result<u32, String> {
@@ -421,7 +474,7 @@ async fn spawn_local_opencode_process(
                 "--port",
                 &port.to_string(),
                 "--hostname",
-                "0.0.0.0",
+                bind_host,
             ],
             worktree_path,
             env_vars,
@@ -483,6 +536,7 @@ pub async fn get_local_opencode_status(
 /// * `environment_id` - The environment ID
 /// * `worktree_path` - Path to the git worktree (working directory)
 /// * `port` - Port to run the server on
+/// * `bind_host` - Address to bind to (see [`resolve_bind_host`])
 /// * `bridge_path` - Path to the claude-bridge dist directory
 /// * `bundled_bun_path` - Optional path to bundled bun binary (for packaged apps)
 ///
@@ -492,6 +546,7 @@ pub async fn start_local_claude_bridge(
     environment_id: &str,
     worktree_path: &str,
     port: u16,
+    bind_host: &str,
     bridge_path: &str,
     bundled_bun_path: Option<&str>,
 ) -> Result<LocalServerStartResult, String> {
@@ -541,8 +596,8 @@ pub async fn start_local_claude_bridge(
     // Prepare environment variables
     let mut env_vars = HashMap::new();
     env_vars.insert("PORT".to_string(), port.to_string());
-    // Bind to localhost to avoid PNA/CORS restrictions in WebView
-    env_vars.insert("HOSTNAME".to_string(), "127.0.0.1".to_string());
+    // Loopback by default to avoid PNA/CORS restrictions in WebView
+    env_vars.insert("HOSTNAME".to_string(), bind_host.to_string());
     env_vars.insert("TERM".to_string(), "xterm-256color".to_string());
     // Increase bash output limit for code reviews and large diffs (default is 30000)
     env_vars.insert("BASH_MAX_OUTPUT_LENGTH".to_string(), "200000".to_string());
@@ -669,13 +724,14 @@ pub async fn get_local_claude_status(
 fn build_local_codex_bridge_env_vars(
     worktree_path: &str,
     port: u16,
+    bind_host: &str,
     bundled_bun_path: Option<&str>,
     bundled_codex_path: Option<&str>,
     raw_log_dir: Option<&str>,
 ) -> HashMap<String, String> {
     let mut env_vars = HashMap::new();
     env_vars.insert("PORT".to_string(), port.to_string());
-    env_vars.insert("HOSTNAME".to_string(), "127.0.0.1".to_string());
+    env_vars.insert("HOSTNAME".to_string(), bind_host.to_string());
     env_vars.insert("TERM".to_string(), "xterm-256color".to_string());
     env_vars.insert(
         "PATH".to_string(),
@@ -711,6 +767,7 @@ pub async fn start_local_codex_bridge(
     environment_id: &str,
     worktree_path: &str,
     port: u16,
+    bind_host: &str,
     bridge_path: &str,
     bundled_bun_path: Option<&str>,
     bundled_codex_path: Option<&str>,
@@ -749,6 +806,7 @@ pub async fn start_local_codex_bridge(
     let env_vars = build_local_codex_bridge_env_vars(
         worktree_path,
         port,
+        bind_host,
         bundled_bun_path,
         bundled_codex_path,
         raw_log_dir,
@@ -1686,6 +1744,12 @@ mod tests {
         assert!(!result);
     }
 
+    #[tokio::test]
+    async fn test_probe_server_health_reports_refused_connection() {
+        let result = probe_server_health(59998, Duration::from_millis(500)).await;
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_isolated_opencode_data_home() {
         let result = isolated_opencode_data_home("test-env-123");
@@ -1707,7 +1771,10 @@ mod tests {
                     .join("share")
             });
 
-        assert_eq!(shared_opencode_data_dir(), Some(expected_base.join("opencode")));
+        assert_eq!(
+            shared_opencode_data_dir(),
+            Some(expected_base.join("opencode"))
+        );
     }
 
     #[cfg(unix)]
@@ -1738,6 +1805,7 @@ mod tests {
         let env_vars = build_local_codex_bridge_env_vars(
             "/tmp/worktree",
             4321,
+            "127.0.0.1",
             Some("/tmp/bun"),
             None,
             Some("/tmp/logs/codex-raw"),
@@ -1759,8 +1827,14 @@ mod tests {
 
     #[test]
     fn test_build_local_codex_bridge_env_vars_omits_raw_log_dir_when_disabled() {
-        let env_vars =
-            build_local_codex_bridge_env_vars("/tmp/worktree", 4321, Some("/tmp/bun"), None, None);
+        let env_vars = build_local_codex_bridge_env_vars(
+            "/tmp/worktree",
+            4321,
+            "127.0.0.1",
+            Some("/tmp/bun"),
+            None,
+            None,
+        );
 
         assert!(!env_vars.contains_key("ORKESTRATOR_CODEX_RAW_LOG_DIR"));
     }
@@ -1770,6 +1844,7 @@ mod tests {
         let env_vars = build_local_codex_bridge_env_vars(
             "/tmp/worktree",
             4321,
+            "127.0.0.1",
             Some("/tmp/bun"),
             Some("/app/bin/codex"),
             None,
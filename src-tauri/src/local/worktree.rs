@@ -9,6 +9,9 @@ use thiserror::Error;
 use tokio::process::Command;
 use tracing::{debug, error, info, warn};
 
+use super::project_config::{read_project_config, ProjectConfigError};
+use crate::models::{WorktreeConflict, WorktreeConflictReason};
+
 /// Error type for worktree operations
 #[derive(Error, Debug)]
 pub enum WorktreeError {
@@ -24,6 +27,12 @@ pub enum WorktreeError {
     #[error("Failed to delete worktree: {0}")]
     WorktreeDeletionFailed(String),
 
+    #[error("Failed to move worktree: {0}")]
+    WorktreeMoveFailed(String),
+
+    #[error("Failed to update branch: {0}")]
+    BranchUpdateFailed(String),
+
     #[error("Failed to detect default branch: {0}")]
     BranchDetectionFailed(String),
 
@@ -43,6 +52,8 @@ pub enum WorktreeError {
 pub struct WorktreeCreateResult {
     pub path: String,
     pub branch: String,
+    /// Set when `branch` differs from the requested branch
+    pub conflict: Option<WorktreeConflict>,
 }
 
 const LOCAL_GIT_EXCLUDE_PATTERNS: &[&str] = &[".orkestrator", "CONTINUITY.md"];
@@ -52,7 +63,7 @@ const LOCAL_SKIP_WORKTREE_PATHS: &[&str] = &["CONTINUITY.md"];
 const WORKTREE_BASE_DIR: &str = "orkestrator-ai/workspaces";
 
 /// Get the base path for worktrees: ~/orkestrator-ai/workspaces/
-fn get_worktree_base_path() -> Result<PathBuf, WorktreeError> {
+pub(crate) fn get_worktree_base_path() -> Result<PathBuf, WorktreeError> {
     let home = dirs::home_dir().ok_or(WorktreeError::HomeDirNotFound)?;
     Ok(home.join(WORKTREE_BASE_DIR))
 }
@@ -415,6 +426,7 @@ pub async fn create_worktree(
     // Resolve a usable branch name (avoid branches already checked out in another worktree)
     let mut target_branch = branch_name.to_string();
     let mut attempt = 0;
+    let mut conflict: Option<(WorktreeConflictReason, Option<String>)> = None;
 
     loop {
         attempt += 1;
@@ -427,6 +439,10 @@ pub async fn create_worktree(
                 branch = %target_branch,
                 "Branch is already checked out in another worktree; generating a new name"
             );
+            if conflict.is_none() {
+                let existing = find_worktree_for_branch(source_repo_path, &target_branch).await?;
+                conflict = Some((WorktreeConflictReason::BranchCheckedOut, existing));
+            }
             target_branch =
                 generate_unique_branch_name(source_repo_path, branch_name, attempt).await?;
             continue;
@@ -439,6 +455,9 @@ pub async fn create_worktree(
                 branch = %target_branch,
                 "Branch exists on remote but not locally; generating a new name to avoid PR collision"
             );
+            if conflict.is_none() {
+                conflict = Some((WorktreeConflictReason::BranchOnRemote, None));
+            }
             target_branch =
                 generate_unique_branch_name(source_repo_path, branch_name, attempt).await?;
             continue;
@@ -491,6 +510,10 @@ pub async fn create_worktree(
         );
 
         if is_branch_in_use_error(&stderr) || is_branch_exists_error(&stderr) {
+            if conflict.is_none() && is_branch_in_use_error(&stderr) {
+                let existing = find_worktree_for_branch(source_repo_path, &target_branch).await?;
+                conflict = Some((WorktreeConflictReason::BranchCheckedOut, existing));
+            }
             target_branch =
                 generate_unique_branch_name(source_repo_path, branch_name, attempt).await?;
             continue;
@@ -510,9 +533,20 @@ pub async fn create_worktree(
         warn!(error = %e, "Failed to configure local git artifacts (non-fatal)");
     }
 
+    let conflict =
+        conflict
+            .filter(|_| target_branch != branch_name)
+            .map(|(reason, existing_worktree)| WorktreeConflict {
+                reason,
+                requested_branch: branch_name.to_string(),
+                adjusted_branch: target_branch.clone(),
+                existing_worktree,
+            });
+
     Ok(WorktreeCreateResult {
         path: worktree_path_str,
         branch: target_branch,
+        conflict,
     })
 }
 
@@ -547,6 +581,16 @@ async fn remote_branch_exists(repo_path: &str, branch_name: &str) -> Result<bool
 }
 
 async fn branch_checked_out(repo_path: &str, branch_name: &str) -> Result<bool, WorktreeError> {
+    Ok(find_worktree_for_branch(repo_path, branch_name)
+        .await?
+        .is_some())
+}
+
+/// Path of the worktree that has `branch_name` checked out, if any
+pub async fn find_worktree_for_branch(
+    repo_path: &str,
+    branch_name: &str,
+) -> Result<Option<String>, WorktreeError> {
     let output = Command::new("git")
         .args(["worktree", "list", "--porcelain"])
         .current_dir(repo_path)
@@ -555,12 +599,25 @@ async fn branch_checked_out(repo_path: &str, branch_name: &str) -> Result<bool,
         .map_err(|e| WorktreeError::WorktreeCreationFailed(e.to_string()))?;
 
     if !output.status.success() {
-        return Ok(false);
+        return Ok(None);
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(worktree_for_branch_in_porcelain(&stdout, branch_name))
+}
+
+/// Find the worktree with `branch_name` checked out in `git worktree list --porcelain` output
+fn worktree_for_branch_in_porcelain(porcelain: &str, branch_name: &str) -> Option<String> {
     let target = format!("branch refs/heads/{}", branch_name);
-    Ok(stdout.lines().any(|line| line.trim() == target))
+    let mut current_worktree = None;
+    for line in porcelain.lines().map(str::trim) {
+        if let Some(path) = line.strip_prefix("worktree ") {
+            current_worktree = Some(path);
+        } else if line == target {
+            return current_worktree.map(str::to_string);
+        }
+    }
+    None
 }
 
 async fn generate_unique_branch_name(
@@ -641,6 +698,132 @@ pub async fn delete_worktree(
     Ok(())
 }
 
+/// Delete a git worktree only if it has no uncommitted or untracked changes.
+/// Unlike [`delete_worktree`] this never forces the removal or falls back to
+/// deleting the directory, so work that isn't committed can't be lost.
+///
+/// # Arguments
+/// * `source_repo_path` - Path to the source git repository
+/// * `worktree_path` - Path to the worktree to delete
+pub async fn remove_clean_worktree(
+    source_repo_path: &str,
+    worktree_path: &str,
+) -> Result<(), WorktreeError> {
+    let status = Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(worktree_path)
+        .output()
+        .await
+        .map_err(|e| WorktreeError::WorktreeDeletionFailed(e.to_string()))?;
+    if !status.status.success() {
+        let stderr = String::from_utf8_lossy(&status.stderr);
+        return Err(WorktreeError::WorktreeDeletionFailed(format!(
+            "Failed to check {} for changes: {}",
+            worktree_path,
+            stderr.trim()
+        )));
+    }
+    if !String::from_utf8_lossy(&status.stdout).trim().is_empty() {
+        return Err(WorktreeError::WorktreeDeletionFailed(format!(
+            "{} has uncommitted or untracked changes",
+            worktree_path
+        )));
+    }
+
+    info!(
+        source = %source_repo_path,
+        worktree = %worktree_path,
+        "Deleting clean git worktree"
+    );
+    let output = Command::new("git")
+        .args(["worktree", "remove", worktree_path])
+        .current_dir(source_repo_path)
+        .output()
+        .await
+        .map_err(|e| WorktreeError::WorktreeDeletionFailed(e.to_string()))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::WorktreeDeletionFailed(
+            stderr.trim().to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Move a git worktree to a new directory, keeping git's tracking in sync
+///
+/// # Arguments
+/// * `source_repo_path` - Path to the source git repository
+/// * `worktree_path` - Current path of the worktree
+/// * `new_path` - Path to move the worktree to (must not exist)
+pub async fn move_worktree(
+    source_repo_path: &str,
+    worktree_path: &str,
+    new_path: &str,
+) -> Result<(), WorktreeError> {
+    info!(
+        source = %source_repo_path,
+        worktree = %worktree_path,
+        new_path = %new_path,
+        "Moving git worktree"
+    );
+
+    let output = Command::new("git")
+        .args(["worktree", "move", worktree_path, new_path])
+        .current_dir(source_repo_path)
+        .output()
+        .await
+        .map_err(|e| WorktreeError::WorktreeMoveFailed(e.to_string()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::WorktreeMoveFailed(stderr.trim().to_string()));
+    }
+
+    info!(new_path = %new_path, "Successfully moved git worktree");
+
+    Ok(())
+}
+
+/// Run a git command that updates branches in `dir`
+async fn run_branch_command(dir: &str, args: &[&str]) -> Result<(), WorktreeError> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .await
+        .map_err(|e| WorktreeError::BranchUpdateFailed(e.to_string()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(WorktreeError::BranchUpdateFailed(stderr.trim().to_string()));
+    }
+    Ok(())
+}
+
+/// Check out an existing branch in a worktree
+pub async fn switch_branch(worktree_path: &str, branch_name: &str) -> Result<(), WorktreeError> {
+    debug!(worktree = %worktree_path, branch = %branch_name, "Switching worktree branch");
+    run_branch_command(worktree_path, &["switch", branch_name]).await
+}
+
+/// Rename the branch checked out in a worktree
+pub async fn rename_branch(
+    worktree_path: &str,
+    old_name: &str,
+    new_name: &str,
+) -> Result<(), WorktreeError> {
+    debug!(worktree = %worktree_path, old = %old_name, new = %new_name, "Renaming worktree branch");
+    run_branch_command(worktree_path, &["branch", "-m", old_name, new_name]).await
+}
+
+/// Delete a local branch. Refuses (like `git branch -d`) when the branch has
+/// commits that aren't merged anywhere else.
+pub async fn delete_branch(repo_path: &str, branch_name: &str) -> Result<(), WorktreeError> {
+    debug!(repo = %repo_path, branch = %branch_name, "Deleting branch");
+    run_branch_command(repo_path, &["branch", "-d", branch_name]).await
+}
+
 /// Copy .env and .env.local files from source to destination
 ///
 /// # Arguments
@@ -772,59 +955,75 @@ pub fn copy_project_files(
     Ok(())
 }
 
-/// Get setupLocal commands from orkestrator-ai.json without executing them
+/// Setup section of `orkestrator-ai.json`.
 ///
-/// Reads the orkestrator-ai.json file from the worktree directory and returns
-/// the commands specified in the `setupLocal` field. Does not execute the commands.
+/// Both keys are optional and accept a single command string or an array of
+/// command strings; see [`crate::local::project_config`] for the full schema.
 ///
-/// # Arguments
-/// * `worktree_path` - Path to the worktree directory
-///
-/// # Returns
-/// A vector of commands to run, or an empty vector if no config file or no commands
-pub async fn get_setup_local_commands(worktree_path: &str) -> Vec<String> {
-    let config_path = Path::new(worktree_path).join("orkestrator-ai.json");
+/// - `setupLocal` runs in a terminal in the worktree of local environments.
+/// - `setupContainer` runs inside containerized environments (workspace-setup.sh
+///   executes it after cloning the repository).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupCommandsKind {
+    Local,
+    Container,
+}
 
-    // Read and parse the config file
-    let config_content = match tokio::fs::read_to_string(&config_path).await {
-        Ok(content) => content,
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-            debug!(worktree_path = %worktree_path, "No orkestrator-ai.json found");
-            return vec![];
-        }
-        Err(e) => {
-            warn!(error = %e, "Failed to read orkestrator-ai.json");
-            return vec![];
+impl SetupCommandsKind {
+    /// JSON key in `orkestrator-ai.json`
+    pub fn key(self) -> &'static str {
+        match self {
+            SetupCommandsKind::Local => "setupLocal",
+            SetupCommandsKind::Container => "setupContainer",
         }
-    };
+    }
+}
 
-    let config: serde_json::Value = match serde_json::from_str(&config_content) {
-        Ok(v) => v,
-        Err(e) => {
-            warn!(error = %e, "Failed to parse orkestrator-ai.json");
-            return vec![];
-        }
+/// Get setup commands of the given kind from orkestrator-ai.json without executing them
+///
+/// Reads and validates the orkestrator-ai.json file from `repo_path` and returns
+/// the commands under the key for `kind`.
+///
+/// # Returns
+/// The commands to run (empty if there is no config file or no commands), or an
+/// error if the file can't be read or doesn't match the schema
+pub async fn get_setup_commands(
+    repo_path: &str,
+    kind: SetupCommandsKind,
+) -> Result<Vec<String>, ProjectConfigError> {
+    let Some(config) = read_project_config(repo_path).await? else {
+        debug!(repo_path = %repo_path, "No orkestrator-ai.json found");
+        return Ok(vec![]);
     };
 
-    // Extract setupLocal field - can be string or array of strings
-    match config.get("setupLocal") {
-        Some(serde_json::Value::String(s)) => {
-            if s.is_empty() {
-                vec![]
-            } else {
-                vec![s.clone()]
-            }
-        }
-        Some(serde_json::Value::Array(arr)) => arr
-            .iter()
-            .filter_map(|v| v.as_str().map(|s| s.to_string()))
-            .filter(|s| !s.is_empty())
-            .collect(),
-        _ => {
-            debug!(worktree_path = %worktree_path, "No setupLocal field found in orkestrator-ai.json");
-            vec![]
-        }
+    let commands = match kind {
+        SetupCommandsKind::Local => config.setup_local,
+        SetupCommandsKind::Container => config.setup_container,
+    };
+    if commands.is_empty() {
+        debug!(repo_path = %repo_path, key = kind.key(), "No setup commands found in orkestrator-ai.json");
     }
+    Ok(commands)
+}
+
+/// Get setupLocal commands from orkestrator-ai.json without executing them
+///
+/// # Arguments
+/// * `worktree_path` - Path to the worktree directory
+pub async fn get_setup_local_commands(
+    worktree_path: &str,
+) -> Result<Vec<String>, ProjectConfigError> {
+    get_setup_commands(worktree_path, SetupCommandsKind::Local).await
+}
+
+/// Get setupContainer commands from orkestrator-ai.json without executing them
+///
+/// # Arguments
+/// * `repo_path` - Path to a checkout of the repository (e.g. the project's local path)
+pub async fn get_setup_container_commands(
+    repo_path: &str,
+) -> Result<Vec<String>, ProjectConfigError> {
+    get_setup_commands(repo_path, SetupCommandsKind::Container).await
 }
 
 #[cfg(test)]
@@ -1005,7 +1204,9 @@ mod tests {
     #[tokio::test]
     async fn test_get_setup_local_commands_no_config_file() {
         let temp_dir = TempDir::new().unwrap();
-        let result = get_setup_local_commands(temp_dir.path().to_str().unwrap()).await;
+        let result = get_setup_local_commands(temp_dir.path().to_str().unwrap())
+            .await
+            .unwrap();
 
         assert!(result.is_empty());
     }
@@ -1018,7 +1219,9 @@ mod tests {
             .await
             .unwrap();
 
-        let result = get_setup_local_commands(temp_dir.path().to_str().unwrap()).await;
+        let result = get_setup_local_commands(temp_dir.path().to_str().unwrap())
+            .await
+            .unwrap();
 
         assert!(result.is_empty());
     }
@@ -1031,7 +1234,9 @@ mod tests {
             .await
             .unwrap();
 
-        let result = get_setup_local_commands(temp_dir.path().to_str().unwrap()).await;
+        let result = get_setup_local_commands(temp_dir.path().to_str().unwrap())
+            .await
+            .unwrap();
 
         assert!(result.is_empty());
     }
@@ -1044,7 +1249,9 @@ mod tests {
             .await
             .unwrap();
 
-        let result = get_setup_local_commands(temp_dir.path().to_str().unwrap()).await;
+        let result = get_setup_local_commands(temp_dir.path().to_str().unwrap())
+            .await
+            .unwrap();
 
         assert_eq!(result.len(), 1);
         assert_eq!(result[0], "echo hello");
@@ -1061,7 +1268,9 @@ mod tests {
         .await
         .unwrap();
 
-        let result = get_setup_local_commands(temp_dir.path().to_str().unwrap()).await;
+        let result = get_setup_local_commands(temp_dir.path().to_str().unwrap())
+            .await
+            .unwrap();
 
         assert_eq!(result.len(), 3);
         assert_eq!(result[0], "echo one");
@@ -1080,7 +1289,9 @@ mod tests {
         .await
         .unwrap();
 
-        let result = get_setup_local_commands(temp_dir.path().to_str().unwrap()).await;
+        let result = get_setup_local_commands(temp_dir.path().to_str().unwrap())
+            .await
+            .unwrap();
 
         assert_eq!(result.len(), 2);
         assert_eq!(result[0], "echo one");
@@ -1097,7 +1308,60 @@ mod tests {
 
         let result = get_setup_local_commands(temp_dir.path().to_str().unwrap()).await;
 
-        assert!(result.is_empty());
+        assert!(matches!(result, Err(ProjectConfigError::InvalidJson(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_setup_local_commands_wrong_type() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("orkestrator-ai.json");
+        tokio::fs::write(&config_path, r#"{"setupLocal": {"cmd": "echo hello"}}"#)
+            .await
+            .unwrap();
+
+        let err = get_setup_local_commands(temp_dir.path().to_str().unwrap())
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.field_errors().len(), 1);
+        assert_eq!(err.field_errors()[0].field_path, "setupLocal");
+    }
+
+    #[tokio::test]
+    async fn test_get_setup_container_commands_reads_container_key_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("orkestrator-ai.json");
+        tokio::fs::write(
+            &config_path,
+            r#"{"setupLocal": "echo local", "setupContainer": ["echo one", "", "echo two"]}"#,
+        )
+        .await
+        .unwrap();
+        let path = temp_dir.path().to_str().unwrap();
+
+        assert_eq!(
+            get_setup_container_commands(path).await.unwrap(),
+            vec!["echo one".to_string(), "echo two".to_string()]
+        );
+        assert_eq!(
+            get_setup_local_commands(path).await.unwrap(),
+            vec!["echo local".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_setup_container_commands_single_string() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("orkestrator-ai.json");
+        tokio::fs::write(&config_path, r#"{"setupContainer": "bun install"}"#)
+            .await
+            .unwrap();
+
+        let result = get_setup_container_commands(temp_dir.path().to_str().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(result, vec!["bun install".to_string()]);
     }
 
     #[tokio::test]
@@ -1325,11 +1589,73 @@ mod tests {
             "Should be a suffixed variant, got: {}",
             result.branch
         );
+        let conflict = result
+            .conflict
+            .as_ref()
+            .expect("conflict should be reported");
+        assert_eq!(conflict.reason, WorktreeConflictReason::BranchOnRemote);
+        assert_eq!(conflict.requested_branch, "my-feature");
+        assert_eq!(conflict.adjusted_branch, result.branch);
 
         // Clean up the worktree
         let _ = delete_worktree(local_path, &result.path).await;
     }
 
+    #[test]
+    fn test_worktree_for_branch_in_porcelain() {
+        let porcelain = "worktree /repo\nHEAD abc\nbranch refs/heads/main\n\nworktree /ws/feature-x\nHEAD def\nbranch refs/heads/feature\n";
+        assert_eq!(
+            worktree_for_branch_in_porcelain(porcelain, "feature"),
+            Some("/ws/feature-x".to_string())
+        );
+        assert_eq!(
+            worktree_for_branch_in_porcelain(porcelain, "main"),
+            Some("/repo".to_string())
+        );
+        assert_eq!(worktree_for_branch_in_porcelain(porcelain, "feat"), None);
+    }
+
+    #[tokio::test]
+    async fn test_move_worktree_relocates_directory() {
+        let (_remote, local, _default_branch) = setup_repo_with_remote().await;
+        let local_path = local.path().to_str().unwrap();
+        let worktree = create_worktree(local_path, "move-me", "test-project", None)
+            .await
+            .unwrap();
+
+        let new_base = TempDir::new().unwrap();
+        let new_path = new_base.path().join("moved");
+        move_worktree(local_path, &worktree.path, new_path.to_str().unwrap())
+            .await
+            .unwrap();
+
+        assert!(!Path::new(&worktree.path).exists());
+        assert!(new_path.join("init.txt").exists());
+
+        let _ = delete_worktree(local_path, new_path.to_str().unwrap()).await;
+    }
+
+    #[tokio::test]
+    async fn test_remove_clean_worktree_refuses_worktrees_with_changes() {
+        let (_remote, local, _default_branch) = setup_repo_with_remote().await;
+        let local_path = local.path().to_str().unwrap();
+        let worktree = create_worktree(local_path, "stale", "test-project", None)
+            .await
+            .unwrap();
+
+        std::fs::write(Path::new(&worktree.path).join("notes.txt"), "wip").unwrap();
+        assert!(remove_clean_worktree(local_path, &worktree.path)
+            .await
+            .is_err());
+        assert!(Path::new(&worktree.path).join("notes.txt").exists());
+
+        std::fs::remove_file(Path::new(&worktree.path).join("notes.txt")).unwrap();
+        remove_clean_worktree(local_path, &worktree.path)
+            .await
+            .unwrap();
+        assert!(!Path::new(&worktree.path).exists());
+    }
+
     #[tokio::test]
     async fn test_configure_local_git_artifacts_marks_continuity_skip_worktree() {
         let temp_dir = TempDir::new().unwrap();
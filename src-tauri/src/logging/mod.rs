@@ -0,0 +1,260 @@
+//! Tracing setup.
+//!
+//! Logs always go to stderr and to an in-memory buffer of recent lines, and,
+//! when "Save logs for debugging" is enabled, to a daily rolling file in the
+//! app data directory. The filter sits behind a reload layer so the level can
+//! be raised at runtime without a restart.
+
+mod recent;
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+use tracing::Level;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+use crate::models::DEFAULT_LOG_BUFFER_LINES;
+use crate::storage::Storage;
+pub use recent::LogRecord;
+use recent::{RecentLogs, RecentLogsLayer};
+
+/// Filter used when `RUST_LOG` isn't set
+const DEFAULT_FILTER: &str = "info,orkestrator_ai_lib=debug";
+
+/// Prefix of the rolling log files (the appender adds a date suffix)
+const LOG_FILE_PREFIX: &str = "orkestrator-ai.log";
+
+/// Log files older than this are removed at startup
+const LOG_RETENTION_DAYS: u64 = 7;
+
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+static FILE_LOGGING: AtomicBool = AtomicBool::new(false);
+static RECENT_LOGS: OnceLock<RecentLogs> = OnceLock::new();
+
+/// Read the `global` section of config.json directly.
+///
+/// This runs before the Tauri runtime is available, so we read the config file
+/// from the app data directory instead of going through the storage layer.
+fn read_global_config() -> Option<serde_json::Value> {
+    let path = Storage::get_data_dir().ok()?.join("config.json");
+    let contents = std::fs::read_to_string(&path).ok()?;
+    let mut value = serde_json::from_str::<serde_json::Value>(&contents).ok()?;
+    Some(value.get_mut("global")?.take())
+}
+
+fn recent_logs() -> &'static RecentLogs {
+    RECENT_LOGS.get_or_init(|| RecentLogs::new(DEFAULT_LOG_BUFFER_LINES as usize))
+}
+
+/// Return the log directory path.
+///
+/// Used both at startup (to configure the file appender) and by the
+/// `get_log_directory` Tauri command so there is a single source of truth.
+/// Lives in the app data directory, so it follows `ORKESTRATOR_AI_DATA_DIR`.
+pub fn log_dir_path() -> PathBuf {
+    Storage::get_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("logs")
+}
+
+fn is_log_file(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("log")
+        || path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with(LOG_FILE_PREFIX))
+}
+
+/// Delete log files in `dir` that are older than `max_age_days`.
+fn cleanup_old_logs(dir: &Path, max_age_days: u64) {
+    let cutoff =
+        std::time::SystemTime::now() - std::time::Duration::from_secs(max_age_days * 24 * 60 * 60);
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if is_log_file(&path) {
+            if let Ok(meta) = path.metadata() {
+                if let Ok(modified) = meta.modified() {
+                    if modified < cutoff {
+                        let _ = std::fs::remove_file(&path);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Most recently written rolling log file in `dir`
+fn newest_log_file(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(LOG_FILE_PREFIX))
+        })
+        .filter_map(|path| Some((path.metadata().ok()?.modified().ok()?, path)))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+/// Install the global subscriber. Call once, before anything logs.
+pub fn init() {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let (filter, handle) = reload::Layer::new(filter);
+
+    let global = read_global_config();
+    let setting = |key: &str| global.as_ref().and_then(|g| g.get(key));
+    if let Some(lines) = setting("logBufferLines").and_then(|v| v.as_u64()) {
+        recent_logs().set_capacity(lines as usize);
+    }
+
+    // Log directory: ~/Library/Application Support/orkestrator-ai/logs/ (macOS)
+    let debug_logging = setting("debugLogging")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let file_layer = if debug_logging {
+        let log_dir = log_dir_path();
+        let _ = std::fs::create_dir_all(&log_dir);
+
+        // Remove old log files to prevent unbounded disk usage.
+        cleanup_old_logs(&log_dir, LOG_RETENTION_DAYS);
+
+        let file_appender = tracing_appender::rolling::daily(&log_dir, LOG_FILE_PREFIX);
+        let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+
+        // Keep the guard alive for the entire app lifetime by leaking it.
+        // Dropping the guard would stop the background writer thread.
+        // Note: this means the write buffer is not flushed on exit, so
+        // the last few log lines may be lost. Acceptable for debug logging.
+        std::mem::forget(guard);
+        FILE_LOGGING.store(true, Ordering::Relaxed);
+
+        Some(
+            tracing_subscriber::fmt::layer()
+                .with_target(false)
+                .with_ansi(false)
+                .with_writer(non_blocking),
+        )
+    } else {
+        None
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().with_target(false))
+        .with(file_layer)
+        .with(RecentLogsLayer::new(recent_logs()))
+        .init();
+
+    let _ = FILTER_HANDLE.set(handle);
+}
+
+/// Filter directive for a level name: the app's own logs at `level`,
+/// dependencies at the same level but never more verbose than info
+fn filter_directive(level: &str) -> Result<String, String> {
+    let level =
+        LevelFilter::from_str(level.trim()).map_err(|_| format!("Invalid log level: {}", level))?;
+    let dependencies = level.min(LevelFilter::INFO);
+    Ok(format!("{},orkestrator_ai_lib={}", dependencies, level).to_lowercase())
+}
+
+/// Replace the active filter with one for `level` ("trace", "debug", "info",
+/// "warn", "error" or "off"). Returns the applied filter directive.
+pub fn set_level(level: &str) -> Result<String, String> {
+    let directive = filter_directive(level)?;
+    let handle = FILTER_HANDLE.get().ok_or("Logging is not initialized")?;
+    handle
+        .reload(EnvFilter::new(&directive))
+        .map_err(|e| format!("Failed to update log filter: {}", e))?;
+    Ok(directive)
+}
+
+/// Change how many recent log lines are kept in memory
+pub fn set_buffer_lines(lines: u32) {
+    recent_logs().set_capacity(lines as usize);
+}
+
+/// The newest `limit` captured log records at `min_level` ("error", "warn",
+/// "info", "debug" or "trace") or more severe, oldest first
+pub fn recent_records(limit: usize, min_level: &str) -> Result<Vec<LogRecord>, String> {
+    let min_level = Level::from_str(min_level.trim())
+        .map_err(|_| format!("Invalid log level: {}", min_level))?;
+    Ok(recent_logs().snapshot(limit, min_level))
+}
+
+/// The log file currently being written, if file logging is enabled
+pub fn current_log_file() -> Option<PathBuf> {
+    if !FILE_LOGGING.load(Ordering::Relaxed) {
+        return None;
+    }
+    newest_log_file(&log_dir_path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filetime::{set_file_mtime, FileTime};
+
+    #[test]
+    fn filter_directive_caps_dependency_verbosity() {
+        assert_eq!(
+            filter_directive("trace").unwrap(),
+            "info,orkestrator_ai_lib=trace"
+        );
+        assert_eq!(
+            filter_directive(" WARN ").unwrap(),
+            "warn,orkestrator_ai_lib=warn"
+        );
+        assert!(filter_directive("verbose").is_err());
+    }
+
+    #[test]
+    fn newest_log_file_picks_latest_rolling_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let older = dir.path().join("orkestrator-ai.log.2026-01-01");
+        let newer = dir.path().join("orkestrator-ai.log.2026-01-02");
+        std::fs::write(&older, "old").unwrap();
+        std::fs::write(dir.path().join("unrelated.txt"), "x").unwrap();
+        std::fs::write(&newer, "new").unwrap();
+        set_file_mtime(&older, FileTime::from_unix_time(1_767_225_600, 0)).unwrap();
+        set_file_mtime(&newer, FileTime::from_unix_time(1_767_312_000, 0)).unwrap();
+
+        assert_eq!(newest_log_file(dir.path()), Some(newer));
+        assert_eq!(newest_log_file(&dir.path().join("missing")), None);
+    }
+
+    #[test]
+    fn cleanup_old_logs_removes_only_expired_log_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let expired = dir.path().join("orkestrator-ai.log.2026-01-01");
+        let recent = dir.path().join("orkestrator-ai.log.2026-01-09");
+        let unrelated = dir.path().join("notes.txt");
+        for path in [&expired, &recent, &unrelated] {
+            std::fs::write(path, "x").unwrap();
+        }
+        let now = FileTime::now().unix_seconds();
+        let days_ago = |days: i64| FileTime::from_unix_time(now - days * 24 * 60 * 60, 0);
+        set_file_mtime(&expired, days_ago(8)).unwrap();
+        set_file_mtime(&recent, days_ago(6)).unwrap();
+        set_file_mtime(&unrelated, days_ago(30)).unwrap();
+
+        cleanup_old_logs(dir.path(), LOG_RETENTION_DAYS);
+
+        assert!(!expired.exists());
+        assert!(recent.exists());
+        assert!(unrelated.exists());
+    }
+}
@@ -0,0 +1,180 @@
+// In-memory ring buffer of recent log events
+// Backs the log panel so users can read and copy logs without finding files
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// One captured log event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogRecord {
+    pub timestamp: DateTime<Utc>,
+    /// "ERROR", "WARN", "INFO", "DEBUG" or "TRACE"
+    pub level: String,
+    pub target: String,
+    /// The event message followed by its fields as `key=value`
+    pub message: String,
+}
+
+/// Bounded, thread-safe buffer holding the most recent log records
+pub struct RecentLogs {
+    records: Mutex<VecDeque<(Level, LogRecord)>>,
+    capacity: AtomicUsize,
+}
+
+impl RecentLogs {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: Mutex::new(VecDeque::new()),
+            capacity: AtomicUsize::new(capacity.max(1)),
+        }
+    }
+
+    /// Change how many records are kept, dropping the oldest if shrinking
+    pub fn set_capacity(&self, capacity: usize) {
+        let capacity = capacity.max(1);
+        self.capacity.store(capacity, Ordering::Relaxed);
+        let mut records = self.records.lock().unwrap();
+        while records.len() > capacity {
+            records.pop_front();
+        }
+    }
+
+    fn push(&self, level: Level, record: LogRecord) {
+        let capacity = self.capacity.load(Ordering::Relaxed);
+        let mut records = self.records.lock().unwrap();
+        while records.len() >= capacity {
+            records.pop_front();
+        }
+        records.push_back((level, record));
+    }
+
+    /// The newest `limit` records at `min_level` severity or above, oldest first
+    pub fn snapshot(&self, limit: usize, min_level: Level) -> Vec<LogRecord> {
+        let records = self.records.lock().unwrap();
+        let mut matching: Vec<LogRecord> = records
+            .iter()
+            .rev()
+            // More verbose levels compare greater
+            .filter(|(level, _)| *level <= min_level)
+            .take(limit)
+            .map(|(_, record)| record.clone())
+            .collect();
+        matching.reverse();
+        matching
+    }
+}
+
+/// Collects an event's message and fields into one line
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Tracing layer that copies every event it sees into a [`RecentLogs`]
+pub struct RecentLogsLayer {
+    logs: &'static RecentLogs,
+}
+
+impl RecentLogsLayer {
+    pub fn new(logs: &'static RecentLogs) -> Self {
+        Self { logs }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for RecentLogsLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        self.logs.push(
+            *metadata.level(),
+            LogRecord {
+                timestamp: Utc::now(),
+                level: metadata.level().to_string(),
+                target: metadata.target().to_string(),
+                message: visitor.message + &visitor.fields,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn leaked(capacity: usize) -> &'static RecentLogs {
+        Box::leak(Box::new(RecentLogs::new(capacity)))
+    }
+
+    #[test]
+    fn captures_events_with_fields() {
+        let logs = leaked(10);
+        let subscriber = tracing_subscriber::registry().with(RecentLogsLayer::new(logs));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(environment_id = "env-1", "Started environment");
+        });
+
+        let records = logs.snapshot(10, Level::TRACE);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].level, "INFO");
+        assert_eq!(
+            records[0].message,
+            "Started environment environment_id=env-1"
+        );
+    }
+
+    #[test]
+    fn keeps_newest_records_within_capacity() {
+        let logs = leaked(3);
+        let subscriber = tracing_subscriber::registry().with(RecentLogsLayer::new(logs));
+        tracing::subscriber::with_default(subscriber, || {
+            for i in 0..5 {
+                tracing::info!("line {}", i);
+            }
+            tracing::warn!("careful");
+            tracing::debug!("noise");
+        });
+
+        let messages = |records: Vec<LogRecord>| -> Vec<String> {
+            records.into_iter().map(|r| r.message).collect()
+        };
+        assert_eq!(
+            messages(logs.snapshot(10, Level::TRACE)),
+            vec!["line 4", "careful", "noise"]
+        );
+        assert_eq!(messages(logs.snapshot(1, Level::TRACE)), vec!["noise"]);
+        assert_eq!(messages(logs.snapshot(10, Level::WARN)), vec!["careful"]);
+
+        logs.set_capacity(1);
+        assert_eq!(messages(logs.snapshot(10, Level::TRACE)), vec!["noise"]);
+    }
+}
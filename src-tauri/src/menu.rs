@@ -0,0 +1,125 @@
+// Application menu
+// Built from the configured keybindings so shortcuts can be remapped
+
+use tauri::menu::{
+    Menu, MenuBuilder, MenuEvent, MenuItemBuilder, PredefinedMenuItem, SubmenuBuilder,
+};
+use tauri::{AppHandle, Emitter, Runtime};
+use tracing::warn;
+
+use crate::models::{default_keybindings, is_valid_accelerator, MenuKeybinding};
+use crate::storage::get_config;
+
+/// Actions shown in the View menu; every other action goes to the Actions menu
+const VIEW_ACTIONS: &[&str] = &["zoom_in", "zoom_out", "zoom_reset"];
+
+/// Keybindings from the saved config (defaults when the config can't be loaded)
+fn load_keybindings() -> Vec<MenuKeybinding> {
+    get_config()
+        .map(|config| config.global.keybindings)
+        .unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to load keybindings, using defaults");
+            default_keybindings()
+        })
+}
+
+/// Build the app menu with the configured keybindings
+pub fn build_app_menu<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<Menu<R>> {
+    // Create App menu with About and Quit (CMD+Q)
+    let app_menu = SubmenuBuilder::new(app, "Orkestrator AI")
+        .item(&PredefinedMenuItem::about(
+            app,
+            Some("About Orkestrator AI"),
+            None,
+        )?)
+        .separator()
+        .item(&PredefinedMenuItem::hide(app, None)?)
+        .item(&PredefinedMenuItem::hide_others(app, None)?)
+        .item(&PredefinedMenuItem::show_all(app, None)?)
+        .separator()
+        .item(&PredefinedMenuItem::quit(app, None)?)
+        .build()?;
+
+    // Create Edit menu with standard editing shortcuts
+    let edit_menu = SubmenuBuilder::new(app, "Edit")
+        .item(&PredefinedMenuItem::undo(app, None)?)
+        .item(&PredefinedMenuItem::redo(app, None)?)
+        .separator()
+        .item(&PredefinedMenuItem::cut(app, None)?)
+        .item(&PredefinedMenuItem::copy(app, None)?)
+        .item(&PredefinedMenuItem::paste(app, None)?)
+        .item(&PredefinedMenuItem::select_all(app, None)?)
+        .build()?;
+
+    let mut view_menu = SubmenuBuilder::new(app, "View");
+    let mut actions_menu = SubmenuBuilder::new(app, "Actions");
+    let mut has_actions = false;
+
+    for binding in load_keybindings() {
+        let mut item = MenuItemBuilder::with_id(binding.action.as_str(), &binding.label);
+        match binding.accelerator.as_deref() {
+            Some(accelerator) if is_valid_accelerator(accelerator) => {
+                item = item.accelerator(accelerator);
+            }
+            Some(accelerator) => {
+                // Hand-edited configs skip save-time validation
+                warn!(action = %binding.action, accelerator, "Ignoring invalid accelerator");
+            }
+            None => {}
+        }
+        let item = item.build(app)?;
+
+        if VIEW_ACTIONS.contains(&binding.action.as_str()) {
+            if binding.action == "zoom_reset" {
+                view_menu = view_menu.separator();
+            }
+            view_menu = view_menu.item(&item);
+        } else {
+            actions_menu = actions_menu.item(&item);
+            has_actions = true;
+        }
+    }
+
+    let view_menu = view_menu.build()?;
+    let mut menu = MenuBuilder::new(app).items(&[&app_menu, &edit_menu, &view_menu]);
+    let actions_menu = actions_menu.build()?;
+    if has_actions {
+        menu = menu.item(&actions_menu);
+    }
+    menu.build()
+}
+
+/// Rebuild the app menu after the keybindings changed
+pub fn refresh_app_menu<R: Runtime>(app: &AppHandle<R>) {
+    match build_app_menu(app) {
+        Ok(menu) => {
+            if let Err(e) = app.set_menu(menu) {
+                warn!(error = %e, "Failed to set rebuilt app menu");
+            }
+        }
+        Err(e) => warn!(error = %e, "Failed to rebuild app menu"),
+    }
+}
+
+/// Forward clicks on configured menu items to the frontend as "menu-action"
+/// events carrying the action id. Zoom actions also keep emitting "menu-zoom".
+pub fn handle_menu_event<R: Runtime>(app: &AppHandle<R>, event: MenuEvent) {
+    let action = event.id().0.as_str();
+    if !load_keybindings().iter().any(|b| b.action == action) {
+        return;
+    }
+
+    let zoom = match action {
+        "zoom_in" => Some("in"),
+        "zoom_out" => Some("out"),
+        "zoom_reset" => Some("reset"),
+        _ => None,
+    };
+    if let Some(zoom) = zoom {
+        let _ = app.emit("menu-zoom", zoom);
+    }
+
+    if let Err(e) = app.emit("menu-action", action) {
+        warn!(action, error = %e, "Failed to emit menu-action");
+    }
+}
@@ -16,6 +16,9 @@ pub struct Project {
     /// Display order in the sidebar (lower values appear first)
     #[serde(default)]
     pub order: i32,
+    /// Sidebar group the project belongs to (key of `AppConfig::groups`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
 }
 
 impl Project {
@@ -28,10 +31,21 @@ impl Project {
             local_path,
             added_at: Utc::now(),
             order: 0,
+            group_id: None,
         }
     }
 }
 
+/// Named group of projects in the sidebar, e.g. "work" or "personal"
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectGroup {
+    pub name: String,
+    /// Display order in the sidebar (lower values appear first)
+    #[serde(default)]
+    pub order: i32,
+}
+
 /// Extract repository name from git URL
 /// Handles both SSH (git@github.com:user/repo.git) and HTTPS (https://github.com/user/repo.git)
 fn extract_repo_name(git_url: &str) -> String {
@@ -83,6 +97,18 @@ pub enum PrState {
     Closed,
 }
 
+/// Summarized CI state of a pull request's check runs
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CiStatus {
+    /// All checks passed or were skipped
+    Passing,
+    /// At least one check failed or was cancelled
+    Failing,
+    /// Checks are still running
+    Pending,
+}
+
 /// Network access mode for environment containers
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "lowercase")]
@@ -123,6 +149,29 @@ impl std::fmt::Display for PortProtocol {
     }
 }
 
+/// Docker restart policy applied to environment containers
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    /// Never restart automatically (default, matches previous behavior)
+    #[default]
+    No,
+    /// Restart when the container exits with a non-zero code
+    OnFailure,
+    /// Always restart unless the container was explicitly stopped
+    UnlessStopped,
+}
+
+impl std::fmt::Display for RestartPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RestartPolicy::No => write!(f, "no"),
+            RestartPolicy::OnFailure => write!(f, "on-failure"),
+            RestartPolicy::UnlessStopped => write!(f, "unless-stopped"),
+        }
+    }
+}
+
 /// Port mapping configuration for container ports
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -136,6 +185,51 @@ pub struct PortMapping {
     pub protocol: PortProtocol,
 }
 
+/// Additional git remote an environment's base ref is fetched from
+/// (the fork of a cross-repository pull request)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitRemote {
+    pub name: String,
+    pub url: String,
+}
+
+/// Why `create_worktree` put a local environment on a different branch than
+/// the one it asked for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WorktreeConflictReason {
+    /// The branch is already checked out in another worktree
+    BranchCheckedOut,
+    /// The branch exists on origin but not locally, and may carry the PR of an
+    /// earlier environment
+    BranchOnRemote,
+}
+
+/// Branch conflict hit while creating a local environment's worktree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorktreeConflict {
+    pub reason: WorktreeConflictReason,
+    /// Branch the environment asked for
+    pub requested_branch: String,
+    /// Branch the worktree was created on instead
+    pub adjusted_branch: String,
+    /// Worktree that has `requested_branch` checked out (for `BranchCheckedOut`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub existing_worktree: Option<String>,
+}
+
+/// App port reserved on the host for a local environment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalAppPort {
+    /// Port configured in the repository's default port mappings
+    pub app_port: u16,
+    /// Port reserved on localhost (differs from `app_port` when it was taken)
+    pub host_port: u16,
+}
+
 impl std::fmt::Display for EnvironmentStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -160,6 +254,13 @@ pub struct Environment {
     /// Defaults to "main" for backward compatibility with existing environments
     #[serde(default = "default_branch")]
     pub branch: String,
+    /// Branch this environment's branch was created from, e.g. a pull
+    /// request's head branch (None = the repository's default branch)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_ref: Option<String>,
+    /// Remote `base_ref` lives on when it is not `origin` (forked pull requests)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_remote: Option<GitRemote>,
     pub container_id: Option<String>,
     pub status: EnvironmentStatus,
     pub pr_url: Option<String>,
@@ -169,6 +270,9 @@ pub struct Environment {
     /// Whether the PR has merge conflicts with the target branch
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub has_merge_conflicts: Option<bool>,
+    /// Summarized CI status of the PR's checks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ci_status: Option<CiStatus>,
     pub created_at: DateTime<Utc>,
     /// Enable debug mode for verbose logging in container entrypoint
     #[serde(default)]
@@ -222,6 +326,9 @@ pub struct Environment {
     /// Host port for codex-bridge server (local mode - static allocation)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub local_codex_port: Option<u16>,
+    /// App ports reserved from the repository's default port mappings (local mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_app_ports: Option<Vec<LocalAppPort>>,
 
     // === Agent settings overrides ===
     /// Per-environment default agent override (None = use global config)
@@ -237,9 +344,29 @@ pub struct Environment {
     /// Per-environment OpenCode mode override (None = use global config)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub opencode_mode: Option<OpenCodeMode>,
+    /// Per-environment OpenCode model, e.g. "anthropic/claude-sonnet-4-6"
+    /// (None = use the global `opencode_model`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opencode_model_override: Option<String>,
     /// Per-environment Codex mode override (None = use global config)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub codex_mode: Option<CodexMode>,
+    /// Per-environment Docker restart policy override (None = inherit from
+    /// repository, then `no`). Applied when the container is created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart_policy: Option<RestartPolicy>,
+    /// Per-environment auto-created sessions override (None = inherit from
+    /// repository). An empty list disables auto-created sessions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_sessions: Option<Vec<SessionType>>,
+    /// Clone the full history even when the repository sets a `clone_depth`.
+    /// Applied when the container is created.
+    #[serde(default)]
+    pub full_clone: bool,
+    /// Whether the container's repository is a shallow clone that has not been
+    /// unshallowed yet, so blame and log only see part of the history
+    #[serde(default)]
+    pub shallow_history: bool,
 
     /// Whether setup scripts (from orkestrator-ai.json setupLocal or container
     /// workspace initialization) have completed for this environment. Persisted
@@ -250,6 +377,28 @@ pub struct Environment {
     /// Initial prompt used when the environment was created.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub initial_prompt: Option<String>,
+    /// Whether the container firewall is temporarily bypassed (all traffic
+    /// allowed) while `network_access_mode` stays Restricted. Cleared when the
+    /// container stops or restarts, since the entrypoint re-applies the rules.
+    #[serde(default)]
+    pub firewall_bypassed: bool,
+    /// Docker Compose project name (`docker compose -p`) while the repository's
+    /// compose services are up for this environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compose_project_name: Option<String>,
+    /// Review mode: file writes and PR merges are refused, and new tabs open
+    /// plain shells instead of launching an agent
+    #[serde(default)]
+    pub read_only: bool,
+    /// Free-form notes (what's being worked on, TODOs). Kept across renames.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Pinned environments are listed before unpinned ones, regardless of order
+    #[serde(default)]
+    pub pinned: bool,
+    /// Unresolved branch conflict from creating the worktree (local environments)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree_conflict: Option<WorktreeConflict>,
 }
 
 /// Default branch for backward compatibility with existing environments
@@ -311,6 +460,28 @@ pub fn sanitize_branch_name(name: &str) -> String {
     sanitize_slug(name, "env", 0)
 }
 
+/// Validate a branch name before passing it to git.
+/// Git ref names cannot contain: space, ~, ^, :, ?, *, [, \, control chars, "..", "@{"
+/// Also rejects shell metacharacters and leading dashes for defense in depth
+pub fn is_valid_branch_name(branch: &str) -> bool {
+    !branch.is_empty()
+        && !branch.starts_with('-')
+        && !branch.starts_with('/')
+        && !branch.ends_with('/')
+        && !branch.ends_with('.')
+        && !branch.ends_with(".lock")
+        && !branch.contains("..")
+        && !branch.contains("//")
+        && !branch.contains("@{")
+        && !branch.chars().any(|c| {
+            c.is_control()
+                || matches!(
+                    c,
+                    ' ' | '~' | '^' | ':' | '?' | '*' | '[' | '\\' | ';' | '&' | '|' | '$' | '`'
+                )
+        })
+}
+
 /// Sanitize a string for use as an environment name.
 /// Produces a lowercase kebab-case slug matching the branch/container name convention.
 /// Delegates to [`sanitize_slug`] with a max length of 100 characters.
@@ -330,11 +501,14 @@ impl Environment {
             project_id,
             name,
             branch,
+            base_ref: None,
+            base_remote: None,
             container_id: None,
             status: EnvironmentStatus::Stopped,
             pr_url: None,
             pr_state: None,
             has_merge_conflicts: None,
+            ci_status: None,
             created_at: Utc::now(),
             debug_mode: false,
             network_access_mode: NetworkAccessMode::default(),
@@ -352,13 +526,25 @@ impl Environment {
             local_opencode_port: None,
             local_claude_port: None,
             local_codex_port: None,
+            local_app_ports: None,
             default_agent: None,
             claude_mode: None,
             claude_native_backend: None,
             opencode_mode: None,
+            opencode_model_override: None,
             codex_mode: None,
+            restart_policy: None,
+            default_sessions: None,
+            full_clone: false,
+            shallow_history: false,
             setup_scripts_complete: false,
             initial_prompt: None,
+            firewall_bypassed: false,
+            compose_project_name: None,
+            read_only: false,
+            notes: None,
+            pinned: false,
+            worktree_conflict: None,
         }
     }
 
@@ -372,11 +558,14 @@ impl Environment {
             project_id,
             name,
             branch,
+            base_ref: None,
+            base_remote: None,
             container_id: None,
             status: EnvironmentStatus::Stopped,
             pr_url: None,
             pr_state: None,
             has_merge_conflicts: None,
+            ci_status: None,
             created_at: Utc::now(),
             debug_mode: false,
             network_access_mode: NetworkAccessMode::default(),
@@ -394,13 +583,25 @@ impl Environment {
             local_opencode_port: None,
             local_claude_port: None,
             local_codex_port: None,
+            local_app_ports: None,
             default_agent: None,
             claude_mode: None,
             claude_native_backend: None,
             opencode_mode: None,
+            opencode_model_override: None,
             codex_mode: None,
+            restart_policy: None,
+            default_sessions: None,
+            full_clone: false,
+            shallow_history: false,
             setup_scripts_complete: false,
             initial_prompt: None,
+            firewall_bypassed: false,
+            compose_project_name: None,
+            read_only: false,
+            notes: None,
+            pinned: false,
+            worktree_conflict: None,
         }
     }
 
@@ -414,11 +615,14 @@ impl Environment {
             project_id,
             name,
             branch,
+            base_ref: None,
+            base_remote: None,
             container_id: None,
             status: EnvironmentStatus::Stopped,
             pr_url: None,
             pr_state: None,
             has_merge_conflicts: None,
+            ci_status: None,
             created_at: Utc::now(),
             debug_mode: false,
             network_access_mode: NetworkAccessMode::Full, // Local environments have full network access
@@ -436,13 +640,25 @@ impl Environment {
             local_opencode_port: None,
             local_claude_port: None,
             local_codex_port: None,
+            local_app_ports: None,
             default_agent: None,
             claude_mode: None,
             claude_native_backend: None,
             opencode_mode: None,
+            opencode_model_override: None,
             codex_mode: None,
+            restart_policy: None,
+            default_sessions: None,
+            full_clone: false,
+            shallow_history: false,
             setup_scripts_complete: false,
             initial_prompt: None,
+            firewall_bypassed: false,
+            compose_project_name: None,
+            read_only: false,
+            notes: None,
+            pinned: false,
+            worktree_conflict: None,
         }
     }
 
@@ -556,6 +772,62 @@ pub struct ProjectNotes {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Hosting metadata of a project's repository, cached in
+/// repository-metadata.json
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryMetadata {
+    pub name: String,
+    pub description: Option<String>,
+    /// Default branch on the remote
+    pub default_branch: Option<String>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// "PUBLIC", "PRIVATE" or "INTERNAL" (None when read without gh)
+    pub visibility: Option<String>,
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// Main window state restored on startup (stored in window_state.json)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowState {
+    /// Zoom level as a percentage (None = 100)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zoom_level: Option<u32>,
+    /// Outer window size in physical pixels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// Outer window position in physical pixels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<i32>,
+}
+
+impl WindowState {
+    pub const MIN_ZOOM_LEVEL: u32 = 50;
+    pub const MAX_ZOOM_LEVEL: u32 = 200;
+
+    /// Apply the fields set in `update`, keeping the others.
+    /// The zoom level is clamped to the range the UI supports.
+    pub fn merge(&mut self, update: WindowState) {
+        if let Some(zoom_level) = update.zoom_level {
+            self.zoom_level = Some(zoom_level.clamp(Self::MIN_ZOOM_LEVEL, Self::MAX_ZOOM_LEVEL));
+        }
+        if update.width.is_some() && update.height.is_some() {
+            self.width = update.width;
+            self.height = update.height;
+        }
+        if update.x.is_some() && update.y.is_some() {
+            self.x = update.x;
+            self.y = update.y;
+        }
+    }
+}
+
 // ============================================================================
 // Session Models - Terminal session tracking for environments
 // ============================================================================
@@ -597,6 +869,61 @@ impl std::fmt::Display for SessionType {
     }
 }
 
+/// Whether a model id can be passed to an agent CLI as-is, e.g.
+/// "sonnet", "claude-opus-4-1" or "anthropic/claude-sonnet-4-6"
+pub fn is_valid_model_id(model: &str) -> bool {
+    !model.is_empty()
+        && model
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/' | ':' | '@'))
+}
+
+/// Agent, model and permission flags a terminal session is launched with
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionLaunchConfig {
+    pub session_type: SessionType,
+    /// Model passed to the agent CLI (None = the agent's default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Skip permission prompts (always on for `claude-yolo` sessions)
+    #[serde(default)]
+    pub yolo: bool,
+}
+
+impl SessionLaunchConfig {
+    /// Shell command that starts the agent, or None for shell sessions
+    pub fn launch_command(&self) -> Option<String> {
+        let model = self
+            .model
+            .as_deref()
+            .map(|model| format!(" --model {}", model))
+            .unwrap_or_default();
+        match self.session_type {
+            SessionType::Claude | SessionType::ClaudeYolo => {
+                let yolo = self.yolo || self.session_type == SessionType::ClaudeYolo;
+                let flags = if yolo {
+                    " --dangerously-skip-permissions"
+                } else {
+                    ""
+                };
+                Some(format!("claude{}{}", model, flags))
+            }
+            // OpenCode has no flag to skip permission prompts
+            SessionType::Opencode => Some(format!("opencode{}", model)),
+            SessionType::Codex => {
+                let flags = if self.yolo {
+                    " --dangerously-bypass-approvals-and-sandbox"
+                } else {
+                    ""
+                };
+                Some(format!("codex{}{}", model, flags))
+            }
+            SessionType::Plain | SessionType::Root => None,
+        }
+    }
+}
+
 /// Connection status of a terminal session
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -655,6 +982,13 @@ pub struct Session {
     /// Used to prevent re-launching Claude on app restart/reconnection
     #[serde(default)]
     pub has_launched_command: bool,
+    /// Container terminal (PTY) session currently backing this session, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminal_session_id: Option<String>,
+    /// Agent and model chosen when the session was created, so reconnects
+    /// relaunch the same command (None = launched from the tab type)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launch_config: Option<SessionLaunchConfig>,
 }
 
 impl Session {
@@ -678,6 +1012,8 @@ impl Session {
             name: None,
             order: 0, // Will be set properly when added to storage
             has_launched_command: false,
+            terminal_session_id: None,
+            launch_config: None,
         }
     }
 
@@ -776,6 +1112,43 @@ pub enum ClaudeNativeBackend {
     Tmux,
 }
 
+/// Container engine the app connects to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerEngineKind {
+    /// Docker, or Podman when only a Podman socket is found
+    #[default]
+    Auto,
+    Docker,
+    /// Podman through its Docker-compatible API socket
+    Podman,
+}
+
+/// What a terminal does when its output channel is full because the
+/// frontend renders slower than the process writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum TerminalOverflowPolicy {
+    /// Wait for the frontend to catch up (pauses the process's output)
+    #[default]
+    Block,
+    /// Discard the oldest buffered output and show an "[output truncated]" marker
+    DropOldest,
+}
+
+/// Which Claude credential is injected into containers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthPreference {
+    /// Inject keychain OAuth credentials when available, plus the API key if configured
+    #[default]
+    Auto,
+    /// Only inject keychain OAuth credentials
+    Oauth,
+    /// Only inject the Anthropic API key; starting fails if none is configured
+    ApiKey,
+}
+
 /// Codex mode - terminal CLI or native chat interface
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -816,7 +1189,7 @@ impl PreferredEditor {
 }
 
 /// Terminal appearance settings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TerminalAppearance {
     /// Font family for terminal and code editor
@@ -846,10 +1219,197 @@ fn default_terminal_scrollback() -> u32 {
     1000
 }
 
+/// Default capacity of terminal input/output channels (chunks)
+pub const DEFAULT_TERMINAL_CHANNEL_CAPACITY: u32 = 1024;
+
+fn default_terminal_channel_capacity() -> u32 {
+    DEFAULT_TERMINAL_CHANNEL_CAPACITY
+}
+
+/// Default size of the rolling output buffer kept per terminal session (bytes)
+pub const DEFAULT_TERMINAL_BUFFER_MAX_BYTES: u32 = 256 * 1024;
+
+fn default_terminal_buffer_max_bytes() -> u32 {
+    DEFAULT_TERMINAL_BUFFER_MAX_BYTES
+}
+
+/// Default number of recent log lines kept in memory for the log panel
+pub const DEFAULT_LOG_BUFFER_LINES: u32 = 2000;
+
+fn default_log_buffer_lines() -> u32 {
+    DEFAULT_LOG_BUFFER_LINES
+}
+
+fn default_terminal_buffer_autosave_secs() -> u64 {
+    30
+}
+
+fn default_config_save_debounce_ms() -> u64 {
+    500
+}
+
+fn default_min_free_disk_gb() -> u32 {
+    5
+}
+
 fn default_experimental_codex_raw_event_logging() -> bool {
     true
 }
 
+fn default_firewall_refresh_interval_secs() -> u64 {
+    300
+}
+
+/// Default maximum number of words in an AI-generated environment name
+pub const DEFAULT_ENVIRONMENT_NAME_MAX_WORDS: u32 = 3;
+
+/// Where the repository is checked out inside containers by default
+pub const DEFAULT_WORKSPACE_PATH: &str = "/workspace";
+
+/// Whether a container workspace path is usable: absolute, not the root,
+/// and free of `..` components, whitespace and control characters
+pub fn is_valid_workspace_path(path: &str) -> bool {
+    path.starts_with('/')
+        && !path.trim_end_matches('/').is_empty()
+        && !path.chars().any(|c| c.is_whitespace() || c.is_control())
+        && !path.split('/').any(|part| part == "..")
+}
+
+/// Whether a Docker network name is usable for environments: starts with an
+/// alphanumeric character, then alphanumerics, `_`, `.` or `-`. The `host`
+/// and `none` drivers are rejected since they bypass port mappings and the
+/// restricted-mode firewall.
+pub fn is_valid_docker_network(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphanumeric())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+        && name.len() <= 64
+        && !matches!(name, "host" | "none")
+}
+
+fn default_environment_name_max_words() -> u32 {
+    DEFAULT_ENVIRONMENT_NAME_MAX_WORDS
+}
+
+/// Default bind address of local native-mode servers (this machine only)
+pub const DEFAULT_NATIVE_SERVER_HOST: &str = "127.0.0.1";
+
+fn default_native_server_host() -> String {
+    DEFAULT_NATIVE_SERVER_HOST.to_string()
+}
+
+/// Parse a bind address for local native-mode servers. Only 127.0.0.1 and the
+/// all-interfaces addresses (0.0.0.0, ::) are accepted, since the app itself
+/// always reaches the servers over 127.0.0.1.
+pub fn parse_native_server_host(host: &str) -> Option<std::net::IpAddr> {
+    let addr: std::net::IpAddr = host.trim().parse().ok()?;
+    (addr == std::net::Ipv4Addr::LOCALHOST || addr.is_unspecified()).then_some(addr)
+}
+
+/// A menu action and the keyboard shortcut that triggers it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuKeybinding {
+    /// Action id sent to the frontend in "menu-action" events, e.g. "new_environment"
+    pub action: String,
+    /// Menu item label
+    pub label: String,
+    /// Accelerator such as "CmdOrCtrl+N" (None = menu item without a shortcut)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accelerator: Option<String>,
+}
+
+/// Default menu keybindings: the View menu zoom shortcuts
+pub fn default_keybindings() -> Vec<MenuKeybinding> {
+    [
+        ("zoom_in", "Zoom In", "CmdOrCtrl+="),
+        ("zoom_out", "Zoom Out", "CmdOrCtrl+-"),
+        ("zoom_reset", "Actual Size", "CmdOrCtrl+0"),
+    ]
+    .into_iter()
+    .map(|(action, label, accelerator)| MenuKeybinding {
+        action: action.to_string(),
+        label: label.to_string(),
+        accelerator: Some(accelerator.to_string()),
+    })
+    .collect()
+}
+
+/// Whether a menu action id is usable: lowercase letters, digits and underscores
+pub fn is_valid_menu_action(action: &str) -> bool {
+    !action.is_empty()
+        && action
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Whether an accelerator string is understood by the native menu, e.g.
+/// "CmdOrCtrl+Shift+N": any number of modifiers followed by exactly one key
+pub fn is_valid_accelerator(accelerator: &str) -> bool {
+    const MODIFIERS: &[&str] = &[
+        "COMMANDORCONTROL",
+        "COMMANDORCTRL",
+        "CMDORCTRL",
+        "CMDORCONTROL",
+        "COMMAND",
+        "CMD",
+        "SUPER",
+        "CONTROL",
+        "CTRL",
+        "ALT",
+        "OPTION",
+        "SHIFT",
+    ];
+    const NAMED_KEYS: &[&str] = &[
+        "BACKSPACE",
+        "TAB",
+        "ENTER",
+        "RETURN",
+        "SPACE",
+        "ESC",
+        "ESCAPE",
+        "DELETE",
+        "INSERT",
+        "HOME",
+        "END",
+        "PAGEUP",
+        "PAGEDOWN",
+        "UP",
+        "DOWN",
+        "LEFT",
+        "RIGHT",
+        "ARROWUP",
+        "ARROWDOWN",
+        "ARROWLEFT",
+        "ARROWRIGHT",
+        "PLUS",
+    ];
+
+    let tokens: Vec<&str> = accelerator.split('+').collect();
+    let Some((key, modifiers)) = tokens.split_last() else {
+        return false;
+    };
+    let is_key = |token: &str| {
+        let upper = token.to_uppercase();
+        let mut chars = token.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c.is_ascii_alphanumeric() || "=-[]\\;',./`".contains(c),
+            _ => {
+                NAMED_KEYS.contains(&upper.as_str())
+                    || upper
+                        .strip_prefix('F')
+                        .and_then(|n| n.parse::<u8>().ok())
+                        .is_some_and(|n| (1..=24).contains(&n))
+            }
+        }
+    };
+
+    is_key(key.trim())
+        && modifiers
+            .iter()
+            .all(|m| MODIFIERS.contains(&m.trim().to_uppercase().as_str()))
+}
+
 fn default_opencode_model() -> String {
     "opencode/grok-code".to_string()
 }
@@ -885,6 +1445,9 @@ pub struct GlobalConfig {
     /// Anthropic API key for Claude Code in containers
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub anthropic_api_key: Option<String>,
+    /// Which Claude credential containers receive (oauth, api_key or auto)
+    #[serde(default)]
+    pub auth_preference: AuthPreference,
     /// GitHub Personal Access Token for HTTPS git operations
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub github_token: Option<String>,
@@ -934,12 +1497,70 @@ pub struct GlobalConfig {
     /// Terminal scrollback buffer size (lines)
     #[serde(default = "default_terminal_scrollback")]
     pub terminal_scrollback: u32,
+    /// Chunks buffered between a terminal and the frontend, in each direction
+    #[serde(default = "default_terminal_channel_capacity")]
+    pub terminal_channel_capacity: u32,
+    /// What to do when terminal output fills the channel
+    #[serde(default)]
+    pub terminal_overflow_policy: TerminalOverflowPolicy,
+    /// Recent output kept in memory per terminal session (bytes)
+    #[serde(default = "default_terminal_buffer_max_bytes")]
+    pub terminal_buffer_max_bytes: u32,
+    /// How often (in seconds) terminal output buffers are saved to disk so
+    /// scrollback survives a crash. 0 disables auto-save.
+    #[serde(default = "default_terminal_buffer_autosave_secs")]
+    pub terminal_buffer_autosave_secs: u64,
     /// Capture raw Codex bridge events for transcript debugging
     #[serde(default = "default_experimental_codex_raw_event_logging")]
     pub experimental_codex_raw_event_logging: bool,
     /// Enable debug logging to a file on disk (requires app restart)
     #[serde(default)]
     pub debug_logging: bool,
+    /// Recent log lines kept in memory for the log panel
+    #[serde(default = "default_log_buffer_lines")]
+    pub log_buffer_lines: u32,
+    /// How often (in seconds) running restricted containers re-resolve their
+    /// allowed domains and update the firewall ipset. 0 disables refreshing.
+    #[serde(default = "default_firewall_refresh_interval_secs")]
+    pub firewall_refresh_interval_secs: u64,
+    /// Free disk space (GB) below which creating a container or worktree
+    /// warns. 0 disables the check.
+    #[serde(default = "default_min_free_disk_gb")]
+    pub min_free_disk_gb: u32,
+    /// Refuse to create containers or worktrees when disk space is low,
+    /// instead of only warning
+    #[serde(default)]
+    pub block_on_low_disk_space: bool,
+    /// Refuse to create containers whose CPU or memory limits exceed what
+    /// Docker's host has, instead of only warning
+    #[serde(default)]
+    pub block_on_excess_resource_limits: bool,
+    /// Quiet period (ms) before settings changes are written to disk, so
+    /// rapid edits coalesce into one write. 0 writes every change immediately.
+    /// Pending changes are written on a normal exit, but a crash or force quit
+    /// within this window loses them.
+    #[serde(default = "default_config_save_debounce_ms")]
+    pub config_save_debounce_ms: u64,
+    /// Maximum number of words in AI-generated environment names
+    #[serde(default = "default_environment_name_max_words")]
+    pub environment_name_max_words: u32,
+    /// App menu actions and their keyboard shortcuts
+    #[serde(default = "default_keybindings")]
+    pub keybindings: Vec<MenuKeybinding>,
+    /// Container engine to use (auto, docker or podman)
+    #[serde(default)]
+    pub container_engine: ContainerEngineKind,
+    /// Address local OpenCode, Claude and Codex servers bind to: 127.0.0.1
+    /// (default) or 0.0.0.0 / :: to allow access from other devices
+    #[serde(default = "default_native_server_host")]
+    pub native_server_host: String,
+    /// Containers (by ID or name) that prune and orphan cleanup never remove
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned_containers: Vec<String>,
+    /// Images (by ID) that prune never removes. Tags are resolved to IDs
+    /// when pinned.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned_images: Vec<String>,
 }
 
 impl Default for GlobalConfig {
@@ -948,6 +1569,7 @@ impl Default for GlobalConfig {
             container_resources: ContainerResources::default(),
             env_file_patterns: vec![".env".to_string(), ".env.local".to_string()],
             anthropic_api_key: None,
+            auth_preference: AuthPreference::default(),
             github_token: None,
             allowed_domains: default_allowed_domains(),
             preferred_editor: None,
@@ -964,8 +1586,24 @@ impl Default for GlobalConfig {
             codex_native_fast_mode_default: false,
             terminal_appearance: TerminalAppearance::default(),
             terminal_scrollback: default_terminal_scrollback(),
+            terminal_channel_capacity: default_terminal_channel_capacity(),
+            terminal_overflow_policy: TerminalOverflowPolicy::default(),
+            terminal_buffer_max_bytes: default_terminal_buffer_max_bytes(),
+            terminal_buffer_autosave_secs: default_terminal_buffer_autosave_secs(),
             experimental_codex_raw_event_logging: default_experimental_codex_raw_event_logging(),
             debug_logging: false,
+            log_buffer_lines: default_log_buffer_lines(),
+            firewall_refresh_interval_secs: default_firewall_refresh_interval_secs(),
+            min_free_disk_gb: default_min_free_disk_gb(),
+            block_on_low_disk_space: false,
+            block_on_excess_resource_limits: false,
+            config_save_debounce_ms: default_config_save_debounce_ms(),
+            environment_name_max_words: default_environment_name_max_words(),
+            keybindings: default_keybindings(),
+            container_engine: ContainerEngineKind::default(),
+            native_server_host: default_native_server_host(),
+            pinned_containers: Vec::new(),
+            pinned_images: Vec::new(),
         }
     }
 }
@@ -1006,6 +1644,50 @@ pub struct RepositoryConfig {
     /// global). Meaningful only when resolved Claude mode is `Native`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub claude_native_backend: Option<ClaudeNativeBackend>,
+    /// Default Docker restart policy for new containers (None = `no`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart_policy: Option<RestartPolicy>,
+    /// Allow `docker compose up/down` for environments of this repository
+    #[serde(default)]
+    pub compose_enabled: bool,
+    /// Absolute path of the repository checkout inside containers
+    /// (None = [`DEFAULT_WORKSPACE_PATH`]). For base images that mount the
+    /// workspace elsewhere.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace_path: Option<String>,
+    /// Docker network new containers join (None = the default bridge). Created
+    /// on demand if missing. Port mappings still publish to the host as usual;
+    /// containers on the same network reach each other by name without them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docker_network: Option<String>,
+    /// Terminal sessions auto-created when an environment of this repository
+    /// starts without any, in tab order (None = none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_sessions: Option<Vec<SessionType>>,
+    /// Commits of history new containers clone (`git clone --depth`; None =
+    /// full history). Shallow clones are faster for large repositories, but
+    /// blame, log and merge-base only see the cloned commits until the
+    /// repository is unshallowed. Environments can opt out with `full_clone`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clone_depth: Option<u32>,
+}
+
+impl RepositoryConfig {
+    /// Workspace path inside containers, falling back to the default
+    pub fn workspace_path(&self) -> &str {
+        self.workspace_path
+            .as_deref()
+            .filter(|path| !path.trim().is_empty())
+            .unwrap_or(DEFAULT_WORKSPACE_PATH)
+    }
+
+    /// Docker network for new containers, ignoring blank values
+    pub fn docker_network(&self) -> Option<&str> {
+        self.docker_network
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+    }
 }
 
 impl Default for RepositoryConfig {
@@ -1022,6 +1704,12 @@ impl Default for RepositoryConfig {
             default_agent: None,
             agent_style: None,
             claude_native_backend: None,
+            restart_policy: None,
+            compose_enabled: false,
+            workspace_path: None,
+            docker_network: None,
+            default_sessions: None,
+            clone_depth: None,
         }
     }
 }
@@ -1033,6 +1721,19 @@ pub struct AppConfig {
     pub version: String,
     pub global: GlobalConfig,
     pub repositories: std::collections::HashMap<String, RepositoryConfig>,
+    /// Project groups by group ID
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub groups: std::collections::HashMap<String, ProjectGroup>,
+}
+
+impl AppConfig {
+    /// Workspace path inside containers of a project
+    pub fn workspace_path(&self, project_id: &str) -> &str {
+        self.repositories
+            .get(project_id)
+            .map(RepositoryConfig::workspace_path)
+            .unwrap_or(DEFAULT_WORKSPACE_PATH)
+    }
 }
 
 impl Default for AppConfig {
@@ -1041,6 +1742,7 @@ impl Default for AppConfig {
             version: "1.0.0".to_string(),
             global: GlobalConfig::default(),
             repositories: std::collections::HashMap::new(),
+            groups: std::collections::HashMap::new(),
         }
     }
 }
@@ -1193,6 +1895,18 @@ mod tests {
         assert!(deserialized.codex_native_fast_mode_default);
     }
 
+    #[test]
+    fn test_global_config_auth_preference_serialization() {
+        let config = GlobalConfig::default();
+        assert_eq!(config.auth_preference, AuthPreference::Auto);
+
+        let json = serde_json::to_string(&AuthPreference::ApiKey).unwrap();
+        assert_eq!(json, "\"api_key\"");
+
+        let deserialized: AuthPreference = serde_json::from_str("\"oauth\"").unwrap();
+        assert_eq!(deserialized, AuthPreference::Oauth);
+    }
+
     #[test]
     fn test_repository_config_default() {
         let config = RepositoryConfig::default();
@@ -1282,6 +1996,12 @@ mod tests {
                 default_agent: None,
                 agent_style: None,
                 claude_native_backend: None,
+                restart_policy: None,
+                compose_enabled: false,
+                workspace_path: None,
+                docker_network: None,
+                default_sessions: None,
+                clone_depth: None,
             },
         );
 
@@ -1300,6 +2020,147 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_valid_accelerator() {
+        for accelerator in [
+            "CmdOrCtrl+=",
+            "CmdOrCtrl+-",
+            "CmdOrCtrl+0",
+            "CmdOrCtrl+Shift+N",
+            "Alt+F12",
+            "Escape",
+        ] {
+            assert!(is_valid_accelerator(accelerator), "{accelerator}");
+        }
+        for accelerator in [
+            "",
+            "CmdOrCtrl+",
+            "CmdOrCtrl",
+            "Shift+CmdOrCtrl+N+M",
+            "Hyper+N",
+            "F25",
+        ] {
+            assert!(!is_valid_accelerator(accelerator), "{accelerator}");
+        }
+        assert!(default_keybindings()
+            .iter()
+            .all(|b| is_valid_menu_action(&b.action)
+                && b.accelerator.as_deref().is_some_and(is_valid_accelerator)));
+    }
+
+    #[test]
+    fn test_window_state_merge() {
+        let mut state = WindowState {
+            zoom_level: Some(110),
+            width: Some(1400),
+            height: Some(900),
+            x: None,
+            y: None,
+        };
+
+        state.merge(WindowState {
+            zoom_level: Some(500),
+            ..Default::default()
+        });
+        assert_eq!(state.zoom_level, Some(WindowState::MAX_ZOOM_LEVEL));
+        assert_eq!(state.width, Some(1400));
+
+        state.merge(WindowState {
+            width: Some(1000),
+            height: Some(700),
+            x: Some(-20),
+            y: Some(40),
+            ..Default::default()
+        });
+        assert_eq!(state.zoom_level, Some(WindowState::MAX_ZOOM_LEVEL));
+        assert_eq!((state.width, state.height), (Some(1000), Some(700)));
+        assert_eq!((state.x, state.y), (Some(-20), Some(40)));
+    }
+
+    #[test]
+    fn test_is_valid_branch_name() {
+        for branch in ["main", "feature/login-form", "release-1.2", "user_name/fix"] {
+            assert!(is_valid_branch_name(branch), "{branch} should be valid");
+        }
+        for branch in [
+            "", "-f", "a..b", "a b", "a~1", "a^", "a:b", "a?", "a*", "a[", "a\\b", "a;b", "$(id)",
+            "a@{1}", "a/", "/a", "a//b", "a.lock", "a.", "a\nb",
+        ] {
+            assert!(
+                !is_valid_branch_name(branch),
+                "{branch:?} should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_native_server_host() {
+        assert!(parse_native_server_host("127.0.0.1").is_some());
+        assert!(parse_native_server_host(" 0.0.0.0 ").is_some());
+        assert!(parse_native_server_host("::").is_some());
+        for host in ["", "localhost", "192.168.1.10", "::1", "127.0.0.2"] {
+            assert!(
+                parse_native_server_host(host).is_none(),
+                "{host:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_session_launch_command() {
+        let config = |session_type, model: Option<&str>, yolo| SessionLaunchConfig {
+            session_type,
+            model: model.map(String::from),
+            yolo,
+        };
+
+        assert_eq!(
+            config(SessionType::Claude, Some("opus"), true).launch_command(),
+            Some("claude --model opus --dangerously-skip-permissions".to_string())
+        );
+        assert_eq!(
+            config(SessionType::ClaudeYolo, None, false).launch_command(),
+            Some("claude --dangerously-skip-permissions".to_string())
+        );
+        assert_eq!(
+            config(SessionType::Opencode, Some("openai/gpt-5"), true).launch_command(),
+            Some("opencode --model openai/gpt-5".to_string())
+        );
+        assert_eq!(
+            config(SessionType::Codex, None, false).launch_command(),
+            Some("codex".to_string())
+        );
+        assert_eq!(
+            config(SessionType::Plain, Some("x"), true).launch_command(),
+            None
+        );
+
+        assert!(is_valid_model_id("openai/gpt-5.1-codex"));
+        assert!(!is_valid_model_id(""));
+        assert!(!is_valid_model_id("opus; rm -rf /"));
+    }
+
+    #[test]
+    fn test_is_valid_docker_network() {
+        for name in ["bridge", "shared-db", "my_net.1", "Net2"] {
+            assert!(is_valid_docker_network(name), "{name} should be valid");
+        }
+        for name in [
+            "",
+            "host",
+            "none",
+            "-net",
+            ".net",
+            "a b",
+            "container:db",
+            "a/b",
+            "a;b",
+        ] {
+            assert!(!is_valid_docker_network(name), "{name:?} should be invalid");
+        }
+        assert!(!is_valid_docker_network(&"n".repeat(65)));
+    }
+
     #[test]
     fn test_sanitize_branch_name() {
         // Basic valid names pass through
@@ -1490,6 +2351,12 @@ mod tests {
             default_agent: Some(DefaultAgent::Opencode),
             agent_style: Some(AgentStyle::Native),
             claude_native_backend: None,
+            restart_policy: None,
+            compose_enabled: false,
+            workspace_path: None,
+            docker_network: None,
+            default_sessions: None,
+            clone_depth: None,
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -1501,6 +2368,47 @@ mod tests {
         assert_eq!(deserialized.agent_style, Some(AgentStyle::Native));
     }
 
+    #[test]
+    fn test_workspace_path_defaults_and_validation() {
+        let mut config = AppConfig::default();
+        assert_eq!(config.workspace_path("project-1"), DEFAULT_WORKSPACE_PATH);
+
+        config.repositories.insert(
+            "project-1".to_string(),
+            RepositoryConfig {
+                workspace_path: Some("/home/dev/app".to_string()),
+                ..RepositoryConfig::default()
+            },
+        );
+        assert_eq!(config.workspace_path("project-1"), "/home/dev/app");
+
+        assert!(is_valid_workspace_path("/workspace"));
+        assert!(is_valid_workspace_path("/home/dev/app/"));
+        assert!(!is_valid_workspace_path("workspace"));
+        assert!(!is_valid_workspace_path("/"));
+        assert!(!is_valid_workspace_path("/srv/../etc"));
+        assert!(!is_valid_workspace_path("/my app"));
+    }
+
+    #[test]
+    fn test_restart_policy_serialization() {
+        let config = RepositoryConfig {
+            restart_policy: Some(RestartPolicy::UnlessStopped),
+            ..RepositoryConfig::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains("\"restartPolicy\":\"unless-stopped\""));
+
+        let deserialized: RepositoryConfig = serde_json::from_str(
+            r#"{"defaultBranch":"main","prBaseBranch":"main","restartPolicy":"on-failure"}"#,
+        )
+        .unwrap();
+        assert_eq!(deserialized.restart_policy, Some(RestartPolicy::OnFailure));
+        assert!(!serde_json::to_string(&RepositoryConfig::default())
+            .unwrap()
+            .contains("restartPolicy"));
+    }
+
     #[test]
     fn test_agent_style_omitted_when_none() {
         let config = RepositoryConfig::default();
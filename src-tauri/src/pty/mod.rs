@@ -1,16 +1,25 @@
 // PTY (pseudo-terminal) management for Docker containers
 // Handles terminal sessions, stdin/stdout streaming, and resize events
 
+use crate::models::{DEFAULT_TERMINAL_BUFFER_MAX_BYTES, DEFAULT_WORKSPACE_PATH};
+use crate::storage::Storage;
 use bollard::exec::{CreateExecOptions, ResizeExecOptions, StartExecOptions};
 use bollard::Docker;
 use futures::StreamExt;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::mpsc;
 use tracing::{debug, error, instrument, warn};
 
+pub mod output;
+
+use output::{output_channel, terminal_channel_settings};
+
 #[derive(Error, Debug)]
 pub enum PtyError {
     #[error("Docker error: {0}")]
@@ -19,6 +28,8 @@ pub enum PtyError {
     SessionNotFound(String),
     #[error("Failed to create exec: {0}")]
     ExecFailed(String),
+    #[error("Terminal disconnected: {0}")]
+    Disconnected(String),
 }
 
 impl From<bollard::errors::Error> for PtyError {
@@ -27,6 +38,79 @@ impl From<bollard::errors::Error> for PtyError {
     }
 }
 
+/// Byte counters shared with a session's input and output tasks
+#[derive(Debug, Default)]
+pub struct SessionCounters {
+    /// Bytes written to the exec's stdin
+    pub bytes_written: AtomicU64,
+    /// Bytes read from the exec's output
+    pub bytes_read: AtomicU64,
+}
+
+/// Interval used to re-check the configuration while auto-save is disabled
+const AUTOSAVE_DISABLED_POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// Lower bound on the auto-save interval so a typo can't thrash the disk
+const MIN_AUTOSAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Tail of a session's output, restored on reattach and auto-saved to disk
+#[derive(Debug)]
+pub struct SavedOutput {
+    bytes: VecDeque<u8>,
+    limit: usize,
+    /// Output arrived since the buffer was last saved to disk
+    dirty: bool,
+}
+
+impl Default for SavedOutput {
+    fn default() -> Self {
+        Self::new(DEFAULT_TERMINAL_BUFFER_MAX_BYTES as usize)
+    }
+}
+
+impl SavedOutput {
+    fn new(limit: usize) -> Self {
+        Self {
+            bytes: VecDeque::new(),
+            limit,
+            dirty: false,
+        }
+    }
+
+    fn push(&mut self, data: &[u8]) {
+        self.bytes.extend(data);
+        let excess = self.bytes.len().saturating_sub(self.limit);
+        self.bytes.drain(..excess);
+        self.dirty = true;
+    }
+
+    fn to_vec(&self) -> Vec<u8> {
+        self.bytes.iter().copied().collect()
+    }
+
+    /// Contents if output arrived since the last call, marking them saved
+    fn take_unsaved(&mut self) -> Option<Vec<u8>> {
+        std::mem::take(&mut self.dirty).then(|| self.to_vec())
+    }
+}
+
+/// Configured size of the per-session output buffer (default if the config
+/// can't be loaded)
+fn saved_output_limit() -> usize {
+    crate::storage::get_config()
+        .map(|config| config.global.terminal_buffer_max_bytes)
+        .unwrap_or(DEFAULT_TERMINAL_BUFFER_MAX_BYTES) as usize
+}
+
+/// Configured buffer auto-save interval, or `None` if auto-save is disabled
+/// or the config can't be loaded
+fn load_autosave_interval() -> Option<Duration> {
+    let config = crate::storage::get_config().ok()?;
+    match config.global.terminal_buffer_autosave_secs {
+        0 => None,
+        secs => Some(Duration::from_secs(secs).max(MIN_AUTOSAVE_INTERVAL)),
+    }
+}
+
 /// A terminal session connected to a Docker container
 #[derive(Debug)]
 pub struct TerminalSession {
@@ -36,6 +120,13 @@ pub struct TerminalSession {
     pub cols: u16,
     pub rows: u16,
     pub is_active: bool,
+    /// When the exec was started (None until `start_session`)
+    pub started_at: Option<Instant>,
+    pub counters: Arc<SessionCounters>,
+    /// User and command the exec runs, reused when reattaching
+    pub user: Option<String>,
+    pub command: Vec<String>,
+    pub saved_output: Arc<Mutex<SavedOutput>>,
 }
 
 impl TerminalSession {
@@ -47,6 +138,59 @@ impl TerminalSession {
             cols,
             rows,
             is_active: false,
+            started_at: None,
+            counters: Arc::new(SessionCounters::default()),
+            user: None,
+            command: Vec::new(),
+            saved_output: Arc::new(Mutex::new(SavedOutput::default())),
+        }
+    }
+}
+
+/// Point-in-time view of a terminal session's in-memory state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalSessionSnapshot {
+    pub session_id: String,
+    pub container_id: String,
+    pub cols: u16,
+    pub rows: u16,
+    pub is_active: bool,
+}
+
+/// Throughput and uptime of a terminal session
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalSessionMetrics {
+    pub session_id: String,
+    /// Bytes sent to the terminal (keystrokes, pastes)
+    pub bytes_written: u64,
+    /// Bytes received from the terminal
+    pub bytes_read: u64,
+    /// Seconds since the session started (0 if not started)
+    pub uptime_secs: f64,
+    /// Average output throughput over the session's uptime
+    pub read_bytes_per_sec: f64,
+    /// Average input throughput over the session's uptime
+    pub write_bytes_per_sec: f64,
+}
+
+impl TerminalSessionMetrics {
+    fn new(session_id: &str, bytes_written: u64, bytes_read: u64, uptime: Duration) -> Self {
+        let uptime_secs = uptime.as_secs_f64();
+        let rate = |bytes: u64| {
+            if uptime_secs > 0.0 {
+                bytes as f64 / uptime_secs
+            } else {
+                0.0
+            }
+        };
+        Self {
+            session_id: session_id.to_string(),
+            bytes_written,
+            bytes_read,
+            uptime_secs,
+            read_bytes_per_sec: rate(bytes_read),
+            write_bytes_per_sec: rate(bytes_written),
         }
     }
 }
@@ -71,7 +215,7 @@ impl TerminalManager {
 
     fn connect_docker() -> Result<Docker, PtyError> {
         // Use a fresh client to avoid hijacked exec connections blocking new requests.
-        Docker::connect_with_local_defaults().map_err(|e| PtyError::Docker(e.to_string()))
+        crate::docker::connect_engine().map_err(|e| PtyError::Docker(e.to_string()))
     }
 
     /// Create a new terminal session for a container
@@ -114,15 +258,46 @@ impl TerminalManager {
         }
 
         debug!("Creating terminal session");
+        let exec_id = Self::create_exec(container_id, cols, rows, user, &command).await?;
+
+        // Create and store session
+        let mut session = TerminalSession::new(container_id, cols, rows);
+        session.exec_id = Some(exec_id.clone());
+        session.is_active = true;
+        session.user = user.map(str::to_string);
+        session.command = command;
+        session.saved_output = Arc::new(Mutex::new(SavedOutput::new(saved_output_limit())));
+
+        let session_id = session.session_id.clone();
+
+        {
+            let mut sessions = self.sessions.lock().unwrap();
+            sessions.insert(session_id.clone(), session);
+        }
+
+        debug!(session_id = %session_id, exec_id = %exec_id, "Terminal session created");
+        Ok(session_id)
+    }
+
+    /// Create a TTY exec in a container running `command` and return its ID
+    async fn create_exec(
+        container_id: &str,
+        cols: u16,
+        rows: u16,
+        user: Option<&str>,
+        command: &[String],
+    ) -> Result<String, PtyError> {
         let docker = Self::connect_docker()?;
 
         // Fetch the container's environment variables so we can pass them to exec
         // This is necessary because docker exec doesn't inherit container env vars
         let container_info = docker.inspect_container(container_id, None).await?;
-        let container_env: Vec<String> = container_info
-            .config
-            .and_then(|c| c.env)
-            .unwrap_or_default();
+        let container_config = container_info.config.unwrap_or_default();
+        let working_dir = container_config
+            .working_dir
+            .filter(|dir| !dir.is_empty())
+            .unwrap_or_else(|| DEFAULT_WORKSPACE_PATH.to_string());
+        let container_env: Vec<String> = container_config.env.unwrap_or_default();
 
         // Build environment variables - start with container's env
         let mut env_vars: Vec<String> = container_env;
@@ -146,29 +321,110 @@ impl TerminalManager {
             attach_stderr: Some(true),
             tty: Some(true),
             cmd: Some(cmd_refs),
-            working_dir: Some("/workspace"),
+            working_dir: Some(working_dir.as_str()),
             env: Some(env_refs),
             user: Some(user.unwrap_or("node")), // Use provided user or default to node
             ..Default::default()
         };
 
         let exec = docker.create_exec(container_id, config).await?;
-        let exec_id = exec.id;
-
-        // Create and store session
-        let mut session = TerminalSession::new(container_id, cols, rows);
-        session.exec_id = Some(exec_id.clone());
-        session.is_active = true;
+        Ok(exec.id)
+    }
 
-        let session_id = session.session_id.clone();
+    /// Start a fresh exec for a session whose previous one died (e.g. the
+    /// container restarted or was recreated), keeping the session ID so
+    /// frontend listeners stay attached. Returns the new output receiver.
+    #[instrument(skip(self), fields(session_id = %session_id))]
+    pub async fn reattach_session(
+        &self,
+        session_id: &str,
+    ) -> Result<mpsc::Receiver<Vec<u8>>, PtyError> {
+        debug!("Reattaching terminal session");
+        let (container_id, cols, rows, user, command) = {
+            let sessions = self.sessions.lock().unwrap();
+            let session = sessions
+                .get(session_id)
+                .ok_or_else(|| PtyError::SessionNotFound(session_id.to_string()))?;
+            (
+                session.container_id.clone(),
+                session.cols,
+                session.rows,
+                session.user.clone(),
+                session.command.clone(),
+            )
+        };
 
+        let exec_id =
+            Self::create_exec(&container_id, cols, rows, user.as_deref(), &command).await?;
         {
             let mut sessions = self.sessions.lock().unwrap();
-            sessions.insert(session_id.clone(), session);
+            let session = sessions
+                .get_mut(session_id)
+                .ok_or_else(|| PtyError::SessionNotFound(session_id.to_string()))?;
+            session.exec_id = Some(exec_id);
+            session.is_active = true;
         }
 
-        debug!(session_id = %session_id, exec_id = %exec_id, "Terminal session created");
-        Ok(session_id)
+        self.start_session(session_id).await
+    }
+
+    /// Point sessions attached to `old_container_id` at its replacement so
+    /// they can be reattached after a recreate
+    pub fn retarget_container(&self, old_container_id: &str, new_container_id: &str) {
+        let mut sessions = self.sessions.lock().unwrap();
+        for session in sessions
+            .values_mut()
+            .filter(|s| s.container_id == old_container_id)
+        {
+            session.container_id = new_container_id.to_string();
+        }
+    }
+
+    /// Output saved for a session, most recent last
+    pub fn saved_output(&self, session_id: &str) -> Option<Vec<u8>> {
+        let sessions = self.sessions.lock().unwrap();
+        sessions
+            .get(session_id)
+            .map(|s| s.saved_output.lock().unwrap().to_vec())
+    }
+
+    /// Whether a session is still registered (not closed by the user)
+    pub fn has_session(&self, session_id: &str) -> bool {
+        self.sessions.lock().unwrap().contains_key(session_id)
+    }
+
+    /// Whether a registered session's exec ended because its container
+    /// stopped, restarted or was replaced, as opposed to the shell exiting on
+    /// its own (e.g. the user typed `exit`)
+    pub async fn lost_container(&self, session_id: &str) -> bool {
+        let (container_id, exec_id) = {
+            let sessions = self.sessions.lock().unwrap();
+            match sessions.get(session_id) {
+                Some(session) => (session.container_id.clone(), session.exec_id.clone()),
+                None => return false,
+            }
+        };
+        let Ok(docker) = Self::connect_docker() else {
+            return false;
+        };
+
+        let exec_exit_code = match exec_id {
+            Some(exec_id) => match docker.inspect_exec(&exec_id).await {
+                Ok(exec) => Some(exec.exit_code),
+                // The exec is gone with its container
+                Err(_) => None,
+            },
+            None => None,
+        };
+        let container_running = docker
+            .inspect_container(&container_id, None)
+            .await
+            .ok()
+            .and_then(|info| info.state)
+            .and_then(|state| state.running)
+            .unwrap_or(false);
+
+        exec_ended_with_container(exec_exit_code, container_running)
     }
 
     /// Start a terminal session and return output receiver
@@ -181,15 +437,20 @@ impl TerminalManager {
         debug!("Starting terminal session");
         let docker = Self::connect_docker()?;
 
-        let exec_id = {
+        let (exec_id, counters, saved_output) = {
             let sessions = self.sessions.lock().unwrap();
             let session = sessions
                 .get(session_id)
                 .ok_or_else(|| PtyError::SessionNotFound(session_id.to_string()))?;
-            session
+            let exec_id = session
                 .exec_id
                 .clone()
-                .ok_or_else(|| PtyError::ExecFailed("No exec ID".to_string()))?
+                .ok_or_else(|| PtyError::ExecFailed("No exec ID".to_string()))?;
+            (
+                exec_id,
+                session.counters.clone(),
+                session.saved_output.clone(),
+            )
         };
 
         // Start the exec with detach: false to get attached streams
@@ -199,10 +460,11 @@ impl TerminalManager {
         };
         let attach = docker.start_exec(&exec_id, Some(options)).await?;
 
-        // Create channels for input/output
-        // Use larger buffers to prevent backpressure issues
-        let (input_tx, mut input_rx) = mpsc::channel::<Vec<u8>>(1024);
-        let (output_tx, output_rx) = mpsc::channel::<Vec<u8>>(1024);
+        // Create channels for input/output. The output side applies the
+        // configured overflow policy when the frontend falls behind.
+        let (capacity, overflow_policy) = terminal_channel_settings();
+        let (input_tx, mut input_rx) = mpsc::channel::<Vec<u8>>(capacity);
+        let (output_tx, output_rx) = output_channel(capacity, overflow_policy);
 
         // Store the input sender for later use
         {
@@ -219,15 +481,25 @@ impl TerminalManager {
             debug!(exec_id = %exec_id, "Exec attached successfully");
             let exec_id_for_output = exec_id.clone();
             let exec_id_for_input = exec_id.clone();
+            let output_counters = counters.clone();
+            let input_counters = counters;
+            let sessions = self.sessions.clone();
+            let session_id_for_output = session_id.to_string();
 
             // Spawn task to read output (runs independently)
             tokio::spawn(async move {
+                let mut receiver_dropped = false;
                 while let Some(result) = output.next().await {
                     match result {
                         Ok(chunk) => {
                             let data = chunk.into_bytes().to_vec();
+                            output_counters
+                                .bytes_read
+                                .fetch_add(data.len() as u64, Ordering::Relaxed);
+                            saved_output.lock().unwrap().push(&data);
                             if output_tx.send(data).await.is_err() {
                                 debug!(exec_id = %exec_id_for_output, "Output channel closed, receiver dropped");
+                                receiver_dropped = true;
                                 break;
                             }
                         }
@@ -236,6 +508,17 @@ impl TerminalManager {
                         }
                     }
                 }
+                if !receiver_dropped {
+                    // The exec died (shell exited, container stopped). Leave
+                    // the session registered so it can be reattached, unless
+                    // it already moved on to a newer exec.
+                    let mut sessions = sessions.lock().unwrap();
+                    if let Some(session) = sessions.get_mut(&session_id_for_output) {
+                        if session.exec_id.as_deref() == Some(exec_id_for_output.as_str()) {
+                            session.is_active = false;
+                        }
+                    }
+                }
                 debug!(exec_id = %exec_id_for_output, "Output reader task ended");
             });
 
@@ -248,6 +531,10 @@ impl TerminalManager {
                                 warn!(exec_id = %exec_id_for_input, error = ?e, "Error writing to exec input");
                             } else if let Err(e) = input.flush().await {
                                 warn!(exec_id = %exec_id_for_input, error = ?e, "Error flushing exec input");
+                            } else {
+                                input_counters
+                                    .bytes_written
+                                    .fetch_add(data.len() as u64, Ordering::Relaxed);
                             }
                         }
                         None => {
@@ -266,8 +553,9 @@ impl TerminalManager {
         // Resize the exec to initialize the PTY properly
         // This is important for TTY echo to work correctly
         let (cols, rows) = {
-            let sessions = self.sessions.lock().unwrap();
-            let session = sessions.get(session_id).unwrap();
+            let mut sessions = self.sessions.lock().unwrap();
+            let session = sessions.get_mut(session_id).unwrap();
+            session.started_at = Some(Instant::now());
             (session.cols, session.rows)
         };
 
@@ -295,6 +583,18 @@ impl TerminalManager {
     /// Write data to a terminal session
     #[instrument(skip(self, data), fields(session_id = %session_id, data_len = data.len()))]
     pub async fn write_to_session(&self, session_id: &str, data: Vec<u8>) -> Result<(), PtyError> {
+        let active = self
+            .sessions
+            .lock()
+            .unwrap()
+            .get(session_id)
+            .map(|s| s.is_active);
+        match active {
+            None => return Err(PtyError::SessionNotFound(session_id.to_string())),
+            Some(false) => return Err(PtyError::Disconnected(session_id.to_string())),
+            Some(true) => {}
+        }
+
         let sender = {
             let senders = self.input_senders.lock().unwrap();
             senders
@@ -378,6 +678,80 @@ impl TerminalManager {
         let sessions = self.sessions.lock().unwrap();
         sessions.keys().cloned().collect()
     }
+
+    /// Snapshot of every session, for joining with stored session records
+    pub fn snapshot_sessions(&self) -> Vec<TerminalSessionSnapshot> {
+        let sessions = self.sessions.lock().unwrap();
+        sessions
+            .values()
+            .map(|s| TerminalSessionSnapshot {
+                session_id: s.session_id.clone(),
+                container_id: s.container_id.clone(),
+                cols: s.cols,
+                rows: s.rows,
+                is_active: s.is_active,
+            })
+            .collect()
+    }
+
+    /// Auto-save the output buffer of every session with new output for the
+    /// stored session linked to it. Raw output goes next to, not over, the
+    /// buffer the frontend saves. Returns how many buffers were written.
+    pub fn autosave_buffers(&self, storage: &Storage) -> usize {
+        let buffers: Vec<(String, Arc<Mutex<SavedOutput>>)> = {
+            let sessions = self.sessions.lock().unwrap();
+            sessions
+                .values()
+                .map(|s| (s.session_id.clone(), s.saved_output.clone()))
+                .collect()
+        };
+        if buffers.is_empty() {
+            return 0;
+        }
+
+        let stored = match storage.load_sessions() {
+            Ok(stored) => stored,
+            Err(e) => {
+                warn!(error = %e, "Skipping terminal buffer auto-save: failed to load sessions");
+                return 0;
+            }
+        };
+
+        let mut saved = 0;
+        for (terminal_session_id, saved_output) in buffers {
+            let Some(session) = stored
+                .iter()
+                .find(|s| s.terminal_session_id.as_deref() == Some(&terminal_session_id))
+            else {
+                continue;
+            };
+            let Some(bytes) = saved_output.lock().unwrap().take_unsaved() else {
+                continue;
+            };
+            match storage
+                .save_session_autosave_buffer(&session.id, &String::from_utf8_lossy(&bytes))
+            {
+                Ok(()) => saved += 1,
+                Err(e) => {
+                    warn!(session_id = %session.id, error = %e, "Failed to auto-save terminal buffer");
+                }
+            }
+        }
+        saved
+    }
+
+    /// Byte counts, uptime and average throughput of a session
+    pub fn session_metrics(&self, session_id: &str) -> Option<TerminalSessionMetrics> {
+        let sessions = self.sessions.lock().unwrap();
+        sessions.get(session_id).map(|s| {
+            TerminalSessionMetrics::new(
+                session_id,
+                s.counters.bytes_written.load(Ordering::Relaxed),
+                s.counters.bytes_read.load(Ordering::Relaxed),
+                s.started_at.map(|t| t.elapsed()).unwrap_or_default(),
+            )
+        })
+    }
 }
 
 // Global terminal manager instance
@@ -395,6 +769,40 @@ pub fn get_terminal_manager() -> Option<&'static TerminalManager> {
     TERMINAL_MANAGER.get()
 }
 
+/// Periodically save terminal output buffers to disk so reconnecting after a
+/// crash still shows recent output
+pub async fn run_buffer_autosave_loop() {
+    loop {
+        match load_autosave_interval() {
+            Some(interval) => {
+                tokio::time::sleep(interval).await;
+                let (Some(manager), Ok(storage)) =
+                    (get_terminal_manager(), crate::storage::get_storage())
+                else {
+                    continue;
+                };
+                let saved = manager.autosave_buffers(storage);
+                if saved > 0 {
+                    debug!(saved, "Auto-saved terminal buffers");
+                }
+            }
+            None => tokio::time::sleep(AUTOSAVE_DISABLED_POLL_INTERVAL).await,
+        }
+    }
+}
+
+/// Decide whether an exec ended with its container. `exec_exit_code` is
+/// `None` when the exec could no longer be inspected, `Some(None)` when it is
+/// still running.
+fn exec_ended_with_container(exec_exit_code: Option<Option<i64>>, container_running: bool) -> bool {
+    match exec_exit_code {
+        None => true,
+        // SIGKILL/SIGTERM from a container stop or restart
+        Some(Some(137 | 143)) => true,
+        Some(_) => !container_running,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -409,6 +817,150 @@ mod tests {
         assert!(command.ends_with("exec /bin/zsh"));
     }
 
+    #[test]
+    fn session_metrics_report_counts_and_throughput() {
+        let manager = TerminalManager::new();
+        let session = TerminalSession::new("container-1", 80, 24);
+        let session_id = session.session_id.clone();
+        session.counters.bytes_read.store(4096, Ordering::Relaxed);
+        session.counters.bytes_written.store(12, Ordering::Relaxed);
+        manager
+            .sessions
+            .lock()
+            .unwrap()
+            .insert(session_id.clone(), session);
+
+        // Not started yet: no uptime, so no throughput
+        let metrics = manager.session_metrics(&session_id).unwrap();
+        assert_eq!(metrics.bytes_read, 4096);
+        assert_eq!(metrics.bytes_written, 12);
+        assert_eq!(metrics.uptime_secs, 0.0);
+        assert_eq!(metrics.read_bytes_per_sec, 0.0);
+
+        let metrics = TerminalSessionMetrics::new("s", 10, 4096, Duration::from_secs(2));
+        assert_eq!(metrics.read_bytes_per_sec, 2048.0);
+        assert_eq!(metrics.write_bytes_per_sec, 5.0);
+
+        assert!(manager.session_metrics("missing").is_none());
+    }
+
+    #[test]
+    fn shell_exit_is_not_a_lost_container() {
+        assert!(!exec_ended_with_container(Some(Some(0)), true));
+        assert!(!exec_ended_with_container(Some(Some(1)), true));
+        assert!(exec_ended_with_container(Some(Some(0)), false));
+        assert!(exec_ended_with_container(Some(Some(137)), true));
+        assert!(exec_ended_with_container(None, true));
+    }
+
+    #[test]
+    fn saved_output_keeps_the_most_recent_bytes() {
+        let mut saved = SavedOutput::default();
+        saved.push(b"hello ");
+        saved.push(b"world");
+        assert_eq!(saved.to_vec(), b"hello world");
+
+        let limit = DEFAULT_TERMINAL_BUFFER_MAX_BYTES as usize;
+        saved.push(&vec![b'x'; limit]);
+        let bytes = saved.to_vec();
+        assert_eq!(bytes.len(), limit);
+        assert!(bytes.iter().all(|&b| b == b'x'));
+    }
+
+    #[test]
+    fn autosave_writes_new_output_of_linked_sessions_once() {
+        let data_dir = tempfile::tempdir().unwrap().keep();
+        let storage = Storage::new_for_tests(data_dir.clone());
+        let manager = TerminalManager::new();
+        let linked = TerminalSession::new("container-1", 80, 24);
+        let unlinked = TerminalSession::new("container-1", 80, 24);
+        linked
+            .saved_output
+            .lock()
+            .unwrap()
+            .push(b"$ make\r\nok\r\n");
+        unlinked.saved_output.lock().unwrap().push(b"ignored");
+
+        let mut stored = crate::models::Session::new(
+            "env-1".to_string(),
+            "container-1".to_string(),
+            "tab-1".to_string(),
+            crate::models::SessionType::Plain,
+        );
+        stored.terminal_session_id = Some(linked.session_id.clone());
+        let stored = storage.add_session(stored).unwrap();
+        {
+            let mut sessions = manager.sessions.lock().unwrap();
+            sessions.insert(linked.session_id.clone(), linked);
+            sessions.insert(unlinked.session_id.clone(), unlinked);
+        }
+
+        assert_eq!(manager.autosave_buffers(&storage), 1);
+        assert_eq!(
+            storage.load_session_buffer(&stored.id).unwrap().as_deref(),
+            Some("$ make\r\nok\r\n")
+        );
+        // Nothing new since the last save
+        assert_eq!(manager.autosave_buffers(&storage), 0);
+
+        // Raw output auto-saved after the frontend's last save wins
+        storage
+            .save_session_buffer(&stored.id, "\x1b[1mformatted\x1b[0m")
+            .unwrap();
+        filetime::set_file_mtime(
+            data_dir.join("buffers").join(format!("{}.txt", stored.id)),
+            filetime::FileTime::from_unix_time(1_000, 0),
+        )
+        .unwrap();
+        manager
+            .sessions
+            .lock()
+            .unwrap()
+            .values()
+            .for_each(|s| s.saved_output.lock().unwrap().push(b"more"));
+        assert_eq!(manager.autosave_buffers(&storage), 1);
+        assert_eq!(
+            storage.load_session_buffer(&stored.id).unwrap().as_deref(),
+            Some("$ make\r\nok\r\nmore")
+        );
+    }
+
+    #[test]
+    fn retarget_container_moves_sessions_to_the_new_container() {
+        let manager = TerminalManager::new();
+        let old = TerminalSession::new("old-container", 80, 24);
+        let other = TerminalSession::new("other-container", 80, 24);
+        let (old_id, other_id) = (old.session_id.clone(), other.session_id.clone());
+        {
+            let mut sessions = manager.sessions.lock().unwrap();
+            sessions.insert(old_id.clone(), old);
+            sessions.insert(other_id.clone(), other);
+        }
+
+        manager.retarget_container("old-container", "new-container");
+
+        assert_eq!(manager.get_session(&old_id).unwrap().0, "new-container");
+        assert_eq!(manager.get_session(&other_id).unwrap().0, "other-container");
+    }
+
+    #[tokio::test]
+    async fn write_to_inactive_session_reports_disconnected() {
+        let manager = TerminalManager::new();
+        let session = TerminalSession::new("container-1", 80, 24);
+        let session_id = session.session_id.clone();
+        manager
+            .sessions
+            .lock()
+            .unwrap()
+            .insert(session_id.clone(), session);
+
+        let err = manager
+            .write_to_session(&session_id, b"ls\n".to_vec())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PtyError::Disconnected(_)));
+    }
+
     #[tokio::test]
     async fn create_session_with_command_rejects_empty_command_before_docker() {
         let manager = TerminalManager::new();
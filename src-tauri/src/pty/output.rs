@@ -0,0 +1,225 @@
+// Terminal output channel with a configurable overflow policy
+// Sits between a PTY reader and the task forwarding output to the frontend
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{mpsc, Notify};
+
+use crate::models::{TerminalOverflowPolicy, DEFAULT_TERMINAL_CHANNEL_CAPACITY};
+
+/// Written in place of output discarded by [`TerminalOverflowPolicy::DropOldest`]
+pub const TRUNCATED_MARKER: &[u8] = b"\r\n[output truncated]\r\n";
+
+/// Channel capacity and overflow policy from the saved config (defaults if
+/// it can't be loaded)
+pub fn terminal_channel_settings() -> (usize, TerminalOverflowPolicy) {
+    let (capacity, policy) = crate::storage::get_config()
+        .map(|config| {
+            (
+                config.global.terminal_channel_capacity,
+                config.global.terminal_overflow_policy,
+            )
+        })
+        .unwrap_or((
+            DEFAULT_TERMINAL_CHANNEL_CAPACITY,
+            TerminalOverflowPolicy::default(),
+        ));
+    (capacity.max(1) as usize, policy)
+}
+
+/// Sending half of a terminal output channel
+pub enum OutputSender {
+    /// Waits for room, pausing the reader while the frontend catches up
+    Block(mpsc::Sender<Vec<u8>>),
+    /// Never waits; discards the oldest queued chunks when full
+    DropOldest(Arc<DropOldestQueue>),
+}
+
+/// The receiver is gone, so the reader should stop
+#[derive(Debug)]
+pub struct OutputClosed;
+
+impl OutputSender {
+    pub async fn send(&self, data: Vec<u8>) -> Result<(), OutputClosed> {
+        match self {
+            OutputSender::Block(tx) => tx.send(data).await.map_err(|_| OutputClosed),
+            OutputSender::DropOldest(queue) => queue.push(data),
+        }
+    }
+
+    /// Like [`send`](Self::send), for readers on plain threads
+    pub fn blocking_send(&self, data: Vec<u8>) -> Result<(), OutputClosed> {
+        match self {
+            OutputSender::Block(tx) => tx.blocking_send(data).map_err(|_| OutputClosed),
+            OutputSender::DropOldest(queue) => queue.push(data),
+        }
+    }
+}
+
+impl Drop for OutputSender {
+    fn drop(&mut self) {
+        if let OutputSender::DropOldest(queue) = self {
+            queue.close();
+        }
+    }
+}
+
+/// Bounded queue drained into the output channel by a relay task
+pub struct DropOldestQueue {
+    state: Mutex<QueueState>,
+    notify: Notify,
+    capacity: usize,
+}
+
+#[derive(Default)]
+struct QueueState {
+    chunks: VecDeque<Vec<u8>>,
+    /// Chunks were dropped since the relay last took one
+    truncated: bool,
+    /// The sender was dropped
+    closed: bool,
+    /// The receiver was dropped
+    receiver_gone: bool,
+}
+
+impl DropOldestQueue {
+    fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(QueueState::default()),
+            notify: Notify::new(),
+            capacity,
+        }
+    }
+
+    fn push(&self, data: Vec<u8>) -> Result<(), OutputClosed> {
+        {
+            let mut state = self.state.lock().unwrap();
+            if state.receiver_gone {
+                return Err(OutputClosed);
+            }
+            if state.chunks.len() >= self.capacity {
+                state.chunks.pop_front();
+                state.truncated = true;
+            }
+            state.chunks.push_back(data);
+        }
+        self.notify.notify_one();
+        Ok(())
+    }
+
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.notify.notify_one();
+    }
+
+    /// Next chunk to forward, preceded by the truncation marker if output was
+    /// dropped. `None` once the sender is gone and the queue is drained.
+    async fn next(&self) -> Option<(bool, Vec<u8>)> {
+        loop {
+            {
+                let mut state = self.state.lock().unwrap();
+                if let Some(chunk) = state.chunks.pop_front() {
+                    return Some((std::mem::take(&mut state.truncated), chunk));
+                }
+                if state.closed {
+                    return None;
+                }
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    fn mark_receiver_gone(&self) {
+        self.state.lock().unwrap().receiver_gone = true;
+    }
+}
+
+/// Create a terminal output channel. With [`TerminalOverflowPolicy::DropOldest`]
+/// a relay task moves queued chunks into the returned receiver, so this must
+/// be called inside a Tokio runtime.
+pub fn output_channel(
+    capacity: usize,
+    policy: TerminalOverflowPolicy,
+) -> (OutputSender, mpsc::Receiver<Vec<u8>>) {
+    let capacity = capacity.max(1);
+    let (tx, rx) = mpsc::channel::<Vec<u8>>(capacity);
+    match policy {
+        TerminalOverflowPolicy::Block => (OutputSender::Block(tx), rx),
+        TerminalOverflowPolicy::DropOldest => {
+            let queue = Arc::new(DropOldestQueue::new(capacity));
+            let relay_queue = queue.clone();
+            tokio::spawn(async move {
+                while let Some((truncated, chunk)) = relay_queue.next().await {
+                    if truncated && tx.send(TRUNCATED_MARKER.to_vec()).await.is_err() {
+                        break;
+                    }
+                    if tx.send(chunk).await.is_err() {
+                        break;
+                    }
+                }
+                relay_queue.mark_receiver_gone();
+            });
+            (OutputSender::DropOldest(queue), rx)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn drain(rx: &mut mpsc::Receiver<Vec<u8>>) -> Vec<Vec<u8>> {
+        let mut chunks = Vec::new();
+        while let Some(chunk) = rx.recv().await {
+            chunks.push(chunk);
+        }
+        chunks
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_keeps_newest_chunks_and_marks_truncation() {
+        let (tx, mut rx) = output_channel(2, TerminalOverflowPolicy::DropOldest);
+        // Fill the queue faster than anyone reads: the relay can forward at
+        // most a couple of chunks, the rest must be dropped oldest-first
+        for i in 0..100u8 {
+            tx.send(vec![i]).await.unwrap();
+        }
+        drop(tx);
+
+        let chunks = drain(&mut rx).await;
+        assert!(chunks.contains(&TRUNCATED_MARKER.to_vec()));
+        assert_eq!(chunks.last(), Some(&vec![99]));
+        assert!(chunks.len() < 100);
+    }
+
+    #[tokio::test]
+    async fn block_delivers_everything_in_order() {
+        let (tx, mut rx) = output_channel(2, TerminalOverflowPolicy::Block);
+        let writer = tokio::spawn(async move {
+            for i in 0..10u8 {
+                tx.send(vec![i]).await.unwrap();
+            }
+        });
+
+        let chunks = drain(&mut rx).await;
+        writer.await.unwrap();
+        assert_eq!(chunks, (0..10u8).map(|i| vec![i]).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_reports_closed_receiver() {
+        let (tx, rx) = output_channel(1, TerminalOverflowPolicy::DropOldest);
+        drop(rx);
+        // The relay notices on its next forward attempt
+        let mut closed = false;
+        for _ in 0..100 {
+            if tx.send(vec![0]).await.is_err() {
+                closed = true;
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert!(closed);
+    }
+}
@@ -3,15 +3,17 @@
 
 use crate::models::{
     AppConfig, Environment, KanbanComment, KanbanImage, KanbanStatus, KanbanTask, Project,
-    ProjectNotes, Session, SessionStatus,
+    ProjectGroup, ProjectNotes, RepositoryMetadata, Session, SessionStatus, WindowState,
 };
 use base64::Engine;
 use chrono::Utc;
 use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::sync::{Mutex, OnceLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
 use std::time::Duration;
 use thiserror::Error;
 use tracing::{debug, info, warn};
@@ -38,12 +40,28 @@ pub enum StorageError {
     ImageProcessing(String),
     #[error("Duplicate project URL: {0}")]
     DuplicateProject(String),
+    #[error("Project group not found: {0}")]
+    ProjectGroupNotFound(String),
+    #[error("{0}")]
+    InvalidConfig(String),
 }
 
+/// Called with a project ID after that project's environments were saved
+type EnvironmentsListener = Box<dyn Fn(&str) + Send + Sync>;
+
+/// File name suffix for terminal output auto-saved by the backend
+const AUTOSAVE_BUFFER_SUFFIX: &str = ".autosave";
+
 /// Storage manager for persisting application data
 pub struct Storage {
     data_dir: PathBuf,
     json_lock: Mutex<()>,
+    environments_listener: RwLock<Option<EnvironmentsListener>>,
+    /// Config waiting for a debounced write (see `update_config_scheduled`).
+    /// Only changed while `json_lock` is held; loads return it over the file.
+    pending_config: Mutex<Option<AppConfig>>,
+    /// Bumped on every scheduled save so only the latest one writes
+    config_save_generation: AtomicU64,
 }
 
 #[derive(Clone, Copy)]
@@ -69,6 +87,9 @@ impl Storage {
         Ok(Self {
             data_dir,
             json_lock: Mutex::new(()),
+            environments_listener: RwLock::new(None),
+            pending_config: Mutex::new(None),
+            config_save_generation: AtomicU64::new(0),
         })
     }
 
@@ -77,11 +98,18 @@ impl Storage {
         Self {
             data_dir,
             json_lock: Mutex::new(()),
+            environments_listener: RwLock::new(None),
+            pending_config: Mutex::new(None),
+            config_save_generation: AtomicU64::new(0),
         }
     }
 
-    /// Get the application data directory path
-    fn get_data_dir() -> Result<PathBuf, StorageError> {
+    /// Get the application data directory path.
+    /// [`DATA_DIR_ENV`] overrides it, e.g. to point integration tests at a temp dir.
+    pub(crate) fn get_data_dir() -> Result<PathBuf, StorageError> {
+        if let Some(data_dir) = data_dir_override(std::env::var_os(DATA_DIR_ENV)) {
+            return Ok(data_dir);
+        }
         let base = dirs::config_dir().ok_or(StorageError::NoAppDataDir)?;
         Ok(base.join("orkestrator-ai"))
     }
@@ -98,6 +126,16 @@ impl Storage {
         self.data_dir.join("config.json")
     }
 
+    /// Directory holding all app data files
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// Path of config.json
+    pub fn config_path(&self) -> PathBuf {
+        self.config_file()
+    }
+
     fn sessions_file(&self) -> PathBuf {
         self.data_dir.join("sessions.json")
     }
@@ -110,6 +148,13 @@ impl Storage {
         self.buffers_dir().join(format!("{}.txt", session_id))
     }
 
+    /// Raw PTY output auto-saved by the backend, kept apart from the
+    /// formatted buffer the frontend saves
+    fn autosave_buffer_file(&self, session_id: &str) -> PathBuf {
+        self.buffers_dir()
+            .join(format!("{}{}.txt", session_id, AUTOSAVE_BUFFER_SUFFIX))
+    }
+
     fn kanban_file(&self) -> PathBuf {
         self.data_dir.join("kanban.json")
     }
@@ -614,6 +659,128 @@ impl Storage {
         })
     }
 
+    // --- Project Group Operations ---
+
+    /// Load all project groups by ID
+    pub fn load_project_groups(&self) -> Result<HashMap<String, ProjectGroup>, StorageError> {
+        self.with_json_lock(|| Ok(self.load_config_unlocked()?.groups))
+    }
+
+    /// Create a project group at the end of the sidebar, returning its ID
+    pub fn create_project_group(&self, name: &str) -> Result<String, StorageError> {
+        self.with_json_lock(|| {
+            let mut config = self.load_config_unlocked()?;
+            let max_order = config.groups.values().map(|g| g.order).max().unwrap_or(-1);
+            let id = uuid::Uuid::new_v4().to_string();
+            config.groups.insert(
+                id.clone(),
+                ProjectGroup {
+                    name: name.to_string(),
+                    order: max_order + 1,
+                },
+            );
+            self.save_config_unlocked(&config)?;
+            Ok(id)
+        })
+    }
+
+    /// Rename a project group
+    pub fn rename_project_group(
+        &self,
+        group_id: &str,
+        name: &str,
+    ) -> Result<ProjectGroup, StorageError> {
+        self.with_json_lock(|| {
+            let mut config = self.load_config_unlocked()?;
+            let group = config
+                .groups
+                .get_mut(group_id)
+                .ok_or_else(|| StorageError::ProjectGroupNotFound(group_id.to_string()))?;
+            group.name = name.to_string();
+            let updated = group.clone();
+            self.save_config_unlocked(&config)?;
+            Ok(updated)
+        })
+    }
+
+    /// Delete a project group, ungrouping its projects.
+    /// Returns the IDs of the projects that were ungrouped.
+    pub fn delete_project_group(&self, group_id: &str) -> Result<Vec<String>, StorageError> {
+        self.with_json_lock(|| {
+            let mut config = self.load_config_unlocked()?;
+            if config.groups.remove(group_id).is_none() {
+                return Err(StorageError::ProjectGroupNotFound(group_id.to_string()));
+            }
+
+            let mut projects = self.load_projects_unlocked()?;
+            let mut ungrouped = Vec::new();
+            for project in &mut projects {
+                if project.group_id.as_deref() == Some(group_id) {
+                    project.group_id = None;
+                    ungrouped.push(project.id.clone());
+                }
+            }
+
+            // Ungroup first so a failure never leaves projects in a missing group
+            if !ungrouped.is_empty() {
+                self.save_projects_unlocked(&projects)?;
+            }
+            self.save_config_unlocked(&config)?;
+            Ok(ungrouped)
+        })
+    }
+
+    /// Reorder project groups based on the provided order of IDs.
+    /// Groups not in the input array are appended at the end in their current relative order.
+    pub fn reorder_project_groups(
+        &self,
+        group_ids: &[String],
+    ) -> Result<HashMap<String, ProjectGroup>, StorageError> {
+        self.with_json_lock(|| {
+            let mut config = self.load_config_unlocked()?;
+
+            let mut missing: Vec<(&String, &mut ProjectGroup)> = Vec::new();
+            for (id, group) in config.groups.iter_mut() {
+                match group_ids.iter().position(|g| g == id) {
+                    Some(index) => group.order = index as i32,
+                    None => missing.push((id, group)),
+                }
+            }
+            missing.sort_by_key(|(_, group)| group.order);
+            for (offset, (_, group)) in missing.into_iter().enumerate() {
+                group.order = (group_ids.len() + offset) as i32;
+            }
+
+            self.save_config_unlocked(&config)?;
+            Ok(config.groups)
+        })
+    }
+
+    /// Assign a project to a group, or ungroup it with None
+    pub fn set_project_group(
+        &self,
+        project_id: &str,
+        group_id: Option<&str>,
+    ) -> Result<Project, StorageError> {
+        self.with_json_lock(|| {
+            if let Some(group_id) = group_id {
+                if !self.load_config_unlocked()?.groups.contains_key(group_id) {
+                    return Err(StorageError::ProjectGroupNotFound(group_id.to_string()));
+                }
+            }
+
+            let mut projects = self.load_projects_unlocked()?;
+            let project = projects
+                .iter_mut()
+                .find(|p| p.id == project_id)
+                .ok_or_else(|| StorageError::ProjectNotFound(project_id.to_string()))?;
+            project.group_id = group_id.map(String::from);
+            let updated = project.clone();
+            self.save_projects_unlocked(&projects)?;
+            Ok(updated)
+        })
+    }
+
     // --- Environment Operations ---
 
     fn load_environments_unlocked(&self) -> Result<Vec<Environment>, StorageError> {
@@ -623,12 +790,46 @@ impl Storage {
         Ok(environments)
     }
 
-    fn save_environments_unlocked(&self, environments: &[Environment]) -> Result<(), StorageError> {
+    fn write_environments_unlocked(
+        &self,
+        environments: &[Environment],
+    ) -> Result<(), StorageError> {
         let path = self.environments_file();
         let contents = serde_json::to_string_pretty(environments)?;
         Self::write_atomic(&path, &contents, JsonBackupPolicy::Always)
     }
 
+    /// Write all environments and notify the listener that `project_id`'s
+    /// environments changed
+    fn save_environments_unlocked(
+        &self,
+        environments: &[Environment],
+        project_id: &str,
+    ) -> Result<(), StorageError> {
+        self.write_environments_unlocked(environments)?;
+        self.notify_environments_changed(project_id);
+        Ok(())
+    }
+
+    fn notify_environments_changed(&self, project_id: &str) {
+        let listener = self
+            .environments_listener
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(listener) = listener.as_ref() {
+            listener(project_id);
+        }
+    }
+
+    /// Register a callback run after every save of a project's environments.
+    /// It runs while the storage lock is held, so it must not call back into storage.
+    pub fn set_environments_listener(&self, listener: impl Fn(&str) + Send + Sync + 'static) {
+        *self
+            .environments_listener
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = Some(Box::new(listener));
+    }
+
     /// Load all environments from storage, sorted by order
     pub fn load_environments(&self) -> Result<Vec<Environment>, StorageError> {
         self.with_json_lock(|| self.load_environments_unlocked())
@@ -637,7 +838,7 @@ impl Storage {
     /// Save all environments to storage (used in tests for bulk setup)
     #[cfg(test)]
     pub fn save_environments(&self, environments: &[Environment]) -> Result<(), StorageError> {
-        self.with_json_lock(|| self.save_environments_unlocked(environments))
+        self.with_json_lock(|| self.write_environments_unlocked(environments))
     }
 
     /// Add a new environment
@@ -657,7 +858,7 @@ impl Storage {
             environment.order = max_order + 1;
 
             environments.push(environment.clone());
-            self.save_environments_unlocked(&environments)?;
+            self.save_environments_unlocked(&environments, &environment.project_id)?;
             Ok(environment)
         })
     }
@@ -666,16 +867,14 @@ impl Storage {
     pub fn remove_environment(&self, environment_id: &str) -> Result<(), StorageError> {
         self.with_json_lock(|| {
             let mut environments = self.load_environments_unlocked()?;
-            let initial_len = environments.len();
+            let project_id = environments
+                .iter()
+                .find(|e| e.id == environment_id)
+                .map(|e| e.project_id.clone())
+                .ok_or_else(|| StorageError::EnvironmentNotFound(environment_id.to_string()))?;
             environments.retain(|e| e.id != environment_id);
 
-            if environments.len() == initial_len {
-                return Err(StorageError::EnvironmentNotFound(
-                    environment_id.to_string(),
-                ));
-            }
-
-            self.save_environments_unlocked(&environments)?;
+            self.save_environments_unlocked(&environments, &project_id)?;
             Ok(())
         })
     }
@@ -691,7 +890,7 @@ impl Storage {
                 .into_iter()
                 .filter(|e| e.project_id == project_id)
                 .collect();
-            filtered.sort_by_key(|e| e.order);
+            filtered.sort_by_key(environment_list_position);
             Ok(filtered)
         })
     }
@@ -724,6 +923,7 @@ impl Storage {
                 .iter_mut()
                 .find(|e| e.id == environment_id)
                 .ok_or_else(|| StorageError::EnvironmentNotFound(environment_id.to_string()))?;
+            let before = serde_json::to_value(&*environment)?;
 
             if let Some(name) = updates.get("name").and_then(|v| v.as_str()) {
                 environment.name = name.to_string();
@@ -738,6 +938,10 @@ impl Storage {
             if let Some(container_id) = updates.get("containerId") {
                 environment.container_id = container_id.as_str().map(String::from);
             }
+            if let Some(base_remote) = updates.get("baseRemote") {
+                environment.base_remote =
+                    serde_json::from_value(base_remote.clone()).ok().flatten();
+            }
             if let Some(pr_url) = updates.get("prUrl") {
                 environment.pr_url = pr_url.as_str().map(String::from);
             }
@@ -755,6 +959,13 @@ impl Storage {
                     environment.has_merge_conflicts = parsed_has_merge_conflicts;
                 }
             }
+            if let Some(ci_status) = updates.get("ciStatus") {
+                if let Ok(parsed_ci_status) =
+                    serde_json::from_value::<Option<crate::models::CiStatus>>(ci_status.clone())
+                {
+                    environment.ci_status = parsed_ci_status;
+                }
+            }
             if let Some(allowed_domains) = updates.get("allowedDomains") {
                 environment.allowed_domains = serde_json::from_value(allowed_domains.clone()).ok();
             }
@@ -786,6 +997,10 @@ impl Storage {
             if let Some(codex_port) = updates.get("localCodexPort") {
                 environment.local_codex_port = codex_port.as_u64().map(|v| v as u16);
             }
+            if let Some(app_ports) = updates.get("localAppPorts") {
+                environment.local_app_ports =
+                    serde_json::from_value(app_ports.clone()).ok().flatten();
+            }
             if let Some(default_agent) = updates.get("defaultAgent") {
                 environment.default_agent =
                     serde_json::from_value(default_agent.clone()).ok().flatten();
@@ -813,14 +1028,70 @@ impl Storage {
             if let Some(codex_mode) = updates.get("codexMode") {
                 environment.codex_mode = serde_json::from_value(codex_mode.clone()).ok().flatten();
             }
+            if let Some(restart_policy) = updates.get("restartPolicy") {
+                environment.restart_policy = serde_json::from_value(restart_policy.clone())
+                    .ok()
+                    .flatten();
+            }
+            if let Some(default_sessions) = updates.get("defaultSessions") {
+                environment.default_sessions = serde_json::from_value(default_sessions.clone())
+                    .ok()
+                    .flatten();
+            }
             if let Some(setup_scripts_complete) = updates.get("setupScriptsComplete") {
                 if let Some(value) = setup_scripts_complete.as_bool() {
                     environment.setup_scripts_complete = value;
                 }
             }
+            if let Some(initial_prompt) = updates.get("initialPrompt") {
+                environment.initial_prompt = initial_prompt.as_str().map(String::from);
+            }
+            if let Some(compose_project_name) = updates.get("composeProjectName") {
+                environment.compose_project_name = compose_project_name.as_str().map(String::from);
+            }
+            if let Some(model) = updates.get("opencodeModelOverride") {
+                environment.opencode_model_override = model.as_str().map(String::from);
+            }
+            if let Some(notes) = updates.get("notes") {
+                environment.notes = notes.as_str().map(String::from);
+            }
+            if let Some(pinned) = updates.get("pinned") {
+                if let Some(value) = pinned.as_bool() {
+                    environment.pinned = value;
+                }
+            }
+            if let Some(read_only) = updates.get("readOnly") {
+                if let Some(value) = read_only.as_bool() {
+                    environment.read_only = value;
+                }
+            }
+            if let Some(firewall_bypassed) = updates.get("firewallBypassed") {
+                if let Some(value) = firewall_bypassed.as_bool() {
+                    environment.firewall_bypassed = value;
+                }
+            }
+            if let Some(full_clone) = updates.get("fullClone") {
+                if let Some(value) = full_clone.as_bool() {
+                    environment.full_clone = value;
+                }
+            }
+            if let Some(shallow_history) = updates.get("shallowHistory") {
+                if let Some(value) = shallow_history.as_bool() {
+                    environment.shallow_history = value;
+                }
+            }
+            if let Some(worktree_conflict) = updates.get("worktreeConflict") {
+                environment.worktree_conflict = serde_json::from_value(worktree_conflict.clone())
+                    .ok()
+                    .flatten();
+            }
 
             let updated = environment.clone();
-            self.save_environments_unlocked(&environments)?;
+            // Skip no-op writes so listeners only hear about real changes
+            if serde_json::to_value(&updated)? == before {
+                return Ok(updated);
+            }
+            self.save_environments_unlocked(&environments, &updated.project_id)?;
             Ok(updated)
         })
     }
@@ -855,13 +1126,13 @@ impl Storage {
                 }
             }
 
-            self.save_environments_unlocked(&environments)?;
+            self.save_environments_unlocked(&environments, project_id)?;
 
             let mut result: Vec<Environment> = environments
                 .into_iter()
                 .filter(|e| e.project_id == project_id)
                 .collect();
-            result.sort_by_key(|e| e.order);
+            result.sort_by_key(environment_list_position);
             Ok(result)
         })
     }
@@ -869,11 +1140,16 @@ impl Storage {
     // --- Config Operations ---
 
     fn load_config_unlocked(&self) -> Result<AppConfig, StorageError> {
+        if let Some(pending) = self.lock_pending_config().as_ref() {
+            return Ok(pending.clone());
+        }
         let path = self.config_file();
         self.load_json_with_recovery(&path, AppConfig::default)
     }
 
     fn save_config_unlocked(&self, config: &AppConfig) -> Result<(), StorageError> {
+        // An immediate write supersedes any debounced one
+        self.lock_pending_config().take();
         let path = self.config_file();
         debug!(path = ?path, "Saving config");
         let contents = serde_json::to_string_pretty(config)?;
@@ -893,10 +1169,127 @@ impl Storage {
         self.with_json_lock(|| self.save_config_unlocked(config))
     }
 
+    fn lock_pending_config(&self) -> std::sync::MutexGuard<'_, Option<AppConfig>> {
+        self.pending_config
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Load the config, let `update` change it and save the result right away,
+    /// all under one lock so a concurrent update can't be lost in between.
+    /// Nothing is saved if `update` fails. Returns the updated config and
+    /// what `update` returned.
+    pub fn update_config<T>(
+        &self,
+        update: impl FnOnce(&mut AppConfig) -> Result<T, StorageError>,
+    ) -> Result<(AppConfig, T), StorageError> {
+        self.with_json_lock(|| {
+            let mut config = self.load_config_unlocked()?;
+            let output = update(&mut config)?;
+            self.save_config_unlocked(&config)?;
+            Ok((config, output))
+        })
+    }
+
+    /// Load the config, let `update` change it and schedule the result to be
+    /// saved, all under one lock so a concurrent update can't be lost in
+    /// between. Nothing is saved if `update` fails (e.g. with
+    /// [`StorageError::InvalidConfig`]). Returns the config from before and
+    /// after the update.
+    pub fn update_config_scheduled(
+        &'static self,
+        update: impl FnOnce(&mut AppConfig) -> Result<(), StorageError>,
+    ) -> Result<(AppConfig, AppConfig), StorageError> {
+        self.with_json_lock(|| {
+            let previous = self.load_config_unlocked()?;
+            let mut config = previous.clone();
+            update(&mut config)?;
+            self.schedule_config_save_unlocked(&config)?;
+            Ok((previous, config))
+        })
+    }
+
+    /// Save application config after `config_save_debounce_ms` without further
+    /// changes, coalescing rapid updates into one write. Loads see the new
+    /// config right away. A debounce of 0 writes immediately.
+    fn schedule_config_save_unlocked(
+        &'static self,
+        config: &AppConfig,
+    ) -> Result<(), StorageError> {
+        let debounce = Duration::from_millis(config.global.config_save_debounce_ms);
+        if debounce.is_zero() {
+            return self.save_config_unlocked(config);
+        }
+
+        let generation = self.stage_config_unlocked(config);
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(debounce).await;
+            if self.config_save_generation.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            if let Err(error) = self.flush_pending_config() {
+                warn!(error = %error, "Failed to write debounced config save");
+            }
+        });
+        Ok(())
+    }
+
+    /// Hold `config` for a debounced write and return its generation
+    #[cfg(test)]
+    fn stage_config(&self, config: &AppConfig) -> u64 {
+        let _guard = self
+            .json_lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        self.stage_config_unlocked(config)
+    }
+
+    fn stage_config_unlocked(&self, config: &AppConfig) -> u64 {
+        *self.lock_pending_config() = Some(config.clone());
+        self.config_save_generation.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// Write a config waiting for a debounced save now, e.g. on app exit.
+    /// Returns whether there was one.
+    pub fn flush_pending_config(&self) -> Result<bool, StorageError> {
+        self.with_json_lock(|| {
+            let Some(config) = self.lock_pending_config().take() else {
+                return Ok(false);
+            };
+            self.save_config_unlocked(&config)?;
+            Ok(true)
+        })
+    }
+
+    /// Pin or unpin a container so prune and orphan cleanup skip it.
+    /// Returns the pinned containers.
+    pub fn set_container_pinned(
+        &self,
+        container: &str,
+        pinned: bool,
+    ) -> Result<Vec<String>, StorageError> {
+        self.with_json_lock(|| {
+            let mut config = self.load_config_unlocked()?;
+            set_pinned(&mut config.global.pinned_containers, container, pinned);
+            self.save_config_unlocked(&config)?;
+            Ok(config.global.pinned_containers)
+        })
+    }
+
+    /// Pin or unpin an image so prune skips it. Returns the pinned images.
+    pub fn set_image_pinned(&self, image: &str, pinned: bool) -> Result<Vec<String>, StorageError> {
+        self.with_json_lock(|| {
+            let mut config = self.load_config_unlocked()?;
+            set_pinned(&mut config.global.pinned_images, image, pinned);
+            self.save_config_unlocked(&config)?;
+            Ok(config.global.pinned_images)
+        })
+    }
+
     // --- Session Operations ---
 
     /// Maximum number of sessions per environment (to prevent unbounded accumulation)
-    const MAX_SESSIONS_PER_ENVIRONMENT: usize = 20;
+    pub(crate) const MAX_SESSIONS_PER_ENVIRONMENT: usize = 20;
 
     fn load_sessions_unlocked(&self) -> Result<Vec<Session>, StorageError> {
         let path = self.sessions_file();
@@ -913,8 +1306,7 @@ impl Storage {
         )
     }
 
-    /// Load all sessions from storage (used in tests)
-    #[cfg(test)]
+    /// Load all sessions from storage
     pub fn load_sessions(&self) -> Result<Vec<Session>, StorageError> {
         self.with_json_lock(|| self.load_sessions_unlocked())
     }
@@ -1035,7 +1427,8 @@ impl Storage {
                 .find(|s| s.id == session_id)
                 .ok_or_else(|| StorageError::SessionNotFound(session_id.to_string()))?;
 
-            session.name = name;
+            // Blank names clear the custom name, reverting to the default label
+            session.name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
             let updated = session.clone();
             self.save_sessions_unlocked(&sessions)?;
             Ok(updated)
@@ -1062,6 +1455,26 @@ impl Storage {
         })
     }
 
+    /// Record which terminal session currently backs a session
+    pub fn set_session_terminal_id(
+        &self,
+        session_id: &str,
+        terminal_session_id: Option<String>,
+    ) -> Result<Session, StorageError> {
+        self.with_json_lock(|| {
+            let mut sessions = self.load_sessions_unlocked()?;
+            let session = sessions
+                .iter_mut()
+                .find(|s| s.id == session_id)
+                .ok_or_else(|| StorageError::SessionNotFound(session_id.to_string()))?;
+
+            session.terminal_session_id = terminal_session_id;
+            let updated = session.clone();
+            self.save_sessions_unlocked(&sessions)?;
+            Ok(updated)
+        })
+    }
+
     /// Remove a session by ID
     pub fn remove_session(&self, session_id: &str) -> Result<(), StorageError> {
         self.with_json_lock(|| {
@@ -1134,6 +1547,20 @@ impl Storage {
 
     /// Save a session's terminal buffer to a separate file
     pub fn save_session_buffer(&self, session_id: &str, buffer: &str) -> Result<(), StorageError> {
+        self.write_buffer_file(&self.buffer_file(session_id), buffer)
+    }
+
+    /// Save raw terminal output captured by the backend. It is only loaded
+    /// for sessions the frontend never saved a buffer for (e.g. after a crash).
+    pub fn save_session_autosave_buffer(
+        &self,
+        session_id: &str,
+        buffer: &str,
+    ) -> Result<(), StorageError> {
+        self.write_buffer_file(&self.autosave_buffer_file(session_id), buffer)
+    }
+
+    fn write_buffer_file(&self, buffer_path: &Path, buffer: &str) -> Result<(), StorageError> {
         let buffers_dir = self.buffers_dir();
 
         // Create buffers directory if it doesn't exist
@@ -1141,8 +1568,6 @@ impl Storage {
             fs::create_dir_all(&buffers_dir)?;
         }
 
-        let buffer_path = self.buffer_file(session_id);
-
         // Truncate buffer if too large (500KB limit)
         const MAX_BUFFER_SIZE: usize = 500 * 1024;
         let buffer_to_save = if buffer.len() > MAX_BUFFER_SIZE {
@@ -1163,24 +1588,37 @@ impl Storage {
         Ok(())
     }
 
-    /// Load a session's terminal buffer from file
+    /// Load a session's terminal buffer from file. When both the frontend's
+    /// buffer and the backend's raw auto-save exist, the more recently written
+    /// one wins, with ties going to the frontend buffer
     pub fn load_session_buffer(&self, session_id: &str) -> Result<Option<String>, StorageError> {
-        let buffer_path = self.buffer_file(session_id);
-
-        if !buffer_path.exists() {
-            return Ok(None);
-        }
+        let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+
+        let frontend_path = self.buffer_file(session_id);
+        let autosave_path = self.autosave_buffer_file(session_id);
+        let buffer_path = match (modified(&frontend_path), modified(&autosave_path)) {
+            (Some(frontend), Some(autosave)) if autosave > frontend => autosave_path,
+            (Some(_), _) => frontend_path,
+            (None, Some(_)) => autosave_path,
+            (None, None) => return Ok(None),
+        };
+        Ok(Some(fs::read_to_string(&buffer_path)?))
+    }
 
-        let contents = fs::read_to_string(&buffer_path)?;
-        Ok(Some(contents))
+    /// Whether a session has a saved terminal buffer
+    pub fn has_session_buffer(&self, session_id: &str) -> bool {
+        self.buffer_file(session_id).exists() || self.autosave_buffer_file(session_id).exists()
     }
 
-    /// Delete a session's buffer file
+    /// Delete a session's buffer files
     pub fn delete_session_buffer(&self, session_id: &str) -> Result<(), StorageError> {
-        let buffer_path = self.buffer_file(session_id);
-
-        if buffer_path.exists() {
-            fs::remove_file(buffer_path)?;
+        for buffer_path in [
+            self.buffer_file(session_id),
+            self.autosave_buffer_file(session_id),
+        ] {
+            if buffer_path.exists() {
+                fs::remove_file(buffer_path)?;
+            }
         }
 
         Ok(())
@@ -1247,7 +1685,11 @@ impl Storage {
                     let path = entry.path();
                     if path.is_file() {
                         if let Some(file_name) = path.file_stem() {
-                            let session_id = file_name.to_string_lossy().to_string();
+                            let file_name = file_name.to_string_lossy();
+                            let session_id = file_name
+                                .strip_suffix(AUTOSAVE_BUFFER_SUFFIX)
+                                .unwrap_or(&file_name)
+                                .to_string();
                             if !session_ids.contains(&session_id) {
                                 if fs::remove_file(&path).is_ok() {
                                     debug!(session_id = %session_id, "Deleted orphaned buffer file");
@@ -1656,23 +2098,141 @@ impl Storage {
             Ok(updated)
         })
     }
+
+    // --- Repository Metadata Cache ---
+
+    fn repository_metadata_file(&self) -> PathBuf {
+        self.data_dir.join("repository-metadata.json")
+    }
+
+    /// Cached repository metadata of a project, regardless of its age
+    pub fn get_cached_repository_metadata(
+        &self,
+        project_id: &str,
+    ) -> Result<Option<RepositoryMetadata>, StorageError> {
+        let path = self.repository_metadata_file();
+        self.with_json_lock(|| {
+            let mut cache: HashMap<String, RepositoryMetadata> =
+                self.load_json_with_recovery(&path, HashMap::new)?;
+            Ok(cache.remove(project_id))
+        })
+    }
+
+    /// Cache the repository metadata of a project
+    pub fn save_repository_metadata(
+        &self,
+        project_id: &str,
+        metadata: &RepositoryMetadata,
+    ) -> Result<(), StorageError> {
+        let path = self.repository_metadata_file();
+        self.with_json_lock(|| {
+            let mut cache: HashMap<String, RepositoryMetadata> =
+                self.load_json_with_recovery(&path, HashMap::new)?;
+            cache.insert(project_id.to_string(), metadata.clone());
+            let contents = serde_json::to_string_pretty(&cache)?;
+            Self::write_atomic(&path, &contents, JsonBackupPolicy::Never)
+        })
+    }
+
+    // --- Window State Operations ---
+
+    fn window_state_file(&self) -> PathBuf {
+        self.data_dir.join("window_state.json")
+    }
+
+    /// Load the saved window state (default when nothing was saved yet)
+    pub fn load_window_state(&self) -> Result<WindowState, StorageError> {
+        let path = self.window_state_file();
+        self.with_json_lock(|| self.load_json_with_recovery(&path, WindowState::default))
+    }
+
+    /// Merge `update` into the saved window state and return the result
+    pub fn update_window_state(&self, update: WindowState) -> Result<WindowState, StorageError> {
+        let path = self.window_state_file();
+        self.with_json_lock(|| {
+            let mut state: WindowState =
+                self.load_json_with_recovery(&path, WindowState::default)?;
+            state.merge(update);
+            let contents = serde_json::to_string_pretty(&state)?;
+            Self::write_atomic(&path, &contents, JsonBackupPolicy::Never)?;
+            Ok(state)
+        })
+    }
+}
+
+/// Sort key for listing a project's environments: pinned first, then by order
+fn environment_list_position(environment: &Environment) -> (bool, i32) {
+    (!environment.pinned, environment.order)
+}
+
+/// Add `item` to or remove it from a pinned list, keeping each item once
+fn set_pinned(list: &mut Vec<String>, item: &str, pinned: bool) {
+    list.retain(|existing| existing != item);
+    if pinned {
+        list.push(item.to_string());
+    }
 }
 
-static STORAGE: OnceLock<Result<Storage, String>> = OnceLock::new();
-static INIT_LOCK: Mutex<()> = Mutex::new(());
+/// Environment variable that overrides the app data directory
+pub const DATA_DIR_ENV: &str = "ORKESTRATOR_AI_DATA_DIR";
+
+/// Data directory from the [`DATA_DIR_ENV`] value, if set to a non-empty path
+fn data_dir_override(value: Option<std::ffi::OsString>) -> Option<PathBuf> {
+    value.filter(|v| !v.is_empty()).map(PathBuf::from)
+}
+
+/// Global storage, initialized on first use. The outcome of the first
+/// initialization (including a failure) is kept for the app's lifetime;
+/// only tests replace it, via [`set_storage_for_test`].
+static STORAGE: RwLock<Option<Result<&'static Storage, String>>> = RwLock::new(None);
 
 /// Get the global storage instance
 pub fn get_storage() -> Result<&'static Storage, StorageError> {
-    let _guard = INIT_LOCK.lock().unwrap();
+    let to_error =
+        |e: &String| StorageError::Io(std::io::Error::new(std::io::ErrorKind::Other, e.clone()));
+
+    if let Some(result) = STORAGE.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return result.as_ref().copied().map_err(to_error);
+    }
+
+    let mut slot = STORAGE.write().unwrap_or_else(|e| e.into_inner());
+    let result = slot.get_or_insert_with(|| {
+        Storage::new()
+            .map(|storage| &*Box::leak(Box::new(storage)))
+            .map_err(|e| e.to_string())
+    });
+    result.as_ref().copied().map_err(to_error)
+}
 
-    let result = STORAGE.get_or_init(|| Storage::new().map_err(|e| e.to_string()));
+/// Serializes tests that swap the global storage
+#[cfg(test)]
+static TEST_STORAGE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Keeps an injected global storage in place; the next `get_storage` call
+/// after it is dropped initializes storage from scratch again.
+#[cfg(test)]
+pub(crate) struct TestStorageGuard {
+    pub storage: &'static Storage,
+    _lock: std::sync::MutexGuard<'static, ()>,
+}
+
+#[cfg(test)]
+impl Drop for TestStorageGuard {
+    fn drop(&mut self) {
+        *STORAGE.write().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
 
-    match result {
-        Ok(storage) => Ok(storage),
-        Err(e) => Err(StorageError::Io(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            e.clone(),
-        ))),
+/// Make `get_storage` return `storage` until the returned guard is dropped,
+/// so commands can be tested end-to-end. Tests holding a guard run one at a time.
+#[cfg(test)]
+pub(crate) fn set_storage_for_test(storage: Storage) -> TestStorageGuard {
+    let lock = TEST_STORAGE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let storage: &'static Storage = Box::leak(Box::new(storage));
+    *STORAGE.write().unwrap_or_else(|e| e.into_inner()) = Some(Ok(storage));
+    TestStorageGuard {
+        storage,
+        _lock: lock,
     }
 }
 
@@ -1793,6 +2353,75 @@ mod tests {
         assert!(matches!(result, Err(StorageError::ProjectNotFound(_))));
     }
 
+    #[test]
+    fn test_project_groups() {
+        let storage = create_test_storage();
+        let project1 = Project::new("https://github.com/test/repo1.git".to_string(), None);
+        let project2 = Project::new("https://github.com/test/repo2.git".to_string(), None);
+        storage.add_project(project1.clone()).unwrap();
+        storage.add_project(project2.clone()).unwrap();
+
+        let work = storage.create_project_group("Work").unwrap();
+        let oss = storage.create_project_group("OSS").unwrap();
+        let groups = storage.load_project_groups().unwrap();
+        assert_eq!(groups[&work].order, 0);
+        assert_eq!(groups[&oss].order, 1);
+
+        let renamed = storage.rename_project_group(&oss, "Open source").unwrap();
+        assert_eq!(renamed.name, "Open source");
+
+        let groups = storage.reorder_project_groups(&[oss.clone()]).unwrap();
+        assert_eq!(groups[&oss].order, 0);
+        assert_eq!(groups[&work].order, 1);
+
+        let assigned = storage
+            .set_project_group(&project1.id, Some(&work))
+            .unwrap();
+        assert_eq!(assigned.group_id.as_deref(), Some(work.as_str()));
+        storage.set_project_group(&project2.id, Some(&oss)).unwrap();
+        assert!(matches!(
+            storage.set_project_group(&project2.id, Some("missing")),
+            Err(StorageError::ProjectGroupNotFound(_))
+        ));
+
+        let ungrouped = storage.delete_project_group(&work).unwrap();
+        assert_eq!(ungrouped, vec![project1.id.clone()]);
+        assert!(!storage.load_project_groups().unwrap().contains_key(&work));
+        let projects = storage.load_projects().unwrap();
+        let group_of = |id: &str| {
+            projects
+                .iter()
+                .find(|p| p.id == id)
+                .unwrap()
+                .group_id
+                .clone()
+        };
+        assert_eq!(group_of(&project1.id), None);
+        assert_eq!(group_of(&project2.id), Some(oss));
+    }
+
+    #[test]
+    fn test_pinned_containers_and_images() {
+        let storage = create_test_storage();
+
+        storage.set_container_pinned("postgres", true).unwrap();
+        let pinned = storage.set_container_pinned("postgres", true).unwrap();
+        assert_eq!(pinned, vec!["postgres".to_string()]);
+        storage.set_image_pinned("redis:7", true).unwrap();
+
+        let config = storage.load_config().unwrap();
+        assert_eq!(
+            config.global.pinned_containers,
+            vec!["postgres".to_string()]
+        );
+        assert_eq!(config.global.pinned_images, vec!["redis:7".to_string()]);
+
+        assert!(storage
+            .set_container_pinned("postgres", false)
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn test_multiple_projects() {
         let storage = create_test_storage();
@@ -1951,6 +2580,45 @@ mod tests {
         assert_eq!(loaded.has_merge_conflicts, Some(true));
     }
 
+    #[test]
+    fn test_environments_listener_hears_changed_projects() {
+        let storage = create_test_storage();
+        let notified = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let sink = notified.clone();
+        storage.set_environments_listener(move |project_id| {
+            sink.lock().unwrap().push(project_id.to_string());
+        });
+
+        let env = storage
+            .add_environment(Environment::new("project-a".to_string()))
+            .unwrap();
+        storage
+            .add_environment(Environment::new("project-b".to_string()))
+            .unwrap();
+        storage
+            .update_environment(&env.id, serde_json::json!({ "status": "running" }))
+            .unwrap();
+        // Writing the same value again is not a change
+        storage
+            .update_environment(&env.id, serde_json::json!({ "status": "running" }))
+            .unwrap();
+        storage
+            .reorder_environments("project-a", std::slice::from_ref(&env.id))
+            .unwrap();
+        storage.remove_environment(&env.id).unwrap();
+
+        assert_eq!(
+            *notified.lock().unwrap(),
+            vec![
+                "project-a",
+                "project-b",
+                "project-a",
+                "project-a",
+                "project-a"
+            ]
+        );
+    }
+
     #[test]
     fn test_update_environment_clears_pr_metadata_with_null() {
         let storage = create_test_storage();
@@ -2200,6 +2868,125 @@ mod tests {
         assert!(loaded.setup_scripts_complete);
     }
 
+    #[test]
+    fn test_update_environment_firewall_bypassed() {
+        let storage = create_test_storage();
+
+        let env = Environment::new("project-123".to_string());
+        storage.add_environment(env.clone()).unwrap();
+
+        let updated = storage
+            .update_environment(&env.id, serde_json::json!({ "firewallBypassed": true }))
+            .unwrap();
+        assert!(updated.firewall_bypassed);
+
+        let updated = storage
+            .update_environment(&env.id, serde_json::json!({ "status": "stopped" }))
+            .unwrap();
+        assert!(updated.firewall_bypassed);
+
+        let updated = storage
+            .update_environment(&env.id, serde_json::json!({ "firewallBypassed": false }))
+            .unwrap();
+        assert!(!updated.firewall_bypassed);
+    }
+
+    #[test]
+    fn test_update_environment_read_only() {
+        let storage = create_test_storage();
+
+        let env = Environment::new("project-123".to_string());
+        storage.add_environment(env.clone()).unwrap();
+        assert!(!env.read_only);
+
+        let updated = storage
+            .update_environment(&env.id, serde_json::json!({ "readOnly": true }))
+            .unwrap();
+        assert!(updated.read_only);
+
+        let loaded = storage.get_environment(&env.id).unwrap().unwrap();
+        assert!(loaded.read_only);
+    }
+
+    #[test]
+    fn test_update_environment_full_clone() {
+        let storage = create_test_storage();
+
+        let env = Environment::new("project-123".to_string());
+        storage.add_environment(env.clone()).unwrap();
+        assert!(!env.full_clone);
+
+        let updated = storage
+            .update_environment(&env.id, serde_json::json!({ "fullClone": true }))
+            .unwrap();
+        assert!(updated.full_clone);
+        let loaded = storage.get_environment(&env.id).unwrap().unwrap();
+        assert!(loaded.full_clone);
+
+        let updated = storage
+            .update_environment(&env.id, serde_json::json!({ "fullClone": false }))
+            .unwrap();
+        assert!(!updated.full_clone);
+        let loaded = storage.get_environment(&env.id).unwrap().unwrap();
+        assert!(!loaded.full_clone);
+    }
+
+    #[test]
+    fn test_update_environment_shallow_history() {
+        let storage = create_test_storage();
+
+        let env = Environment::new("project-123".to_string());
+        storage.add_environment(env.clone()).unwrap();
+        assert!(!env.shallow_history);
+
+        let updated = storage
+            .update_environment(&env.id, serde_json::json!({ "shallowHistory": true }))
+            .unwrap();
+        assert!(updated.shallow_history);
+
+        // unshallow_repo clears the flag once the full history is fetched
+        let updated = storage
+            .update_environment(&env.id, serde_json::json!({ "shallowHistory": false }))
+            .unwrap();
+        assert!(!updated.shallow_history);
+        let loaded = storage.get_environment(&env.id).unwrap().unwrap();
+        assert!(!loaded.shallow_history);
+    }
+
+    #[test]
+    fn test_pinned_environments_sort_first_and_survive_reorder() {
+        let storage = create_test_storage();
+
+        let mut first = Environment::new("project-1".to_string());
+        let mut second = Environment::new("project-1".to_string());
+        let mut pinned = Environment::new("project-1".to_string());
+        first.order = 0;
+        second.order = 1;
+        pinned.order = 2;
+        pinned.pinned = true;
+        storage
+            .save_environments(&[first.clone(), second.clone(), pinned.clone()])
+            .unwrap();
+
+        let ids = |envs: Vec<Environment>| envs.into_iter().map(|e| e.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(storage.get_environments_by_project("project-1").unwrap()),
+            vec![pinned.id.clone(), first.id.clone(), second.id.clone()]
+        );
+
+        let reordered = storage
+            .reorder_environments(
+                "project-1",
+                &[second.id.clone(), first.id.clone(), pinned.id.clone()],
+            )
+            .unwrap();
+        assert!(reordered[0].pinned);
+        assert_eq!(
+            ids(reordered),
+            vec![pinned.id.clone(), second.id.clone(), first.id.clone()]
+        );
+    }
+
     #[test]
     fn test_update_nonexistent_environment() {
         let storage = create_test_storage();
@@ -2279,6 +3066,77 @@ mod tests {
         assert_eq!(loaded.global.container_resources.memory_gb, 8);
     }
 
+    #[test]
+    fn test_staged_config_is_loaded_and_flushed_once() {
+        let storage = create_test_storage();
+        let mut config = AppConfig::default();
+        config.global.min_free_disk_gb = 42;
+
+        storage.stage_config(&config);
+        assert!(!storage.config_file().exists());
+        assert_eq!(storage.load_config().unwrap().global.min_free_disk_gb, 42);
+
+        assert!(storage.flush_pending_config().unwrap());
+        assert!(!storage.flush_pending_config().unwrap());
+        let on_disk: AppConfig =
+            serde_json::from_str(&fs::read_to_string(storage.config_file()).unwrap()).unwrap();
+        assert_eq!(on_disk.global.min_free_disk_gb, 42);
+
+        // An immediate save drops a staged one
+        storage.stage_config(&config);
+        storage.save_config(&AppConfig::default()).unwrap();
+        assert!(!storage.flush_pending_config().unwrap());
+        assert_eq!(storage.load_config().unwrap().global.min_free_disk_gb, 5);
+    }
+
+    #[test]
+    fn test_update_config_saves_only_successful_updates() {
+        let storage = create_test_storage();
+
+        let (updated, previous) = storage
+            .update_config(|config| {
+                let previous = config.global.min_free_disk_gb;
+                config.global.min_free_disk_gb = 42;
+                Ok(previous)
+            })
+            .unwrap();
+        assert_eq!(previous, 5);
+        assert_eq!(updated.global.min_free_disk_gb, 42);
+        assert_eq!(storage.load_config().unwrap().global.min_free_disk_gb, 42);
+
+        let result = storage.update_config(|config| {
+            config.global.min_free_disk_gb = 7;
+            Err::<(), _>(StorageError::InvalidConfig("rejected".to_string()))
+        });
+        assert!(matches!(result, Err(StorageError::InvalidConfig(_))));
+        assert_eq!(storage.load_config().unwrap().global.min_free_disk_gb, 42);
+    }
+
+    #[test]
+    fn test_update_config_scheduled_saves_only_successful_updates() {
+        let storage: &'static Storage = Box::leak(Box::new(create_test_storage()));
+        let mut config = AppConfig::default();
+        config.global.config_save_debounce_ms = 0;
+        storage.save_config(&config).unwrap();
+
+        let (previous, updated) = storage
+            .update_config_scheduled(|config| {
+                config.global.min_free_disk_gb = 42;
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(previous.global.min_free_disk_gb, 5);
+        assert_eq!(updated.global.min_free_disk_gb, 42);
+        assert_eq!(storage.load_config().unwrap().global.min_free_disk_gb, 42);
+
+        let result = storage.update_config_scheduled(|config| {
+            config.global.min_free_disk_gb = 7;
+            Err(StorageError::InvalidConfig("rejected".to_string()))
+        });
+        assert!(matches!(result, Err(StorageError::InvalidConfig(_))));
+        assert_eq!(storage.load_config().unwrap().global.min_free_disk_gb, 42);
+    }
+
     #[test]
     fn test_config_with_repositories() {
         let storage = create_test_storage();
@@ -2298,6 +3156,12 @@ mod tests {
                 default_agent: None,
                 agent_style: None,
                 claude_native_backend: None,
+                restart_policy: None,
+                compose_enabled: false,
+                workspace_path: None,
+                docker_network: None,
+                default_sessions: None,
+                clone_depth: None,
             },
         );
 
@@ -2510,6 +3374,66 @@ mod tests {
         assert!(deleted.is_none());
     }
 
+    #[test]
+    fn test_load_session_buffer_prefers_newer_file() {
+        let storage = create_test_storage();
+        let session = storage
+            .add_session(Session::new(
+                "env-1".to_string(),
+                "container-1".to_string(),
+                "tab-1".to_string(),
+                SessionType::Plain,
+            ))
+            .unwrap();
+
+        storage
+            .save_session_autosave_buffer(&session.id, "raw")
+            .unwrap();
+        assert!(storage.has_session_buffer(&session.id));
+        assert_eq!(
+            storage.load_session_buffer(&session.id).unwrap().as_deref(),
+            Some("raw")
+        );
+
+        storage
+            .save_session_buffer(&session.id, "\x1b[32mformatted\x1b[0m")
+            .unwrap();
+        set_file_mtime(
+            storage.autosave_buffer_file(&session.id),
+            FileTime::from_unix_time(1_000, 0),
+        )
+        .unwrap();
+        set_file_mtime(
+            storage.buffer_file(&session.id),
+            FileTime::from_unix_time(2_000, 0),
+        )
+        .unwrap();
+        assert_eq!(
+            storage.load_session_buffer(&session.id).unwrap().as_deref(),
+            Some("\x1b[32mformatted\x1b[0m")
+        );
+
+        // Output auto-saved after the frontend's last save is restored
+        storage
+            .save_session_autosave_buffer(&session.id, "newer raw")
+            .unwrap();
+        set_file_mtime(
+            storage.autosave_buffer_file(&session.id),
+            FileTime::from_unix_time(3_000, 0),
+        )
+        .unwrap();
+        assert_eq!(
+            storage.load_session_buffer(&session.id).unwrap().as_deref(),
+            Some("newer raw")
+        );
+
+        // Auto-saves of live sessions aren't orphans
+        assert!(storage.cleanup_orphaned_buffers().unwrap().is_empty());
+
+        storage.delete_session_buffer(&session.id).unwrap();
+        assert!(!storage.has_session_buffer(&session.id));
+    }
+
     #[test]
     fn test_session_buffer_truncation() {
         let storage = create_test_storage();
@@ -2603,6 +3527,39 @@ mod tests {
         assert!(sessions.is_empty());
     }
 
+    #[test]
+    fn test_rename_session_then_clear_name() {
+        let storage = create_test_storage();
+        let session = storage
+            .add_session(Session::new(
+                "env-1".to_string(),
+                "container-1".to_string(),
+                "tab-1".to_string(),
+                SessionType::Claude,
+            ))
+            .unwrap();
+
+        let renamed = storage
+            .rename_session(&session.id, Some("  Reviewer  ".to_string()))
+            .unwrap();
+        assert_eq!(renamed.name.as_deref(), Some("Reviewer"));
+
+        let cleared = storage.rename_session(&session.id, None).unwrap();
+        assert_eq!(cleared.name, None);
+
+        storage
+            .rename_session(&session.id, Some("Reviewer".to_string()))
+            .unwrap();
+        let cleared = storage
+            .rename_session(&session.id, Some("   ".to_string()))
+            .unwrap();
+        assert_eq!(cleared.name, None);
+        assert_eq!(
+            storage.get_session(&session.id).unwrap().unwrap().name,
+            None
+        );
+    }
+
     #[test]
     fn test_save_rename_launch_and_reorder_sessions() {
         let storage = create_test_storage();
@@ -2999,6 +3956,54 @@ mod tests {
         assert_eq!(fetched.content, "hello world");
     }
 
+    #[test]
+    fn test_data_dir_override() {
+        assert_eq!(data_dir_override(None), None);
+        assert_eq!(data_dir_override(Some("".into())), None);
+        assert_eq!(
+            data_dir_override(Some("/tmp/orkestrator-test".into())),
+            Some(PathBuf::from("/tmp/orkestrator-test"))
+        );
+    }
+
+    #[test]
+    fn test_set_storage_for_test_replaces_global_storage() {
+        let guard = set_storage_for_test(create_test_storage());
+        let storage = get_storage().unwrap();
+        assert!(std::ptr::eq(storage, guard.storage));
+
+        storage
+            .save_project_notes_for_project("proj-1", "injected".to_string())
+            .unwrap();
+        assert_eq!(
+            guard.storage.get_project_notes("proj-1").unwrap().content,
+            "injected"
+        );
+    }
+
+    #[test]
+    fn test_window_state_round_trip() {
+        let storage = create_test_storage();
+        assert_eq!(storage.load_window_state().unwrap(), WindowState::default());
+
+        storage
+            .update_window_state(WindowState {
+                zoom_level: Some(120),
+                ..Default::default()
+            })
+            .unwrap();
+        let saved = storage
+            .update_window_state(WindowState {
+                width: Some(1200),
+                height: Some(800),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(saved.zoom_level, Some(120));
+        assert_eq!(storage.load_window_state().unwrap(), saved);
+    }
+
     #[test]
     fn test_should_rotate_never_policy_returns_false() {
         let storage = create_test_storage();
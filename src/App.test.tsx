@@ -186,13 +186,20 @@ mock.module("@/hooks", () => ({
 
 const mockCheckDocker = mock(async () => true);
 const mockSyncAllEnvironmentsWithDocker = mock(async () => [] as string[]);
-const mockCheckClaudeCli = mock(async () => true);
+const cliStatus = (available: boolean) => ({
+  available,
+  path: available ? "/usr/local/bin/cli" : null,
+  version: null,
+});
+const mockCheckClaudeCli = mock(async () => cliStatus(true));
 const mockCheckClaudeConfig = mock(async () => true);
-const mockCheckOpencodeCli = mock(async () => true);
-const mockCheckCodexCli = mock(async () => true);
-const mockCheckGithubCli = mock(async () => true);
+const mockCheckOpencodeCli = mock(async () => cliStatus(true));
+const mockCheckCodexCli = mock(async () => cliStatus(true));
+const mockCheckGithubCli = mock(async () => cliStatus(true));
 const mockGetAvailableAiCli = mock<() => Promise<string | null>>(async () => "claude");
 const mockGetConfig = mock(async () => mockConfig);
+const mockGetWindowState = mock(async () => ({}));
+const mockSaveWindowState = mock(async () => ({}));
 
 mock.module("@/lib/tauri", () => ({
   checkDocker: mockCheckDocker,
@@ -203,6 +210,8 @@ mock.module("@/lib/tauri", () => ({
   checkGithubCli: mockCheckGithubCli,
   getAvailableAiCli: mockGetAvailableAiCli,
   getConfig: mockGetConfig,
+  getWindowState: mockGetWindowState,
+  saveWindowState: mockSaveWindowState,
   syncAllEnvironmentsWithDocker: mockSyncAllEnvironmentsWithDocker,
 }));
 
@@ -330,15 +339,15 @@ function resetAppMocks() {
   mockSyncAllEnvironmentsWithDocker.mockClear();
   mockSyncAllEnvironmentsWithDocker.mockImplementation(async () => []);
   mockCheckClaudeCli.mockClear();
-  mockCheckClaudeCli.mockImplementation(async () => true);
+  mockCheckClaudeCli.mockImplementation(async () => cliStatus(true));
   mockCheckClaudeConfig.mockClear();
   mockCheckClaudeConfig.mockImplementation(async () => true);
   mockCheckOpencodeCli.mockClear();
-  mockCheckOpencodeCli.mockImplementation(async () => true);
+  mockCheckOpencodeCli.mockImplementation(async () => cliStatus(true));
   mockCheckCodexCli.mockClear();
-  mockCheckCodexCli.mockImplementation(async () => true);
+  mockCheckCodexCli.mockImplementation(async () => cliStatus(true));
   mockCheckGithubCli.mockClear();
-  mockCheckGithubCli.mockImplementation(async () => true);
+  mockCheckGithubCli.mockImplementation(async () => cliStatus(true));
   mockGetAvailableAiCli.mockClear();
   mockGetAvailableAiCli.mockImplementation(async () => "claude");
   mockGetConfig.mockClear();
@@ -778,10 +787,10 @@ describe("App startup checks and global events", () => {
   });
 
   test("shows the no-AI-CLI dialog and retries CLI checks", async () => {
-    mockCheckClaudeCli.mockImplementation(async () => false);
+    mockCheckClaudeCli.mockImplementation(async () => cliStatus(false));
     mockCheckClaudeConfig.mockImplementation(async () => false);
-    mockCheckOpencodeCli.mockImplementation(async () => false);
-    mockCheckCodexCli.mockImplementation(async () => false);
+    mockCheckOpencodeCli.mockImplementation(async () => cliStatus(false));
+    mockCheckCodexCli.mockImplementation(async () => cliStatus(false));
     mockGetAvailableAiCli.mockImplementation(async () => null);
 
     resetStores({
@@ -796,10 +805,10 @@ describe("App startup checks and global events", () => {
       expect(screen.getByText("AI CLI Required")).toBeTruthy();
     });
 
-    mockCheckClaudeCli.mockImplementation(async () => true);
+    mockCheckClaudeCli.mockImplementation(async () => cliStatus(true));
     mockCheckClaudeConfig.mockImplementation(async () => true);
-    mockCheckOpencodeCli.mockImplementation(async () => false);
-    mockCheckCodexCli.mockImplementation(async () => false);
+    mockCheckOpencodeCli.mockImplementation(async () => cliStatus(false));
+    mockCheckCodexCli.mockImplementation(async () => cliStatus(false));
     mockGetAvailableAiCli.mockImplementation(async () => "claude");
 
     act(() => {
@@ -813,10 +822,10 @@ describe("App startup checks and global events", () => {
   });
 
   test("shows Claude login required when Claude is installed but not configured", async () => {
-    mockCheckClaudeCli.mockImplementation(async () => true);
+    mockCheckClaudeCli.mockImplementation(async () => cliStatus(true));
     mockCheckClaudeConfig.mockImplementation(async () => false);
-    mockCheckOpencodeCli.mockImplementation(async () => false);
-    mockCheckCodexCli.mockImplementation(async () => false);
+    mockCheckOpencodeCli.mockImplementation(async () => cliStatus(false));
+    mockCheckCodexCli.mockImplementation(async () => cliStatus(false));
     mockGetAvailableAiCli.mockImplementation(async () => "claude");
 
     resetStores({
@@ -833,7 +842,7 @@ describe("App startup checks and global events", () => {
   });
 
   test("shows and dismisses the GitHub CLI warning", async () => {
-    mockCheckGithubCli.mockImplementation(async () => false);
+    mockCheckGithubCli.mockImplementation(async () => cliStatus(false));
 
     resetStores({
       environments: [],
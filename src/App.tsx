@@ -27,7 +27,7 @@ import { getBackgroundProcessingEnvironments } from "@/lib/background-pipelines"
 import { cn, getEnvironmentIdFromSessionKey } from "@/lib/utils";
 import { Toaster } from "@/components/ui/sonner";
 import { ErrorDetailsDialog } from "@/components/errors";
-import { checkDocker, checkClaudeCli, checkClaudeConfig, checkCodexCli, checkOpencodeCli, checkGithubCli, getAvailableAiCli, getConfig, syncAllEnvironmentsWithDocker } from "@/lib/tauri";
+import { checkDocker, checkClaudeCli, checkClaudeConfig, checkCodexCli, checkOpencodeCli, checkGithubCli, getAvailableAiCli, getConfig, getWindowState, saveWindowState, syncAllEnvironmentsWithDocker } from "@/lib/tauri";
 import { usePrMonitorService } from "@/hooks/usePrMonitorService";
 import { useGlobalActivityMonitor } from "@/hooks/useGlobalActivityMonitor";
 import { useEnvironments } from "@/hooks";
@@ -42,9 +42,10 @@ import {
 } from "@/components/ui/alert-dialog";
 import { Button } from "@/components/ui/button";
 import { Loader2 } from "lucide-react";
+import type { TerminalAppearanceChangedPayload } from "@/types";
 
 function App() {
-  const { selectedEnvironmentId, selectedProjectId, zoomLevel, zoomIn, zoomOut, resetZoom } = useUIStore();
+  const { selectedEnvironmentId, selectedProjectId, zoomLevel, setZoomLevel, zoomIn, zoomOut, resetZoom } = useUIStore();
   const environments = useEnvironmentStore((state) => state.environments);
   const getEnvironmentById = useEnvironmentStore((state) => state.getEnvironmentById);
   const setConfig = useConfigStore((state) => state.setConfig);
@@ -236,17 +237,17 @@ function App() {
       getAvailableAiCli(),
     ])
       .then(([claudeCli, claudeConfig, opencodeCli, codexCli, githubCli, aiCli]) => {
-        console.log("[App] Claude CLI available:", claudeCli);
+        console.log("[App] Claude CLI:", claudeCli);
         console.log("[App] Claude config available:", claudeConfig);
-        console.log("[App] OpenCode CLI available:", opencodeCli);
-        console.log("[App] Codex CLI available:", codexCli);
-        console.log("[App] GitHub CLI available:", githubCli);
+        console.log("[App] OpenCode CLI:", opencodeCli);
+        console.log("[App] Codex CLI:", codexCli);
+        console.log("[App] GitHub CLI:", githubCli);
         console.log("[App] Available AI CLI:", aiCli);
-        setClaudeCliAvailable(claudeCli);
+        setClaudeCliAvailable(claudeCli.available);
         setClaudeConfigAvailable(claudeConfig);
-        setOpencodeCliAvailable(opencodeCli);
-        setCodexCliAvailable(codexCli);
-        setGithubCliAvailable(githubCli);
+        setOpencodeCliAvailable(opencodeCli.available);
+        setCodexCliAvailable(codexCli.available);
+        setGithubCliAvailable(githubCli.available);
         setAvailableAiCli(aiCli);
       })
       .catch((error) => {
@@ -310,11 +311,11 @@ function App() {
         getAvailableAiCli(),
       ]);
       console.log("[App] CLI retry check - Claude:", claudeCli, "OpenCode:", opencodeCli, "Codex:", codexCli, "GitHub:", githubCli, "Available AI:", aiCli);
-      setClaudeCliAvailable(claudeCli);
+      setClaudeCliAvailable(claudeCli.available);
       setClaudeConfigAvailable(claudeConfig);
-      setOpencodeCliAvailable(opencodeCli);
-      setCodexCliAvailable(codexCli);
-      setGithubCliAvailable(githubCli);
+      setOpencodeCliAvailable(opencodeCli.available);
+      setCodexCliAvailable(codexCli.available);
+      setGithubCliAvailable(githubCli.available);
       setAvailableAiCli(aiCli);
     } catch (error) {
       console.error("[App] CLI retry check failed:", error);
@@ -334,6 +335,29 @@ function App() {
     document.documentElement.style.zoom = `${zoomLevel}%`;
   }, [zoomLevel]);
 
+  // Restore the zoom level saved by the backend (window_state.json)
+  const windowStateLoadedRef = useRef(false);
+  useEffect(() => {
+    getWindowState()
+      .then((state) => {
+        if (state.zoomLevel !== undefined) {
+          setZoomLevel(state.zoomLevel);
+        }
+      })
+      .catch((error) => console.warn("[App] Failed to load window state:", error))
+      .finally(() => {
+        windowStateLoadedRef.current = true;
+      });
+  }, [setZoomLevel]);
+
+  // Persist zoom changes once the saved state has been restored
+  useEffect(() => {
+    if (!windowStateLoadedRef.current) return;
+    saveWindowState({ zoomLevel }).catch((error) =>
+      console.warn("[App] Failed to save window state:", error)
+    );
+  }, [zoomLevel]);
+
   // Surface Claude credential refresh/push failures as a non-blocking toast.
   // The backend de-dupes (only emits after repeated failures or actual push
   // problems), but we also guard against toast spam here.
@@ -362,6 +386,24 @@ function App() {
     };
   }, []);
 
+  // Apply terminal appearance changes saved elsewhere (e.g. another window)
+  // so open terminals pick up the new font, colors, and scrollback live.
+  const updateGlobalConfig = useConfigStore((state) => state.updateGlobalConfig);
+  useEffect(() => {
+    const unlisten = listen<TerminalAppearanceChangedPayload>(
+      "terminal-appearance-changed",
+      (event) => {
+        updateGlobalConfig({
+          terminalAppearance: event.payload.terminalAppearance,
+          terminalScrollback: event.payload.terminalScrollback,
+        });
+      }
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [updateGlobalConfig]);
+
   // Listen for menu zoom events from Tauri backend
   useEffect(() => {
     const unlisten = listen<string>("menu-zoom", (event) => {
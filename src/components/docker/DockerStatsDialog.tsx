@@ -25,7 +25,7 @@ import {
 } from "lucide-react";
 import * as tauri from "@/lib/tauri";
 import { FullscreenSettingsLayout, type SettingsMenuItem } from "@/components/settings/FullscreenSettingsLayout";
-import type { DockerSystemStats, ContainerInfo, SystemPruneResult } from "@/lib/tauri";
+import type { DockerSystemStats, ContainerInfo, OrphanCleanupResult, SystemPruneResult } from "@/lib/tauri";
 import { useProjectStore, useEnvironmentStore } from "@/stores";
 import { Checkbox } from "@/components/ui/checkbox";
 import { Label } from "@/components/ui/label";
@@ -72,7 +72,7 @@ export function DockerStatsDialog({ open, onOpenChange }: DockerStatsDialogProps
   const [containers, setContainers] = useState<ContainerInfo[]>([]);
   const [showCleanupConfirm, setShowCleanupConfirm] = useState(false);
   const [isCleaningUp, setIsCleaningUp] = useState(false);
-  const [cleanupResult, setCleanupResult] = useState<number | null>(null);
+  const [cleanupResult, setCleanupResult] = useState<OrphanCleanupResult | null>(null);
   // Track individual container operations
   const [stoppingContainerId, setStoppingContainerId] = useState<string | null>(null);
   const [deletingContainerId, setDeletingContainerId] = useState<string | null>(null);
@@ -315,6 +315,7 @@ export function DockerStatsDialog({ open, onOpenChange }: DockerStatsDialogProps
                       <div className="font-medium mt-1">{formatBytes(pruneResult.spaceReclaimed)} reclaimed</div>
                     </div></>
                   )}
+                  {pruneResult.skippedPinned.length > 0 && <div className="text-xs mt-1 opacity-80">Kept pinned: {pruneResult.skippedPinned.join(", ")}</div>}
                 </div>
                 <button onClick={() => setPruneResult(null)} className="shrink-0 p-0.5 rounded hover:bg-green-500/20 transition-colors" aria-label="Dismiss"><X className="h-4 w-4" /></button>
               </div>
@@ -335,7 +336,10 @@ export function DockerStatsDialog({ open, onOpenChange }: DockerStatsDialogProps
           {cleanupResult !== null && (
             <div className="flex items-center gap-2 p-3 rounded-md bg-green-500/10 text-green-700 dark:text-green-400 text-sm">
               <CheckCircle2 className="h-4 w-4 shrink-0" />
-              <span>{cleanupResult === 0 ? "No orphaned containers to remove." : `Successfully removed ${cleanupResult} orphaned container${cleanupResult > 1 ? "s" : ""}.`}</span>
+              <span>
+                {cleanupResult.removed === 0 ? "No orphaned containers to remove." : `Successfully removed ${cleanupResult.removed} orphaned container${cleanupResult.removed > 1 ? "s" : ""}.`}
+                {cleanupResult.skippedPinned.length > 0 && ` Kept pinned: ${cleanupResult.skippedPinned.join(", ")}.`}
+              </span>
             </div>
           )}
           {containers.length === 0 ? (
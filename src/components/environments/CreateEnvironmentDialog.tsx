@@ -24,15 +24,17 @@ import {
   SelectTrigger,
   SelectValue,
 } from "@/components/ui/select";
-import { Loader2, Shield, Globe, Network, Plus, Trash2, ChevronDown, Container, Laptop, Terminal, Bot, X } from "lucide-react";
+import { Loader2, Shield, Globe, Network, Plus, Trash2, ChevronDown, Container, Laptop, Terminal, Bot, X, Sparkles } from "lucide-react";
 import { ClaudeIcon, CodexIcon, OpenCodeIcon } from "@/components/icons/AgentIcons";
 import { cn } from "@/lib/utils";
 import { readImage } from "@tauri-apps/plugin-clipboard-manager";
 import { resizeCanvasIfNeeded } from "@/lib/canvas-utils";
 import { toast } from "sonner";
+import { detectCompose, suggestEnvironmentName } from "@/lib/tauri";
 import type {
   ClaudeMode,
   CodexMode,
+  ComposeProject,
   EnvironmentType,
   NetworkAccessMode,
   OpenCodeMode,
@@ -123,6 +125,7 @@ export function CreateEnvironmentDialog({
 
   const [environmentType, setEnvironmentType] = useState<EnvironmentType>(configEnvironmentType);
   const [environmentName, setEnvironmentName] = useState("");
+  const [isSuggestingName, setIsSuggestingName] = useState(false);
   const [launchAgent, setLaunchAgent] = useState(true);
   const [agentType, setAgentType] = useState<AgentType>(configDefaultAgent);
   const [claudeMode, setClaudeMode] = useState<ClaudeMode>(configClaudeMode);
@@ -133,6 +136,7 @@ export function CreateEnvironmentDialog({
   const [networkAccessMode, setNetworkAccessMode] = useState<NetworkAccessMode>("full");
   const [portMappings, setPortMappings] = useState<PortMapping[]>(defaultPortMappings);
   const [showPortConfig, setShowPortConfig] = useState(defaultPortMappings.length > 0);
+  const [composeProject, setComposeProject] = useState<ComposeProject | null>(null);
   const formRef = useRef<HTMLFormElement>(null);
   const promptRef = useRef<HTMLTextAreaElement>(null);
 
@@ -155,6 +159,24 @@ export function CreateEnvironmentDialog({
     }
   }, [open, launchAgent, projectId]);
 
+  // Detect multi-container compose projects so we can suggest a service/port to focus on
+  useEffect(() => {
+    if (!open || !projectId) return;
+    let cancelled = false;
+    detectCompose(projectId)
+      .then((project) => {
+        if (!cancelled) setComposeProject(project);
+      })
+      .catch((error) => {
+        console.warn("[CreateEnvironmentDialog] Failed to detect compose file:", error);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [open, projectId]);
+
+  const composeSuggestion = composeProject?.services.find((service) => service.ports.length > 0);
+
   const resetForm = useCallback(() => {
     setEnvironmentType(configEnvironmentType);
     setEnvironmentName("");
@@ -230,6 +252,23 @@ export function CreateEnvironmentDialog({
     return () => document.removeEventListener("paste", listener, { capture: true });
   }, [open, handlePromptPaste]);
 
+  // Preview an AI-generated name for the current prompt so the user can
+  // accept or edit it before the environment is created
+  const handleSuggestName = useCallback(async () => {
+    const prompt = initialPrompt.trim();
+    if (!prompt) return;
+    setIsSuggestingName(true);
+    try {
+      setEnvironmentName(await suggestEnvironmentName(prompt));
+    } catch (err) {
+      toast.error("Could not suggest a name", {
+        description: err instanceof Error ? err.message : String(err),
+      });
+    } finally {
+      setIsSuggestingName(false);
+    }
+  }, [initialPrompt]);
+
   const removeInitialPromptAttachment = useCallback((id: string) => {
     setInitialPromptAttachments((prev) => prev.filter((attachment) => attachment.id !== id));
   }, []);
@@ -434,13 +473,25 @@ export function CreateEnvironmentDialog({
               <Label htmlFor="environment-name">
                 Environment Name <span className="text-muted-foreground">(optional)</span>
               </Label>
-              <Input
-                id="environment-name"
-                placeholder="e.g., feature-dark-mode"
-                value={environmentName}
-                onChange={(e) => setEnvironmentName(e.target.value)}
-                disabled={isLoading}
-              />
+              <div className="flex gap-2">
+                <Input
+                  id="environment-name"
+                  placeholder="e.g., feature-dark-mode"
+                  value={environmentName}
+                  onChange={(e) => setEnvironmentName(e.target.value)}
+                  disabled={isLoading}
+                />
+                <Button
+                  type="button"
+                  variant="outline"
+                  size="icon"
+                  onClick={handleSuggestName}
+                  disabled={isLoading || isSuggestingName || !initialPrompt.trim()}
+                  title="Suggest a name from the initial prompt"
+                >
+                  {isSuggestingName ? <Loader2 className="h-4 w-4 animate-spin" /> : <Sparkles className="h-4 w-4" />}
+                </Button>
+              </div>
               <p className="text-xs text-muted-foreground">
                 Also used as the git branch name.
               </p>
@@ -658,6 +709,20 @@ export function CreateEnvironmentDialog({
             </div>
           </div>
 
+          {composeProject && composeProject.services.length > 1 && (
+            <div className="flex items-start gap-2 p-3 rounded-lg border border-yellow-500/30 bg-yellow-500/5 text-xs text-muted-foreground">
+              <Container className="h-4 w-4 shrink-0 text-yellow-500" />
+              <p>
+                This repository uses Docker Compose ({composeProject.services.length} services), but
+                environments run a single container.
+                {composeSuggestion && (
+                  <> Focus on <span className="font-medium text-foreground">{composeSuggestion.name}</span>
+                  {" "}(port {composeSuggestion.ports[0].containerPort}).</>
+                )}
+              </p>
+            </div>
+          )}
+
           {/* Port Configuration - only for containerized environments */}
           {environmentType === "containerized" && (
           <Collapsible open={showPortConfig} onOpenChange={setShowPortConfig}>
@@ -18,15 +18,22 @@ import {
   ContextMenuSeparator,
   ContextMenuTrigger,
 } from "@/components/ui/context-menu";
-import { Trash2, Play, Square, Container, Laptop, Shield, Globe, Settings2, RotateCw, Loader2, Network, Copy } from "lucide-react";
+import { Trash2, Play, Square, Container, Laptop, Shield, Globe, Settings2, RotateCw, Loader2, Network, Copy, XCircle, CheckCircle2, CircleDot, Pin, PinOff } from "lucide-react";
 import { toast } from "sonner";
-import type { Environment } from "@/types";
-import { useAgentActivityStore, useEnvironmentStore, useEnvironmentDiffStore, useBuildPipelineStore } from "@/stores";
+import type { Environment, StartProgressStage } from "@/types";
+import { useAgentActivityStore, useConfigStore, useEnvironmentStore, useEnvironmentDiffStore, useBuildPipelineStore } from "@/stores";
 import { EnvironmentSettingsDialog } from "./EnvironmentSettingsDialog";
 import { cn } from "@/lib/utils";
 import * as tauri from "@/lib/tauri";
 import { getEnvironmentPortAddress } from "@/lib/environment-address";
 
+/** Human-readable labels for start progress stages */
+const START_PROGRESS_LABELS: Record<StartProgressStage, string> = {
+  "creating-container": "Creating container",
+  "starting-container": "Starting container",
+  "running-setup": "Running workspace setup",
+};
+
 interface EnvironmentItemProps {
   environment: Environment;
   isSelected: boolean;
@@ -68,6 +75,8 @@ export function EnvironmentItem({
 
   // Check if this environment is being deleted
   const isEnvironmentDeleting = useEnvironmentStore((s) => s.isDeleting(environment.id));
+  const isEnvironmentNaming = useEnvironmentStore((s) => s.namingEnvironments.has(environment.id));
+  const startProgress = useEnvironmentStore((s) => s.startProgress[environment.id]);
 
   // Get diff stats for this environment
   const diffStats = useEnvironmentDiffStore((s) => s.stats.get(environment.id));
@@ -159,6 +168,37 @@ export function EnvironmentItem({
     });
   };
 
+  const togglePinned = () => {
+    tauri.setEnvironmentPinned(environment.id, !environment.pinned).then((updated) => {
+      onUpdate?.(updated);
+    }).catch(() => {
+      toast.error(environment.pinned ? "Failed to unpin environment" : "Failed to pin environment");
+    });
+  };
+
+  const cancelStart = () => {
+    tauri.cancelEnvironmentStart(environment.id).then((cancelled) => {
+      if (!cancelled) {
+        toast.info("Environment is no longer starting");
+      }
+    }).catch(() => {
+      toast.error("Failed to cancel environment start");
+    });
+  };
+
+  const composeEnabled = useConfigStore((s) => s.config.repositories[environment.projectId]?.composeEnabled ?? false);
+
+  const runCompose = (action: "up" | "down") => {
+    const run = action === "up" ? tauri.composeUp : tauri.composeDown;
+    run(environment.id).then((status) => {
+      toast.success(action === "up" ? "Compose services started" : "Compose services stopped", {
+        description: `Status: ${status}`,
+      });
+    }).catch((error) => {
+      toast.error(`docker compose ${action} failed`, { description: String(error) });
+    });
+  };
+
   const createdDate = new Date(environment.createdAt).toLocaleDateString();
 
   return (
@@ -217,6 +257,18 @@ export function EnvironmentItem({
                 <span className={cn("flex-1 truncate", isBuildEnvironment && "text-yellow-400")}>
                   {isBuildEnvironment ? environment.name.replace(/^Build:\s*/, "") : environment.name}
                 </span>
+                {isEnvironmentNaming && (
+                  <Loader2 className="h-3 w-3 shrink-0 animate-spin text-muted-foreground" aria-label="Generating name" />
+                )}
+                {environment.prUrl && environment.prState === "open" && environment.ciStatus === "passing" && (
+                  <CheckCircle2 className="h-3 w-3 shrink-0 text-green-500" aria-label="CI passing" />
+                )}
+                {environment.prUrl && environment.prState === "open" && environment.ciStatus === "failing" && (
+                  <XCircle className="h-3 w-3 shrink-0 text-red-500" aria-label="CI failing" />
+                )}
+                {environment.prUrl && environment.prState === "open" && environment.ciStatus === "pending" && (
+                  <CircleDot className="h-3 w-3 shrink-0 text-amber-500 animate-pulse" aria-label="CI pending" />
+                )}
                 {diffStats && (diffStats.additions > 0 || diffStats.deletions > 0 || diffStats.filesChanged > 0) && (
                   <span className="ml-1 flex shrink-0 items-center gap-1 font-mono text-[10px] tabular-nums">
                     {diffStats.additions > 0 && (
@@ -237,6 +289,12 @@ export function EnvironmentItem({
             <div className="space-y-1">
               <p className="font-medium">{environment.name}</p>
               <p className="text-xs text-muted-foreground">Created: {createdDate}</p>
+              {isCreating && startProgress && (
+                <p className="text-xs text-blue-400 flex items-center gap-1">
+                  <Loader2 className="h-3 w-3 animate-spin" />
+                  {START_PROGRESS_LABELS[startProgress]}…
+                </p>
+              )}
               {isLocalEnvironment ? (
                 <p className="text-xs text-muted-foreground flex items-center gap-1">
                   <Laptop className="h-3 w-3" />
@@ -270,6 +328,13 @@ export function EnvironmentItem({
                   }
                 </p>
               )}
+              {isLocalEnvironment && environment.localAppPorts?.map((appPort) => (
+                <p key={appPort.appPort} className="text-xs text-muted-foreground flex items-center gap-1">
+                  <Network className="h-3 w-3" />
+                  App: localhost:{appPort.hostPort}
+                  {appPort.hostPort !== appPort.appPort && <> (instead of {appPort.appPort})</>}
+                </p>
+              ))}
               {diffStats && (diffStats.additions > 0 || diffStats.deletions > 0 || diffStats.filesChanged > 0) && (
                 <div className="border-t border-border/50 pt-1 mt-1">
                   <p className="text-xs text-muted-foreground">
@@ -288,6 +353,9 @@ export function EnvironmentItem({
               {environment.prUrl && (
                 <p className="text-xs text-blue-400">PR: {environment.prUrl}</p>
               )}
+              {environment.prUrl && environment.ciStatus && (
+                <p className="text-xs text-muted-foreground">CI: {environment.ciStatus}</p>
+              )}
             </div>
           </TooltipContent>
         </Tooltip>
@@ -296,6 +364,10 @@ export function EnvironmentItem({
             <Settings2 className="h-4 w-4 mr-2" />
             Settings
           </ContextMenuItem>
+          <ContextMenuItem onClick={togglePinned}>
+            {environment.pinned ? <PinOff className="h-4 w-4 mr-2" /> : <Pin className="h-4 w-4 mr-2" />}
+            {environment.pinned ? "Unpin" : "Pin to Top"}
+          </ContextMenuItem>
           {localAddress && (
             <ContextMenuItem onClick={copyAddress}>
               <Copy className="h-4 w-4 mr-2" />
@@ -329,6 +401,25 @@ export function EnvironmentItem({
                 <RotateCw className="h-4 w-4 mr-2" />
                 Restart
               </ContextMenuItem>
+              {isCreating && (
+                <ContextMenuItem onClick={cancelStart}>
+                  <XCircle className="h-4 w-4 mr-2" />
+                  Cancel Start
+                </ContextMenuItem>
+              )}
+            </>
+          )}
+          {composeEnabled && (
+            <>
+              <ContextMenuSeparator />
+              <ContextMenuItem onClick={() => runCompose("up")}>
+                <Play className="h-4 w-4 mr-2" />
+                Compose Up
+              </ContextMenuItem>
+              <ContextMenuItem onClick={() => runCompose("down")}>
+                <Square className="h-4 w-4 mr-2" />
+                Compose Down
+              </ContextMenuItem>
             </>
           )}
           <ContextMenuSeparator />
@@ -68,6 +68,8 @@ import type {
 
 // Domain validation regex
 const DOMAIN_REGEX = /^([a-zA-Z0-9]([a-zA-Z0-9-]*[a-zA-Z0-9])?\.)+[a-zA-Z]{2,}$/;
+// Raw IPv4 addresses and CIDR ranges (range checks are done by the backend)
+const IP_CIDR_REGEX = /^\d{1,3}(\.\d{1,3}){3}(\/\d{1,2})?$/;
 
 interface EnvironmentSettingsDialogProps {
   open: boolean;
@@ -120,6 +122,8 @@ export function EnvironmentSettingsDialog({
   // Name state
   const [name, setName] = useState(environment.name);
   const [nameError, setNameError] = useState<string | null>(null);
+  const [readOnly, setReadOnly] = useState(environment.readOnly ?? false);
+  const [notes, setNotes] = useState(environment.notes ?? "");
 
   // Network state
   const [useGlobalDefaults, setUseGlobalDefaults] = useState(
@@ -189,6 +193,8 @@ export function EnvironmentSettingsDialog({
       // Reset name
       setName(environment.name);
       setNameError(null);
+      setReadOnly(environment.readOnly ?? false);
+      setNotes(environment.notes ?? "");
 
       // Reset network settings
       const customDomainList = environment.allowedDomains ?? [];
@@ -215,7 +221,7 @@ export function EnvironmentSettingsDialog({
       setEnvOpencodeMode(environment.opencodeMode ?? "global");
       setEnvCodexMode(environment.codexMode ?? "global");
     }
-  }, [open, environment.name, environment.allowedDomains, environment.portMappings, environment.defaultAgent, environment.claudeMode, environment.opencodeMode, environment.codexMode, globalDomains]);
+  }, [open, environment.name, environment.readOnly, environment.notes, environment.allowedDomains, environment.portMappings, environment.defaultAgent, environment.claudeMode, environment.opencodeMode, environment.codexMode, globalDomains]);
 
   // Update custom domains when toggling to global
   useEffect(() => {
@@ -325,7 +331,7 @@ export function EnvironmentSettingsDialog({
 
     const errors: string[] = [];
     for (const domain of domains) {
-      if (!DOMAIN_REGEX.test(domain)) {
+      if (!DOMAIN_REGEX.test(domain) && !IP_CIDR_REGEX.test(domain)) {
         errors.push(`Invalid domain format: ${domain}`);
       }
     }
@@ -463,6 +469,14 @@ export function EnvironmentSettingsDialog({
         updated = await tauri.renameEnvironment(environment.id, trimmedName);
       }
 
+      if (readOnly !== (environment.readOnly ?? false)) {
+        updated = await tauri.setEnvironmentReadOnly(environment.id, readOnly);
+      }
+
+      if (notes !== (environment.notes ?? "")) {
+        updated = await tauri.setEnvironmentNotes(environment.id, notes.trim() ? notes : null);
+      }
+
       // Update domains if not in full access mode
       const isFullAccess = (environment.networkAccessMode ?? "restricted") === "full";
       if (!isFullAccess) {
@@ -539,6 +553,26 @@ export function EnvironmentSettingsDialog({
               <Input id="env-name" value={name} onChange={handleNameChange} placeholder="Environment name" />
               {nameError && <p className="text-sm text-destructive">{nameError}</p>}
             </div>
+            <div className="flex items-center justify-between">
+              <div className="space-y-0.5">
+                <Label htmlFor="env-read-only">Read-only (review) mode</Label>
+                <p className="text-xs text-muted-foreground">
+                  Refuse file writes and PR merges, and open plain shells instead of launching an agent.
+                </p>
+              </div>
+              <Switch id="env-read-only" checked={readOnly} onCheckedChange={setReadOnly} />
+            </div>
+            <div className="space-y-2">
+              <Label htmlFor="env-notes">Notes</Label>
+              <Textarea
+                id="env-notes"
+                value={notes}
+                onChange={(e) => setNotes(e.target.value)}
+                placeholder="What you're working on, TODOs..."
+                rows={5}
+                maxLength={10240}
+              />
+            </div>
             {isLocalEnvironment && (
               <div className="space-y-4">
                 <Label>Environment Type</Label>
@@ -25,6 +25,8 @@ import type { Environment, DomainTestResult } from "@/types";
 
 // Domain validation regex
 const DOMAIN_REGEX = /^([a-zA-Z0-9]([a-zA-Z0-9-]*[a-zA-Z0-9])?\.)+[a-zA-Z]{2,}$/;
+// Raw IPv4 addresses and CIDR ranges (range checks are done by the backend)
+const IP_CIDR_REGEX = /^\d{1,3}(\.\d{1,3}){3}(\/\d{1,2})?$/;
 
 interface NetworkWhitelistDialogProps {
   open: boolean;
@@ -85,7 +87,7 @@ export function NetworkWhitelistDialog({
 
     const errors: string[] = [];
     for (const domain of domains) {
-      if (!DOMAIN_REGEX.test(domain)) {
+      if (!DOMAIN_REGEX.test(domain) && !IP_CIDR_REGEX.test(domain)) {
         errors.push(`Invalid domain format: ${domain}`);
       }
     }
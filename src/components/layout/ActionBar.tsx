@@ -577,6 +577,22 @@ export function ActionBar() {
     }
   }, [selectedEnvironment?.containerId, selectedEnvironmentId, prUrl, isLocalEnvironment, setEnvironmentPR]);
 
+  // Handler for closing or reopening a PR
+  const handleSetPrOpen = useCallback(async (open: boolean) => {
+    if (!selectedEnvironmentId) return;
+    try {
+      const updated = open
+        ? await tauri.reopenPullRequest(selectedEnvironmentId)
+        : await tauri.closePullRequest(selectedEnvironmentId);
+      updateEnvironment(updated.id, updated);
+      toast.success(open ? "PR reopened" : "PR closed");
+    } catch (err) {
+      console.error("[ActionBar] Failed to change PR state:", err);
+      const message = err instanceof Error ? err.message : typeof err === "string" ? err : "An unexpected error occurred";
+      toast.error(open ? "Failed to reopen PR" : "Failed to close PR", { description: message });
+    }
+  }, [selectedEnvironmentId, updateEnvironment]);
+
   // Get target branch for PR dialog
   const targetBranch = selectedProjectId
     ? config.repositories[selectedProjectId]?.prBaseBranch || "main"
@@ -1005,32 +1021,57 @@ export function ActionBar() {
 
           {selectedEnvironment && hasPR && (
             <>
-              <Tooltip>
-                <TooltipTrigger asChild>
-                  <Button
-                    variant={isPRFinished ? "secondary" : "outline"}
-                    size="sm"
-                    className="gap-2"
-                    onClick={viewPR}
-                  >
-                    {isPRMerged ? (
-                      <GitMerge className="h-4 w-4" />
-                    ) : isPRClosed ? (
-                      <GitPullRequestClosed className="h-4 w-4" />
-                    ) : (
-                      <ExternalLink className="h-4 w-4" />
-                    )}
-                    {isPRMerged ? "PR Merged" : isPRClosed ? "PR Closed" : "View PR"}
-                  </Button>
-                </TooltipTrigger>
-                <TooltipContent>
-                  {isPRMerged
-                    ? "PR has been merged - click to view"
-                    : isPRClosed
-                      ? "PR was closed without merging - click to view"
-                      : "Open PR in browser"}
-                </TooltipContent>
-              </Tooltip>
+              <ContextMenu>
+                <Tooltip>
+                  <ContextMenuTrigger asChild>
+                    <TooltipTrigger asChild>
+                      <span className="inline-flex" data-toolbar-custom-context-menu="true">
+                        <Button
+                          variant={isPRFinished ? "secondary" : "outline"}
+                          size="sm"
+                          className="gap-2"
+                          onClick={viewPR}
+                        >
+                          {isPRMerged ? (
+                            <GitMerge className="h-4 w-4" />
+                          ) : isPRClosed ? (
+                            <GitPullRequestClosed className="h-4 w-4" />
+                          ) : (
+                            <ExternalLink className="h-4 w-4" />
+                          )}
+                          {isPRMerged ? "PR Merged" : isPRClosed ? "PR Closed" : "View PR"}
+                        </Button>
+                      </span>
+                    </TooltipTrigger>
+                  </ContextMenuTrigger>
+                  <TooltipContent>
+                    {isPRMerged
+                      ? "PR has been merged - click to view"
+                      : isPRClosed
+                        ? "PR was closed without merging - click to view (right-click to reopen)"
+                        : "Open PR in browser (right-click to close)"}
+                  </TooltipContent>
+                </Tooltip>
+                <ContextMenuContent>
+                  {isPRClosed ? (
+                    <ContextMenuItem
+                      onClick={() => handleSetPrOpen(true)}
+                      disabled={!isRunning || !!selectedEnvironment?.readOnly}
+                    >
+                      <GitPullRequest className="mr-2 h-4 w-4" />
+                      Reopen PR
+                    </ContextMenuItem>
+                  ) : (
+                    <ContextMenuItem
+                      onClick={() => handleSetPrOpen(false)}
+                      disabled={isPRMerged || !isRunning || !!selectedEnvironment?.readOnly}
+                    >
+                      <GitPullRequestClosed className="mr-2 h-4 w-4" />
+                      Close PR
+                    </ContextMenuItem>
+                  )}
+                </ContextMenuContent>
+              </ContextMenu>
 
               {!isPRFinished && hasMergeConflicts === false && (
                 <Tooltip>
@@ -1040,7 +1081,7 @@ export function ActionBar() {
                       size="sm"
                       className="gap-2 bg-green-600 text-white hover:bg-green-700"
                       onClick={() => !isMerging && setMergeDialogOpen(true)}
-                      disabled={!isRunning || isMerging}
+                      disabled={!isRunning || isMerging || !!selectedEnvironment?.readOnly}
                     >
                       {isMerging ? (
                         <>
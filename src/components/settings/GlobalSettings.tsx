@@ -19,14 +19,18 @@ import { ClaudeIcon, CodexIcon, OpenCodeIcon } from "@/components/icons/AgentIco
 import { Textarea } from "@/components/ui/textarea";
 import { cn } from "@/lib/utils";
 import type {
+  AuthPreference,
   ClaudeMode,
   ClaudeNativeBackend,
   CodexMode,
+  ContainerEngineKind,
   DefaultAgent,
   DomainTestResult,
+  MenuKeybinding,
   OpenCodeMode,
   PreferredEditor,
   TerminalAppearance,
+  TerminalOverflowPolicy,
 } from "@/types";
 import {
   DEFAULT_TERMINAL_APPEARANCE,
@@ -38,6 +42,19 @@ import {
 
 // Domain validation regex
 const DOMAIN_REGEX = /^([a-zA-Z0-9]([a-zA-Z0-9-]*[a-zA-Z0-9])?\.)+[a-zA-Z]{2,}$/;
+// Raw IPv4 addresses and CIDR ranges (range checks are done by the backend)
+const IP_CIDR_REGEX = /^\d{1,3}(\.\d{1,3}){3}(\/\d{1,2})?$/;
+
+/** e.g. "Claude Pro, expires in 3 days" */
+function formatCredentialStatus(status: tauri.CredentialStatus): string {
+  const plan = status.subscriptionType
+    ? `Claude ${status.subscriptionType.charAt(0).toUpperCase()}${status.subscriptionType.slice(1)}`
+    : "Claude";
+  if (!status.expiresIn) return plan;
+  return status.expired
+    ? `${plan}, expired ${status.expiresIn} ago`
+    : `${plan}, expires in ${status.expiresIn}`;
+}
 
 interface GlobalSettingsProps {
   activeSection: string;
@@ -52,7 +69,13 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
   const [memoryGb, setMemoryGb] = useState(global.containerResources.memoryGb);
   const [envPatterns, setEnvPatterns] = useState(global.envFilePatterns.join(", "));
   const [anthropicApiKey, setAnthropicApiKey] = useState(global.anthropicApiKey || "");
+  const [authPreference, setAuthPreference] = useState<AuthPreference>(
+    global.authPreference || "auto"
+  );
   const [githubToken, setGithubToken] = useState(global.githubToken || "");
+  const [containerEngine, setContainerEngine] = useState<ContainerEngineKind>(
+    global.containerEngine || "auto"
+  );
   const [allowedDomains, setAllowedDomains] = useState(
     (global.allowedDomains || []).join("\n")
   );
@@ -92,6 +115,9 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
   const [terminalBackgroundColor, setTerminalBackgroundColor] = useState(
     global.terminalAppearance?.backgroundColor || DEFAULT_TERMINAL_APPEARANCE.backgroundColor
   );
+  const [terminalOverflowPolicy, setTerminalOverflowPolicy] = useState<TerminalOverflowPolicy>(
+    global.terminalOverflowPolicy || "block"
+  );
   const [terminalScrollback, setTerminalScrollback] = useState(
     typeof global.terminalScrollback === "number"
       ? global.terminalScrollback
@@ -102,6 +128,9 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
   );
   const [debugLogging, setDebugLogging] = useState(global.debugLogging ?? false);
   const [logDirectory, setLogDirectory] = useState<string | null>(null);
+  const [credentialStatus, setCredentialStatus] = useState<tauri.CredentialStatus | null>(null);
+  const [diagnostics, setDiagnostics] = useState<tauri.DiagnosticsReport | null>(null);
+  const [isRunningDiagnostics, setIsRunningDiagnostics] = useState(false);
   const [showApiKey, setShowApiKey] = useState(false);
   const [showGithubToken, setShowGithubToken] = useState(false);
   const [isSaving, setIsSaving] = useState(false);
@@ -118,7 +147,9 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
     setMemoryGb(global.containerResources.memoryGb);
     setEnvPatterns(global.envFilePatterns.join(", "));
     setAnthropicApiKey(global.anthropicApiKey || "");
+    setAuthPreference(global.authPreference || "auto");
     setGithubToken(global.githubToken || "");
+    setContainerEngine(global.containerEngine || "auto");
     setAllowedDomains((global.allowedDomains || []).join("\n"));
     setPreferredEditor(global.preferredEditor || "vscode");
     setDefaultAgent(global.defaultAgent || "claude");
@@ -137,6 +168,7 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
         ? global.terminalScrollback
         : DEFAULT_TERMINAL_SCROLLBACK
     );
+    setTerminalOverflowPolicy(global.terminalOverflowPolicy || "block");
     setExperimentalCodexRawEventLogging(global.experimentalCodexRawEventLogging ?? true);
     setDebugLogging(global.debugLogging ?? false);
   }, [global]);
@@ -146,6 +178,11 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
     tauri.getLogDirectory().then(setLogDirectory).catch(() => {});
   }, []);
 
+  // Fetch keychain credential status once on mount
+  useEffect(() => {
+    tauri.getCredentialStatus().then(setCredentialStatus).catch(() => {});
+  }, []);
+
   // Check for changes
   useEffect(() => {
     const terminalAppearance = global.terminalAppearance || DEFAULT_TERMINAL_APPEARANCE;
@@ -154,7 +191,9 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
       memoryGb !== global.containerResources.memoryGb ||
       envPatterns !== global.envFilePatterns.join(", ") ||
       anthropicApiKey !== (global.anthropicApiKey || "") ||
+      authPreference !== (global.authPreference || "auto") ||
       githubToken !== (global.githubToken || "") ||
+      containerEngine !== (global.containerEngine || "auto") ||
       allowedDomains !== (global.allowedDomains || []).join("\n") ||
       preferredEditor !== (global.preferredEditor || "vscode") ||
       defaultAgent !== (global.defaultAgent || "claude") ||
@@ -169,13 +208,14 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
       terminalFontSize !== terminalAppearance.fontSize ||
       terminalBackgroundColor !== terminalAppearance.backgroundColor ||
       terminalScrollback !== (global.terminalScrollback ?? DEFAULT_TERMINAL_SCROLLBACK) ||
+      terminalOverflowPolicy !== (global.terminalOverflowPolicy || "block") ||
       experimentalCodexRawEventLogging !== (global.experimentalCodexRawEventLogging ?? true) ||
       debugLogging !== (global.debugLogging ?? false);
     setHasChanges(changed);
     if (changed) {
       setSaveSuccess(false);
     }
-  }, [cpuCores, memoryGb, envPatterns, anthropicApiKey, githubToken, allowedDomains, preferredEditor, defaultAgent, opencodeModel, opencodeMode, claudeMode, claudeNativeBackend, claudeNativeFastModeDefault, codexMode, codexNativeFastModeDefault, terminalFontFamily, terminalFontSize, terminalBackgroundColor, terminalScrollback, experimentalCodexRawEventLogging, debugLogging, global]);
+  }, [cpuCores, memoryGb, envPatterns, anthropicApiKey, authPreference, githubToken, containerEngine, allowedDomains, preferredEditor, defaultAgent, opencodeModel, opencodeMode, claudeMode, claudeNativeBackend, claudeNativeFastModeDefault, codexMode, codexNativeFastModeDefault, terminalFontFamily, terminalFontSize, terminalBackgroundColor, terminalScrollback, terminalOverflowPolicy, experimentalCodexRawEventLogging, debugLogging, global]);
 
   // Validate domains on change
   const validateDomainsLocally = useCallback((domainsText: string) => {
@@ -186,7 +226,7 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
 
     const errors: string[] = [];
     for (const domain of domains) {
-      if (!DOMAIN_REGEX.test(domain)) {
+      if (!DOMAIN_REGEX.test(domain) && !IP_CIDR_REGEX.test(domain)) {
         errors.push(`Invalid domain format: ${domain}`);
       }
     }
@@ -230,6 +270,18 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
     }
   };
 
+  const handleRunDiagnostics = async () => {
+    setIsRunningDiagnostics(true);
+    try {
+      setDiagnostics(await tauri.runDiagnostics());
+    } catch (err) {
+      console.error("[settings] Failed to run diagnostics:", err);
+      toast.error("Failed to run diagnostics");
+    } finally {
+      setIsRunningDiagnostics(false);
+    }
+  };
+
   const handleSave = async () => {
     setIsSaving(true);
     try {
@@ -248,6 +300,7 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
         envFilePatterns: string[];
         allowedDomains: string[];
         anthropicApiKey?: string;
+        authPreference: AuthPreference;
         githubToken?: string;
         preferredEditor?: PreferredEditor;
         defaultAgent: DefaultAgent;
@@ -263,12 +316,27 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
         codexNativeFastModeDefault: boolean;
         terminalAppearance: TerminalAppearance;
         terminalScrollback: number;
+        terminalChannelCapacity?: number;
+        terminalOverflowPolicy: TerminalOverflowPolicy;
+        terminalBufferMaxBytes?: number;
+        terminalBufferAutosaveSecs?: number;
+        minFreeDiskGb?: number;
+        blockOnLowDiskSpace?: boolean;
+        blockOnExcessResourceLimits?: boolean;
+        configSaveDebounceMs?: number;
         experimentalCodexRawEventLogging: boolean;
         debugLogging: boolean;
+        logBufferLines?: number;
+        keybindings?: MenuKeybinding[];
+        containerEngine: ContainerEngineKind;
+        nativeServerHost?: string;
+        pinnedContainers?: string[];
+        pinnedImages?: string[];
       } = {
         containerResources: { cpuCores, memoryGb },
         envFilePatterns: patterns,
         allowedDomains: domains,
+        authPreference,
         preferredEditor,
         defaultAgent,
         opencodeModel,
@@ -287,8 +355,22 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
           backgroundColor: terminalBackgroundColor,
         },
         terminalScrollback,
+        terminalChannelCapacity: global.terminalChannelCapacity,
+        terminalOverflowPolicy,
+        terminalBufferMaxBytes: global.terminalBufferMaxBytes,
+        terminalBufferAutosaveSecs: global.terminalBufferAutosaveSecs,
+        minFreeDiskGb: global.minFreeDiskGb,
+        blockOnLowDiskSpace: global.blockOnLowDiskSpace,
+        blockOnExcessResourceLimits: global.blockOnExcessResourceLimits,
+        configSaveDebounceMs: global.configSaveDebounceMs,
         experimentalCodexRawEventLogging,
         debugLogging,
+        logBufferLines: global.logBufferLines,
+        keybindings: global.keybindings,
+        containerEngine,
+        nativeServerHost: global.nativeServerHost,
+        pinnedContainers: global.pinnedContainers,
+        pinnedImages: global.pinnedImages,
       };
 
       if (anthropicApiKey) newGlobal.anthropicApiKey = anthropicApiKey;
@@ -331,7 +413,9 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
     setMemoryGb(global.containerResources.memoryGb);
     setEnvPatterns(global.envFilePatterns.join(", "));
     setAnthropicApiKey(global.anthropicApiKey || "");
+    setAuthPreference(global.authPreference || "auto");
     setGithubToken(global.githubToken || "");
+    setContainerEngine(global.containerEngine || "auto");
     setAllowedDomains((global.allowedDomains || []).join("\n"));
     setPreferredEditor(global.preferredEditor || "vscode");
     setDefaultAgent(global.defaultAgent || "claude");
@@ -350,6 +434,7 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
         ? global.terminalScrollback
         : DEFAULT_TERMINAL_SCROLLBACK
     );
+    setTerminalOverflowPolicy(global.terminalOverflowPolicy || "block");
     setExperimentalCodexRawEventLogging(global.experimentalCodexRawEventLogging ?? true);
     setDebugLogging(global.debugLogging ?? false);
   };
@@ -645,6 +730,24 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
         "Claude",
       )}
 
+      {/* Keychain credentials */}
+      {credentialStatus?.available && (
+        <div className="space-y-1">
+          <h3 className="text-sm font-medium text-foreground flex items-center gap-2">
+            <ClaudeIcon />
+            Claude Account
+          </h3>
+          <p className="text-xs text-muted-foreground">
+            {formatCredentialStatus(credentialStatus)}
+          </p>
+          {credentialStatus.rateLimitTier && (
+            <p className="text-xs text-muted-foreground/60">
+              Rate limit tier: {credentialStatus.rateLimitTier}
+            </p>
+          )}
+        </div>
+      )}
+
       {/* Anthropic API Key */}
       <div className="space-y-3">
         <div>
@@ -679,6 +782,38 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
           </a>
         </p>
       </div>
+
+      {/* Auth Preference */}
+      <div className="space-y-3">
+        <div>
+          <h3 className="text-sm font-medium text-foreground">Container Authentication</h3>
+          <p className="text-xs text-muted-foreground mt-1">
+            Which credential new containers use for Claude
+          </p>
+        </div>
+        <div className="grid grid-cols-3 gap-2">
+          {([
+            { value: "auto", label: "Auto", hint: "Keychain login when available, plus API key" },
+            { value: "oauth", label: "Claude Account", hint: "Keychain login only" },
+            { value: "api_key", label: "API Key", hint: "API key only; start fails without one" },
+          ] as const).map((opt) => (
+            <button
+              key={opt.value}
+              type="button"
+              onClick={() => setAuthPreference(opt.value)}
+              className={cn(
+                "p-3 rounded-lg border-2 text-left transition-colors",
+                authPreference === opt.value
+                  ? "border-primary bg-primary/5"
+                  : "border-transparent bg-zinc-900 hover:border-zinc-600",
+              )}
+            >
+              <div className="text-sm font-medium">{opt.label}</div>
+              <div className="text-xs text-muted-foreground mt-1">{opt.hint}</div>
+            </button>
+          ))}
+        </div>
+      </div>
     </div>
   );
 
@@ -762,6 +897,25 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
           </p>
         </div>
 
+        {/* Output Overflow */}
+        <div className="space-y-3">
+          <Label>When Output Outpaces Rendering</Label>
+          <Select
+            value={terminalOverflowPolicy}
+            onValueChange={(value: TerminalOverflowPolicy) => setTerminalOverflowPolicy(value)}
+          >
+            <SelectTrigger className="max-w-xs"><SelectValue /></SelectTrigger>
+            <SelectContent>
+              <SelectItem value="block">Pause output</SelectItem>
+              <SelectItem value="drop-oldest">Drop oldest output</SelectItem>
+            </SelectContent>
+          </Select>
+          <p className="text-xs text-muted-foreground">
+            Pausing keeps every byte but can stall very verbose commands. Dropping keeps the terminal responsive and
+            marks skipped output with [output truncated]. Applies to newly opened terminals.
+          </p>
+        </div>
+
         {/* Background Color */}
         <div className="space-y-3">
           <Label>Background Color</Label>
@@ -891,6 +1045,27 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
 
   const renderContainer = () => (
     <div className="max-w-2xl space-y-8">
+      <div className="space-y-3">
+        <div>
+          <Label className="text-sm">Container Engine</Label>
+          <p className="text-xs text-muted-foreground mt-1">
+            Podman is used through its Docker-compatible API socket
+          </p>
+        </div>
+        <Select
+          value={containerEngine}
+          onValueChange={(value) => setContainerEngine(value as ContainerEngineKind)}
+        >
+          <SelectTrigger className="w-full max-w-xs">
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            <SelectItem value="auto">Auto-detect</SelectItem>
+            <SelectItem value="docker">Docker</SelectItem>
+            <SelectItem value="podman">Podman</SelectItem>
+          </SelectContent>
+        </Select>
+      </div>
       <div className="space-y-3">
         <div className="flex justify-between max-w-xs">
           <Label className="text-sm">CPU Cores</Label>
@@ -974,6 +1149,75 @@ export function GlobalSettings({ activeSection, onSaveSuccess }: GlobalSettingsP
       <p className="text-xs text-muted-foreground/60">
         Requires app restart to take effect
       </p>
+
+      <div className="pt-4">
+        <h3 className="text-sm font-medium text-foreground">App Data</h3>
+        <p className="text-xs text-muted-foreground mt-1">
+          Open the directory holding projects, environments and config files
+        </p>
+      </div>
+      <div className="flex gap-2">
+        <Button
+          variant="outline"
+          size="sm"
+          onClick={() => {
+            tauri.openDataDir().catch((err) => toast.error("Failed to open data directory", { description: String(err) }));
+          }}
+        >
+          <FolderOpen className="h-4 w-4 mr-2" />
+          Open Data Directory
+        </Button>
+        <Button
+          variant="outline"
+          size="sm"
+          onClick={() => {
+            tauri.openConfigFile().catch((err) => toast.error("Failed to reveal config file", { description: String(err) }));
+          }}
+        >
+          <FolderOpen className="h-4 w-4 mr-2" />
+          Reveal config.json
+        </Button>
+      </div>
+
+      <div className="pt-4">
+        <h3 className="text-sm font-medium text-foreground">Diagnostics</h3>
+        <p className="text-xs text-muted-foreground mt-1">
+          Check Docker, the base image, CLIs, credentials and network access
+        </p>
+      </div>
+      <Button
+        variant="outline"
+        size="sm"
+        onClick={handleRunDiagnostics}
+        disabled={isRunningDiagnostics}
+      >
+        {isRunningDiagnostics && <Loader2 className="h-4 w-4 mr-2 animate-spin" />}
+        Run Diagnostics
+      </Button>
+      {diagnostics && (
+        <div className="space-y-2">
+          {diagnostics.checks.map((check) => (
+            <div key={check.id} className="flex items-start gap-2 text-sm">
+              {check.status === "pass" ? (
+                <CheckCircle2 className="h-4 w-4 mt-0.5 shrink-0 text-green-500" />
+              ) : check.status === "warn" ? (
+                <AlertCircle className="h-4 w-4 mt-0.5 shrink-0 text-yellow-500" />
+              ) : (
+                <XCircle className="h-4 w-4 mt-0.5 shrink-0 text-red-500" />
+              )}
+              <div className="min-w-0">
+                <div>
+                  <span className="font-medium">{check.label}</span>
+                  <span className="text-muted-foreground"> — {check.detail}</span>
+                </div>
+                {check.remediation && (
+                  <p className="text-xs text-muted-foreground/80">{check.remediation}</p>
+                )}
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
     </div>
   );
 
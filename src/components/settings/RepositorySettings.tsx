@@ -3,6 +3,7 @@ import { toast } from "sonner";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
 import { ClaudeIcon, CodexIcon, OpenCodeIcon } from "@/components/icons/AgentIcons";
 import {
   Select,
@@ -24,7 +25,7 @@ import { CODEX_MODELS } from "@/lib/codex-client";
 import { Loader2, Network, Plus, Trash2, FolderOpen, ExternalLink, FileText, Bot, Settings2, GitBranch } from "lucide-react";
 import { FullscreenSettingsLayout, type SettingsMenuItem } from "./FullscreenSettingsLayout";
 import { open as openDialog } from "@tauri-apps/plugin-dialog";
-import type { Project, RepositoryConfig, PortMapping, PortProtocol, DefaultAgent, AgentStyle, ClaudeNativeBackend } from "@/types";
+import type { Project, RepositoryConfig, PortMapping, PortProtocol, DefaultAgent, AgentStyle, ClaudeNativeBackend, RestartPolicy } from "@/types";
 
 interface RepositorySettingsProps {
   project: Project;
@@ -108,6 +109,10 @@ export function RepositorySettings({
   const [projectClaudeNativeBackend, setProjectClaudeNativeBackend] = useState<string>(
     initialConfig.claudeNativeBackend ?? APP_DEFAULT,
   );
+  const [restartPolicy, setRestartPolicy] = useState<RestartPolicy>(initialConfig.restartPolicy ?? "no");
+  const [composeEnabled, setComposeEnabled] = useState(initialConfig.composeEnabled ?? false);
+  const [workspacePath, setWorkspacePath] = useState(initialConfig.workspacePath ?? "");
+  const [dockerNetwork, setDockerNetwork] = useState(initialConfig.dockerNetwork ?? "");
   const [isSaving, setIsSaving] = useState(false);
 
   // Reset form when project changes or dialog opens
@@ -130,6 +135,10 @@ export function RepositorySettings({
       setProjectDefaultAgent(config.defaultAgent ?? APP_DEFAULT);
       setProjectAgentStyle(config.agentStyle ?? APP_DEFAULT);
       setProjectClaudeNativeBackend(config.claudeNativeBackend ?? APP_DEFAULT);
+      setRestartPolicy(config.restartPolicy ?? "no");
+      setComposeEnabled(config.composeEnabled ?? false);
+      setWorkspacePath(config.workspacePath ?? "");
+      setDockerNetwork(config.dockerNetwork ?? "");
     }
   }, [open, project.id, project.name, project.localPath, getRepositoryConfig]);
 
@@ -343,6 +352,12 @@ export function RepositorySettings({
           projectClaudeNativeBackend !== APP_DEFAULT
             ? (projectClaudeNativeBackend as ClaudeNativeBackend)
             : undefined,
+        restartPolicy: restartPolicy !== "no" ? restartPolicy : undefined,
+        composeEnabled,
+        workspacePath: workspacePath.trim() || undefined,
+        dockerNetwork: dockerNetwork.trim() || undefined,
+        defaultSessions: currentRepoConfig?.defaultSessions,
+        cloneDepth: currentRepoConfig?.cloneDepth,
       };
 
       // Update backend
@@ -354,6 +369,18 @@ export function RepositorySettings({
 
       toast.success("Settings saved");
       onOpenChange(false);
+
+      // Warn about branches missing on the remote without blocking the save
+      tauri
+        .validateRepositoryBranches(project.id)
+        .then((missing) => {
+          if (missing.length > 0) {
+            toast.warning("Branches not found on the remote", {
+              description: `${missing.join(", ")} ${missing.length === 1 ? "does" : "do"} not exist on the remote; pull requests may fail.`,
+            });
+          }
+        })
+        .catch((err) => console.warn("Failed to validate repository branches:", err));
     } catch (err) {
       console.error("Failed to save settings:", err);
       const message = err instanceof Error ? err.message : "Failed to save settings";
@@ -381,6 +408,10 @@ export function RepositorySettings({
     setProjectDefaultAgent(config.defaultAgent ?? APP_DEFAULT);
     setProjectAgentStyle(config.agentStyle ?? APP_DEFAULT);
     setProjectClaudeNativeBackend(config.claudeNativeBackend ?? APP_DEFAULT);
+    setRestartPolicy(config.restartPolicy ?? "no");
+    setComposeEnabled(config.composeEnabled ?? false);
+    setWorkspacePath(config.workspacePath ?? "");
+    setDockerNetwork(config.dockerNetwork ?? "");
     onOpenChange(false);
   };
 
@@ -631,6 +662,39 @@ export function RepositorySettings({
       case "ports":
         return (
           <div className="max-w-2xl space-y-6">
+            <div className="space-y-2">
+              <Label htmlFor="workspacePath">Container Workspace Path</Label>
+              <Input
+                id="workspacePath"
+                value={workspacePath}
+                onChange={(e) => setWorkspacePath(e.target.value)}
+                placeholder="/workspace"
+                className="max-w-[300px] font-mono"
+                disabled={isSaving}
+              />
+              <p className="text-xs text-muted-foreground">
+                Absolute path the repository is cloned to inside new containers. Leave empty to use /workspace.
+                Existing containers keep the path they were created with.
+              </p>
+            </div>
+
+            <div className="space-y-2">
+              <Label htmlFor="dockerNetwork">Docker Network</Label>
+              <Input
+                id="dockerNetwork"
+                value={dockerNetwork}
+                onChange={(e) => setDockerNetwork(e.target.value)}
+                placeholder="bridge"
+                className="max-w-[300px] font-mono"
+                disabled={isSaving}
+              />
+              <p className="text-xs text-muted-foreground">
+                Network new containers join, e.g. to reach a shared database container by name. It is created if it
+                doesn't exist. Port mappings and the entry port are still published to your machine. Leave empty to
+                use the default bridge network.
+              </p>
+            </div>
+
             <div className="space-y-2">
               <Label htmlFor="entryPort">Container Entry Port</Label>
               <Input
@@ -651,6 +715,31 @@ export function RepositorySettings({
               </p>
             </div>
 
+            <div className="space-y-2">
+              <Label htmlFor="restartPolicy">Container Restart Policy</Label>
+              <Select value={restartPolicy} onValueChange={(value: RestartPolicy) => setRestartPolicy(value)} disabled={isSaving}>
+                <SelectTrigger id="restartPolicy" className="max-w-[200px]"><SelectValue /></SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="no">No</SelectItem>
+                  <SelectItem value="on-failure">On Failure</SelectItem>
+                  <SelectItem value="unless-stopped">Unless Stopped</SelectItem>
+                </SelectContent>
+              </Select>
+              <p className="text-xs text-muted-foreground">
+                Whether Docker should automatically restart containers that crash. Applies to newly created containers.
+              </p>
+            </div>
+
+            <div className="flex items-start justify-between gap-4">
+              <div className="space-y-1">
+                <Label htmlFor="composeEnabled">Docker Compose</Label>
+                <p className="text-xs text-muted-foreground">
+                  Allow starting this repository's compose services (docker-compose.yml / compose.yaml) per environment.
+                </p>
+              </div>
+              <Switch id="composeEnabled" checked={composeEnabled} onCheckedChange={setComposeEnabled} disabled={isSaving} />
+            </div>
+
             <div className="border-t border-border pt-4 space-y-4">
               <Label>Additional Port Mappings</Label>
               <p className="text-xs text-muted-foreground">These port mappings will be pre-filled when creating new environments for this repository.</p>
@@ -33,10 +33,11 @@ import {
 } from "@/components/ui/alert-dialog";
 import { useProjects } from "@/hooks/useProjects";
 import { useEnvironments } from "@/hooks/useEnvironments";
-import { useUIStore, useClaudeOptionsStore, useConfigStore } from "@/stores";
+import { useUIStore, useClaudeOptionsStore, useConfigStore, compareEnvironments } from "@/stores";
 import { RepositorySettings } from "@/components/settings/RepositorySettings";
 import { updateEnvironmentAgentSettings } from "@/lib/tauri";
 import { useEnvironmentDiffStats } from "@/hooks/useEnvironmentDiffStats";
+import { useMenuAction } from "@/hooks/useMenuAction";
 import type { Environment, Project } from "@/types";
 
 export function HierarchicalSidebar() {
@@ -66,6 +67,7 @@ export function HierarchicalSidebar() {
   } = useEnvironments(null);
 
   const {
+    selectedProjectId,
     selectedEnvironmentId,
     selectProject,
     selectProjectAndEnvironment,
@@ -150,7 +152,7 @@ export function HierarchicalSidebar() {
     (projectId: string): Environment[] => {
       return allEnvironments
         .filter((e) => e.projectId === projectId)
-        .sort((a, b) => a.order - b.order);
+        .sort(compareEnvironments);
     },
     [allEnvironments]
   );
@@ -264,6 +266,13 @@ export function HierarchicalSidebar() {
     setShowCreateEnvDialog(true);
   };
 
+  // "new_environment" menu keybinding opens the create dialog for the selected project
+  useMenuAction("new_environment", () => {
+    if (selectedProjectId) {
+      handleOpenCreateEnvDialog(selectedProjectId);
+    }
+  });
+
   const handleCreateEnvironment = async (options: ClaudeOptions) => {
     if (!createEnvProjectId) return;
 
@@ -10,7 +10,7 @@ import { useAgentActivityStore } from "@/stores/agentActivityStore";
 import { useSessionStore } from "@/stores/sessionStore";
 import { useTerminalPortalStore, createTerminalKey, type PersistentTerminalData } from "@/stores/terminalPortalStore";
 import { cn } from "@/lib/utils";
-import { setSessionHasLaunchedCommand } from "@/lib/tauri";
+import { setSessionHasLaunchedCommand, setSessionTerminalId } from "@/lib/tauri";
 import type { TabType } from "@/contexts";
 import {
   DEFAULT_TERMINAL_APPEARANCE,
@@ -416,6 +416,15 @@ export function PersistentTerminal({
   // Determine user based on tab type - root tabs connect as orkroot
   const terminalUser = tabType === "root" ? ROOT_TERMINAL_USER : undefined;
 
+  // Redraw the output saved before a container restart once the session is reattached
+  const handleReattach = useCallback(
+    (savedOutput: Uint8Array) => {
+      terminal.reset();
+      terminal.write(savedOutput);
+    },
+    [terminal]
+  );
+
   const { sessionId, isConnected, isConnecting, connect, resize, write } =
     useTerminal({
       containerId,
@@ -425,6 +434,7 @@ export function PersistentTerminal({
       existingSessionId,
       persistSession: true,
       user: terminalUser,
+      onReattach: handleReattach,
     });
 
   // Keep connect ref up to date to avoid stale closures in effects
@@ -537,6 +547,11 @@ export function PersistentTerminal({
       persistentSessionCreatedRef.current = true;
       persistentSessionIdRef.current = existingPersistentSession.id;
       setPersistentSessionId(sessionKey, existingPersistentSession.id);
+      if (existingPersistentSession.terminalSessionId !== sessionId) {
+        setSessionTerminalId(existingPersistentSession.id, sessionId).catch((err) => {
+          console.error("[PersistentTerminal] Failed to link terminal session:", err);
+        });
+      }
       if (existingPersistentSession.status === "disconnected") {
         updateSessionStatus(existingPersistentSession.id, "connected").catch((err) => {
           console.error("[PersistentTerminal] Failed to update session status:", err);
@@ -559,6 +574,7 @@ export function PersistentTerminal({
           persistentSessionIdRef.current = session.id;
           persistentSessionCreatedRef.current = true;
           setPersistentSessionId(sessionKey, session.id);
+          return setSessionTerminalId(session.id, sessionId);
         })
         .catch((err) => {
           console.error("[PersistentTerminal] Failed to create persistent session:", err);
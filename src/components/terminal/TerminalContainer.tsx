@@ -508,7 +508,9 @@ export function TerminalContainer({
 
     if (currentTabs.length === 0) {
       const pendingAttachments = claudeOptions?.initialPromptAttachments ?? [];
-      if (claudeOptions?.launchAgent && pendingAttachments.length > 0) {
+      // Read-only (review) environments open a plain shell instead of launching an agent
+      const shouldLaunchAgent = (claudeOptions?.launchAgent ?? false) && !environment?.readOnly;
+      if (shouldLaunchAgent && pendingAttachments.length > 0) {
         if (!isSavingInitialPromptAttachmentsRef.current) {
           isSavingInitialPromptAttachmentsRef.current = true;
           void (async () => {
@@ -551,7 +553,7 @@ export function TerminalContainer({
       // Determine initial tab type based on agent options
       let initialTabType: TerminalTabType = "plain";
       let pendingInitialPrompt: string | undefined;
-      const launchAgent = claudeOptions?.launchAgent ?? false;
+      const launchAgent = shouldLaunchAgent;
       if (launchAgent) {
         initialTabType = claudeOptions!.agentType;
         hasAppliedClaudeOptionsRef.current = true;
@@ -727,7 +729,7 @@ export function TerminalContainer({
         addTab("default", initialTab, environmentId);
       }
     }
-  }, [isEnvironmentRunning, containerId, isLocalEnvironmentReady, isLocalEnvironment, setupCommandsResolved, claudeOptions, initialize, addTab, environmentId, currentEnvState, opencodeMode, claudeMode, claudeNativeBackend, codexMode, setWorkspaceReady, consumePendingSetupCommands, setSetupScriptsRunning, setPendingNativeLaunch, setOptions, worktreePath]);
+  }, [isEnvironmentRunning, containerId, isLocalEnvironmentReady, isLocalEnvironment, setupCommandsResolved, claudeOptions, initialize, addTab, environmentId, currentEnvState, opencodeMode, claudeMode, claudeNativeBackend, codexMode, setWorkspaceReady, consumePendingSetupCommands, setSetupScriptsRunning, setPendingNativeLaunch, setOptions, worktreePath, environment?.readOnly]);
 
   // Reset pane layout when container changes within the same environment
   // (e.g., container was stopped and restarted with a new ID)
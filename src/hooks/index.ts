@@ -15,3 +15,4 @@ export type { PastedImageAttachment } from "./useNativeComposeBarPaste";
 export { useElapsedTimer } from "./useElapsedTimer";
 export { useFileSearch } from "./useFileSearch";
 export { useFileMentions } from "./useFileMentions";
+export { useMenuAction } from "./useMenuAction";
@@ -5,7 +5,7 @@ import { toast } from "sonner";
 import { useConfigStore, useEnvironmentStore, useErrorDialogStore } from "@/stores";
 import { useSessionStore } from "@/stores/sessionStore";
 import * as tauri from "@/lib/tauri";
-import type { EnvironmentType, NetworkAccessMode, PortMapping, PrState } from "@/types";
+import type { EnvironmentType, NetworkAccessMode, PortMapping, PrState, StartProgressStage } from "@/types";
 
 /**
  * Extract error message from various error types.
@@ -39,6 +39,46 @@ interface EnvironmentRenamedPayload {
   new_branch: string;
 }
 
+/** Payload emitted when an environment's branch is switched */
+interface EnvironmentBranchChangedPayload {
+  environment_id: string;
+  branch: string;
+}
+
+/** Payload emitted when background naming starts for an environment */
+interface EnvironmentNamingStartedPayload {
+  environment_id: string;
+}
+
+/** Payload emitted when background naming finishes for an environment */
+interface EnvironmentNamingFinishedPayload {
+  environment_id: string;
+  outcome: "renamed" | "cancelled" | "skipped" | "failed";
+}
+
+/** Payload emitted when an in-flight environment start is cancelled */
+interface EnvironmentStartCancelledPayload {
+  environment_id: string;
+  message: string;
+}
+
+/** Payload emitted as an environment start moves through its steps */
+interface EnvironmentStartProgressPayload {
+  environment_id: string;
+  stage: StartProgressStage;
+}
+
+/** Payload emitted after a project's environments were saved by the backend */
+interface EnvironmentsInvalidatedPayload {
+  project_id: string;
+}
+
+/** Invalidations for a project within this window are coalesced into one reload */
+const INVALIDATION_DEBOUNCE_MS = 150;
+
+/** Error returned by start_environment when the start was cancelled */
+const START_CANCELLED_MESSAGE = "Environment start was cancelled";
+
 interface UseEnvironmentsOptions {
   listenForRenameEvents?: boolean;
 }
@@ -90,8 +130,68 @@ export function useEnvironments(
     }
 
     let unlisten: UnlistenFn | null = null;
+    let unlistenNamingStarted: UnlistenFn | null = null;
+    let unlistenNamingFinished: UnlistenFn | null = null;
+    let unlistenStartCancelled: UnlistenFn | null = null;
+    let unlistenStartProgress: UnlistenFn | null = null;
+    let unlistenBranchChanged: UnlistenFn | null = null;
+    let unlistenInvalidated: UnlistenFn | null = null;
+    const pendingReloads = new Map<string, ReturnType<typeof setTimeout>>();
+    const { setNaming, updateEnvironmentStatus, setStartProgress } = useEnvironmentStore.getState();
 
     const setupListener = async () => {
+      unlistenInvalidated = await listen<EnvironmentsInvalidatedPayload>(
+        "environments-invalidated",
+        (event) => {
+          const pid = event.payload.project_id;
+          const pending = pendingReloads.get(pid);
+          if (pending) clearTimeout(pending);
+          pendingReloads.set(
+            pid,
+            setTimeout(() => {
+              pendingReloads.delete(pid);
+              // Reload quietly (no loading state) so the list doesn't flash
+              tauri
+                .getEnvironments(pid)
+                .then((envs) => useEnvironmentStore.getState().mergeEnvironmentsForProject(pid, envs))
+                .catch((err) => {
+                  console.warn("[useEnvironments] Failed to reload invalidated environments:", err);
+                });
+            }, INVALIDATION_DEBOUNCE_MS)
+          );
+        }
+      );
+      unlistenNamingStarted = await listen<EnvironmentNamingStartedPayload>(
+        "environment-naming-started",
+        (event) => setNaming(event.payload.environment_id, true)
+      );
+      unlistenNamingFinished = await listen<EnvironmentNamingFinishedPayload>(
+        "environment-naming-finished",
+        (event) => setNaming(event.payload.environment_id, false)
+      );
+      unlistenStartCancelled = await listen<EnvironmentStartCancelledPayload>(
+        "environment-start-cancelled",
+        (event) => updateEnvironmentStatus(event.payload.environment_id, "stopped")
+      );
+      unlistenStartProgress = await listen<EnvironmentStartProgressPayload>(
+        "environment-start-progress",
+        (event) => setStartProgress(event.payload.environment_id, event.payload.stage)
+      );
+      unlistenBranchChanged = await listen<EnvironmentBranchChangedPayload>(
+        "environment-branch-changed",
+        (event) => {
+          const { environment_id, branch } = event.payload;
+          // PR state belongs to the previous branch
+          const currentEnv = useEnvironmentStore.getState().getEnvironmentById(environment_id);
+          if (currentEnv && currentEnv.branch !== branch && currentEnv.prUrl) {
+            tauri.clearEnvironmentPr(environment_id).catch((err) => {
+              console.warn("[useEnvironments] Failed to clear PR state after branch switch:", err);
+            });
+            setPRInStore(environment_id, null, null, null);
+          }
+          updateEnvironmentInStore(environment_id, { branch });
+        }
+      );
       unlisten = await listen<EnvironmentRenamedPayload>("environment-renamed", (event) => {
         console.log("[useEnvironments] Received environment-renamed event:", event.payload);
         const { environment_id, new_name, new_branch } = event.payload;
@@ -124,6 +224,13 @@ export function useEnvironments(
       if (unlisten) {
         unlisten();
       }
+      unlistenNamingStarted?.();
+      unlistenNamingFinished?.();
+      unlistenStartCancelled?.();
+      unlistenStartProgress?.();
+      unlistenBranchChanged?.();
+      unlistenInvalidated?.();
+      pendingReloads.forEach((timer) => clearTimeout(timer));
     };
   }, [listenForRenameEvents, updateEnvironmentInStore, setPRInStore]);
 
@@ -145,11 +252,11 @@ export function useEnvironments(
   );
 
   const createEnvironment = useCallback(
-    async (pid: string, name?: string, networkAccessMode?: NetworkAccessMode, initialPrompt?: string, portMappings?: PortMapping[], environmentType?: EnvironmentType) => {
+    async (pid: string, name?: string, networkAccessMode?: NetworkAccessMode, initialPrompt?: string, portMappings?: PortMapping[], environmentType?: EnvironmentType, baseRef?: string) => {
       setLoading(true);
       setError(null);
       try {
-        const environment = await tauri.createEnvironment(pid, name, networkAccessMode, initialPrompt, portMappings, environmentType);
+        const environment = await tauri.createEnvironment(pid, name, networkAccessMode, initialPrompt, portMappings, environmentType, baseRef);
         addEnvironmentToStore(environment);
         useConfigStore.getState().setRepositoryLastEnvironmentType(pid, environment.environmentType);
         toast.success("Environment created");
@@ -248,6 +355,13 @@ export function useEnvironments(
           setPendingSetupCommands(environmentId, result.setupCommands);
         }
 
+        for (const warning of result.diskSpaceWarnings ?? []) {
+          toast.warning("Low disk space", { description: warning.message });
+        }
+        for (const warning of result.resourceLimitWarnings ?? []) {
+          toast.warning("Resource limits exceed host capacity", { description: warning.message });
+        }
+
         // Refresh the full environment data (including containerId / worktreePath)
         const updatedEnv = await tauri.getEnvironment(environmentId);
         if (updatedEnv) {
@@ -276,8 +390,14 @@ export function useEnvironments(
         if (isLocal) {
           setSetupCommandsResolved(environmentId, true);
         }
-        console.error("[useEnvironments] Error starting environment:", err);
         const message = getErrorMessage(err, "Failed to start environment");
+        if (message === START_CANCELLED_MESSAGE) {
+          console.info("[useEnvironments] Environment start cancelled:", environmentId);
+          updateStatusInStore(environmentId, "stopped");
+          toast.info("Environment start cancelled");
+          throw new Error(message);
+        }
+        console.error("[useEnvironments] Error starting environment:", err);
         setError(message);
         updateStatusInStore(environmentId, "error");
         toast.error("Failed to start environment", {
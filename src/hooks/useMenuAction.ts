@@ -0,0 +1,23 @@
+import { useEffect, useRef } from "react";
+import { listen } from "@tauri-apps/api/event";
+
+/**
+ * Run `handler` when the app menu item bound to `action` is clicked
+ * (or its keybinding pressed). Actions come from the `keybindings` config.
+ */
+export function useMenuAction(action: string, handler: () => void): void {
+  const handlerRef = useRef(handler);
+  handlerRef.current = handler;
+
+  useEffect(() => {
+    const unlisten = listen<string>("menu-action", (event) => {
+      if (event.payload === action) {
+        handlerRef.current();
+      }
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [action]);
+}
@@ -197,6 +197,18 @@ export function usePrMonitorService(): void {
           useEnvironmentStore.getState().setEnvironmentPR
         );
 
+        // Refresh CI status alongside the PR state while the PR is open
+        if (detectionResult.status === "success" && detectionResult.data.state === "open") {
+          try {
+            const { ciStatus } = await tauri.getPrChecks(environmentId);
+            useEnvironmentStore
+              .getState()
+              .updateEnvironment(environmentId, { ciStatus: ciStatus ?? undefined });
+          } catch (error) {
+            console.debug(`[PrMonitorService] Failed to load PR checks for ${environmentId}:`, error);
+          }
+        }
+
         // When a PR transitions to "merged", move the associated kanban task to "review"
         if (
           detectionResult.status === "success" &&
@@ -19,6 +19,8 @@ interface UseTerminalOptions {
   persistSession?: boolean;
   /** User to run the terminal session as (e.g., "orkroot" for root access) */
   user?: string;
+  /** Called with the output saved before a disconnect once a container session is reattached */
+  onReattach?: (savedOutput: Uint8Array) => void;
 }
 
 interface UseTerminalReturn {
@@ -42,6 +44,7 @@ export function useTerminal({
   existingSessionId,
   persistSession = false,
   user,
+  onReattach,
 }: UseTerminalOptions): UseTerminalReturn {
   const [sessionId, setSessionId] = useState<string | null>(null);
   const [isConnected, setIsConnected] = useState(false);
@@ -56,6 +59,11 @@ export function useTerminal({
     onDataRef.current = onData;
   }, [onData]);
 
+  const onReattachRef = useRef(onReattach);
+  useEffect(() => {
+    onReattachRef.current = onReattach;
+  }, [onReattach]);
+
   // Track previous containerId to detect changes
   const previousContainerIdRef = useRef<string | null>(null);
 
@@ -97,6 +105,38 @@ export function useTerminal({
     persistSessionRef.current = persistSession;
   }, [persistSession]);
 
+  // Reattach container sessions whose exec died with their container (e.g. a
+  // restart or recreate) and replay the output saved before the disconnect.
+  useEffect(() => {
+    if (isLocal) return;
+    let cancelled = false;
+    const unlistenPromise = listen<{ sessionId: string }>("terminal-disconnected", async (event) => {
+      if (event.payload.sessionId !== sessionIdRef.current) return;
+      console.log("[useTerminal] Session disconnected, reattaching:", event.payload.sessionId);
+      setIsConnected(false);
+      for (let attempt = 1; attempt <= 5 && !cancelled; attempt++) {
+        await new Promise((resolve) => setTimeout(resolve, attempt * 1000));
+        if (cancelled || event.payload.sessionId !== sessionIdRef.current) return;
+        try {
+          const savedOutput = await tauri.reattachSession(event.payload.sessionId);
+          onReattachRef.current?.(new Uint8Array(savedOutput));
+          setIsConnected(true);
+          console.log("[useTerminal] Reattached session:", event.payload.sessionId);
+          return;
+        } catch (err) {
+          console.warn("[useTerminal] Reattach attempt", attempt, "failed:", err);
+        }
+      }
+      if (!cancelled) {
+        setError("Terminal disconnected");
+      }
+    });
+    return () => {
+      cancelled = true;
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, [isLocal]);
+
   // Track isLocal in a ref for cleanup
   const isLocalRef = useRef(isLocal);
   useEffect(() => {
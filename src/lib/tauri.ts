@@ -1,9 +1,11 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
   Project,
+  ProjectGroup,
   Environment,
   EnvironmentType,
   AppConfig,
+  ContainerResources,
   GlobalConfig,
   RepositoryConfig,
   EnvironmentStatus,
@@ -12,15 +14,25 @@ import type {
   PreferredEditor,
   PortMapping,
   Session,
+  SessionLaunchConfig,
   SessionType,
   SessionStatus,
   PrState,
+  CiStatus,
   StartEnvironmentResult,
+  DiskSpaceWarning,
+  ContainerConfigPreview,
+  EnvironmentActivity,
   DefaultAgent,
   ClaudeMode,
   ClaudeNativeBackend,
   CodexMode,
   OpenCodeMode,
+  RestartPolicy,
+  ProjectConfigValidation,
+  ComposeProject,
+  WindowState,
+  WorktreeConflictStrategy,
 } from "@/types";
 
 /** PR detection result containing URL, state, and merge conflict status */
@@ -58,6 +70,48 @@ export async function updateProject(
   return invoke<Project>("update_project", { projectId, updates });
 }
 
+/**
+ * Check that a project's default and PR base branches exist on its remote.
+ * Returns the names of the missing branches.
+ */
+export async function validateRepositoryBranches(projectId: string): Promise<string[]> {
+  return invoke<string[]>("validate_repository_branches", { projectId });
+}
+
+// --- Project Group Commands ---
+
+export async function getProjectGroups(): Promise<Record<string, ProjectGroup>> {
+  return invoke<Record<string, ProjectGroup>>("get_project_groups");
+}
+
+/** Create a project group at the end of the sidebar, returning its ID */
+export async function createProjectGroup(name: string): Promise<string> {
+  return invoke<string>("create_project_group", { name });
+}
+
+export async function renameProjectGroup(groupId: string, name: string): Promise<ProjectGroup> {
+  return invoke<ProjectGroup>("rename_project_group", { groupId, name });
+}
+
+/** Delete a project group, ungrouping its projects. Returns the ungrouped project IDs. */
+export async function deleteProjectGroup(groupId: string): Promise<string[]> {
+  return invoke<string[]>("delete_project_group", { groupId });
+}
+
+export async function reorderProjectGroups(
+  groupIds: string[]
+): Promise<Record<string, ProjectGroup>> {
+  return invoke<Record<string, ProjectGroup>>("reorder_project_groups", { groupIds });
+}
+
+/** Assign a project to a group, or ungroup it with null */
+export async function setProjectGroup(
+  projectId: string,
+  groupId: string | null
+): Promise<Project> {
+  return invoke<Project>("set_project_group", { projectId, groupId });
+}
+
 // --- Environment Commands ---
 
 export async function getEnvironments(projectId: string): Promise<Environment[]> {
@@ -78,9 +132,15 @@ export async function createEnvironment(
   networkAccessMode?: NetworkAccessMode,
   initialPrompt?: string,
   portMappings?: PortMapping[],
-  environmentType?: EnvironmentType
+  environmentType?: EnvironmentType,
+  baseRef?: string
 ): Promise<Environment> {
-  return invoke<Environment>("create_environment", { projectId, name, networkAccessMode, initialPrompt, portMappings, environmentType });
+  return invoke<Environment>("create_environment", { projectId, name, networkAccessMode, initialPrompt, portMappings, environmentType, baseRef });
+}
+
+/** Generate a suggested environment name from a prompt without creating anything */
+export async function suggestEnvironmentName(prompt: string): Promise<string> {
+  return invoke<string>("suggest_environment_name", { prompt });
 }
 
 export async function deleteEnvironment(environmentId: string): Promise<void> {
@@ -91,10 +151,30 @@ export async function startEnvironment(environmentId: string): Promise<StartEnvi
   return invoke<StartEnvironmentResult>("start_environment", { environmentId });
 }
 
+/** Locations that would be low on disk space when starting an environment */
+export async function checkDiskSpace(environmentId: string): Promise<DiskSpaceWarning[]> {
+  return invoke<DiskSpaceWarning[]>("check_disk_space", { environmentId });
+}
+
+/** Abort an in-flight start. Returns whether a start was in progress. */
+export async function cancelEnvironmentStart(environmentId: string): Promise<boolean> {
+  return invoke<boolean>("cancel_environment_start", { environmentId });
+}
+
+/** Container configuration a start would create, with secrets redacted. Nothing is created. */
+export async function previewContainerConfig(environmentId: string): Promise<ContainerConfigPreview> {
+  return invoke<ContainerConfigPreview>("preview_container_config", { environmentId });
+}
+
 export async function stopEnvironment(environmentId: string): Promise<void> {
   return invoke("stop_environment", { environmentId });
 }
 
+/** Kill a wedged environment container with SIGKILL, skipping the graceful stop */
+export async function forceKillEnvironment(environmentId: string): Promise<void> {
+  return invoke("force_kill_environment", { environmentId });
+}
+
 /**
  * Recreate an environment - preserves filesystem state via docker commit, then creates new container with updated port mappings
  * Note: All running processes will be terminated, but installed packages and file changes are preserved
@@ -103,6 +183,22 @@ export async function recreateEnvironment(environmentId: string): Promise<void>
   return invoke("recreate_environment", { environmentId });
 }
 
+/**
+ * Move a local environment's worktree into another base directory via `git worktree move`.
+ * Local servers are stopped for the move; the environment is started again if it was running.
+ */
+export async function relocateWorktree(environmentId: string, newBase: string): Promise<Environment> {
+  return invoke<Environment>("relocate_worktree", { environmentId, newBase });
+}
+
+/** Settle the branch conflict recorded when a local environment's worktree was created */
+export async function resolveWorktreeConflict(
+  environmentId: string,
+  strategy: WorktreeConflictStrategy
+): Promise<Environment> {
+  return invoke<Environment>("resolve_worktree_conflict", { environmentId, strategy });
+}
+
 export async function syncEnvironmentStatus(environmentId: string): Promise<Environment> {
   return invoke<Environment>("sync_environment_status", { environmentId });
 }
@@ -129,6 +225,21 @@ export async function renameEnvironmentFromPrompt(environmentId: string, prompt:
   return invoke<void>("rename_environment_from_prompt", { environmentId, prompt });
 }
 
+/** Re-roll an environment's AI-generated name from a new prompt (defaults to its stored initial prompt) */
+export async function regenerateEnvironmentName(environmentId: string, prompt?: string): Promise<Environment> {
+  return invoke<Environment>("regenerate_environment_name", { environmentId, prompt });
+}
+
+/** Cancel an in-progress background naming task. Returns whether one was running. */
+export async function cancelEnvironmentNaming(environmentId: string): Promise<boolean> {
+  return invoke<boolean>("cancel_environment_naming", { environmentId });
+}
+
+/** Whether a background naming task is running for an environment */
+export async function isEnvironmentNaming(environmentId: string): Promise<boolean> {
+  return invoke<boolean>("is_environment_naming", { environmentId });
+}
+
 export async function getEnvironmentStatus(
   environmentId: string
 ): Promise<EnvironmentStatus> {
@@ -177,6 +288,86 @@ export async function resizeTerminal(
   return invoke("terminal_resize", { sessionId, cols, rows });
 }
 
+/** Byte counts, uptime and average throughput of a container terminal session */
+export interface TerminalSessionMetrics {
+  sessionId: string;
+  /** Bytes sent to the terminal (keystrokes, pastes) */
+  bytesWritten: number;
+  /** Bytes received from the terminal */
+  bytesRead: number;
+  /** Seconds since the session started (0 if not started) */
+  uptimeSecs: number;
+  readBytesPerSec: number;
+  writeBytesPerSec: number;
+}
+
+export async function getSessionMetrics(sessionId: string): Promise<TerminalSessionMetrics> {
+  return invoke<TerminalSessionMetrics>("get_session_metrics", { sessionId });
+}
+
+/** A terminal session joined across the terminal manager and stored sessions */
+export interface TerminalSessionDetails {
+  /** Terminal manager session ID (null if only stored) */
+  terminalSessionId: string | null;
+  /** Stored session ID (null if the terminal isn't linked to one) */
+  persistentSessionId: string | null;
+  environmentId: string | null;
+  containerId: string;
+  cols: number | null;
+  rows: number | null;
+  /** The terminal has a running exec */
+  isActive: boolean;
+  sessionType: SessionType | null;
+  status: SessionStatus | null;
+  lastActivityAt: string | null;
+}
+
+export async function listTerminalSessionsDetailed(): Promise<TerminalSessionDetails[]> {
+  return invoke<TerminalSessionDetails[]>("list_terminal_sessions_detailed");
+}
+
+/** Reattach a disconnected container terminal; returns the output saved before the disconnect */
+export async function reattachSession(sessionId: string): Promise<number[]> {
+  return invoke<number[]>("reattach_session", { sessionId });
+}
+
+// --- Environment Terminal Commands (routed to container or local terminals) ---
+
+/** Create a terminal session for any environment type; `user` applies to containers only */
+export async function createEnvironmentTerminal(
+  environmentId: string,
+  cols: number,
+  rows: number,
+  user?: string
+): Promise<string> {
+  return invoke<string>("create_environment_terminal", { environmentId, cols, rows, user });
+}
+
+export async function startEnvironmentTerminal(environmentId: string, sessionId: string): Promise<void> {
+  return invoke("start_environment_terminal", { environmentId, sessionId });
+}
+
+export async function writeEnvironmentTerminal(
+  environmentId: string,
+  sessionId: string,
+  data: string
+): Promise<void> {
+  return invoke("environment_terminal_write", { environmentId, sessionId, data });
+}
+
+export async function resizeEnvironmentTerminal(
+  environmentId: string,
+  sessionId: string,
+  cols: number,
+  rows: number
+): Promise<void> {
+  return invoke("environment_terminal_resize", { environmentId, sessionId, cols, rows });
+}
+
+export async function closeEnvironmentTerminal(environmentId: string, sessionId: string): Promise<void> {
+  return invoke("close_environment_terminal", { environmentId, sessionId });
+}
+
 // --- Configuration Commands ---
 
 export async function getConfig(): Promise<AppConfig> {
@@ -195,6 +386,13 @@ export async function updateGlobalConfig(global: GlobalConfig): Promise<AppConfi
   return invoke<AppConfig>("update_global_config", { global });
 }
 
+/** Merge only the given keys into the global config; other settings are kept */
+export async function updateGlobalConfigPartial(
+  patch: Partial<GlobalConfig>
+): Promise<AppConfig> {
+  return invoke<AppConfig>("update_global_config_partial", { patch });
+}
+
 export async function getRepositoryConfig(projectId: string): Promise<RepositoryConfig> {
   return invoke<RepositoryConfig>("get_repository_config", { projectId });
 }
@@ -206,10 +404,114 @@ export async function updateRepositoryConfig(
   return invoke<AppConfig>("update_repository_config", { projectId, repoConfig });
 }
 
+/**
+ * Copy a project's repository config to another project, e.g. a fork.
+ * Fails if the target already has one unless `force` is set.
+ */
+export async function copyRepositoryConfig(
+  fromProjectId: string,
+  toProjectId: string,
+  force?: boolean
+): Promise<AppConfig> {
+  return invoke<AppConfig>("copy_repository_config", { fromProjectId, toProjectId, force });
+}
+
+/** A single invalid config value, addressed by its camelCase JSON path */
+export interface ConfigFieldError {
+  /** Path to the field, e.g. "global.containerResources.cpuCores" */
+  fieldPath: string;
+  message: string;
+}
+
+/** Validate a config without saving it (empty result = valid) */
+export async function checkConfig(config: AppConfig): Promise<ConfigFieldError[]> {
+  return invoke<ConfigFieldError[]>("check_config", { config });
+}
+
 export async function getLogDirectory(): Promise<string> {
   return invoke<string>("get_log_directory");
 }
 
+/** Log file currently being written, or null when saving logs is disabled */
+export async function getLogFilePath(): Promise<string | null> {
+  return invoke<string | null>("get_log_file_path");
+}
+
+export type LogLevel = "trace" | "debug" | "info" | "warn" | "error" | "off";
+
+export interface LogRecord {
+  timestamp: string;
+  /** "ERROR", "WARN", "INFO", "DEBUG" or "TRACE" */
+  level: string;
+  target: string;
+  message: string;
+}
+
+/** Recently captured log lines, oldest first */
+export async function getRecentLogs(
+  limit?: number,
+  minLevel?: Exclude<LogLevel, "off">
+): Promise<LogRecord[]> {
+  return invoke<LogRecord[]>("get_recent_logs", { limit, minLevel });
+}
+
+/** Change the log level until restart; returns the applied filter */
+export async function setLogLevel(level: LogLevel): Promise<string> {
+  return invoke<string>("set_log_level", { level });
+}
+
+/** Open the app data directory in Finder/Explorer; returns its path */
+export async function openDataDir(): Promise<string> {
+  return invoke<string>("open_data_dir");
+}
+
+/** Reveal config.json in Finder/Explorer; returns its path */
+export async function openConfigFile(): Promise<string> {
+  return invoke<string>("open_config_file");
+}
+
+/** Fully resolved settings for an environment (environment → repository → global) */
+export interface EffectiveEnvironmentConfig {
+  environmentId: string;
+  projectId: string;
+  environmentType: EnvironmentType;
+  containerResources: ContainerResources;
+  restartPolicy: RestartPolicy;
+  /** Branch new environment branches are created from (null = repo default) */
+  baseBranch: string | null;
+  prBaseBranch: string;
+  /** Docker network the container joins (null = default bridge) */
+  dockerNetwork: string | null;
+  networkAccessMode: NetworkAccessMode;
+  /** Domains whitelisted in restricted mode (global plus environment additions) */
+  allowedDomains: string[];
+  firewallBypassed: boolean;
+  portMappings: PortMapping[];
+  entryPort: number | null;
+  envFilePatterns: string[];
+  filesToCopy: string[];
+  hasAnthropicApiKey: boolean;
+  hasGithubToken: boolean;
+  defaultAgent: DefaultAgent;
+  claudeMode: ClaudeMode;
+  claudeNativeBackend: ClaudeNativeBackend;
+  opencodeMode: OpenCodeMode;
+  codexMode: CodexMode;
+  defaultModel: string;
+  defaultEffort: string | null;
+  /** Path the repository is cloned to inside the container */
+  workspacePath: string;
+  /** Commits of history the container clones (null = full history) */
+  cloneDepth: number | null;
+}
+
+/** Get the fully merged configuration an environment will use */
+export async function getEffectiveEnvironmentConfig(
+  environmentId: string
+): Promise<EffectiveEnvironmentConfig> {
+  return invoke<EffectiveEnvironmentConfig>("get_effective_environment_config", { environmentId });
+}
+
 // --- GitHub Commands ---
 
 export async function openInBrowser(url: string): Promise<void> {
@@ -237,6 +539,35 @@ export async function setEnvironmentPr(
   return invoke<Environment>("set_environment_pr", { environmentId, prUrl, prState, hasMergeConflicts });
 }
 
+/** Turn review (read-only) mode on or off for an environment */
+export async function setEnvironmentReadOnly(
+  environmentId: string,
+  readOnly: boolean
+): Promise<Environment> {
+  return invoke<Environment>("set_environment_read_only", { environmentId, readOnly });
+}
+
+/** Age and last activity of every environment in a project */
+export async function getEnvironmentActivity(projectId: string): Promise<EnvironmentActivity[]> {
+  return invoke<EnvironmentActivity[]>("get_environment_activity", { projectId });
+}
+
+/** Pin or unpin an environment; pinned environments are listed first */
+export async function setEnvironmentPinned(
+  environmentId: string,
+  pinned: boolean
+): Promise<Environment> {
+  return invoke<Environment>("set_environment_pinned", { environmentId, pinned });
+}
+
+/** Set an environment's free-form notes; null or blank text clears them */
+export async function setEnvironmentNotes(
+  environmentId: string,
+  notes: string | null
+): Promise<Environment> {
+  return invoke<Environment>("set_environment_notes", { environmentId, notes });
+}
+
 export async function setEnvironmentSetupComplete(
   environmentId: string,
   complete: boolean
@@ -262,6 +593,72 @@ export async function detectPrLocal(environmentId: string, branch: string): Prom
   return invoke<PrDetectionResult | null>("detect_pr_local", { environmentId, branch });
 }
 
+/** Open pull request of a project's repository */
+export interface ProjectPullRequest {
+  number: number;
+  title: string;
+  /** Head branch, usable as `baseRef` when creating an environment */
+  headRefName: string;
+  /** GitHub login of the author */
+  author: string;
+  url: string;
+}
+
+/** List open pull requests of a project's repository using gh */
+export async function listProjectPullRequests(projectId: string): Promise<ProjectPullRequest[]> {
+  return invoke<ProjectPullRequest[]>("list_project_pull_requests", { projectId });
+}
+
+/** Hosting metadata of a project's repository */
+export interface RepositoryMetadata {
+  name: string;
+  description: string | null;
+  /** Default branch on the remote */
+  defaultBranch: string | null;
+  topics: string[];
+  /** "PUBLIC", "PRIVATE" or "INTERNAL" (null when read without gh) */
+  visibility: string | null;
+  /** When the metadata was fetched (ISO timestamp) */
+  fetchedAt: string;
+}
+
+/**
+ * Get a project's repository metadata, read with gh (or git without it) and
+ * cached for a few hours. `refresh` bypasses the cache.
+ */
+export async function getRepositoryMetadata(
+  projectId: string,
+  refresh?: boolean
+): Promise<RepositoryMetadata> {
+  return invoke<RepositoryMetadata>("get_repository_metadata", { projectId, refresh });
+}
+
+/** Create an environment that checks out an open pull request's head branch */
+export async function createEnvironmentFromPr(projectId: string, prNumber: number): Promise<Environment> {
+  return invoke<Environment>("create_environment_from_pr", { projectId, prNumber });
+}
+
+/** A single CI check run of a pull request */
+export interface PrCheck {
+  name: string;
+  /** Raw check state, e.g. "SUCCESS", "FAILURE" or "IN_PROGRESS" */
+  state: string;
+  link: string;
+  /** gh's categorization: pass, fail, pending, skipping or cancel */
+  bucket: string;
+}
+
+/** Check runs of an environment's PR plus their summarized state */
+export interface PrChecks {
+  ciStatus: CiStatus | null;
+  checks: PrCheck[];
+}
+
+/** Get the CI checks of an environment's PR (also stores the summarized status) */
+export async function getPrChecks(environmentId: string): Promise<PrChecks> {
+  return invoke<PrChecks>("get_pr_checks", { environmentId });
+}
+
 /** Merge method options for PR merging */
 export type MergeMethod = "squash" | "merge" | "rebase";
 
@@ -283,6 +680,67 @@ export async function mergePrLocal(
   return invoke("merge_pr_local", { environmentId, method, deleteBranch });
 }
 
+/** A label or reviewer gh refused to attach to a new PR */
+export interface RejectedPrMetadata {
+  value: string;
+  reason: string;
+}
+
+/** Result of creating a PR */
+export interface CreatedPullRequest {
+  url: string;
+  rejectedLabels: RejectedPrMetadata[];
+  rejectedReviewers: RejectedPrMetadata[];
+}
+
+/**
+ * Create a PR for the environment's branch using gh pr create.
+ * Unknown labels/reviewers don't fail the creation; they are reported back instead.
+ */
+export async function createPullRequest(
+  environmentId: string,
+  title: string,
+  options: {
+    body?: string;
+    base?: string;
+    draft?: boolean;
+    labels?: string[];
+    reviewers?: string[];
+  } = {}
+): Promise<CreatedPullRequest> {
+  return invoke<CreatedPullRequest>("create_pull_request", {
+    environmentId,
+    title,
+    body: options.body,
+    base: options.base,
+    draft: options.draft,
+    labels: options.labels ?? [],
+    reviewers: options.reviewers ?? [],
+  });
+}
+
+/** Close an environment's PR without merging (gh pr close) */
+export async function closePullRequest(environmentId: string): Promise<Environment> {
+  return invoke<Environment>("close_pull_request", { environmentId });
+}
+
+/** Reopen an environment's closed PR (gh pr reopen); merged PRs are rejected */
+export async function reopenPullRequest(environmentId: string): Promise<Environment> {
+  return invoke<Environment>("reopen_pull_request", { environmentId });
+}
+
+// --- Window State Commands ---
+
+/** Get the saved zoom level and main window geometry */
+export async function getWindowState(): Promise<WindowState> {
+  return invoke<WindowState>("get_window_state");
+}
+
+/** Save window state; fields left undefined keep their saved values */
+export async function saveWindowState(state: WindowState): Promise<WindowState> {
+  return invoke<WindowState>("save_window_state", { state });
+}
+
 // --- Docker Commands ---
 
 export async function checkDocker(): Promise<boolean> {
@@ -379,9 +837,17 @@ export async function getOrkestratorContainers(): Promise<ContainerInfo[]> {
   return invoke<ContainerInfo[]>("get_orkestrator_containers");
 }
 
-/** Remove orphaned containers (not assigned to any environment) */
-export async function cleanupOrphanedContainers(): Promise<number> {
-  return invoke<number>("cleanup_orphaned_containers");
+/** Result of removing orphaned containers */
+export interface OrphanCleanupResult {
+  /** Number of containers removed */
+  removed: number;
+  /** Orphaned containers left in place because they are pinned */
+  skippedPinned: string[];
+}
+
+/** Remove orphaned containers (not assigned to any environment), skipping pinned ones */
+export async function cleanupOrphanedContainers(): Promise<OrphanCleanupResult> {
+  return invoke<OrphanCleanupResult>("cleanup_orphaned_containers");
 }
 
 /** Reattach an orphaned container to a project by creating a new environment entry */
@@ -405,6 +871,8 @@ export interface SystemPruneResult {
   volumesDeleted: number;
   /** Total space reclaimed in bytes */
   spaceReclaimed: number;
+  /** Pinned containers and images that were left in place */
+  skippedPinned: string[];
 }
 
 /** Perform Docker system prune - removes unused containers, images, networks, and optionally volumes */
@@ -412,6 +880,62 @@ export async function dockerSystemPrune(pruneVolumes: boolean = false): Promise<
   return invoke<SystemPruneResult>("docker_system_prune", { pruneVolumes });
 }
 
+/** Docker disk usage per category, in bytes */
+export interface DockerDiskBreakdown {
+  /** Image layers, deduplicated across images */
+  images: number;
+  /** Writable layers of containers */
+  containers: number;
+  volumes: number;
+  buildCache: number;
+}
+
+/** Get Docker disk usage split into images, containers, volumes and build cache */
+export async function getDockerDiskBreakdown(): Promise<DockerDiskBreakdown> {
+  return invoke<DockerDiskBreakdown>("get_docker_disk_breakdown");
+}
+
+/** Category of unused Docker data that can be pruned on its own */
+export type PruneTarget = "containers" | "images" | "volumes" | "build-cache";
+
+/** Space reclaimed per category in bytes; null for categories that were not selected */
+export interface DockerPruneResult {
+  containers: number | null;
+  images: number | null;
+  volumes: number | null;
+  buildCache: number | null;
+  /** Pinned containers and images that were left in place */
+  skippedPinned: string[];
+}
+
+/** Prune only the selected categories: stopped containers, dangling images, unused volumes, build cache */
+export async function pruneDocker(targets: PruneTarget[]): Promise<DockerPruneResult> {
+  return invoke<DockerPruneResult>("prune_docker", { targets });
+}
+
+/** Pin a container (by ID or name) so prune and orphan cleanup skip it. Returns the pinned containers. */
+export async function pinContainer(containerId: string): Promise<string[]> {
+  return invoke<string[]>("pin_container", { containerId });
+}
+
+/** Unpin a container. Returns the pinned containers. */
+export async function unpinContainer(containerId: string): Promise<string[]> {
+  return invoke<string[]>("unpin_container", { containerId });
+}
+
+/**
+ * Pin an image (by tag or ID) so prune skips it. Tags are resolved to the
+ * image ID, which is what gets pinned. Returns the pinned images.
+ */
+export async function pinImage(tag: string): Promise<string[]> {
+  return invoke<string[]>("pin_image", { tag });
+}
+
+/** Unpin an image. Returns the pinned images. */
+export async function unpinImage(tag: string): Promise<string[]> {
+  return invoke<string[]>("unpin_image", { tag });
+}
+
 /** Get container logs (non-streaming, returns last N lines) */
 export async function getContainerLogs(containerId: string, tail?: string): Promise<string> {
   return invoke<string>("get_container_logs", { containerId, tail });
@@ -427,6 +951,84 @@ export async function getContainerHostPort(containerId: string, containerPort: n
   return invoke<number | null>("get_container_host_port", { containerId, containerPort });
 }
 
+/** A mount as reported by docker inspect */
+export interface ContainerMountDetails {
+  /** Mount type (bind, volume, tmpfs, ...) */
+  mountType: string | null;
+  /** Volume name (for named volumes) */
+  name: string | null;
+  /** Host path or volume location */
+  source: string | null;
+  /** Path inside the container */
+  destination: string | null;
+  /** Whether the mount is writable */
+  readWrite: boolean;
+}
+
+/** A published port as reported by docker inspect */
+export interface ContainerPortBindingDetails {
+  /** Container port with protocol (e.g. "3000/tcp") */
+  containerPort: string;
+  /** Host interface the port is bound to */
+  hostIp: string | null;
+  /** Host port the container port is published on */
+  hostPort: string | null;
+}
+
+/** Curated subset of docker inspect output for diagnostics */
+export interface ContainerInspectDetails {
+  id: string;
+  name: string;
+  image: string | null;
+  /** Container state (running, exited, created, etc.) */
+  status: string;
+  running: boolean;
+  restarting: boolean;
+  oomKilled: boolean;
+  exitCode: number | null;
+  error: string | null;
+  restartCount: number;
+  /** Creation timestamp (RFC 3339) */
+  createdAt: string | null;
+  /** Last start timestamp (RFC 3339) */
+  startedAt: string | null;
+  /** Last finish timestamp (RFC 3339) */
+  finishedAt: string | null;
+  mounts: ContainerMountDetails[];
+  /** Environment variable names only - values are never returned */
+  envVarNames: string[];
+  portBindings: ContainerPortBindingDetails[];
+}
+
+/** Inspect a container for debugging (state, mounts, env var names, ports) */
+export async function inspectContainer(containerId: string): Promise<ContainerInspectDetails> {
+  return invoke<ContainerInspectDetails>("inspect_container", { containerId });
+}
+
+/** A container environment variable name; values are never returned */
+export interface ContainerEnvKey {
+  name: string;
+  /** Whether the name looks like it holds a credential (e.g. GITHUB_TOKEN) */
+  secret: boolean;
+}
+
+/** List the env var names set in a container, flagging likely secrets */
+export async function listContainerEnvKeys(containerId: string): Promise<ContainerEnvKey[]> {
+  return invoke<ContainerEnvKey[]>("list_container_env_keys", { containerId });
+}
+
+/** Result of waiting for a container's workspace setup */
+export interface WorkspaceReadyResult {
+  ready: boolean;
+  /** Tail of the setup progress log, included on timeout */
+  logTail: string | null;
+}
+
+/** Wait until workspace setup inside a container has completed (default 60s timeout) */
+export async function waitForWorkspaceReady(containerId: string, timeoutSecs?: number): Promise<WorkspaceReadyResult> {
+  return invoke<WorkspaceReadyResult>("wait_for_workspace_ready", { containerId, timeoutSecs });
+}
+
 /** Result of propagating GitHub token to containers */
 export interface PropagateTokenResult {
   /** Environment IDs where token was successfully updated */
@@ -525,9 +1127,12 @@ export async function getClaudeServerStatus(containerId: string): Promise<Claude
   return invoke<ClaudeServerStatus>("get_claude_server_status", { containerId });
 }
 
-/** Get the Claude bridge server log from a container (for debugging) */
-export async function getClaudeServerLog(containerId: string): Promise<string> {
-  return invoke<string>("get_claude_server_log", { containerId });
+/**
+ * Get the Claude bridge server log from a container (for debugging).
+ * `since` skips the first that many bytes, `tail` keeps the last that many lines.
+ */
+export async function getClaudeServerLog(containerId: string, tail?: number, since?: number): Promise<string> {
+  return invoke<string>("get_claude_server_log", { containerId, tail, since });
 }
 
 /** Start the Codex bridge server in a container */
@@ -555,6 +1160,13 @@ export async function getCodexServerLog(containerId: string): Promise<string> {
 export interface CredentialStatus {
   available: boolean;
   expiresAt: number | null;
+  /** Whether the access token has already expired */
+  expired: boolean;
+  /** Human-friendly time until (or since) expiry, e.g. "3 days" */
+  expiresIn: string | null;
+  /** Subscription plan, e.g. "pro" or "max" */
+  subscriptionType: string | null;
+  rateLimitTier: string | null;
 }
 
 export async function hasClaudeCredentials(): Promise<boolean> {
@@ -565,11 +1177,43 @@ export async function getCredentialStatus(): Promise<CredentialStatus> {
   return invoke<CredentialStatus>("get_credential_status");
 }
 
+export interface ContainerCredentialsRefresh {
+  /** Whether unexpired credentials were found in the keychain */
+  valid: boolean;
+  /** Whether they were written into the container */
+  injected: boolean;
+  expiresAt: number | null;
+  error: string | null;
+}
+
+/** Re-read keychain credentials and write them into a running container */
+export async function refreshContainerCredentials(
+  environmentId: string
+): Promise<ContainerCredentialsRefresh> {
+  return invoke<ContainerCredentialsRefresh>("refresh_container_credentials", {
+    environmentId,
+  });
+}
+
+/** Re-read credentials from the keychain instead of the in-memory cache */
+export async function refreshCredentials(): Promise<CredentialStatus> {
+  return invoke<CredentialStatus>("refresh_credentials");
+}
+
 // --- CLI Detection and Onboarding Commands ---
 
-/** Check if the Claude CLI binary is installed and available */
-export async function checkClaudeCli(): Promise<boolean> {
-  return invoke<boolean>("check_claude_cli");
+/** Result of a CLI availability check */
+export interface CliStatus {
+  available: boolean;
+  /** Resolved path of the binary that will be used */
+  path: string | null;
+  /** First line of `--version` output */
+  version: string | null;
+}
+
+/** Check if the Claude CLI binary is installed, and which path/version is used */
+export async function checkClaudeCli(): Promise<CliStatus> {
+  return invoke<CliStatus>("check_claude_cli");
 }
 
 /** Check if the Claude config file (~/.claude.json) exists (indicates user is logged in) */
@@ -577,19 +1221,19 @@ export async function checkClaudeConfig(): Promise<boolean> {
   return invoke<boolean>("check_claude_config");
 }
 
-/** Check if the OpenCode CLI binary is installed and available */
-export async function checkOpencodeCli(): Promise<boolean> {
-  return invoke<boolean>("check_opencode_cli");
+/** Check if the OpenCode CLI binary is installed, and which path/version is used */
+export async function checkOpencodeCli(): Promise<CliStatus> {
+  return invoke<CliStatus>("check_opencode_cli");
 }
 
-/** Check if the Codex CLI binary is installed and available */
-export async function checkCodexCli(): Promise<boolean> {
-  return invoke<boolean>("check_codex_cli");
+/** Check if the Codex CLI binary is installed, and which path/version is used */
+export async function checkCodexCli(): Promise<CliStatus> {
+  return invoke<CliStatus>("check_codex_cli");
 }
 
-/** Check if the GitHub CLI (gh) binary is installed and available */
-export async function checkGithubCli(): Promise<boolean> {
-  return invoke<boolean>("check_github_cli");
+/** Check if the GitHub CLI (gh) binary is installed, and which path/version is used */
+export async function checkGithubCli(): Promise<CliStatus> {
+  return invoke<CliStatus>("check_github_cli");
 }
 
 /** Check if any AI CLI (Claude or OpenCode) is available for name generation */
@@ -602,12 +1246,52 @@ export async function getAvailableAiCli(): Promise<string | null> {
   return invoke<string | null>("get_available_ai_cli");
 }
 
-// --- Utility Commands ---
+export type DiagnosticStatus = "pass" | "warn" | "fail";
+
+/** One item of the diagnostics report */
+export interface DiagnosticCheck {
+  /** Stable identifier, e.g. "docker_daemon" */
+  id: string;
+  label: string;
+  status: DiagnosticStatus;
+  detail: string;
+  /** How to fix it (omitted for passing checks) */
+  remediation?: string;
+}
+
+export interface DiagnosticsReport {
+  /** Worst status across all checks */
+  overall: DiagnosticStatus;
+  checks: DiagnosticCheck[];
+}
 
-export async function greet(name: string): Promise<string> {
-  return invoke<string>("greet", { name });
+/** Run all onboarding checks (Docker, base image, CLIs, credentials, network) */
+export async function runDiagnostics(): Promise<DiagnosticsReport> {
+  return invoke<DiagnosticsReport>("run_diagnostics");
 }
 
+/** About-style summary of the app for support and bug reports */
+export interface AppInfo {
+  version: string;
+  /** Git commit the app was built from ("unknown" outside a checkout) */
+  buildCommit: string;
+  /** Operating system and CPU architecture, e.g. "macos aarch64" */
+  os: string;
+  /** Container engine in use ("Docker" or "Podman") */
+  containerEngine: string;
+  dataDir: string;
+  projectCount: number;
+  environmentCount: number;
+  sessionCount: number;
+}
+
+/** Get version, build commit, platform, container engine, data dir and counts */
+export async function appInfo(): Promise<AppInfo> {
+  return invoke<AppInfo>("app_info");
+}
+
+// --- Utility Commands ---
+
 export async function browseForDirectory(): Promise<string | null> {
   return invoke<string | null>("browse_for_directory");
 }
@@ -620,6 +1304,40 @@ export async function getGitRemoteUrl(path: string): Promise<string | null> {
   return invoke<string | null>("get_git_remote_url", { path });
 }
 
+/**
+ * Detect a docker-compose.yml / compose.yaml in a project's local path.
+ * Returns null when the project has no local path or no compose file.
+ */
+export async function detectCompose(projectId: string): Promise<ComposeProject | null> {
+  return invoke<ComposeProject | null>("detect_compose", { projectId });
+}
+
+/**
+ * Run `docker compose up -d` for an environment's repository (opt-in per repo).
+ * Output streams as "compose-output" events; resolves with the aggregate status.
+ */
+export async function composeUp(environmentId: string): Promise<EnvironmentStatus> {
+  return invoke<EnvironmentStatus>("compose_up", { environmentId });
+}
+
+/** Run `docker compose down` for an environment's compose project */
+export async function composeDown(environmentId: string): Promise<EnvironmentStatus> {
+  return invoke<EnvironmentStatus>("compose_down", { environmentId });
+}
+
+/** Aggregate status of an environment's compose services */
+export async function getComposeStatus(environmentId: string): Promise<EnvironmentStatus> {
+  return invoke<EnvironmentStatus>("get_compose_status", { environmentId });
+}
+
+/**
+ * Lint an orkestrator-ai.json file. `path` may be the file or the repository
+ * directory containing it. Rejects only when no config file exists.
+ */
+export async function validateOrkestratorConfig(path: string): Promise<ProjectConfigValidation> {
+  return invoke<ProjectConfigValidation>("validate_orkestrator_config", { path });
+}
+
 // --- Network Commands ---
 
 export async function testDomainResolution(
@@ -655,6 +1373,81 @@ export async function updateEnvironmentAllowedDomains(
   return invoke<Environment>("update_environment_allowed_domains", { environmentId, domains });
 }
 
+/** Outcome of applying new allowed domains to one environment's firewall */
+export interface EnvironmentFirewallUpdate {
+  environmentId: string;
+  name: string;
+  success: boolean;
+  error: string | null;
+}
+
+export interface BulkDomainUpdateResult {
+  /** Global allowed domains after the update */
+  allowedDomains: string[];
+  /** One entry per running restricted environment */
+  environments: EnvironmentFirewallUpdate[];
+}
+
+/** Add domains to the global whitelist and every running restricted environment's firewall */
+export async function addDomainsToAllEnvironments(domains: string[]): Promise<BulkDomainUpdateResult> {
+  return invoke<BulkDomainUpdateResult>("add_domains_to_all_environments", { domains });
+}
+
+/** Where imported allowed domains are merged */
+export type DomainImportScope =
+  | { type: "global" }
+  | { type: "environment"; environmentId: string };
+
+export interface DomainImportResult {
+  /** Entries that weren't in the list yet */
+  added: number;
+  /** Entries that aren't valid domains, IPs, or CIDR ranges */
+  rejected: string[];
+}
+
+/** Import allowed domains from a newline- or JSON-formatted file */
+export async function importAllowedDomains(
+  path: string,
+  scope: DomainImportScope
+): Promise<DomainImportResult> {
+  return invoke<DomainImportResult>("import_allowed_domains", { path, scope });
+}
+
+export interface AllowedDomainsDiff {
+  /** Whether the container records its applied entries (older images don't, so only missingFromIpset is reported) */
+  entriesTracked: boolean;
+  /** Stored entries the container's firewall hasn't applied */
+  onlyInStorage: string[];
+  /** Entries applied to the container's firewall but not stored */
+  onlyInContainer: string[];
+  /** Stored entries none of whose IPs are in the container's ipset */
+  missingFromIpset: string[];
+}
+
+/** Which side is treated as the source of truth when reconciling */
+export type ReconcileDirection = "toContainer" | "toStorage";
+
+/** Compare an environment's stored allowed domains with its container's live firewall */
+export async function diffAllowedDomains(environmentId: string): Promise<AllowedDomainsDiff> {
+  return invoke<AllowedDomainsDiff>("diff_allowed_domains", { environmentId });
+}
+
+/** Resync stored allowed domains and the container firewall, returning any remaining drift */
+export async function reconcileAllowedDomains(
+  environmentId: string,
+  direction: ReconcileDirection
+): Promise<AllowedDomainsDiff> {
+  return invoke<AllowedDomainsDiff>("reconcile_allowed_domains", { environmentId, direction });
+}
+
+/** Temporarily allow all traffic for a running restricted environment (or restore its firewall) */
+export async function setFirewallBypass(
+  environmentId: string,
+  enabled: boolean
+): Promise<Environment> {
+  return invoke<Environment>("set_firewall_bypass", { environmentId, enabled });
+}
+
 // --- Claude State Commands ---
 
 export async function startClaudeStatePolling(containerId: string): Promise<void> {
@@ -719,6 +1512,22 @@ export async function getGitStatus(
   return invoke<GitFileChange[]>("get_git_status", { containerId, targetBranch });
 }
 
+/** Aggregate diff stats for a branch against its target branch */
+export interface DiffSummary {
+  filesChanged: number;
+  additions: number;
+  deletions: number;
+  commitsAhead: number;
+}
+
+/** Get aggregate diff stats (tracked changes since the merge-base and commits ahead) */
+export async function getDiffSummary(
+  containerId: string,
+  targetBranch: string
+): Promise<DiffSummary> {
+  return invoke<DiffSummary>("get_diff_summary", { containerId, targetBranch });
+}
+
 /** Get workspace file tree from a container */
 export async function getFileTree(containerId: string): Promise<FileNode[]> {
   return invoke<FileNode[]>("get_file_tree", { containerId });
@@ -732,6 +1541,27 @@ export async function readContainerFile(
   return invoke<FileContent>("read_container_file", { containerId, filePath });
 }
 
+/** How `copyFileReference` formats a file reference */
+export type FileReferenceFormat = "path" | "github_permalink" | "container_path";
+
+/**
+ * Copy a reference to a file in an environment to the clipboard and return it.
+ * `line` becomes `:<line>` on paths and `#L<line>` on GitHub permalinks.
+ */
+export async function copyFileReference(
+  environmentId: string,
+  filePath: string,
+  format: FileReferenceFormat,
+  line?: number
+): Promise<string> {
+  return invoke<string>("copy_file_reference", { environmentId, filePath, format, line });
+}
+
+/** GitHub permalink for a file in a container, pinned to the checked-out commit */
+export async function getGithubPermalink(containerId: string, filePath: string, line?: number): Promise<string> {
+  return invoke<string>("get_github_permalink", { containerId, filePath, line });
+}
+
 /** Read a file from a specific git branch inside a container
  * Returns null if the file doesn't exist in the specified branch (e.g., new file)
  */
@@ -747,6 +1577,97 @@ export async function readFileAtBranch(
   });
 }
 
+/** Local and remote branches of a container's workspace repository */
+export interface ContainerBranches {
+  /** Checked-out branch (null when HEAD is detached) */
+  current: string | null;
+  local: string[];
+  /** Remote-tracking branches, e.g. "origin/main" */
+  remote: string[];
+}
+
+/** List local and remote branches inside a container */
+export async function listContainerBranches(containerId: string): Promise<ContainerBranches> {
+  return invoke<ContainerBranches>("list_container_branches", { containerId });
+}
+
+/** Fetch every branch from origin and list the container's branches again */
+export async function refreshRemoteBranches(containerId: string): Promise<ContainerBranches> {
+  return invoke<ContainerBranches>("refresh_remote_branches", { containerId });
+}
+
+/** Switch the branch checked out in a container.
+ * A branch that only exists on origin is checked out as a local tracking branch.
+ * Fails on uncommitted changes unless `force` is set (which discards them).
+ */
+export async function checkoutBranch(
+  containerId: string,
+  branch: string,
+  create = false,
+  force = false
+): Promise<void> {
+  return invoke("checkout_branch", { containerId, branch, create, force });
+}
+
+/** Git operation left in progress in a repository */
+export type RepoOperation = "merge" | "rebase" | "cherry-pick" | "bisect";
+
+/** In-progress git operation of a container's workspace repository */
+export interface RepoOperationState {
+  /** null when no merge, rebase, cherry-pick or bisect is in progress */
+  operation: RepoOperation | null;
+  /** Files with unresolved conflicts */
+  conflictedFiles: string[];
+}
+
+/** Detect a merge, rebase, cherry-pick or bisect left in progress in a container */
+export async function getRepoOperationState(containerId: string): Promise<RepoOperationState> {
+  return invoke<RepoOperationState>("get_repo_operation_state", { containerId });
+}
+
+/** Abort the git operation in progress in a container and return which one it was */
+export async function abortRepoOperation(containerId: string): Promise<RepoOperation> {
+  return invoke<RepoOperation>("abort_repo_operation", { containerId });
+}
+
+/** Result of `unshallowRepo` */
+export interface UnshallowResult {
+  /** false when the repository already had its full history */
+  unshallowed: boolean;
+  message: string;
+}
+
+/** Payload of "unshallow-progress" events */
+export interface UnshallowProgressPayload {
+  containerId: string;
+  /** Raw `git fetch --progress` output; progress lines end in "\r" */
+  text: string;
+}
+
+/**
+ * Fetch the full history of a shallow-cloned container repository. Output is
+ * streamed as "unshallow-progress" events; the environment's shallowHistory
+ * flag is cleared when done.
+ */
+export async function unshallowRepo(containerId: string): Promise<UnshallowResult> {
+  return invoke<UnshallowResult>("unshallow_repo", { containerId });
+}
+
+/** Side of a conflict to keep when resolving a conflicted file */
+export type ConflictStrategy = "ours" | "theirs";
+
+/**
+ * Resolve a conflicted file in a container by keeping one side and staging it.
+ * During a rebase git swaps the sides. Returns the resolved content.
+ */
+export async function resolveConflict(
+  containerId: string,
+  filePath: string,
+  strategy: ConflictStrategy
+): Promise<FileContent> {
+  return invoke<FileContent>("resolve_conflict", { containerId, filePath, strategy });
+}
+
 /** Read a binary file from inside a container as base64 */
 export async function readContainerFileBase64(
   containerId: string,
@@ -811,6 +1732,27 @@ export async function writeLocalFile(
   return invoke<string>("write_local_file", { worktreePath, filePath, base64Data });
 }
 
+/** Result of copying a file between two environments */
+export interface FileCopyResult {
+  destPath: string;
+  bytesTransferred: number;
+}
+
+/** Copy a file from one environment to another (container or local on either side) */
+export async function copyFileBetweenEnvironments(
+  fromEnvironmentId: string,
+  toEnvironmentId: string,
+  srcPath: string,
+  destPath: string
+): Promise<FileCopyResult> {
+  return invoke<FileCopyResult>("copy_file_between_environments", {
+    fromEnvironmentId,
+    toEnvironmentId,
+    srcPath,
+    destPath,
+  });
+}
+
 // --- Port Mapping Commands ---
 
 /** Update port mappings for an environment (requires restart to apply) */
@@ -843,6 +1785,47 @@ export async function updateEnvironmentAgentSettings(
   });
 }
 
+/** Set the OpenCode model for an environment (null = use the global model) */
+export async function setEnvironmentOpencodeModel(
+  environmentId: string,
+  model: string | null,
+): Promise<Environment> {
+  return invoke<Environment>("set_environment_opencode_model", {
+    environmentId,
+    model,
+  });
+}
+
+/** Update the per-environment restart policy override (null = inherit from repository) */
+export async function updateEnvironmentRestartPolicy(
+  environmentId: string,
+  restartPolicy: RestartPolicy | null,
+): Promise<Environment> {
+  return invoke<Environment>("update_environment_restart_policy", { environmentId, restartPolicy });
+}
+
+/**
+ * Clone the full history for an environment even when its repository sets a
+ * cloneDepth. Applies when the container is next created.
+ */
+export async function setEnvironmentFullClone(
+  environmentId: string,
+  fullClone: boolean
+): Promise<Environment> {
+  return invoke<Environment>("set_environment_full_clone", { environmentId, fullClone });
+}
+
+/**
+ * Set the sessions opened by createDefaultSessions for an environment,
+ * overriding the repository default. Pass null to use the repository default.
+ */
+export async function setEnvironmentDefaultSessions(
+  environmentId: string,
+  sessions: SessionType[] | null
+): Promise<Environment> {
+  return invoke<Environment>("set_environment_default_sessions", { environmentId, sessions });
+}
+
 // --- Session Commands (Persistent Session Tracking) ---
 
 /** Create a new persistent session for tracking */
@@ -850,16 +1833,63 @@ export async function createSession(
   environmentId: string,
   containerId: string,
   tabId: string,
-  sessionType: SessionType
+  sessionType: SessionType,
+  launchConfig?: SessionLaunchConfig
 ): Promise<Session> {
   return invoke<Session>("create_session", {
     environmentId,
     containerId,
     tabId,
     sessionType,
+    launchConfig,
   });
 }
 
+/** A disconnected session with what is needed to reopen its tab */
+export interface RestorableSession {
+  sessionId: string;
+  tabId: string;
+  order: number;
+  sessionType: SessionType;
+  name: string | null;
+  /** Whether a saved terminal buffer can be replayed */
+  hasBuffer: boolean;
+  /** Whether the agent command already ran (so it must not be relaunched) */
+  hasLaunchedCommand: boolean;
+  launchConfig: SessionLaunchConfig | null;
+}
+
+/**
+ * Create session records for an environment's default sessions so a terminal
+ * can be opened for each. Returns an empty list if it already has sessions.
+ */
+export async function createDefaultSessions(environmentId: string): Promise<Session[]> {
+  return invoke<Session[]>("create_default_sessions", { environmentId });
+}
+
+/** Get an environment's disconnected sessions in tab order, for restoring tabs */
+export async function getRestorableSessions(environmentId: string): Promise<RestorableSession[]> {
+  return invoke<RestorableSession[]>("get_restorable_sessions", { environmentId });
+}
+
+export interface RestoredSession {
+  session: Session;
+  /** Reattached terminal, or null when a new terminal must be created and linked */
+  terminalSessionId: string | null;
+  /** Output to replay into the terminal */
+  buffer: string | null;
+}
+
+/** Reconnect a stored session and get the output to replay */
+export async function restoreSession(sessionId: string): Promise<RestoredSession> {
+  return invoke<RestoredSession>("restore_session", { sessionId });
+}
+
+/** Command that relaunches the agent of a session created with a launch config */
+export async function getSessionLaunchCommand(sessionId: string): Promise<string | null> {
+  return invoke<string | null>("get_session_launch_command", { sessionId });
+}
+
 /** Get a single session by ID */
 export async function getSession(sessionId: string): Promise<Session | null> {
   return invoke<Session | null>("get_session", { sessionId });
@@ -899,7 +1929,7 @@ export async function deleteSessionsByEnvironment(
   return invoke<string[]>("delete_sessions_by_environment", { environmentId });
 }
 
-/** Rename a session */
+/** Rename a session; pass null (or a blank name) to revert to the default label */
 export async function renameSession(
   sessionId: string,
   name: string | null
@@ -915,6 +1945,14 @@ export async function setSessionHasLaunchedCommand(
   return invoke<Session>("set_session_has_launched_command", { sessionId, hasLaunched });
 }
 
+/** Link a session to the terminal session currently backing it */
+export async function setSessionTerminalId(
+  sessionId: string,
+  terminalSessionId: string | null
+): Promise<Session> {
+  return invoke<Session>("set_session_terminal_id", { sessionId, terminalSessionId });
+}
+
 /** Mark all sessions for an environment as disconnected */
 export async function disconnectEnvironmentSessions(
   environmentId: string
@@ -990,6 +2028,11 @@ export async function getLocalOpencodeServerStatus(environmentId: string): Promi
   return invoke<LocalServerStatus>("get_local_opencode_server_status", { environmentId });
 }
 
+/** Restart only the local OpenCode server on its allocated port */
+export async function restartLocalOpencode(environmentId: string): Promise<LocalServerStatus> {
+  return invoke<LocalServerStatus>("restart_local_opencode", { environmentId });
+}
+
 /** Start the local Claude-bridge server for a local environment */
 export async function startLocalClaudeServer(environmentId: string): Promise<LocalServerStartResult> {
   return invoke<LocalServerStartResult>("start_local_claude_server_cmd", { environmentId });
@@ -1005,6 +2048,11 @@ export async function getLocalClaudeServerStatus(environmentId: string): Promise
   return invoke<LocalServerStatus>("get_local_claude_server_status", { environmentId });
 }
 
+/** Restart only the local Claude-bridge server on its allocated port */
+export async function restartLocalClaudeBridge(environmentId: string): Promise<LocalServerStatus> {
+  return invoke<LocalServerStatus>("restart_local_claude_bridge", { environmentId });
+}
+
 /** Start the local Codex bridge server for a local environment */
 export async function startLocalCodexServer(environmentId: string): Promise<LocalServerStartResult> {
   return invoke<LocalServerStartResult>("start_local_codex_server_cmd", { environmentId });
@@ -1020,6 +2068,70 @@ export async function getLocalCodexServerStatus(environmentId: string): Promise<
   return invoke<LocalServerStatus>("get_local_codex_server_status", { environmentId });
 }
 
+export interface LocalServerPing {
+  server: "opencode" | "claude" | "codex";
+  port: number;
+  reachable: boolean;
+  latencyMs: number | null;
+  error: string | null;
+}
+
+/** Probe the health endpoint of each agent server a local environment has a port for */
+export async function pingLocalServers(environmentId: string): Promise<LocalServerPing[]> {
+  return invoke<LocalServerPing[]>("ping_local_servers", { environmentId });
+}
+
+/** Payload of "local-server-log" events */
+export interface LocalServerLogPayload {
+  environment_id: string;
+  server: LocalServerPing["server"];
+  text: string;
+}
+
+/**
+ * Get the log of a local agent server (for debugging).
+ * `since` skips the first that many bytes, `tail` keeps the last that many lines.
+ */
+export async function getLocalServerLog(
+  environmentId: string,
+  which: LocalServerPing["server"],
+  tail?: number,
+  since?: number
+): Promise<string> {
+  return invoke<string>("get_local_server_log", { environmentId, which, tail, since });
+}
+
+/** Start streaming a local agent server's log via "local-server-log" events until it stops (no-op if already streaming) */
+export async function streamLocalServerLog(environmentId: string, which: LocalServerPing["server"]): Promise<void> {
+  return invoke("stream_local_server_log", { environmentId, which });
+}
+
+/** Stop streaming a local agent server's log */
+export async function stopLocalServerLogStream(environmentId: string, which: LocalServerPing["server"]): Promise<void> {
+  return invoke("stop_local_server_log_stream", { environmentId, which });
+}
+
+/** Response of a local agent server to a proxied request */
+export interface AgentProxyResponse {
+  status: number;
+  contentType: string | null;
+  body: string;
+}
+
+/**
+ * Send a request to a local agent server through the backend instead of
+ * hitting its port directly. Only allowlisted methods and paths are forwarded.
+ */
+export async function proxyAgentRequest(
+  environmentId: string,
+  which: LocalServerPing["server"],
+  method: string,
+  path: string,
+  body?: unknown
+): Promise<AgentProxyResponse> {
+  return invoke<AgentProxyResponse>("proxy_agent_request", { environmentId, which, method, path, body });
+}
+
 // --- Local Terminal Commands (for local/worktree environments) ---
 
 /** Create a local terminal session for a local environment */
@@ -1031,6 +2143,32 @@ export async function createLocalTerminalSession(
   return invoke<string>("create_local_terminal_session", { environmentId, cols, rows });
 }
 
+/**
+ * Create a plain host shell session not tied to any environment (e.g. for
+ * `gh auth login`). Starts in `cwd`, or the home directory when omitted.
+ * Drive it with the other local terminal functions below.
+ */
+export async function createHostTerminal(
+  cwd: string | null,
+  cols: number,
+  rows: number
+): Promise<string> {
+  return invoke<string>("create_host_terminal", { cwd, cols, rows });
+}
+
+/**
+ * Create a host shell in a local environment's worktree with the
+ * environment's variables set. Drive it with the environment terminal
+ * functions like any other session of the environment.
+ */
+export async function createWorktreeTerminal(
+  environmentId: string,
+  cols: number,
+  rows: number
+): Promise<string> {
+  return invoke<string>("create_worktree_terminal", { environmentId, cols, rows });
+}
+
 /** Start a local terminal session and begin forwarding output */
 export async function startLocalTerminalSession(sessionId: string): Promise<void> {
   return invoke("start_local_terminal_session", { sessionId });
@@ -1,9 +1,13 @@
 import { create } from "zustand";
-import type { Environment, EnvironmentStatus, PrState } from "@/types";
+import type { Environment, EnvironmentStatus, PrState, StartProgressStage } from "@/types";
 
-/** Sort environments by their order field */
+/** Pinned environments first, then by their order field */
+export const compareEnvironments = (a: Environment, b: Environment): number =>
+  Number(b.pinned ?? false) - Number(a.pinned ?? false) || a.order - b.order;
+
+/** Sort environments pinned-first, then by order */
 const sortByOrder = (environments: Environment[]): Environment[] =>
-  [...environments].sort((a, b) => a.order - b.order);
+  [...environments].sort(compareEnvironments);
 
 /**
  * Seed the runtime readiness sets from each environment's persisted
@@ -45,6 +49,10 @@ interface EnvironmentState {
   workspaceReadyEnvironments: Set<string>;
   /** Runtime state: environments currently being deleted */
   deletingEnvironments: Set<string>;
+  /** Runtime state: environments with a background AI naming task in progress */
+  namingEnvironments: Set<string>;
+  /** Runtime state: latest start progress stage for environments being started */
+  startProgress: Record<string, StartProgressStage>;
   /** Runtime state: pending setup commands to run in terminal (from orkestrator-ai.json setupLocal) */
   pendingSetupCommands: Map<string, string[]>;
   /** Runtime state: tracks whether setup commands have been resolved for an environment (true = we know if there are commands or not) */
@@ -81,6 +89,10 @@ interface EnvironmentState {
   setWorkspaceReady: (environmentId: string, isReady: boolean) => void;
   /** Mark an environment as being deleted */
   setDeleting: (environmentId: string, isDeleting: boolean) => void;
+  /** Mark whether background naming is in progress for an environment */
+  setNaming: (environmentId: string, isNaming: boolean) => void;
+  /** Record (or clear, with null) the start progress stage for an environment */
+  setStartProgress: (environmentId: string, stage: StartProgressStage | null) => void;
   /** Set pending setup commands for an environment (to be run in terminal) */
   setPendingSetupCommands: (environmentId: string, commands: string[]) => void;
   /** Get and clear pending setup commands for an environment */
@@ -103,6 +115,8 @@ interface EnvironmentState {
   isWorkspaceReady: (environmentId: string) => boolean;
   /** Check if an environment is being deleted */
   isDeleting: (environmentId: string) => boolean;
+  /** Check if background naming is in progress for an environment */
+  isNaming: (environmentId: string) => boolean;
   /** Check if setup commands have been resolved for an environment */
   isSetupCommandsResolved: (environmentId: string) => boolean;
   /** Check if setup scripts are currently running for an environment */
@@ -116,6 +130,8 @@ export const useEnvironmentStore = create<EnvironmentState>()((set, get) => ({
   error: null,
   workspaceReadyEnvironments: new Set<string>(),
   deletingEnvironments: new Set<string>(),
+  namingEnvironments: new Set<string>(),
+  startProgress: {},
   pendingSetupCommands: new Map<string, string[]>(),
   setupCommandsResolved: new Set<string>(),
   setupScriptsRunning: new Set<string>(),
@@ -244,16 +260,31 @@ export const useEnvironmentStore = create<EnvironmentState>()((set, get) => ({
     }),
 
   updateEnvironmentStatus: (environmentId, status) =>
-    set((state) => ({
-      environments: state.environments.map((e) =>
+    set((state) => {
+      const environments = state.environments.map((e) =>
         e.id === environmentId ? { ...e, status } : e
-      ),
-    })),
+      );
+      // Start progress only matters while the environment is being created
+      if (status === "creating" || !(environmentId in state.startProgress)) {
+        return { environments };
+      }
+      const { [environmentId]: _, ...startProgress } = state.startProgress;
+      return { environments, startProgress };
+    }),
 
   setEnvironmentPR: (environmentId, prUrl, prState, hasMergeConflicts) =>
     set((state) => ({
       environments: state.environments.map((e) =>
-        e.id === environmentId ? { ...e, prUrl, prState, hasMergeConflicts: hasMergeConflicts ?? null } : e
+        e.id === environmentId
+          ? {
+              ...e,
+              prUrl,
+              prState,
+              hasMergeConflicts: hasMergeConflicts ?? null,
+              // CI status belongs to the previous PR once it is cleared
+              ciStatus: prUrl ? e.ciStatus : undefined,
+            }
+          : e
       ),
     })),
 
@@ -304,6 +335,32 @@ export const useEnvironmentStore = create<EnvironmentState>()((set, get) => ({
       return { deletingEnvironments: newSet };
     }),
 
+  setStartProgress: (environmentId, stage) =>
+    set((state) => {
+      if (stage === null) {
+        if (!(environmentId in state.startProgress)) {
+          return state;
+        }
+        const { [environmentId]: _, ...startProgress } = state.startProgress;
+        return { startProgress };
+      }
+      return { startProgress: { ...state.startProgress, [environmentId]: stage } };
+    }),
+
+  setNaming: (environmentId, isNaming) =>
+    set((state) => {
+      if (state.namingEnvironments.has(environmentId) === isNaming) {
+        return state;
+      }
+      const newSet = new Set(state.namingEnvironments);
+      if (isNaming) {
+        newSet.add(environmentId);
+      } else {
+        newSet.delete(environmentId);
+      }
+      return { namingEnvironments: newSet };
+    }),
+
   setPendingSetupCommands: (environmentId, commands) =>
     set((state) => {
       const newMap = new Map(state.pendingSetupCommands);
@@ -380,6 +437,9 @@ export const useEnvironmentStore = create<EnvironmentState>()((set, get) => ({
   isDeleting: (environmentId) =>
     get().deletingEnvironments.has(environmentId),
 
+  isNaming: (environmentId) =>
+    get().namingEnvironments.has(environmentId),
+
   isSetupCommandsResolved: (environmentId) =>
     get().setupCommandsResolved.has(environmentId),
 
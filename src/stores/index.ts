@@ -1,6 +1,6 @@
 export { useUIStore } from "./uiStore";
 export { useProjectStore } from "./projectStore";
-export { useEnvironmentStore } from "./environmentStore";
+export { useEnvironmentStore, compareEnvironments } from "./environmentStore";
 export { useConfigStore } from "./configStore";
 export { useClaudeOptionsStore } from "./claudeOptionsStore";
 export type { ClaudeOptions, AgentType } from "./claudeOptionsStore";
@@ -7,14 +7,95 @@ export interface Project {
   addedAt: string;
   /** Display order in the sidebar (lower values appear first) */
   order: number;
+  /** Sidebar group the project belongs to (key of `AppConfig.groups`) */
+  groupId?: string;
+}
+
+/** Named group of projects in the sidebar, e.g. "work" or "personal" */
+export interface ProjectGroup {
+  name: string;
+  /** Display order in the sidebar (lower values appear first) */
+  order: number;
+}
+
+/** A published port of a Docker Compose service */
+export interface ComposePort {
+  /** Host port, if fixed in the compose file */
+  hostPort?: number;
+  containerPort: number;
+  protocol: PortProtocol;
+}
+
+/** A service declared in a Docker Compose file */
+export interface ComposeService {
+  name: string;
+  image?: string;
+  ports: ComposePort[];
+}
+
+/** Docker Compose file detected in a project's local path */
+export interface ComposeProject {
+  filePath: string;
+  services: ComposeService[];
+}
+
+/** Stream a "compose-output" line came from */
+export type ComposeOutputStream = "stdout" | "stderr";
+
+/** Payload of "compose-output" events */
+export interface ComposeOutputEvent {
+  environment_id: string;
+  stream: ComposeOutputStream;
+  line: string;
+}
+
+/** Payload of "compose-status" events */
+export interface ComposeStatusEvent {
+  environment_id: string;
+  status: EnvironmentStatus;
+}
+
+/** Parsed `orkestrator-ai.json` project config */
+export interface OrkestratorProjectConfig {
+  root: string[];
+  setupContainer: string[];
+  setupLocal: string[];
+  run: string[];
+  ports: number[];
+  defaultAgent?: DefaultAgent;
+  env: Record<string, string>;
+}
+
+/** A problem with a single field of `orkestrator-ai.json` */
+export interface ProjectConfigFieldError {
+  /** Key path, e.g. "setupLocal" or "ports[1]" */
+  fieldPath: string;
+  message: string;
+}
+
+/** Result of linting an `orkestrator-ai.json` file */
+export interface ProjectConfigValidation {
+  valid: boolean;
+  config?: OrkestratorProjectConfig;
+  error?: string;
+  fieldErrors: ProjectConfigFieldError[];
 }
 
 // Environment types
 export type EnvironmentStatus = "running" | "stopped" | "error" | "creating" | "stopping";
 
+/** Payload of the "environment-status-changed" event emitted while stopping */
+export interface EnvironmentStatusChangedPayload {
+  environment_id: string;
+  status: EnvironmentStatus;
+}
+
 /** Pull request state from GitHub */
 export type PrState = "open" | "merged" | "closed";
 
+/** Summarized CI status of a PR's check runs */
+export type CiStatus = "passing" | "failing" | "pending";
+
 /** Network access mode for environment containers */
 export type NetworkAccessMode = "full" | "restricted";
 
@@ -24,6 +105,38 @@ export type EnvironmentType = "containerized" | "local";
 /** Port protocol type for port mappings */
 export type PortProtocol = "tcp" | "udp";
 
+/** Docker restart policy for environment containers */
+export type RestartPolicy = "no" | "on-failure" | "unless-stopped";
+
+/** App port reserved on localhost for a local environment */
+export interface LocalAppPort {
+  /** Port configured in the repository's default port mappings */
+  appPort: number;
+  /** Port the app should run on (shifted when the configured port was taken) */
+  hostPort: number;
+}
+
+/** Branch conflict hit while creating a local environment's worktree */
+export interface WorktreeConflict {
+  /** `branchCheckedOut`: the branch is checked out in another worktree; `branchOnRemote`: it exists only on origin */
+  reason: "branchCheckedOut" | "branchOnRemote";
+  /** Branch the environment asked for */
+  requestedBranch: string;
+  /** Branch the worktree was created on instead */
+  adjustedBranch: string;
+  /** Worktree that has the requested branch checked out */
+  existingWorktree?: string;
+}
+
+/** How `resolveWorktreeConflict` settles a worktree conflict */
+export type WorktreeConflictStrategy = "keepAdjusted" | "reuseExisting" | "forceNewBranch" | "deleteStaleWorktree";
+
+/** Step reached while starting an environment container (from "environment-start-progress") */
+export type StartProgressStage =
+  | "creating-container"
+  | "starting-container"
+  | "running-setup";
+
 /** Port mapping configuration for container ports */
 export interface PortMapping {
   /** Port number inside the container */
@@ -34,12 +147,22 @@ export interface PortMapping {
   protocol: PortProtocol;
 }
 
+/** Additional git remote, e.g. the fork of a cross-repository pull request */
+export interface GitRemote {
+  name: string;
+  url: string;
+}
+
 export interface Environment {
   id: string;
   projectId: string;
   name: string;
   /** Git branch name (defaults to "main" for legacy environments via serde default) */
   branch: string;
+  /** Branch the environment's branch was created from, e.g. a PR head branch (undefined = repository default) */
+  baseRef?: string;
+  /** Remote the base ref lives on when it is not origin (forked pull requests) */
+  baseRemote?: GitRemote;
   containerId: string | null;
   status: EnvironmentStatus;
   prUrl: string | null;
@@ -47,6 +170,8 @@ export interface Environment {
   prState: PrState | null;
   /** Whether the PR has merge conflicts with the target branch */
   hasMergeConflicts: boolean | null;
+  /** Summarized CI status of the PR's checks (undefined = no checks or not polled yet) */
+  ciStatus?: CiStatus;
   createdAt: string;
   /** Network access mode (defaults to "restricted" for security) */
   networkAccessMode: NetworkAccessMode;
@@ -79,6 +204,18 @@ export interface Environment {
   localClaudePort?: number;
   /** Host port for codex-bridge server (local mode) */
   localCodexPort?: number;
+  /** App ports reserved from the repository's default port mappings (local mode) */
+  localAppPorts?: LocalAppPort[];
+  /** Docker Compose project name while compose services are up for this environment */
+  composeProjectName?: string;
+  /** Review mode: file writes and PR merges are refused, new tabs open plain shells */
+  readOnly?: boolean;
+  /** Free-form notes (what's being worked on, TODOs) */
+  notes?: string;
+  /** Pinned environments are listed first, regardless of order */
+  pinned?: boolean;
+  /** Unresolved branch conflict from creating the worktree (local mode) */
+  worktreeConflict?: WorktreeConflict;
 
   // === Agent settings overrides ===
   /** Per-environment default agent override (undefined = use global config) */
@@ -93,8 +230,18 @@ export interface Environment {
   claudeNativeBackend?: ClaudeNativeBackend;
   /** Per-environment OpenCode mode override (undefined = use global config) */
   opencodeMode?: OpenCodeMode;
+  /** Per-environment OpenCode model (undefined = use the global opencodeModel) */
+  opencodeModelOverride?: string;
   /** Per-environment Codex mode override (undefined = use global config) */
   codexMode?: CodexMode;
+  /** Per-environment Docker restart policy override (undefined = inherit from repository, then "no") */
+  restartPolicy?: RestartPolicy;
+  /** Per-environment auto-created sessions override (undefined = inherit from repository, [] = none) */
+  defaultSessions?: SessionType[];
+  /** Clone the full history even when the repository sets a cloneDepth */
+  fullClone?: boolean;
+  /** Whether the container's repository is a shallow clone not yet unshallowed */
+  shallowHistory?: boolean;
   /**
    * Whether setup scripts have completed for this environment. Persisted so
    * native chat tabs can skip the "waiting for setup" UI after app restart,
@@ -103,6 +250,8 @@ export interface Environment {
   setupScriptsComplete?: boolean;
   /** Initial prompt used when this environment was created. */
   initialPrompt?: string;
+  /** Whether the restricted-mode firewall is temporarily bypassed (all traffic allowed) */
+  firewallBypassed?: boolean;
 }
 
 /** Result of testing a domain for DNS resolution */
@@ -116,8 +265,85 @@ export interface DomainTestResult {
 
 /** Result from starting an environment */
 export interface StartEnvironmentResult {
-  /** Setup commands to run in a terminal (for local environments with orkestrator-ai.json) */
+  /**
+   * Setup commands declared in orkestrator-ai.json: `setupLocal` for local
+   * environments (run in a terminal by the frontend), `setupContainer` for
+   * containerized ones (already executed in the container by workspace-setup.sh)
+   */
   setupCommands?: string[];
+  /** Locations that were low on disk space when the environment started */
+  diskSpaceWarnings?: DiskSpaceWarning[];
+  /** Container resource limits above the host's capacity when the container was created */
+  resourceLimitWarnings?: ResourceLimitWarning[];
+  /** Branch conflict hit while creating a local environment's worktree */
+  worktreeConflict?: WorktreeConflict;
+}
+
+/** Age and most recent activity of an environment, for finding stale ones */
+export interface EnvironmentActivity {
+  environmentId: string;
+  name: string;
+  createdAt: string;
+  /** Latest activity of the environment's stored sessions */
+  lastSessionActivityAt: string | null;
+  /** When the container was last started (containerized environments only) */
+  containerStartedAt: string | null;
+  /** Latest of the timestamps above */
+  lastActiveAt: string;
+  /** Whole days since lastActiveAt */
+  daysIdle: number;
+}
+
+/** A directory checked for free disk space */
+export type DiskLocation = "data-dir" | "docker-root" | "worktree-base";
+
+/** A location with less free space than the configured minimum */
+export interface DiskSpaceWarning {
+  location: DiskLocation;
+  path: string;
+  availableBytes: number;
+  thresholdBytes: number;
+  /** Human-readable summary */
+  message: string;
+}
+
+/** A host resource a container limit is checked against */
+export type HostResource = "cpu" | "memory";
+
+/** A configured container limit above what Docker's host has */
+export interface ResourceLimitWarning {
+  resource: HostResource;
+  /** Requested amount: cores for CPU, bytes for memory */
+  requested: number;
+  /** Host total: cores for CPU, bytes for memory */
+  available: number;
+  /** Human-readable summary */
+  message: string;
+}
+
+/** Container configuration an environment would be started with; secrets are redacted */
+export interface ContainerConfigPreview {
+  name: string;
+  image: string;
+  branch: string;
+  baseBranch: string | null;
+  workingDir: string;
+  cpuLimit: number | null;
+  /** Memory limit in bytes */
+  memoryLimit: number | null;
+  portMappings: PortMapping[];
+  /** Container ports published with a dynamically allocated host port */
+  dynamicPorts: number[];
+  /** Bind mounts as `host:container[:mode]` */
+  mounts: string[];
+  /** Names of the environment variables passed to the container, without values */
+  envVarNames: string[];
+  network: string | null;
+  networkAccessMode: NetworkAccessMode;
+  allowedDomains: string[];
+  restartPolicy: RestartPolicy;
+  /** Commits of history cloned (null = full history) */
+  cloneDepth: number | null;
 }
 
 // Session types - Terminal session tracking for environments
@@ -128,6 +354,15 @@ export type SessionType = "plain" | "claude" | "opencode" | "codex" | "root";
 /** Connection status of a terminal session */
 export type SessionStatus = "connected" | "disconnected";
 
+/** Agent, model and permission flags a session is launched with */
+export interface SessionLaunchConfig {
+  sessionType: SessionType;
+  /** Model passed to the agent CLI (undefined = the agent's default) */
+  model?: string;
+  /** Skip permission prompts */
+  yolo: boolean;
+}
+
 /** Terminal session represents a PTY session within an environment container */
 export interface Session {
   /** Unique session ID (UUID) */
@@ -152,6 +387,18 @@ export interface Session {
   order: number;
   /** Whether the auto-launch command (e.g., claude) was executed */
   hasLaunchedCommand?: boolean;
+  /** Container terminal session currently backing this session */
+  terminalSessionId?: string;
+  /** Agent and model chosen at creation, relaunched on reconnect */
+  launchConfig?: SessionLaunchConfig;
+}
+
+/** Payload of the "session-renamed" event */
+export interface SessionRenamedPayload {
+  sessionId: string;
+  environmentId: string;
+  /** New name, or null when reverted to the default label */
+  name: string | null;
 }
 
 // Configuration types
@@ -179,6 +426,10 @@ export type ClaudeMode = "terminal" | "native";
  * three-tier: environment override → repo override → global default.
  */
 export type ClaudeNativeBackend = "sdk" | "tmux";
+/** Which Claude credential containers receive: keychain OAuth, the API key, or both when available */
+export type AuthPreference = "auto" | "oauth" | "api_key";
+/** Container engine the app connects to; "auto" uses Docker unless only a Podman socket is found */
+export type ContainerEngineKind = "auto" | "docker" | "podman";
 /** Codex mode - terminal CLI or native chat interface */
 export type CodexMode = "terminal" | "native";
 /** Agent style - terminal CLI or native chat interface (used for project-level override) */
@@ -201,6 +452,22 @@ export interface TerminalAppearance {
   backgroundColor: string;
 }
 
+/** Payload of the "terminal-appearance-changed" event */
+export interface TerminalAppearanceChangedPayload {
+  terminalAppearance: TerminalAppearance;
+  terminalScrollback: number;
+}
+
+/** A menu action and the keyboard shortcut that triggers it */
+export interface MenuKeybinding {
+  /** Action id sent in "menu-action" events, e.g. "new_environment" */
+  action: string;
+  /** Menu item label */
+  label: string;
+  /** Accelerator such as "CmdOrCtrl+N" (undefined = no shortcut) */
+  accelerator?: string;
+}
+
 export interface GlobalConfig {
   containerResources: ContainerResources;
   envFilePatterns: string[];
@@ -208,6 +475,8 @@ export interface GlobalConfig {
   githubToken?: string;
   /** Domains allowed when environments are in restricted network mode */
   allowedDomains: string[];
+  /** Which Claude credential containers receive (defaults to "auto") */
+  authPreference?: AuthPreference;
   /** Preferred editor for opening containers (VS Code or Cursor) */
   preferredEditor?: PreferredEditor;
   /** Default agent for new environments (Claude or OpenCode) */
@@ -236,12 +505,50 @@ export interface GlobalConfig {
   terminalAppearance: TerminalAppearance;
   /** Terminal scrollback buffer size (lines) */
   terminalScrollback: number;
+  /** Chunks buffered between a terminal and the UI in each direction (defaults to 1024) */
+  terminalChannelCapacity?: number;
+  /** What terminals do when output outpaces rendering (defaults to "block") */
+  terminalOverflowPolicy?: TerminalOverflowPolicy;
+  /** Recent output kept in memory per terminal session, in bytes (defaults to 256 KiB) */
+  terminalBufferMaxBytes?: number;
+  /** Seconds between terminal buffer auto-saves (0 = disabled, defaults to 30) */
+  terminalBufferAutosaveSecs?: number;
   /** Capture raw Codex bridge events for subagent transcript debugging */
   experimentalCodexRawEventLogging?: boolean;
   /** Enable debug logging to disk (requires app restart) */
   debugLogging?: boolean;
+  /** Recent log lines kept in memory for the log panel (defaults to 2000) */
+  logBufferLines?: number;
+  /** Seconds between firewall ipset refreshes for restricted containers (0 = disabled) */
+  firewallRefreshIntervalSecs?: number;
+  /** Free disk space (GB) below which starting an environment warns (0 = disabled, defaults to 5) */
+  minFreeDiskGb?: number;
+  /** Refuse to start environments when disk space is low instead of warning */
+  blockOnLowDiskSpace?: boolean;
+  /** Refuse to create containers whose CPU or memory limits exceed the host's, instead of warning */
+  blockOnExcessResourceLimits?: boolean;
+  /**
+   * Quiet period (ms) before settings changes are written to disk (0 = immediately, defaults to 500).
+   * Changes made within this window are lost if the app crashes or is force quit.
+   */
+  configSaveDebounceMs?: number;
+  /** Maximum number of words in AI-generated environment names (defaults to 3) */
+  environmentNameMaxWords?: number;
+  /** App menu actions and their shortcuts (defaults to the View menu zoom actions) */
+  keybindings?: MenuKeybinding[];
+  /** Container engine to use (defaults to "auto") */
+  containerEngine?: ContainerEngineKind;
+  /** Address local native-mode servers bind to: "127.0.0.1" (default), "0.0.0.0" or "::" */
+  nativeServerHost?: string;
+  /** Containers (by ID or name) that prune and orphan cleanup never remove */
+  pinnedContainers?: string[];
+  /** Images (by ID) that prune never removes. Tags are resolved to IDs when pinned. */
+  pinnedImages?: string[];
 }
 
+/** "block" pauses output until the UI catches up; "drop-oldest" discards old output with a marker */
+export type TerminalOverflowPolicy = "block" | "drop-oldest";
+
 export interface RepositoryConfig {
   defaultBranch: string;
   prBaseBranch: string;
@@ -267,12 +574,42 @@ export interface RepositoryConfig {
    * global). Only meaningful when the resolved Claude mode is "native".
    */
   claudeNativeBackend?: ClaudeNativeBackend;
+  /** Default Docker restart policy for new containers (undefined = "no") */
+  restartPolicy?: RestartPolicy;
+  /** Allow `docker compose up/down` for environments of this repository */
+  composeEnabled?: boolean;
+  /** Absolute path the repository is cloned to inside containers (undefined = "/workspace") */
+  workspacePath?: string;
+  /** Docker network new containers join, created if missing (undefined = default bridge) */
+  dockerNetwork?: string;
+  /** Terminal sessions auto-created when an environment starts without any, in tab order */
+  defaultSessions?: SessionType[];
+  /**
+   * Commits of history new containers clone (undefined = full history).
+   * Blame, log and branch comparisons only see cloned commits until the
+   * repository is unshallowed.
+   */
+  cloneDepth?: number;
 }
 
 export interface AppConfig {
   version: string;
   global: GlobalConfig;
   repositories: Record<string, RepositoryConfig>;
+  /** Project groups by group ID */
+  groups?: Record<string, ProjectGroup>;
+}
+
+/** Zoom level and main window geometry persisted in window_state.json */
+export interface WindowState {
+  /** Zoom level as a percentage (undefined = 100) */
+  zoomLevel?: number;
+  /** Outer window size in physical pixels */
+  width?: number;
+  height?: number;
+  /** Outer window position in physical pixels */
+  x?: number;
+  y?: number;
 }
 
 // UI State types